@@ -1,5 +1,7 @@
 use crate::api::handlers;
 use crate::application::state::AppState;
+use crate::domain::models::annotation::{AnnotationExport, ReanchorReport};
+use crate::domain::models::locator::Locator;
 use tauri::State;
 
 /// Creates a new annotation (highlight with optional note) for a book.
@@ -7,8 +9,8 @@ use tauri::State;
 /// # Arguments
 ///
 /// * `book_id` - The book's database ID.
-/// * `start_position` - Start of the highlighted text range.
-/// * `end_position` - End of the highlighted text range.
+/// * `start_position` - Start of the highlighted text range, as a [`Locator`].
+/// * `end_position` - End of the highlighted text range, in the same format.
 /// * `chapter_title` - Optional chapter title.
 /// * `highlighted_text` - Optional captured highlight text.
 /// * `note` - Optional user-written note.
@@ -17,8 +19,8 @@ use tauri::State;
 #[allow(clippy::too_many_arguments)]
 pub async fn add_annotation(
     book_id: i32,
-    start_position: String,
-    end_position: String,
+    start_position: Locator,
+    end_position: Locator,
     chapter_title: Option<String>,
     highlighted_text: Option<String>,
     note: Option<String>,
@@ -68,3 +70,49 @@ pub async fn delete_annotation(
         .await
         .map_err(|e| e.to_string())
 }
+
+/// Finds a book's annotations again in its current file, moving those whose
+/// highlighted text moved and flagging those it cannot find confidently.
+///
+/// Runs on its own whenever the book's checksum changes; this runs it on
+/// demand.
+///
+/// # Arguments
+///
+/// * `book_id` - The book's database ID; must be an EPUB.
+///
+/// # Returns
+///
+/// A [`ReanchorReport`] with the outcome and confidence of each annotation.
+#[tauri::command]
+pub async fn reanchor_annotations(
+    book_id: i32,
+    state: State<'_, AppState>,
+) -> Result<ReanchorReport, String> {
+    handlers::annotation_handler::reanchor_annotations(book_id, &state)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Writes a PDF book's annotations into a copy of its file as standard
+/// Highlight and Text annotations, so other PDF viewers show them.
+///
+/// # Arguments
+///
+/// * `book_id` - The book's database ID; must be a PDF.
+/// * `destination` - Path of the annotated copy; must not be the book's file.
+///
+/// # Returns
+///
+/// The number of annotations written and the IDs of the highlights skipped
+/// for spanning more than two pages.
+#[tauri::command]
+pub async fn export_pdf_annotations(
+    book_id: i32,
+    destination: String,
+    state: State<'_, AppState>,
+) -> Result<AnnotationExport, String> {
+    handlers::annotation_handler::export_pdf_annotations(book_id, destination, &state)
+        .await
+        .map_err(|e| e.to_string())
+}
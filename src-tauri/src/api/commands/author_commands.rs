@@ -0,0 +1,56 @@
+use crate::api::handlers;
+use crate::application::state::AppState;
+use crate::domain::dto::author_dto::AuthorSummary;
+use crate::domain::models::author::Author;
+use tauri::State;
+
+/// Returns all authors with the number of books linked to each.
+#[tauri::command]
+pub async fn list_authors(state: State<'_, AppState>) -> Result<Vec<AuthorSummary>, String> {
+    handlers::author_handler::list_authors(&state)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Renames an author. Fails if another author already has the name; merge
+/// them instead.
+///
+/// # Arguments
+///
+/// * `author_id` - The author's database ID.
+/// * `name` - The new name.
+#[tauri::command]
+pub async fn rename_author(
+    author_id: i32,
+    name: String,
+    state: State<'_, AppState>,
+) -> Result<Author, String> {
+    handlers::author_handler::rename_author(author_id, name, &state)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Merges duplicate authors into one, re-pointing their book links.
+///
+/// # Arguments
+///
+/// * `source_ids` - Authors to merge away and delete.
+/// * `target_id` - The author that remains.
+#[tauri::command]
+pub async fn merge_authors(
+    source_ids: Vec<i32>,
+    target_id: i32,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    handlers::author_handler::merge_authors(source_ids, target_id, &state)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Deletes authors not linked to any book and returns how many were removed.
+#[tauri::command]
+pub async fn delete_orphan_authors(state: State<'_, AppState>) -> Result<usize, String> {
+    handlers::author_handler::delete_orphan_authors(&state)
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -1,4 +1,5 @@
 use crate::api::handlers;
+use crate::application::book::RemoveMode;
 use crate::application::state::AppState;
 use tauri::State;
 
@@ -84,14 +85,20 @@ pub async fn get_pdf_page_count(path: String) -> Result<u32, String> {
 ///
 /// * `path` - Absolute path to the PDF file.
 /// * `page_number` - 0-based page index to render.
+/// * `options` - Resolution, clip and output format; a 150 DPI PNG of the
+///   whole page when omitted.
 ///
 /// # Returns
 ///
 /// A [`PdfPage`](crate::infrastructure::file_handlers::pdf_handler::PdfPage) with
 /// base64 image data and text spans.
 #[tauri::command]
-pub async fn read_pdf_page(path: String, page_number: u32) -> Result<crate::infrastructure::file_handlers::pdf_handler::PdfPage, String> {
-    handlers::book_handler::read_pdf_page(path, page_number)
+pub async fn read_pdf_page(
+    path: String,
+    page_number: u32,
+    options: Option<crate::infrastructure::file_handlers::pdf_handler::PdfRenderOptions>,
+) -> Result<crate::infrastructure::file_handlers::pdf_handler::PdfPage, String> {
+    handlers::book_handler::read_pdf_page(path, page_number, options.unwrap_or_default())
         .await
         .map_err(|e| e.to_string())
 }
@@ -111,6 +118,27 @@ pub async fn list_books(
         .map_err(|e| e.to_string())
 }
 
+/// Returns a filtered, sorted and paginated page of books.
+///
+/// # Arguments
+///
+/// * `query` - Filters, sort key/direction and pagination. Omitted fields use
+///   their defaults (no filters, title ascending, all books).
+///
+/// # Returns
+///
+/// A [`BookPage`](crate::domain::dto::book_dto::BookPage) with the books,
+/// the total match count and the cursor for the next page.
+#[tauri::command]
+pub async fn query_books(
+    query: crate::domain::repository::BookQuery,
+    state: State<'_, AppState>,
+) -> Result<crate::domain::dto::book_dto::BookPage, String> {
+    handlers::book_handler::query_books(query, &state)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 /// Returns details for a single book by ID.
 ///
 /// # Arguments
@@ -149,14 +177,68 @@ pub async fn get_cover_img(
         .map_err(|e| e.to_string())
 }
 
-/// Removes a book from the library by ID.
+/// Moves a book to the trash by ID.
+///
+/// The book keeps its bookmarks, annotations and reading progress and can be
+/// brought back with [`restore_book`] until the trash is emptied or the
+/// retention period runs out. Its file is added to the ignored files so
+/// directory scans do not import it again.
+///
+/// # Arguments
+///
+/// * `book_id` - The book's database ID.
+/// * `mode` - `keep_file` (default), `trash_file` to move the file to the
+///   operating system's trash, or `delete_file` to delete it permanently.
+#[tauri::command]
+pub async fn remove_book(
+    book_id: i32,
+    mode: Option<RemoveMode>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    handlers::book_handler::remove_book(book_id, mode.unwrap_or_default(), &state)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Lists the books in the trash, most recently removed first.
+///
+/// Books trashed longer than `TRASH_RETENTION_DAYS` ago are purged first.
+///
+/// # Returns
+///
+/// A list of [`BookDto`](crate::domain::dto::book_dto::BookDto) with
+/// `deleted_at` set.
+#[tauri::command]
+pub async fn list_trash(
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::domain::dto::book_dto::BookDto>, String> {
+    handlers::book_handler::list_trash(&state)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Moves a book out of the trash back into the library and stops ignoring
+/// its file. Fails when the book's file no longer exists.
 ///
 /// # Arguments
 ///
 /// * `book_id` - The book's database ID.
 #[tauri::command]
-pub async fn remove_book(book_id: i32, state: State<'_, AppState>) -> Result<(), String> {
-    handlers::book_handler::remove_book(book_id, &state)
+pub async fn restore_book(book_id: i32, state: State<'_, AppState>) -> Result<(), String> {
+    handlers::book_handler::restore_book(book_id, &state)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Permanently deletes every book in the trash, with its bookmarks,
+/// annotations and reading progress. The files stay on disk.
+///
+/// # Returns
+///
+/// The number of books deleted.
+#[tauri::command]
+pub async fn empty_trash(state: State<'_, AppState>) -> Result<usize, String> {
+    handlers::book_handler::empty_trash(&state)
         .await
         .map_err(|e| e.to_string())
 }
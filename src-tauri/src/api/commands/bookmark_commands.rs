@@ -1,5 +1,6 @@
 use crate::api::handlers;
 use crate::application::state::AppState;
+use crate::domain::models::locator::Locator;
 use tauri::State;
 
 /// Creates a new bookmark for a book at the given position.
@@ -7,13 +8,13 @@ use tauri::State;
 /// # Arguments
 ///
 /// * `book_id` - The book's database ID.
-/// * `position` - Position identifier (e.g., EPUB CFI, byte offset).
+/// * `position` - Where the bookmark is placed, as a [`Locator`].
 /// * `chapter_title` - Optional chapter title.
 /// * `page_number` - Optional page number (PDF books).
 #[tauri::command]
 pub async fn add_bookmark(
     book_id: i32,
-    position: String,
+    position: Locator,
     chapter_title: Option<String>,
     page_number: Option<i32>,
     state: State<'_, AppState>,
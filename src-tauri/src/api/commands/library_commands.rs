@@ -1,5 +1,8 @@
 use crate::api::handlers;
+use crate::application::book::{FilenamePatternPreview, ScanReport};
 use crate::application::state::AppState;
+use crate::domain::dto::duplicate_dto::DuplicateGroup;
+use crate::domain::models::ignored_file::IgnoredFile;
 use tauri::State;
 
 /// Recursively scans a directory for ebooks and imports them into the library.
@@ -10,13 +13,100 @@ use tauri::State;
 ///
 /// # Returns
 ///
-/// A list of error messages for files that failed to import.
+/// The error messages for files that failed to import, and the
+/// re-anchoring results of library files whose contents changed.
 #[tauri::command]
 pub async fn scan_books_directory(
     directory_path: String,
     state: State<'_, AppState>,
-) -> Result<Vec<String>, String> {
+) -> Result<ScanReport, String> {
     handlers::library_handler::scan_directory(directory_path, &state)
         .await
         .map_err(|e| e.to_string())
 }
+
+/// Shows what filename patterns would extract from a set of files, without
+/// importing them.
+///
+/// # Arguments
+///
+/// * `patterns` - Patterns such as `{author} - {title} ({year})` or
+///   `{author}/{series}/{index} - {title}`, tried in order. An empty list
+///   previews the patterns configured for import.
+/// * `paths` - File paths to match against.
+///
+/// # Returns
+///
+/// For each path, the fields every pattern extracts (or `null` when it does
+/// not match), or an error message when a pattern is invalid.
+#[tauri::command]
+pub async fn preview_filename_patterns(
+    patterns: Vec<String>,
+    paths: Vec<String>,
+) -> Result<Vec<FilenamePatternPreview>, String> {
+    handlers::library_handler::preview_filename_patterns(patterns, paths)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Finds books that look like copies of the same file, scored on title,
+/// author, ISBN, page or spine count and text fingerprint.
+///
+/// # Arguments
+///
+/// * `min_score` - Score from `0.0` to `1.0` a pair needs to be reported;
+///   `0.8` when omitted.
+///
+/// # Returns
+///
+/// Groups of suspected duplicates with their scored pairs and the book
+/// suggested to keep.
+#[tauri::command]
+pub async fn find_duplicate_books(
+    min_score: Option<f32>,
+    state: State<'_, AppState>,
+) -> Result<Vec<DuplicateGroup>, String> {
+    handlers::library_handler::find_duplicate_books(min_score, &state)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Merges duplicate books: bookmarks, annotations, tags and the latest
+/// reading progress move to the target, then the duplicates are removed
+/// from the library.
+///
+/// # Arguments
+///
+/// * `source_ids` - Duplicates to merge away.
+/// * `target_id` - The book that remains.
+#[tauri::command]
+pub async fn merge_duplicate_books(
+    source_ids: Vec<i32>,
+    target_id: i32,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    handlers::library_handler::merge_duplicate_books(source_ids, target_id, &state)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Lists the files that directory scans skip because their books were
+/// removed, most recently ignored first.
+#[tauri::command]
+pub async fn list_ignored_files(state: State<'_, AppState>) -> Result<Vec<IgnoredFile>, String> {
+    handlers::library_handler::list_ignored_files(&state)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Stops ignoring a file, so the next directory scan imports it again.
+///
+/// # Arguments
+///
+/// * `file_path` - The ignored path.
+#[tauri::command]
+pub async fn unignore_file(file_path: String, state: State<'_, AppState>) -> Result<(), String> {
+    handlers::library_handler::unignore_file(file_path, &state)
+        .await
+        .map_err(|e| e.to_string())
+}
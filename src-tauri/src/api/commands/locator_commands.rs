@@ -0,0 +1,80 @@
+use crate::api::handlers;
+use crate::application::state::AppState;
+use crate::domain::models::cfi::{CfiTarget, DomPosition};
+use crate::domain::models::locator::{EpubLocation, Locator};
+use tauri::State;
+
+/// Resolves an EPUB CFI against the book's content documents.
+///
+/// # Arguments
+///
+/// * `book_id` - The book's database ID.
+/// * `cfi` - The CFI, e.g. `"epubcfi(/6/4[chap01]!/4/2/1:12)"`, or a range.
+///
+/// # Returns
+///
+/// The spine item and the element path and text offset the CFI points to.
+#[tauri::command]
+pub async fn resolve_epub_cfi(
+    book_id: i32,
+    cfi: String,
+    state: State<'_, AppState>,
+) -> Result<CfiTarget, String> {
+    handlers::locator_handler::resolve_epub_cfi(book_id, cfi, &state)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Generates a standard EPUB CFI for a position in a spine item.
+///
+/// # Arguments
+///
+/// * `book_id` - The book's database ID.
+/// * `spine_index` - 0-based index of the spine item.
+/// * `start` - Element path from the root element and text offset.
+/// * `end` - End of a range in the same spine item, if any.
+///
+/// # Returns
+///
+/// An [`EpubLocation`] with the href, CFI and progression, for a locator.
+#[tauri::command]
+pub async fn generate_epub_cfi(
+    book_id: i32,
+    spine_index: usize,
+    start: DomPosition,
+    end: Option<DomPosition>,
+    state: State<'_, AppState>,
+) -> Result<EpubLocation, String> {
+    handlers::locator_handler::generate_epub_cfi(book_id, spine_index, start, end, &state)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Builds the locator of a text selection on a PDF page, with one quad per
+/// selected line and the selected text.
+///
+/// # Arguments
+///
+/// * `book_id` - The book's database ID.
+/// * `page_index` - 0-based page index.
+/// * `first_char` - Index of the first selected character in the page's
+///   `chars`.
+/// * `last_char` - Index of the last selected character.
+///
+/// # Returns
+///
+/// A PDF [`Locator`] to store as an annotation's start and end positions.
+#[tauri::command]
+pub async fn generate_pdf_locator(
+    book_id: i32,
+    page_index: u32,
+    first_char: usize,
+    last_char: usize,
+    state: State<'_, AppState>,
+) -> Result<Locator, String> {
+    handlers::locator_handler::generate_pdf_locator(
+        book_id, page_index, first_char, last_char, &state,
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
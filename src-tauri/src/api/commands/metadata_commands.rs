@@ -1,5 +1,9 @@
 use crate::api::handlers;
+use crate::application::provider::{MetadataComparison, MetadataField};
 use crate::application::state::AppState;
+use crate::domain::dto::book_dto::BookDto;
+use crate::domain::models::metadata_change::MetadataChangeSet;
+use crate::domain::repository::BookMetadataPatch;
 use crate::infrastructure::file_handlers::BookMetadata;
 use tauri::State;
 
@@ -35,23 +39,164 @@ pub async fn list_metadata(state: State<'_, AppState>) -> Result<Vec<BookMetadat
         .map_err(|e| e.to_string())
 }
 
-/// Updates metadata fields for a book found by title search.
+/// Updates a book's metadata by ID and returns the updated book.
 ///
 /// # Arguments
 ///
-/// * `book_name` - Substring to search book titles against.
-/// * `title` - Optional new title.
-/// * `published_date` - Optional new publication date.
-/// * `isbn` - Optional new ISBN.
+/// * `book_id` - The book's database ID.
+/// * `patch` - Fields to change: title, authors, publisher, published date,
+///   ISBN, language, description, series and tags. Omitted fields are kept;
+///   empty strings clear optional fields.
+///
+/// # Returns
+///
+/// The updated [`BookDto`], or an error message when validation fails.
+#[tauri::command]
+pub async fn update_book_metadata(
+    book_id: i32,
+    patch: BookMetadataPatch,
+    state: State<'_, AppState>,
+) -> Result<BookDto, String> {
+    handlers::metadata_handler::update_book_metadata(book_id, patch, &state)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Writes a book's stored metadata back into its EPUB file.
+///
+/// Rewrites title, authors, publisher, date, ISBN and series in the OPF
+/// package document and updates the stored checksum. Only EPUB books are
+/// supported.
+///
+/// # Arguments
+///
+/// * `book_id` - The book's database ID.
+/// * `include_cover` - Whether to embed the cached cover image as well,
+///   unless it is a generated placeholder.
+///
+/// # Returns
+///
+/// The [`BookDto`] with its new checksum, or an error message.
+#[tauri::command]
+pub async fn write_metadata_to_file(
+    book_id: i32,
+    include_cover: bool,
+    state: State<'_, AppState>,
+) -> Result<BookDto, String> {
+    handlers::metadata_handler::write_metadata_to_file(book_id, include_cover, &state)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Looks up candidate metadata for a book with the configured metadata
+/// provider (Open Library by default).
+///
+/// Searches by ISBN first, then by title and author.
+///
+/// # Arguments
+///
+/// * `book_id` - The book's database ID.
+///
+/// # Returns
+///
+/// A [`MetadataComparison`] with the book's current values next to the
+/// candidates, each with its cover when the provider has one.
+#[tauri::command]
+pub async fn find_metadata_candidates(
+    book_id: i32,
+    state: State<'_, AppState>,
+) -> Result<MetadataComparison, String> {
+    handlers::metadata_handler::find_metadata_candidates(book_id, &state)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Applies the chosen fields of a metadata candidate to a book.
+///
+/// # Arguments
+///
+/// * `book_id` - The book's database ID.
+/// * `candidate` - The candidate metadata, as returned by
+///   `find_metadata_candidates`.
+/// * `fields` - Fields to copy: `title`, `authors`, `publisher`,
+///   `published_date`, `isbn`, `language`, `description` and `cover`.
+///
+/// # Returns
+///
+/// The updated [`BookDto`], or an error message when validation fails.
+#[tauri::command]
+pub async fn apply_metadata_candidate(
+    book_id: i32,
+    candidate: BookMetadata,
+    fields: Vec<MetadataField>,
+    state: State<'_, AppState>,
+) -> Result<BookDto, String> {
+    handlers::metadata_handler::apply_metadata_candidate(book_id, candidate, fields, &state)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Returns a book's metadata edit history.
+///
+/// Every change to the title, authors, publisher, date, ISBN, language,
+/// description, series, tags and sort keys is recorded with its old and new
+/// value and its source: `user`, `provider`, `import` or `file_writeback`.
+///
+/// # Arguments
+///
+/// * `book_id` - The book's database ID.
+///
+/// # Returns
+///
+/// The change sets, newest first, each with its field changes.
+#[tauri::command]
+pub async fn get_metadata_history(
+    book_id: i32,
+    state: State<'_, AppState>,
+) -> Result<Vec<MetadataChangeSet>, String> {
+    handlers::metadata_handler::get_metadata_history(book_id, &state)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Sets one field back to its value before a recorded change.
+///
+/// The revert is validated like a manual edit and recorded as a new change.
+/// Changes written to the file cannot be reverted.
+///
+/// # Arguments
+///
+/// * `change_id` - The ID of the field change.
+///
+/// # Returns
+///
+/// The updated [`BookDto`], or an error message.
+#[tauri::command]
+pub async fn revert_metadata_change(
+    change_id: i32,
+    state: State<'_, AppState>,
+) -> Result<BookDto, String> {
+    handlers::metadata_handler::revert_metadata_change(change_id, &state)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Sets every field of a recorded change set back to its value before the
+/// change.
+///
+/// # Arguments
+///
+/// * `change_set_id` - The ID of the change set.
+///
+/// # Returns
+///
+/// The updated [`BookDto`], or an error message.
 #[tauri::command]
-pub async fn update_metadata(
-    book_name: String,
-    title: Option<String>,
-    published_date: Option<String>,
-    isbn: Option<String>,
+pub async fn revert_metadata_change_set(
+    change_set_id: i32,
     state: State<'_, AppState>,
-) -> Result<(), String> {
-    handlers::metadata_handler::update_metadata(book_name, title, published_date, isbn, &state)
+) -> Result<BookDto, String> {
+    handlers::metadata_handler::revert_metadata_change_set(change_set_id, &state)
         .await
         .map_err(|e| e.to_string())
 }
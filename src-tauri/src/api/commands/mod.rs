@@ -5,8 +5,12 @@
 //! serialization over IPC.
 
 pub mod annotation_commands;
+pub mod author_commands;
 pub mod book_commands;
 pub mod bookmark_commands;
 pub mod library_commands;
+pub mod locator_commands;
 pub mod metadata_commands;
+pub mod publisher_commands;
 pub mod reading_progress_commands;
+pub mod work_commands;
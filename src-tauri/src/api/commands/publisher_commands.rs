@@ -0,0 +1,56 @@
+use crate::api::handlers;
+use crate::application::state::AppState;
+use crate::domain::dto::publisher_dto::PublisherSummary;
+use crate::domain::models::publisher::Publisher;
+use tauri::State;
+
+/// Returns all publishers with the number of books referencing each.
+#[tauri::command]
+pub async fn list_publishers(state: State<'_, AppState>) -> Result<Vec<PublisherSummary>, String> {
+    handlers::publisher_handler::list_publishers(&state)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Renames a publisher. Fails if another publisher already has the name;
+/// merge them instead.
+///
+/// # Arguments
+///
+/// * `publisher_id` - The publisher's database ID.
+/// * `name` - The new name.
+#[tauri::command]
+pub async fn rename_publisher(
+    publisher_id: i32,
+    name: String,
+    state: State<'_, AppState>,
+) -> Result<Publisher, String> {
+    handlers::publisher_handler::rename_publisher(publisher_id, name, &state)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Merges duplicate publishers into one, re-pointing their books.
+///
+/// # Arguments
+///
+/// * `source_ids` - Publishers to merge away and delete.
+/// * `target_id` - The publisher that remains.
+#[tauri::command]
+pub async fn merge_publishers(
+    source_ids: Vec<i32>,
+    target_id: i32,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    handlers::publisher_handler::merge_publishers(source_ids, target_id, &state)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Deletes publishers no book references and returns how many were removed.
+#[tauri::command]
+pub async fn delete_orphan_publishers(state: State<'_, AppState>) -> Result<usize, String> {
+    handlers::publisher_handler::delete_orphan_publishers(&state)
+        .await
+        .map_err(|e| e.to_string())
+}
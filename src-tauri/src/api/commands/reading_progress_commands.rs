@@ -1,5 +1,6 @@
 use crate::api::handlers;
 use crate::application::state::AppState;
+use crate::domain::models::locator::Locator;
 use tauri::State;
 
 /// Updates reading progress for a book (upserts).
@@ -7,14 +8,14 @@ use tauri::State;
 /// # Arguments
 ///
 /// * `book_id` - The book's database ID.
-/// * `current_position` - Current reading position identifier.
+/// * `current_position` - Current reading position, as a [`Locator`].
 /// * `chapter_title` - Optional current chapter title.
 /// * `page_number` - Optional current page number.
 /// * `progress_percentage` - Optional completion percentage (0.0–100.0).
 #[tauri::command]
 pub async fn update_reading_progress(
     book_id: i32,
-    current_position: String,
+    current_position: Locator,
     chapter_title: Option<String>,
     page_number: Option<i32>,
     progress_percentage: Option<f32>,
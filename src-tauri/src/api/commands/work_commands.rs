@@ -0,0 +1,72 @@
+use crate::api::handlers;
+use crate::application::state::AppState;
+use crate::domain::dto::book_dto::BookDto;
+use crate::domain::dto::work_dto::{WorkDto, WorkSuggestion};
+use tauri::State;
+
+/// Returns groups of books that share an ISBN or a normalized title and
+/// author but are not linked into one work yet.
+#[tauri::command]
+pub async fn suggest_work_links(state: State<'_, AppState>) -> Result<Vec<WorkSuggestion>, String> {
+    handlers::work_handler::suggest_work_links(&state)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Links books into one work so the library lists them as a single entry.
+///
+/// # Arguments
+///
+/// * `book_ids` - At least two books; works they already belong to are
+///   merged.
+#[tauri::command]
+pub async fn link_books(book_ids: Vec<i32>, state: State<'_, AppState>) -> Result<WorkDto, String> {
+    handlers::work_handler::link_books(book_ids, &state)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Removes a book from its work. A work left with one file is dissolved.
+///
+/// # Arguments
+///
+/// * `book_id` - The book's database ID.
+#[tauri::command]
+pub async fn unlink_book(book_id: i32, state: State<'_, AppState>) -> Result<(), String> {
+    handlers::work_handler::unlink_book(book_id, &state)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Sets the file the reader opens for a work.
+///
+/// # Arguments
+///
+/// * `work_id` - The work's database ID.
+/// * `book_id` - A file of the work.
+#[tauri::command]
+pub async fn set_preferred_format(
+    work_id: i32,
+    book_id: i32,
+    state: State<'_, AppState>,
+) -> Result<WorkDto, String> {
+    handlers::work_handler::set_preferred_format(work_id, book_id, &state)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Returns the file to open for a library entry: the work's preferred
+/// format, or the book itself when it belongs to no work.
+///
+/// # Arguments
+///
+/// * `book_id` - Any file of the work.
+#[tauri::command]
+pub async fn get_preferred_book(
+    book_id: i32,
+    state: State<'_, AppState>,
+) -> Result<BookDto, String> {
+    handlers::work_handler::get_preferred_book(book_id, &state)
+        .await
+        .map_err(|e| e.to_string())
+}
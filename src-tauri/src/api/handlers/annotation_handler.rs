@@ -1,14 +1,15 @@
 use crate::application::state::AppState;
 use crate::domain::error::DomainError;
-use crate::domain::models::annotation::Annotation;
+use crate::domain::models::annotation::{Annotation, AnnotationExport, ReanchorReport};
+use crate::domain::models::locator::Locator;
 use crate::domain::repository::*;
 
 /// Creates a new annotation (highlight with optional note) for a book.
 #[allow(clippy::too_many_arguments)]
 pub async fn add_annotation(
     book_id: i32,
-    start_position: String,
-    end_position: String,
+    start_position: Locator,
+    end_position: Locator,
     chapter_title: Option<String>,
     highlighted_text: Option<String>,
     note: Option<String>,
@@ -42,3 +43,31 @@ pub async fn get_annotations(
 pub async fn delete_annotation(id: i32, state: &AppState) -> Result<(), DomainError> {
     crate::application::annotation::delete_annotation(id, &state.annotation_repo).await
 }
+
+/// Finds a book's annotations again in its current file.
+pub async fn reanchor_annotations(
+    book_id: i32,
+    state: &AppState,
+) -> Result<ReanchorReport, DomainError> {
+    crate::application::annotation::reanchor_annotations(
+        book_id,
+        &state.book_repo,
+        &state.annotation_repo,
+    )
+    .await
+}
+
+/// Writes a PDF book's annotations into a copy of its file.
+pub async fn export_pdf_annotations(
+    book_id: i32,
+    destination: String,
+    state: &AppState,
+) -> Result<AnnotationExport, DomainError> {
+    crate::application::annotation::export_pdf_annotations(
+        book_id,
+        &destination,
+        &state.book_repo,
+        &state.annotation_repo,
+    )
+    .await
+}
@@ -0,0 +1,44 @@
+use crate::application::state::AppState;
+use crate::domain::dto::author_dto::AuthorSummary;
+use crate::domain::error::DomainError;
+use crate::domain::models::author::Author;
+
+/// Returns all authors with their book counts.
+pub async fn list_authors(state: &AppState) -> Result<Vec<AuthorSummary>, DomainError> {
+    crate::application::author::list_authors(&state.author_repo).await
+}
+
+/// Renames an author.
+pub async fn rename_author(id: i32, name: String, state: &AppState) -> Result<Author, DomainError> {
+    crate::application::author::rename_author(
+        id,
+        &name,
+        &state.book_repo,
+        &state.author_repo,
+        &state.publisher_repo,
+        &state.metadata_history_repo,
+    )
+    .await
+}
+
+/// Merges the source authors into the target author.
+pub async fn merge_authors(
+    source_ids: Vec<i32>,
+    target_id: i32,
+    state: &AppState,
+) -> Result<(), DomainError> {
+    crate::application::author::merge_authors(
+        &source_ids,
+        target_id,
+        &state.book_repo,
+        &state.author_repo,
+        &state.publisher_repo,
+        &state.metadata_history_repo,
+    )
+    .await
+}
+
+/// Deletes authors not linked to any book.
+pub async fn delete_orphan_authors(state: &AppState) -> Result<usize, DomainError> {
+    crate::application::author::delete_orphan_authors(&state.author_repo).await
+}
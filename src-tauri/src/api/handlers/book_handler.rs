@@ -1,8 +1,10 @@
 use std::path::Path;
 
+use crate::application::book::RemoveMode;
 use crate::application::state::AppState;
-use crate::domain::dto::book_dto::BookDto;
+use crate::domain::dto::book_dto::{BookDto, BookPage};
 use crate::domain::error::DomainError;
+use crate::domain::repository::BookQuery;
 
 /// Imports an ebook file at the given path into the library.
 pub async fn import_book(path: String, state: &AppState) -> Result<BookDto, DomainError> {
@@ -12,18 +14,20 @@ pub async fn import_book(path: String, state: &AppState) -> Result<BookDto, Doma
         &state.author_repo,
         &state.book_author_repo,
         &state.publisher_repo,
+        &state.ignored_file_repo,
+        &state.metadata_history_repo,
     )
     .await
 }
 
 /// Returns all books in the library as DTOs.
 pub async fn list_books(state: &AppState) -> Result<Vec<BookDto>, DomainError> {
-    crate::application::book::list_books(
-        &state.book_repo,
-        &state.author_repo,
-        &state.publisher_repo,
-    )
-    .await
+    crate::application::book::list_books(&state.book_repo).await
+}
+
+/// Returns a filtered, sorted and paginated page of books.
+pub async fn query_books(query: BookQuery, state: &AppState) -> Result<BookPage, DomainError> {
+    crate::application::book::query_books(query, &state.book_repo).await
 }
 
 /// Returns book details by ID as a DTO.
@@ -64,18 +68,40 @@ pub async fn get_pdf_page_count(path: String) -> Result<u32, DomainError> {
 pub async fn read_pdf_page(
     path: String,
     page_number: u32,
+    options: crate::infrastructure::file_handlers::pdf_handler::PdfRenderOptions,
 ) -> Result<crate::infrastructure::file_handlers::pdf_handler::PdfPage, DomainError> {
-    crate::infrastructure::file_handlers::pdf_handler::read_pdf_page(&path, page_number)
+    crate::infrastructure::file_handlers::pdf_handler::read_pdf_page(&path, page_number, &options)
         .await
         .map_err(|e| DomainError::Parse(e.to_string()))
 }
 
 /// Returns the cover image bytes for a book.
 pub async fn get_cover_img(book_id: i32, state: &AppState) -> Result<Option<Vec<u8>>, DomainError> {
-    crate::application::book::get_cover(book_id, &state.book_repo).await
+    crate::application::book::get_cover(book_id, &state.book_repo, &state.author_repo).await
+}
+
+/// Moves a book to the trash by ID, handling its file as `mode` says.
+pub async fn remove_book(
+    book_id: i32,
+    mode: RemoveMode,
+    state: &AppState,
+) -> Result<(), DomainError> {
+    crate::application::book::remove_book(book_id, mode, &state.book_repo, &state.ignored_file_repo)
+        .await
+}
+
+/// Lists the books in the trash.
+pub async fn list_trash(state: &AppState) -> Result<Vec<BookDto>, DomainError> {
+    crate::application::book::list_trash(&state.book_repo).await
+}
+
+/// Moves a book out of the trash.
+pub async fn restore_book(book_id: i32, state: &AppState) -> Result<(), DomainError> {
+    crate::application::book::restore_book(book_id, &state.book_repo, &state.ignored_file_repo)
+        .await
 }
 
-/// Removes a book from the library by ID.
-pub async fn remove_book(book_id: i32, state: &AppState) -> Result<(), DomainError> {
-    crate::application::book::remove_book(book_id, &state.book_repo).await
+/// Permanently deletes every book in the trash.
+pub async fn empty_trash(state: &AppState) -> Result<usize, DomainError> {
+    crate::application::book::empty_trash(&state.book_repo).await
 }
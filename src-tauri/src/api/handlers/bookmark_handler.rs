@@ -1,12 +1,13 @@
 use crate::application::state::AppState;
 use crate::domain::error::DomainError;
 use crate::domain::models::bookmark::Bookmark;
+use crate::domain::models::locator::Locator;
 use crate::domain::repository::*;
 
 /// Creates a new bookmark for a book.
 pub async fn add_bookmark(
     book_id: i32,
-    position: String,
+    position: Locator,
     chapter_title: Option<String>,
     page_number: Option<i32>,
     state: &AppState,
@@ -1,19 +1,67 @@
 use std::path::Path;
 
+use crate::application::book::{FilenamePatternPreview, ScanReport};
 use crate::application::state::AppState;
+use crate::domain::dto::duplicate_dto::DuplicateGroup;
 use crate::domain::error::DomainError;
+use crate::domain::models::ignored_file::IgnoredFile;
 
 /// Recursively scans a directory for ebooks and imports them.
 pub async fn scan_directory(
     directory_path: String,
     state: &AppState,
-) -> Result<Vec<String>, DomainError> {
+) -> Result<ScanReport, DomainError> {
     crate::application::book::scan_directory(
         Path::new(&directory_path),
         &state.book_repo,
         &state.author_repo,
         &state.book_author_repo,
         &state.publisher_repo,
+        &state.ignored_file_repo,
+        &state.metadata_history_repo,
+        &state.annotation_repo,
     )
     .await
 }
+
+/// Shows what each filename pattern would extract from the given files.
+pub async fn preview_filename_patterns(
+    patterns: Vec<String>,
+    paths: Vec<String>,
+) -> Result<Vec<FilenamePatternPreview>, DomainError> {
+    crate::application::book::preview_filename_patterns(&patterns, &paths)
+}
+
+/// Returns groups of books suspected to be duplicates.
+pub async fn find_duplicate_books(
+    min_score: Option<f32>,
+    state: &AppState,
+) -> Result<Vec<DuplicateGroup>, DomainError> {
+    crate::application::duplicate::find_duplicates(
+        min_score,
+        &state.book_repo,
+        &state.author_repo,
+        &state.publisher_repo,
+        &state.reading_progress_repo,
+    )
+    .await
+}
+
+/// Merges duplicate books into the one to keep.
+pub async fn merge_duplicate_books(
+    source_ids: Vec<i32>,
+    target_id: i32,
+    state: &AppState,
+) -> Result<(), DomainError> {
+    crate::application::duplicate::merge_duplicates(&source_ids, target_id, &state.book_repo).await
+}
+
+/// Lists the files that directory scans skip.
+pub async fn list_ignored_files(state: &AppState) -> Result<Vec<IgnoredFile>, DomainError> {
+    crate::application::book::list_ignored_files(&state.ignored_file_repo).await
+}
+
+/// Stops ignoring a file.
+pub async fn unignore_file(file_path: String, state: &AppState) -> Result<(), DomainError> {
+    crate::application::book::unignore_file(&file_path, &state.ignored_file_repo).await
+}
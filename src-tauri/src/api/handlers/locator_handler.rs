@@ -0,0 +1,49 @@
+use crate::application::state::AppState;
+use crate::domain::error::DomainError;
+use crate::domain::models::cfi::{CfiTarget, DomPosition};
+use crate::domain::models::locator::{EpubLocation, Locator};
+
+/// Resolves an EPUB CFI to a spine item and document position.
+pub async fn resolve_epub_cfi(
+    book_id: i32,
+    cfi: String,
+    state: &AppState,
+) -> Result<CfiTarget, DomainError> {
+    crate::application::locator::resolve_epub_cfi(book_id, &cfi, &state.book_repo).await
+}
+
+/// Generates an EPUB CFI for a document position.
+pub async fn generate_epub_cfi(
+    book_id: i32,
+    spine_index: usize,
+    start: DomPosition,
+    end: Option<DomPosition>,
+    state: &AppState,
+) -> Result<EpubLocation, DomainError> {
+    crate::application::locator::generate_epub_cfi(
+        book_id,
+        spine_index,
+        start,
+        end,
+        &state.book_repo,
+    )
+    .await
+}
+
+/// Builds the locator of a text selection on a PDF page.
+pub async fn generate_pdf_locator(
+    book_id: i32,
+    page_index: u32,
+    first_char: usize,
+    last_char: usize,
+    state: &AppState,
+) -> Result<Locator, DomainError> {
+    crate::application::locator::generate_pdf_locator(
+        book_id,
+        page_index,
+        first_char,
+        last_char,
+        &state.book_repo,
+    )
+    .await
+}
@@ -1,5 +1,9 @@
+use crate::application::provider::{MetadataComparison, MetadataField};
 use crate::application::state::AppState;
+use crate::domain::dto::book_dto::BookDto;
 use crate::domain::error::DomainError;
+use crate::domain::models::metadata_change::{ChangeSource, MetadataChangeSet};
+use crate::domain::repository::BookMetadataPatch;
 use crate::infrastructure::file_handlers::BookMetadata;
 
 /// Re-parses the ebook file for a book and returns fresh metadata.
@@ -15,20 +19,114 @@ pub async fn list_metadata(state: &AppState) -> Result<Vec<BookMetadata>, Domain
     crate::application::service::book_service::list_metadata(&state.book_repo).await
 }
 
-/// Updates metadata fields for a book found by title search.
-pub async fn update_metadata(
-    book_name: String,
-    title: Option<String>,
-    published_date: Option<String>,
-    isbn: Option<String>,
+/// Applies a validated metadata patch to a book by ID.
+pub async fn update_book_metadata(
+    book_id: i32,
+    patch: BookMetadataPatch,
+    state: &AppState,
+) -> Result<BookDto, DomainError> {
+    crate::application::service::book_service::update_book_metadata(
+        book_id,
+        patch,
+        ChangeSource::User,
+        &state.book_repo,
+        &state.author_repo,
+        &state.publisher_repo,
+        &state.metadata_history_repo,
+    )
+    .await
+}
+
+/// Writes a book's stored metadata (and optionally its cover) into its EPUB file.
+pub async fn write_metadata_to_file(
+    book_id: i32,
+    include_cover: bool,
+    state: &AppState,
+) -> Result<BookDto, DomainError> {
+    crate::application::service::book_service::write_metadata_to_file(
+        book_id,
+        include_cover,
+        &state.book_repo,
+        &state.author_repo,
+        &state.publisher_repo,
+        &state.metadata_history_repo,
+    )
+    .await
+}
+
+/// Looks up candidate metadata for a book with the configured provider.
+pub async fn find_metadata_candidates(
+    book_id: i32,
+    state: &AppState,
+) -> Result<MetadataComparison, DomainError> {
+    crate::application::service::book_service::find_metadata_candidates(
+        book_id,
+        &state.book_repo,
+        &state.author_repo,
+        &state.publisher_repo,
+        &state.metadata_provider,
+    )
+    .await
+}
+
+/// Applies the chosen fields of a provider candidate to a book.
+pub async fn apply_metadata_candidate(
+    book_id: i32,
+    candidate: BookMetadata,
+    fields: Vec<MetadataField>,
+    state: &AppState,
+) -> Result<BookDto, DomainError> {
+    crate::application::service::book_service::apply_metadata_candidate(
+        book_id,
+        candidate,
+        fields,
+        &state.book_repo,
+        &state.author_repo,
+        &state.publisher_repo,
+        &state.metadata_history_repo,
+    )
+    .await
+}
+
+/// Returns a book's metadata edit history, newest first.
+pub async fn get_metadata_history(
+    book_id: i32,
+    state: &AppState,
+) -> Result<Vec<MetadataChangeSet>, DomainError> {
+    crate::application::history::get_metadata_history(
+        book_id,
+        &state.book_repo,
+        &state.metadata_history_repo,
+    )
+    .await
+}
+
+/// Reverts one recorded field change.
+pub async fn revert_metadata_change(
+    change_id: i32,
+    state: &AppState,
+) -> Result<BookDto, DomainError> {
+    crate::application::history::revert_metadata_change(
+        change_id,
+        &state.book_repo,
+        &state.author_repo,
+        &state.publisher_repo,
+        &state.metadata_history_repo,
+    )
+    .await
+}
+
+/// Reverts every field of a recorded change set.
+pub async fn revert_metadata_change_set(
+    change_set_id: i32,
     state: &AppState,
-) -> Result<(), DomainError> {
-    crate::application::service::book_service::update_metadata(
-        &book_name,
-        title.as_deref(),
-        published_date.as_deref(),
-        isbn.as_deref(),
+) -> Result<BookDto, DomainError> {
+    crate::application::history::revert_metadata_change_set(
+        change_set_id,
         &state.book_repo,
+        &state.author_repo,
+        &state.publisher_repo,
+        &state.metadata_history_repo,
     )
     .await
 }
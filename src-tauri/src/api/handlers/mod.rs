@@ -4,8 +4,12 @@
 //! them to the appropriate application-layer function.
 
 pub mod annotation_handler;
+pub mod author_handler;
 pub mod book_handler;
 pub mod bookmark_handler;
 pub mod library_handler;
+pub mod locator_handler;
 pub mod metadata_handler;
+pub mod publisher_handler;
 pub mod reading_progress_handler;
+pub mod work_handler;
@@ -0,0 +1,48 @@
+use crate::application::state::AppState;
+use crate::domain::dto::publisher_dto::PublisherSummary;
+use crate::domain::error::DomainError;
+use crate::domain::models::publisher::Publisher;
+
+/// Returns all publishers with their book counts.
+pub async fn list_publishers(state: &AppState) -> Result<Vec<PublisherSummary>, DomainError> {
+    crate::application::publisher::list_publishers(&state.publisher_repo).await
+}
+
+/// Renames a publisher.
+pub async fn rename_publisher(
+    id: i32,
+    name: String,
+    state: &AppState,
+) -> Result<Publisher, DomainError> {
+    crate::application::publisher::rename_publisher(
+        id,
+        &name,
+        &state.book_repo,
+        &state.author_repo,
+        &state.publisher_repo,
+        &state.metadata_history_repo,
+    )
+    .await
+}
+
+/// Merges the source publishers into the target publisher.
+pub async fn merge_publishers(
+    source_ids: Vec<i32>,
+    target_id: i32,
+    state: &AppState,
+) -> Result<(), DomainError> {
+    crate::application::publisher::merge_publishers(
+        &source_ids,
+        target_id,
+        &state.book_repo,
+        &state.author_repo,
+        &state.publisher_repo,
+        &state.metadata_history_repo,
+    )
+    .await
+}
+
+/// Deletes publishers not referenced by any book.
+pub async fn delete_orphan_publishers(state: &AppState) -> Result<usize, DomainError> {
+    crate::application::publisher::delete_orphan_publishers(&state.publisher_repo).await
+}
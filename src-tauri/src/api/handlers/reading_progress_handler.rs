@@ -1,5 +1,6 @@
 use crate::application::state::AppState;
 use crate::domain::error::DomainError;
+use crate::domain::models::locator::Locator;
 use crate::domain::models::reading_progress::ReadingProgress;
 use crate::domain::repository::*;
 
@@ -15,7 +16,7 @@ pub async fn get_progress(
 /// Creates or updates reading progress for a book (upsert).
 pub async fn update_progress(
     book_id: i32,
-    current_position: String,
+    current_position: Locator,
     chapter_title: Option<String>,
     page_number: Option<i32>,
     progress_percentage: Option<f32>,
@@ -0,0 +1,50 @@
+use crate::application::state::AppState;
+use crate::domain::dto::book_dto::BookDto;
+use crate::domain::dto::work_dto::{WorkDto, WorkSuggestion};
+use crate::domain::error::DomainError;
+
+/// Returns groups of books that look like formats of the same work.
+pub async fn suggest_work_links(state: &AppState) -> Result<Vec<WorkSuggestion>, DomainError> {
+    crate::application::work::suggest_work_links(
+        &state.book_repo,
+        &state.author_repo,
+        &state.publisher_repo,
+    )
+    .await
+}
+
+/// Links books into one work.
+pub async fn link_books(book_ids: Vec<i32>, state: &AppState) -> Result<WorkDto, DomainError> {
+    crate::application::work::link_books(&book_ids, &state.work_repo, &state.book_repo).await
+}
+
+/// Removes a book from its work.
+pub async fn unlink_book(book_id: i32, state: &AppState) -> Result<(), DomainError> {
+    crate::application::work::unlink_book(book_id, &state.work_repo).await
+}
+
+/// Sets the file the reader opens for a work.
+pub async fn set_preferred_format(
+    work_id: i32,
+    book_id: i32,
+    state: &AppState,
+) -> Result<WorkDto, DomainError> {
+    crate::application::work::set_preferred_format(
+        work_id,
+        book_id,
+        &state.work_repo,
+        &state.book_repo,
+    )
+    .await
+}
+
+/// Returns the file the reader should open for a book.
+pub async fn get_preferred_book(book_id: i32, state: &AppState) -> Result<BookDto, DomainError> {
+    crate::application::work::get_preferred_book(
+        book_id,
+        &state.book_repo,
+        &state.author_repo,
+        &state.publisher_repo,
+    )
+    .await
+}
@@ -1,8 +1,30 @@
+use std::path::Path;
 use std::sync::Arc;
 
+use crate::application::locator::epub_location;
 use crate::domain::error::DomainError;
-use crate::domain::models::annotation::Annotation;
+use crate::domain::models::annotation::{
+    Annotation, AnnotationExport, ReanchorOutcome, ReanchorReport, ReanchoredAnnotation,
+};
+use crate::domain::models::cfi::{Cfi, DomPosition};
+use crate::domain::models::locator::{Locator, LocatorTarget, LocatorText};
+use crate::domain::models::text_quote::{QuoteMatch, REANCHOR_CONFIDENCE, TextQuote};
 use crate::domain::repository::*;
+use crate::infrastructure::file_handlers::epub_cfi::{self, SpineDocument};
+use crate::infrastructure::file_handlers::pdf_writer::{self, PdfAnnotation};
+
+/// Weight of closeness to an annotation's old position when ranking the
+/// places its text was found: enough to choose between equally good
+/// matches, too little to outweigh a better one.
+const PROXIMITY_WEIGHT: f32 = 0.05;
+
+/// Matches ranked closer than this to the best one are taken as equally
+/// likely, and the annotation is flagged instead of moved.
+const AMBIGUITY_MARGIN: f32 = 0.01;
+
+/// Colour of exported annotations with no colour, or one that is not
+/// recognised: highlighter yellow.
+const DEFAULT_HIGHLIGHT_COLOR: [f32; 3] = [1.0, 0.92, 0.23];
 
 /// Creates a new annotation (highlight with optional note) for a book.
 ///
@@ -13,11 +35,35 @@ use crate::domain::repository::*;
 ///
 /// # Errors
 ///
-/// Delegates to the repository; returns [`DomainError::Database`] on failure.
+/// Returns [`DomainError::InvalidInput`] when a position fails
+/// [`Locator::validate`](crate::domain::models::locator::Locator::validate) or
+/// the start and end are in different formats or, for a PDF, more than one
+/// page apart, or [`DomainError::Database`] on failure.
 pub async fn add_annotation(
     annotation: NewAnnotation,
     annotation_repo: &Arc<dyn AnnotationRepository>,
 ) -> Result<(), DomainError> {
+    annotation.start_position.validate()?;
+    annotation.end_position.validate()?;
+    let same_format = matches!(
+        (
+            &annotation.start_position.target,
+            &annotation.end_position.target
+        ),
+        (LocatorTarget::Epub(_), LocatorTarget::Epub(_))
+            | (LocatorTarget::Pdf(_), LocatorTarget::Pdf(_))
+            | (LocatorTarget::Legacy { .. }, LocatorTarget::Legacy { .. })
+    );
+    if !same_format {
+        return Err(DomainError::InvalidInput(
+            "Annotation start and end must be locators of the same format".into(),
+        ));
+    }
+    if spans_more_than_two_pages(&annotation.start_position, &annotation.end_position) {
+        return Err(DomainError::InvalidInput(
+            "A PDF annotation can span at most two pages".into(),
+        ));
+    }
     annotation_repo.insert(annotation).await
 }
 
@@ -59,3 +105,428 @@ pub async fn delete_annotation(
 ) -> Result<(), DomainError> {
     annotation_repo.delete(id).await
 }
+
+/// Finds a book's annotations again after its file changed.
+///
+/// Each annotation's highlighted text (or the text captured in its start
+/// locator), together with the text before and after it, is looked up in
+/// the book's content documents with [`TextQuote::find_in`]. Matches are
+/// ranked by score, and among equal ones by closeness to the annotation's
+/// old position. An annotation whose best match scores at least
+/// [`REANCHOR_CONFIDENCE`], and clearly beats the next one, gets new
+/// locators with freshly generated CFIs and text context; one still pointing
+/// at its text is left alone. Any other annotation keeps its positions and
+/// is flagged with `needs_review`.
+///
+/// # Arguments
+///
+/// * `book_id` - The book's database ID.
+/// * `book_repo` - Repository for looking up the book's file.
+/// * `annotation_repo` - Repository for loading and updating the annotations.
+///
+/// # Returns
+///
+/// A [`ReanchorReport`] with the outcome of every annotation of the book.
+///
+/// # Errors
+///
+/// Returns [`DomainError::BookNotFound`] if the book does not exist,
+/// [`DomainError::InvalidInput`] if the book is not an EPUB,
+/// [`DomainError::File`] if the book has no file, [`DomainError::Parse`] if
+/// the file cannot be read, or [`DomainError::Database`] on failure.
+pub async fn reanchor_annotations(
+    book_id: i32,
+    book_repo: &Arc<dyn BookRepository>,
+    annotation_repo: &Arc<dyn AnnotationRepository>,
+) -> Result<ReanchorReport, DomainError> {
+    let book = book_repo
+        .find_by_id(book_id)
+        .await?
+        .ok_or(DomainError::BookNotFound(book_id))?;
+    if book.file_type.as_deref() != Some("epub") {
+        return Err(DomainError::InvalidInput(
+            "Only annotations in EPUB books can be re-anchored".into(),
+        ));
+    }
+    let path = book
+        .file_path
+        .ok_or_else(|| DomainError::File("No file path for book".into()))?;
+
+    let mut report = ReanchorReport {
+        book_id,
+        annotations: Vec::new(),
+    };
+    let annotations = annotation_repo.find_by_book(book_id).await?;
+    if annotations.is_empty() {
+        return Ok(report);
+    }
+
+    let (spine, documents) = epub_cfi::read_spine_documents(&path)
+        .await
+        .map_err(|e| DomainError::Parse(e.to_string()))?;
+    let texts: Vec<String> = documents
+        .iter()
+        .map(|document| {
+            document
+                .as_ref()
+                .map(SpineDocument::text)
+                .unwrap_or_default()
+        })
+        .collect();
+    let lengths: Vec<usize> = texts
+        .iter()
+        .map(|text| text.encode_utf16().count())
+        .collect();
+    let total: usize = lengths.iter().sum();
+    // Where each spine item's text starts in the text of the whole book.
+    let starts: Vec<usize> = lengths
+        .iter()
+        .scan(0, |before, len| {
+            let start = *before;
+            *before += len;
+            Some(start)
+        })
+        .collect();
+    let book_progression = |index: usize, offset: usize| match total {
+        0 => 0.0,
+        total => (starts[index] + offset) as f64 / total as f64,
+    };
+
+    for annotation in annotations {
+        let annotation_id = annotation.id;
+        let flag = |confidence: Option<f32>| ReanchoredAnnotation {
+            annotation_id,
+            outcome: ReanchorOutcome::NeedsReview,
+            confidence,
+        };
+
+        let Some(quote) = text_quote(&annotation) else {
+            annotation_repo.flag_for_review(annotation_id, None).await?;
+            report.annotations.push(flag(None));
+            continue;
+        };
+        let hint = match &annotation.start_position.target {
+            LocatorTarget::Epub(epub) => spine
+                .items
+                .iter()
+                .position(|item| item.href == epub.href)
+                .map(|index| {
+                    let offset = epub.progression.unwrap_or(0.0) * lengths[index] as f64;
+                    book_progression(index, offset as usize)
+                })
+                .or(epub.total_progression),
+            _ => None,
+        };
+
+        let mut ranked: Vec<(f32, usize, QuoteMatch)> = texts
+            .iter()
+            .enumerate()
+            .flat_map(|(index, text)| {
+                quote
+                    .find_in(text)
+                    .into_iter()
+                    .map(move |found| (index, found))
+            })
+            .map(|(index, found)| {
+                let proximity = hint.map_or(0.0, |hint| {
+                    1.0 - (book_progression(index, found.start) - hint).abs() as f32
+                });
+                (found.score + PROXIMITY_WEIGHT * proximity, index, found)
+            })
+            .collect();
+        ranked.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+        let Some(&(rank, index, found)) = ranked.first() else {
+            annotation_repo.flag_for_review(annotation_id, None).await?;
+            report.annotations.push(flag(None));
+            continue;
+        };
+        let ambiguous = ranked
+            .get(1)
+            .is_some_and(|&(next, _, _)| rank - next < AMBIGUITY_MARGIN);
+        let Some(document) = documents[index]
+            .as_ref()
+            .filter(|_| found.score >= REANCHOR_CONFIDENCE && !ambiguous)
+        else {
+            annotation_repo
+                .flag_for_review(annotation_id, Some(found.score))
+                .await?;
+            report.annotations.push(flag(Some(found.score)));
+            continue;
+        };
+
+        let href = &spine.items[index].href;
+        let unchanged = text_offset(&annotation.start_position, href, document, false)
+            == Some(found.start)
+            && text_offset(&annotation.end_position, href, document, true) == Some(found.end);
+        let outcome = if unchanged {
+            if annotation.needs_review {
+                annotation_repo
+                    .update_anchor(
+                        annotation_id,
+                        AnnotationAnchor {
+                            start_position: annotation.start_position,
+                            end_position: annotation.end_position,
+                            highlighted_text: annotation.highlighted_text,
+                            confidence: found.score,
+                        },
+                    )
+                    .await?;
+            }
+            ReanchorOutcome::Unchanged
+        } else {
+            let context = TextQuote::at(&texts[index], found.start, found.end);
+            let text = LocatorText {
+                before: context.prefix,
+                highlight: Some(context.exact.clone()),
+                after: context.suffix,
+            };
+            let locator = |offset: usize| -> Result<Locator, DomainError> {
+                let position = DomPosition {
+                    element_path: Vec::new(),
+                    offset: Some(offset),
+                };
+                let mut location = epub_location(&spine, index, document, &position, None)?;
+                location.total_progression = Some(book_progression(index, offset));
+                Ok(Locator {
+                    target: LocatorTarget::Epub(location),
+                    text: Some(text.clone()),
+                })
+            };
+            annotation_repo
+                .update_anchor(
+                    annotation_id,
+                    AnnotationAnchor {
+                        start_position: locator(found.start)?,
+                        end_position: locator(found.end)?,
+                        highlighted_text: Some(context.exact),
+                        confidence: found.score,
+                    },
+                )
+                .await?;
+            ReanchorOutcome::Moved
+        };
+        report.annotations.push(ReanchoredAnnotation {
+            annotation_id,
+            outcome,
+            confidence: Some(found.score),
+        });
+    }
+
+    Ok(report)
+}
+
+/// Writes a PDF book's annotations into a copy of its file, as standard
+/// annotations any PDF viewer shows.
+///
+/// Each annotation with quads becomes a `Highlight` on the page of its start
+/// position, carrying its note; a highlight whose end is on a later page
+/// also gets the end position's quads on that page. An annotation without
+/// quads becomes a `Text` note showing its note, or else its highlighted
+/// text. Annotations with neither quads nor text, and those whose positions
+/// are not PDF locators, are left out. So are highlights spanning more than
+/// two pages, which [`add_annotation`] refuses: only their first and last
+/// pages have quads, and the pages between would be left unmarked. Their
+/// IDs are returned so the caller can tell the user. The book's own file is
+/// not modified.
+///
+/// # Arguments
+///
+/// * `book_id` - The book's database ID.
+/// * `destination` - Path of the annotated copy; an existing file is
+///   replaced.
+/// * `book_repo` - Repository for looking up the book's file.
+/// * `annotation_repo` - Repository for loading the annotations.
+///
+/// # Returns
+///
+/// An [`AnnotationExport`] with the number of annotations written and the
+/// IDs of the highlights skipped for spanning more than two pages.
+///
+/// # Errors
+///
+/// Returns [`DomainError::BookNotFound`] if the book does not exist,
+/// [`DomainError::InvalidInput`] if the book is not a PDF or `destination`
+/// is its file, [`DomainError::File`] if the book has no file or the copy
+/// cannot be written, or [`DomainError::Database`] on failure.
+pub async fn export_pdf_annotations(
+    book_id: i32,
+    destination: &str,
+    book_repo: &Arc<dyn BookRepository>,
+    annotation_repo: &Arc<dyn AnnotationRepository>,
+) -> Result<AnnotationExport, DomainError> {
+    let book = book_repo
+        .find_by_id(book_id)
+        .await?
+        .ok_or(DomainError::BookNotFound(book_id))?;
+    if book.file_type.as_deref() != Some("pdf") {
+        return Err(DomainError::InvalidInput(
+            "Only annotations in PDF books can be exported".into(),
+        ));
+    }
+    let path = book
+        .file_path
+        .ok_or_else(|| DomainError::File("No file path for book".into()))?;
+    let same_file = Path::new(destination) == Path::new(&path)
+        || std::fs::canonicalize(destination)
+            .is_ok_and(|d| std::fs::canonicalize(&path).is_ok_and(|p| d == p));
+    if same_file {
+        return Err(DomainError::InvalidInput(
+            "Annotations are exported into a copy, not the book's own file".into(),
+        ));
+    }
+
+    let mut exported = Vec::new();
+    let mut count = 0;
+    let mut skipped = Vec::new();
+    for annotation in annotation_repo.find_by_book(book_id).await? {
+        let LocatorTarget::Pdf(start) = &annotation.start_position.target else {
+            continue;
+        };
+        if spans_more_than_two_pages(&annotation.start_position, &annotation.end_position) {
+            skipped.push(annotation.id);
+            continue;
+        }
+        let color = highlight_color(annotation.color.as_deref());
+        let name = format!("stellaron-annotation-{}", annotation.id);
+
+        let written = if start.quads.is_empty() {
+            let contents = annotation
+                .note
+                .clone()
+                .or_else(|| annotation.highlighted_text.clone())
+                .filter(|text| !text.trim().is_empty());
+            contents.map(|contents| {
+                vec![PdfAnnotation {
+                    page_index: start.page_index,
+                    quads: Vec::new(),
+                    contents: Some(contents),
+                    color,
+                    name: name.clone(),
+                }]
+            })
+        } else {
+            let mut written = vec![PdfAnnotation {
+                page_index: start.page_index,
+                quads: start.quads.clone(),
+                contents: annotation.note.clone(),
+                color,
+                name: name.clone(),
+            }];
+            if let LocatorTarget::Pdf(end) = &annotation.end_position.target
+                && end.page_index > start.page_index
+                && !end.quads.is_empty()
+            {
+                written.push(PdfAnnotation {
+                    page_index: end.page_index,
+                    quads: end.quads.clone(),
+                    contents: None,
+                    color,
+                    name: format!("{}-end", name),
+                });
+            }
+            Some(written)
+        };
+        if let Some(written) = written {
+            exported.extend(written);
+            count += 1;
+        }
+    }
+
+    pdf_writer::write_pdf_annotations(path, destination.to_string(), exported)
+        .await
+        .map_err(|e| DomainError::File(e.to_string()))?;
+    Ok(AnnotationExport {
+        written: count,
+        skipped,
+    })
+}
+
+/// Whether a PDF annotation's end is more than one page after its start.
+///
+/// Its locators only hold quads for the first and last page, so the pages
+/// in between cannot be highlighted.
+fn spans_more_than_two_pages(start: &Locator, end: &Locator) -> bool {
+    match (&start.target, &end.target) {
+        (LocatorTarget::Pdf(start), LocatorTarget::Pdf(end)) => {
+            end.page_index.saturating_sub(start.page_index) > 1
+        }
+        _ => false,
+    }
+}
+
+/// Reads an annotation colour: `#rgb`, `#rrggbb` or a common colour name.
+fn highlight_color(color: Option<&str>) -> [f32; 3] {
+    let Some(color) = color.map(|c| c.trim().to_lowercase()) else {
+        return DEFAULT_HIGHLIGHT_COLOR;
+    };
+    if let Some(hex) = color.strip_prefix('#') {
+        let digits: Option<Vec<u8>> = match hex.len() {
+            3 => hex
+                .chars()
+                .map(|c| c.to_digit(16).map(|d| d as u8 * 17))
+                .collect(),
+            6 => (0..6)
+                .step_by(2)
+                .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+                .collect(),
+            _ => None,
+        };
+        return match digits.as_deref() {
+            Some(&[r, g, b]) => [r, g, b].map(|c| c as f32 / 255.0),
+            _ => DEFAULT_HIGHLIGHT_COLOR,
+        };
+    }
+    match color.as_str() {
+        "green" => [0.55, 0.9, 0.45],
+        "blue" => [0.45, 0.75, 1.0],
+        "pink" => [1.0, 0.55, 0.75],
+        "red" => [1.0, 0.45, 0.45],
+        "orange" => [1.0, 0.7, 0.3],
+        "purple" => [0.75, 0.55, 1.0],
+        _ => DEFAULT_HIGHLIGHT_COLOR,
+    }
+}
+
+/// Returns the text an annotation highlights, with its context, or `None`
+/// when the annotation has no text to look for.
+fn text_quote(annotation: &Annotation) -> Option<TextQuote> {
+    let start = annotation.start_position.text.as_ref();
+    let exact = annotation
+        .highlighted_text
+        .clone()
+        .or_else(|| start.and_then(|text| text.highlight.clone()))
+        .filter(|text| !text.trim().is_empty())?;
+    Some(TextQuote {
+        exact,
+        prefix: start.and_then(|text| text.before.clone()),
+        suffix: annotation
+            .end_position
+            .text
+            .as_ref()
+            .and_then(|text| text.after.clone()),
+    })
+}
+
+/// Returns the offset in a spine item's text that an EPUB locator's CFI
+/// resolves to, or `None` when it points elsewhere or does not resolve.
+/// The end of a range CFI is used for `end`.
+fn text_offset(
+    locator: &Locator,
+    href: &str,
+    document: &SpineDocument,
+    end: bool,
+) -> Option<usize> {
+    let LocatorTarget::Epub(epub) = &locator.target else {
+        return None;
+    };
+    if epub.href != href {
+        return None;
+    }
+    let cfi = Cfi::parse(epub.cfi.as_deref()?).ok()?;
+    let point = match &cfi.end {
+        Some(range_end) if end => range_end,
+        _ => &cfi.start,
+    };
+    document.text_offset(&document.resolve(point).ok()?).ok()
+}
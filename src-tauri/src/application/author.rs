@@ -0,0 +1,143 @@
+use std::sync::Arc;
+
+use crate::application::history::{record_snapshot_changes, snapshot_books};
+use crate::domain::dto::author_dto::AuthorSummary;
+use crate::domain::error::DomainError;
+use crate::domain::models::author::Author;
+use crate::domain::models::metadata_change::ChangeSource;
+use crate::domain::repository::*;
+
+/// Returns every author with the number of books linked to them.
+///
+/// # Arguments
+///
+/// * `author_repo` - Repository for querying authors.
+///
+/// # Returns
+///
+/// A vector of [`AuthorSummary`] entries ordered by name.
+///
+/// # Errors
+///
+/// Delegates to the repository; returns [`DomainError::Database`] on failure.
+pub async fn list_authors(
+    author_repo: &Arc<dyn AuthorRepository>,
+) -> Result<Vec<AuthorSummary>, DomainError> {
+    author_repo.list_with_counts().await
+}
+
+/// Renames an author. Surrounding whitespace is trimmed from the new name.
+/// The renamed author is recorded in the edit history of each of their books.
+///
+/// # Arguments
+///
+/// * `id` - The author's database ID.
+/// * `name` - The new name.
+/// * `book_repo` - Repository for loading the affected books.
+/// * `author_repo` - Repository for updating the author.
+/// * `publisher_repo` - Repository for resolving publisher names.
+/// * `history_repo` - Repository for recording the changes.
+///
+/// # Returns
+///
+/// The renamed [`Author`].
+///
+/// # Errors
+///
+/// Returns [`DomainError::InvalidInput`] if the name is empty or already used
+/// by another author, [`DomainError::NotFound`] if the author does not exist,
+/// or [`DomainError::Database`] on failure.
+pub async fn rename_author(
+    id: i32,
+    name: &str,
+    book_repo: &Arc<dyn BookRepository>,
+    author_repo: &Arc<dyn AuthorRepository>,
+    publisher_repo: &Arc<dyn PublisherRepository>,
+    history_repo: &Arc<dyn MetadataHistoryRepository>,
+) -> Result<Author, DomainError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(DomainError::InvalidInput(
+            "Author name cannot be empty".into(),
+        ));
+    }
+    let book_ids = author_repo.find_book_ids(&[id]).await?;
+    let before = snapshot_books(&book_ids, book_repo, author_repo, publisher_repo).await?;
+    let renamed = author_repo.rename(id, name).await?;
+    record_snapshot_changes(
+        before,
+        ChangeSource::User,
+        book_repo,
+        author_repo,
+        publisher_repo,
+        history_repo,
+    )
+    .await?;
+    Ok(renamed)
+}
+
+/// Merges duplicate authors into one, moving all their book links to the
+/// target and deleting the duplicates.
+///
+/// The change is recorded in the edit history of each book of a merged
+/// author.
+///
+/// # Arguments
+///
+/// * `source_ids` - Authors to merge away.
+/// * `target_id` - The author that remains.
+/// * `book_repo` - Repository for loading the affected books.
+/// * `author_repo` - Repository for merging authors.
+/// * `publisher_repo` - Repository for resolving publisher names.
+/// * `history_repo` - Repository for recording the changes.
+///
+/// # Errors
+///
+/// Returns [`DomainError::InvalidInput`] if there is nothing to merge,
+/// [`DomainError::NotFound`] if the target does not exist, or
+/// [`DomainError::Database`] on failure.
+pub async fn merge_authors(
+    source_ids: &[i32],
+    target_id: i32,
+    book_repo: &Arc<dyn BookRepository>,
+    author_repo: &Arc<dyn AuthorRepository>,
+    publisher_repo: &Arc<dyn PublisherRepository>,
+    history_repo: &Arc<dyn MetadataHistoryRepository>,
+) -> Result<(), DomainError> {
+    if source_ids.iter().all(|&id| id == target_id) {
+        return Err(DomainError::InvalidInput(
+            "Select at least one author other than the merge target".into(),
+        ));
+    }
+    let book_ids = author_repo.find_book_ids(source_ids).await?;
+    let before = snapshot_books(&book_ids, book_repo, author_repo, publisher_repo).await?;
+    author_repo.merge(source_ids, target_id).await?;
+    record_snapshot_changes(
+        before,
+        ChangeSource::User,
+        book_repo,
+        author_repo,
+        publisher_repo,
+        history_repo,
+    )
+    .await
+}
+
+/// Deletes authors that are not linked to any book.
+///
+/// # Arguments
+///
+/// * `author_repo` - Repository for deleting authors.
+///
+/// # Returns
+///
+/// The number of deleted authors.
+///
+/// # Errors
+///
+/// Delegates to the repository; returns [`DomainError::Database`] on failure.
+pub async fn delete_orphan_authors(
+    author_repo: &Arc<dyn AuthorRepository>,
+) -> Result<usize, DomainError> {
+    author_repo.delete_orphans().await
+}
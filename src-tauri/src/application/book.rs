@@ -1,14 +1,31 @@
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use std::sync::Arc;
 
-use serde::Serialize;
+use chrono::{Duration, Utc};
+use serde::{Deserialize, Serialize};
 
-use crate::domain::dto::book_dto::BookDto;
+use crate::application::history::metadata_snapshot;
+use crate::domain::dto::book_dto::{BookDto, BookFormat, BookPage};
 use crate::domain::error::DomainError;
+use crate::domain::models::annotation::ReanchorReport;
+use crate::domain::models::contributor::Contributor;
+use crate::domain::models::filename_pattern::{FilenameMetadata, FilenamePattern};
+use crate::domain::models::identifier::{Identifier, SCHEME_ISBN};
+use crate::domain::models::ignored_file::IgnoredFile;
+use crate::domain::models::isbn::Isbn;
+use crate::domain::models::metadata_change::{ChangeSource, MetadataSnapshot};
+use crate::domain::models::sort_key;
 use crate::domain::repository::*;
+use crate::infrastructure::file_handlers::BookMetadata;
+use crate::infrastructure::file_handlers::cover_handler;
 use crate::infrastructure::file_handlers::epub_handler;
 use crate::infrastructure::file_handlers::pdf_handler;
-use crate::infrastructure::file_handlers::pdf_handler::PdfPage;
+use crate::infrastructure::file_handlers::pdf_handler::{
+    PdfImageFormat, PdfPage, PdfRenderOptions,
+};
+use crate::infrastructure::file_handlers::removal_handler;
+use crate::utils::file::compute_checksum;
 
 /// Serialized content returned by [`read_book`], tagged by file format.
 #[derive(Serialize, Clone)]
@@ -20,6 +37,53 @@ pub enum BookContent {
     Pdf(PdfPage),
 }
 
+/// What [`remove_book`] does with the book's file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RemoveMode {
+    /// Leave the file where it is.
+    #[default]
+    KeepFile,
+    /// Move the file to the operating system's trash.
+    TrashFile,
+    /// Delete the file permanently.
+    DeleteFile,
+}
+
+/// Filename patterns used when `FILENAME_PATTERNS` is not set, in the order
+/// they are tried.
+pub const DEFAULT_FILENAME_PATTERNS: [&str; 2] =
+    ["{author} - {title} ({year})", "{author} - {title}"];
+
+/// Days a book stays in the trash when `TRASH_RETENTION_DAYS` is not set.
+pub const DEFAULT_TRASH_RETENTION_DAYS: i64 = 30;
+
+/// What [`scan_directory`] reports besides the books it imported.
+#[derive(Serialize, Clone)]
+pub struct ScanReport {
+    /// Error messages for files that failed to import or refresh.
+    pub errors: Vec<String>,
+    /// Re-anchoring results for library files whose contents changed.
+    pub reanchored: Vec<ReanchorReport>,
+}
+
+/// What each filename pattern extracts from one file, returned by
+/// [`preview_filename_patterns`].
+#[derive(Serialize, Clone)]
+pub struct FilenamePatternPreview {
+    pub path: String,
+    /// One entry per pattern, in the order the patterns are tried.
+    pub matches: Vec<FilenamePatternMatch>,
+}
+
+/// The result of one pattern for one file.
+#[derive(Serialize, Clone)]
+pub struct FilenamePatternMatch {
+    pub pattern: String,
+    /// Extracted fields, or `None` when the pattern does not match.
+    pub metadata: Option<FilenameMetadata>,
+}
+
 /// Retrieves a single book by ID, resolved to a [`BookDto`] with author and
 /// publisher names.
 ///
@@ -33,7 +97,7 @@ pub enum BookContent {
 /// # Returns
 ///
 /// `Ok(None)` if no book with the given ID exists. Otherwise the book as a
-/// `BookDto` with its first author and publisher names included.
+/// `BookDto` with its contributors and publisher name included.
 ///
 /// # Errors
 ///
@@ -50,73 +114,156 @@ pub async fn get_book(
         None => return Ok(None),
     };
 
-    let authors = author_repo.get_authors_by_book(book.id).await?;
-    let author = authors.first().map(|a| a.name.clone());
+    let contributors = author_repo.get_contributors_by_book(book.id).await?;
 
     let publisher = match book.publisher_id {
         Some(pid) => publisher_repo.find_by_id(pid).await?.map(|p| p.name),
         None => None,
     };
 
-    Ok(Some(BookDto::new(&book, author, publisher)))
+    let tags = book_repo.get_tags(book.id).await?;
+    let subjects = book_repo.get_subjects(book.id).await?;
+    let identifiers = book_repo.get_identifiers(book.id).await?;
+    let formats = book_repo.get_formats(book.id).await?;
+
+    Ok(Some(BookDto::new(
+        &book,
+        contributors,
+        publisher,
+        tags,
+        subjects,
+        identifiers,
+        formats,
+    )))
 }
 
 /// Returns the cover image bytes for the given book.
 ///
-/// For PDFs the first page is rendered as a PNG at 150 DPI. For EPUBs the
-/// embedded cover image is extracted. Returns `None` if no cover is available
-/// or extraction fails.
+/// A previously cached cover (`cover_image_path`) is returned directly, after
+/// computing its palette and BlurHash if the book has none yet. Otherwise
+/// the cover is extracted from the file: for PDFs the first page is rendered as
+/// a JPEG thumbnail [`PDF_COVER_WIDTH`](cover_handler::PDF_COVER_WIDTH) pixels
+/// wide, for EPUBs the embedded cover image is used. Books without
+/// any cover art get a deterministic placeholder generated from their title,
+/// first author and checksum. Extracted and generated covers are written to the
+/// cover cache and recorded on the book, placeholders flagged as such, so later
+/// calls skip the work.
 ///
 /// # Arguments
 ///
 /// * `book_id` - The book's database ID.
-/// * `book_repo` - Repository for looking up the book record.
+/// * `book_repo` - Repository for looking up and updating the book record.
+/// * `author_repo` - Repository for resolving the author printed on placeholders.
 ///
 /// # Returns
 ///
-/// `Ok(None)` when the file type has no cover support or extraction fails.
+/// `Ok(None)` only when no cover could be extracted or generated.
 /// `Ok(Some(bytes))` with the raw cover image bytes on success.
 ///
 /// # Errors
 ///
-/// Returns [`DomainError::BookNotFound`] if no book has the given ID.
+/// Returns [`DomainError::BookNotFound`] if no book has the given ID, or
+/// [`DomainError::File`] if an extracted or generated cover cannot be
+/// written to the cover cache.
 ///
 /// # Examples
 ///
 /// ```rust,ignore
-/// let bytes = get_cover(42, &book_repo).await?;
+/// let bytes = get_cover(42, &book_repo, &author_repo).await?;
 /// ```
 pub async fn get_cover(
     book_id: i32,
     book_repo: &Arc<dyn BookRepository>,
+    author_repo: &Arc<dyn AuthorRepository>,
 ) -> Result<Option<Vec<u8>>, DomainError> {
     let book = book_repo
         .find_by_id(book_id)
         .await?
         .ok_or(DomainError::BookNotFound(book_id))?;
 
-    match book.file_type.as_deref() {
+    if let Some(cached) = book.cover_image_path.as_deref()
+        && let Ok(bytes) = tokio::fs::read(cached).await
+    {
+        // Covers cached before palettes were stored get one on first use.
+        if book.blurhash.is_none()
+            && let Ok(palette) = cover_handler::extract_cover_palette(bytes.clone()).await
+        {
+            let update = UpdateBook {
+                dominant_colors: Some(palette.dominant_colors),
+                blurhash: Some(palette.blurhash),
+                ..Default::default()
+            };
+            book_repo.update(book.id, update).await?;
+        }
+        return Ok(Some(bytes));
+    }
+
+    let extracted = match book.file_type.as_deref() {
         Some("pdf") => {
             let path = book.file_path.as_deref()
                 .ok_or_else(|| DomainError::File("No file path for PDF book".into()))?;
-            match pdf_handler::get_pdf_cover(path).await {
-                Ok(img) => Ok(Some(img)),
-                Err(_) => Ok(None),
+            let thumbnail = PdfRenderOptions {
+                target_width: Some(cover_handler::PDF_COVER_WIDTH),
+                format: PdfImageFormat::Jpeg,
+                ..Default::default()
+            };
+            pdf_handler::get_pdf_cover(path, &thumbnail).await.ok()
+        }
+        Some("epub") => epub_handler::get_cover_image_by_book_id(book_id).await.ok(),
+        _ => None,
+    };
+
+    let checksum = book.checksum.clone().unwrap_or_else(|| book.id.to_string());
+    let placeholder = extracted.as_ref().is_none_or(|img| img.is_empty());
+    let cover = match extracted.filter(|img| !img.is_empty()) {
+        Some(img) => img,
+        None => {
+            let authors = author_repo.get_authors_by_book(book.id).await?;
+            let author = authors.first().map(|a| a.name.as_str());
+            let generated =
+                cover_handler::generate_placeholder_cover(&book.title, author, &checksum).await;
+            match generated {
+                Ok(img) => img,
+                Err(_) => return Ok(None),
             }
         }
-        Some("epub") => match epub_handler::get_cover_image_by_book_id(book_id).await {
-            Ok(img) => Ok(Some(img)),
-            Err(_) => Ok(None),
-        },
-        _ => Ok(None),
+    };
+
+    let cached_path = cover_handler::cache_cover(&checksum, &cover)
+        .await
+        .map_err(|e| {
+            DomainError::File(format!("Failed to cache cover for book {}: {}", book.id, e))
+        })?;
+    let mut update = UpdateBook {
+        cover_image_path: Some(cached_path.to_string_lossy().to_string()),
+        cover_is_placeholder: Some(placeholder),
+        ..Default::default()
+    };
+    if book.blurhash.is_none()
+        && let Ok(palette) = cover_handler::extract_cover_palette(cover.clone()).await
+    {
+        update.dominant_colors = Some(palette.dominant_colors);
+        update.blurhash = Some(palette.blurhash);
     }
+    book_repo.update(book.id, update).await?;
+
+    Ok(Some(cover))
 }
 
 /// Imports a single ebook file into the library.
 ///
-/// Parses metadata from the file, checks for duplicates via checksum, creates
-/// author and publisher records as needed, and inserts the book with all
-/// foreign-key links in a single transaction.
+/// Parses metadata from the file and fills in a missing or placeholder
+/// title, missing authors, date and ISBN, and the series from the first
+/// [`filename_patterns`] entry that matches the path. Then checks for
+/// duplicates via checksum and ISBN, creates author and publisher records as
+/// needed, and inserts the book with all foreign-key links in a single
+/// transaction. When the file carries embedded cover art, its dominant colors
+/// and BlurHash are stored with the book. The imported metadata is recorded
+/// as the first entry of the book's edit history.
+///
+/// Re-importing a file whose book is in the trash restores that book
+/// instead, pointing it at the imported path and no longer ignoring its old
+/// one, so a book whose file was deleted can be brought back from a copy.
 ///
 /// # Arguments
 ///
@@ -125,6 +272,8 @@ pub async fn get_cover(
 /// * `author_repo` - Repository for finding or creating authors.
 /// * `_book_author_repo` - Repository for linking authors to the book.
 /// * `publisher_repo` - Repository for finding or creating the publisher.
+/// * `ignored_file_repo` - Repository for un-ignoring a restored book's file.
+/// * `history_repo` - Repository for recording the imported metadata.
 ///
 /// # Returns
 ///
@@ -132,16 +281,21 @@ pub async fn get_cover(
 ///
 /// # Errors
 ///
-/// Returns [`DomainError::DuplicateBook`] when a book with the same SHA-256
-/// checksum already exists. Returns [`DomainError::Parse`] when the file
-/// cannot be read or parsed. Returns [`DomainError::File`] for unsupported
-/// file extensions.
+/// Returns [`DomainError::DuplicateBook`] when a library book with the same
+/// SHA-256 checksum, or one of the same file type with the same ISBN (in
+/// either its 10- or 13-digit form), already exists. Returns
+/// [`DomainError::Parse`] when the file cannot be read or parsed. Returns
+/// [`DomainError::File`] for unsupported file extensions, and
+/// [`DomainError::InvalidInput`] when `FILENAME_PATTERNS` holds an invalid
+/// pattern.
 pub async fn import_book(
     file_path: &Path,
     book_repo: &Arc<dyn BookRepository>,
     author_repo: &Arc<dyn AuthorRepository>,
     _book_author_repo: &Arc<dyn BookAuthorRepository>,
     publisher_repo: &Arc<dyn PublisherRepository>,
+    ignored_file_repo: &Arc<dyn IgnoredFileRepository>,
+    history_repo: &Arc<dyn MetadataHistoryRepository>,
 ) -> Result<BookDto, DomainError> {
     let ext = file_path
         .extension()
@@ -149,7 +303,7 @@ pub async fn import_book(
         .unwrap_or("")
         .to_lowercase();
 
-    let (metadata, file_type) = match ext.as_str() {
+    let (mut metadata, file_type) = match ext.as_str() {
         "pdf" => {
             let meta = pdf_handler::parse_pdf_meta(file_path.to_string_lossy().to_string())
                 .await
@@ -167,16 +321,70 @@ pub async fn import_book(
         }
     };
 
-    if let Some(_existing) = book_repo.find_by_checksum(&metadata.checksum).await? {
-        return Err(DomainError::DuplicateBook(
-            file_path.to_string_lossy().to_string(),
-        ));
+    let from_filename = fill_from_filename(&mut metadata, file_path, &filename_patterns()?);
+
+    if let Some(existing) = book_repo.find_by_checksum(&metadata.checksum).await? {
+        if existing.deleted_at.is_none() {
+            return Err(DomainError::DuplicateBook(
+                file_path.to_string_lossy().to_string(),
+            ));
+        }
+        // Bringing a trashed file back restores its highlights and progress
+        // instead of starting over. The file may come from another path, for
+        // instance a copy of one that was deleted when the book was trashed.
+        if existing.file_path.as_deref() != Some(metadata.file_path.as_str()) {
+            book_repo
+                .update(
+                    existing.id,
+                    UpdateBook {
+                        file_path: Some(metadata.file_path.clone()),
+                        ..Default::default()
+                    },
+                )
+                .await?;
+        }
+        book_repo.restore(existing.id).await?;
+        if let Some(old_path) = existing.file_path.as_deref() {
+            ignored_file_repo.remove(old_path).await?;
+        }
+        ignored_file_repo.remove(&metadata.file_path).await?;
+        return get_book(existing.id, book_repo, author_repo, publisher_repo)
+            .await?
+            .ok_or(DomainError::BookNotFound(existing.id));
+    }
+
+    // A different file of the same edition (re-encoded, re-packaged) shares
+    // its ISBN but not its checksum. A file in another format is not a
+    // duplicate; it can be linked into the same work.
+    if let Some(isbn) = metadata.isbn.as_deref().and_then(|i| Isbn::parse(i).ok())
+        && book_repo
+            .find_by_isbn(&isbn)
+            .await?
+            .iter()
+            .any(|b| b.deleted_at.is_none() && b.file_type.as_deref() == Some(file_type.as_str()))
+    {
+        return Err(DomainError::DuplicateBook(format!(
+            "{} (ISBN {})",
+            file_path.to_string_lossy(),
+            isbn
+        )));
     }
 
-    let mut author_ids = Vec::new();
-    for author_name in &metadata.authors {
-        let author = author_repo.find_or_create(author_name).await?;
-        author_ids.push((author.id, author.name.clone()));
+    let mut author_links: Vec<NewBookAuthor> = Vec::new();
+    for contributor in &metadata.contributors {
+        let author = author_repo.find_or_create(&contributor.name).await?;
+        // The same person may be listed twice in one role; keep the first credit.
+        if author_links
+            .iter()
+            .any(|l| l.author_id == author.id && l.role == contributor.role)
+        {
+            continue;
+        }
+        author_links.push(NewBookAuthor {
+            author_id: author.id,
+            role: contributor.role.clone(),
+            sort_name: contributor.sort_name.clone(),
+        });
     }
 
     let publisher_id = if let Some(pub_name) = metadata.publishers.first() {
@@ -186,6 +394,11 @@ pub async fn import_book(
         None
     };
 
+    let palette = match metadata.cover_data.clone() {
+        Some((bytes, _mime)) => cover_handler::extract_cover_palette(bytes).await.ok(),
+        None => None,
+    };
+
     let book = book_repo
         .import_with_links(
             NewBook {
@@ -197,56 +410,246 @@ pub async fn import_book(
                 file_path: metadata.file_path.clone(),
                 cover_image_path: None,
                 checksum: Some(metadata.checksum.clone()),
+                dominant_colors: palette
+                    .as_ref()
+                    .map(|p| p.dominant_colors.clone())
+                    .unwrap_or_default(),
+                blurhash: palette.map(|p| p.blurhash),
+                language: metadata.language.clone(),
+                description: metadata.description.clone(),
+                rights: metadata.rights.clone(),
+                modified_date: metadata.modified_date.clone(),
+                epub_version: metadata.epub_version.clone(),
+                series: from_filename.as_ref().and_then(|m| m.series.clone()),
+                series_index: from_filename.and_then(|m| m.series_index),
+                title_sort: Some(sort_key::title_sort(
+                    &metadata.title,
+                    metadata.language.as_deref(),
+                )),
+                author_sort: sort_key::authors_sort(&metadata.contributors),
+                subjects: metadata.subjects.clone(),
+                identifiers: metadata.identifiers.clone(),
             },
-            &author_ids,
+            &author_links,
             publisher_id,
         )
         .await?;
 
-    Ok(BookDto::new(
+    let dto = BookDto::new(
         &book,
-        metadata.authors.first().cloned(),
+        metadata.contributors.clone(),
         metadata.publishers.first().cloned(),
-    ))
+        Vec::new(),
+        metadata.subjects,
+        metadata.identifiers,
+        vec![BookFormat {
+            book_id: book.id,
+            file_type: book.file_type.clone().unwrap_or_default(),
+        }],
+    );
+    let imported = MetadataSnapshot::default().diff(&metadata_snapshot(&dto));
+    history_repo
+        .record(book.id, ChangeSource::Import, &imported)
+        .await?;
+
+    Ok(dto)
 }
 
-/// Returns all books in the library, each resolved to a [`BookDto`].
+/// Returns the filename patterns applied during import.
+///
+/// Read from the `FILENAME_PATTERNS` environment variable, which lists
+/// patterns separated by `;` in the order they are tried, defaulting to
+/// [`DEFAULT_FILENAME_PATTERNS`]. Empty entries are skipped.
+///
+/// # Errors
+///
+/// Returns [`DomainError::InvalidInput`] naming the first invalid pattern.
+pub fn filename_patterns() -> Result<Vec<FilenamePattern>, DomainError> {
+    dotenvy::dotenv().ok();
+    let sources: Vec<String> = match std::env::var("FILENAME_PATTERNS") {
+        Ok(value) if !value.trim().is_empty() => value.split(';').map(str::to_string).collect(),
+        _ => DEFAULT_FILENAME_PATTERNS
+            .iter()
+            .map(|p| p.to_string())
+            .collect(),
+    };
+    sources
+        .iter()
+        .filter(|source| !source.trim().is_empty())
+        .map(|source| FilenamePattern::parse(source))
+        .collect()
+}
+
+/// Shows what each filename pattern would extract from a set of files.
+///
+/// Nothing is imported or written; the files do not need to exist.
 ///
 /// # Arguments
 ///
-/// * `book_repo` - Repository for listing all book records.
-/// * `author_repo` - Repository for resolving author names per book.
-/// * `publisher_repo` - Repository for resolving publisher names per book.
+/// * `patterns` - Patterns to try, in order. When empty, the configured
+///   [`filename_patterns`] are used.
+/// * `paths` - File paths to match the patterns against.
 ///
 /// # Returns
 ///
-/// A vector of [`BookDto`] for every book in the database.
+/// One [`FilenamePatternPreview`] per path, with the result of every
+/// pattern.
 ///
 /// # Errors
 ///
-/// Delegates to repository methods; returns [`DomainError::Database`] on
-/// query failures.
-pub async fn list_books(
-    book_repo: &Arc<dyn BookRepository>,
-    author_repo: &Arc<dyn AuthorRepository>,
-    publisher_repo: &Arc<dyn PublisherRepository>,
-) -> Result<Vec<BookDto>, DomainError> {
-    let books = book_repo.find_all().await?;
-    let mut dtos = Vec::new();
+/// Returns [`DomainError::InvalidInput`] when a given or configured pattern
+/// is invalid.
+pub fn preview_filename_patterns(
+    patterns: &[String],
+    paths: &[String],
+) -> Result<Vec<FilenamePatternPreview>, DomainError> {
+    let patterns = if patterns.is_empty() {
+        filename_patterns()?
+    } else {
+        patterns
+            .iter()
+            .map(|p| FilenamePattern::parse(p))
+            .collect::<Result<Vec<_>, _>>()?
+    };
 
-    for book in books {
-        let authors = author_repo.get_authors_by_book(book.id).await?;
-        let author = authors.first().map(|a| a.name.clone());
+    Ok(paths
+        .iter()
+        .map(|path| FilenamePatternPreview {
+            path: path.clone(),
+            matches: patterns
+                .iter()
+                .map(|pattern| FilenamePatternMatch {
+                    pattern: pattern.to_string(),
+                    metadata: pattern.extract(Path::new(path)),
+                })
+                .collect(),
+        })
+        .collect())
+}
 
-        let publisher = match book.publisher_id {
-            Some(pid) => publisher_repo.find_by_id(pid).await?.map(|p| p.name),
-            None => None,
-        };
+/// Fills in metadata the file does not provide from the first pattern that
+/// matches `path`.
+///
+/// The title is replaced only when it is a placeholder ("Unknown Title" or
+/// the bare file name), authors only when none besides "Unknown Author" are
+/// credited, and the date and ISBN only when missing.
+///
+/// # Returns
+///
+/// The match, whose series and series index the caller stores as they
+/// never come from the file itself.
+fn fill_from_filename(
+    metadata: &mut BookMetadata,
+    path: &Path,
+    patterns: &[FilenamePattern],
+) -> Option<FilenameMetadata> {
+    let found = patterns.iter().find_map(|p| p.extract(path))?;
 
-        dtos.push(BookDto::new(&book, author, publisher));
+    if let Some(title) = &found.title
+        && is_placeholder_title(&metadata.title, path)
+    {
+        metadata.title = title.clone();
     }
 
-    Ok(dtos)
+    let has_author = metadata
+        .contributors
+        .iter()
+        .any(|c| c.is_author() && c.name != "Unknown Author");
+    if !has_author && !found.authors.is_empty() {
+        metadata
+            .contributors
+            .retain(|c| !(c.is_author() && c.name == "Unknown Author"));
+        for (i, name) in found.authors.iter().enumerate() {
+            metadata
+                .contributors
+                .insert(i, Contributor::author(name.clone()));
+        }
+    }
+
+    if metadata.published_date.is_none() {
+        metadata.published_date = found.year.map(|y| y.to_string());
+    }
+
+    if metadata.isbn.is_none()
+        && let Some(isbn) = &found.isbn
+    {
+        metadata.isbn = Some(isbn.clone());
+        if !metadata
+            .identifiers
+            .iter()
+            .any(|i| i.scheme == SCHEME_ISBN && &i.value == isbn)
+        {
+            metadata
+                .identifiers
+                .push(Identifier::new(SCHEME_ISBN, isbn.clone()));
+        }
+    }
+
+    Some(found)
+}
+
+/// Whether a parsed title is a placeholder rather than real metadata: empty,
+/// "Unknown Title", or the file name the PDF parser falls back to.
+fn is_placeholder_title(title: &str, path: &Path) -> bool {
+    let normalize = |s: &str| {
+        s.replace('_', " ")
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    let title = normalize(title);
+    let stem = path
+        .file_stem()
+        .map(|s| normalize(&s.to_string_lossy()))
+        .unwrap_or_default();
+    title.is_empty() || title == "Unknown Title" || title == stem
+}
+
+/// Returns all books in the library, each resolved to a [`BookDto`].
+///
+/// Equivalent to [`query_books`] with the default query (every book, ordered
+/// by title).
+///
+/// # Arguments
+///
+/// * `book_repo` - Repository for listing book records.
+///
+/// # Returns
+///
+/// A vector of [`BookDto`] for every book in the database.
+///
+/// # Errors
+///
+/// Delegates to the repository; returns [`DomainError::Database`] on query
+/// failures.
+pub async fn list_books(book_repo: &Arc<dyn BookRepository>) -> Result<Vec<BookDto>, DomainError> {
+    Ok(book_repo.query(&BookQuery::default()).await?.books)
+}
+
+/// Returns a filtered, sorted and paginated page of books.
+///
+/// # Arguments
+///
+/// * `query` - Filters (author, publisher, file type, added date range,
+///   reading status, language, subject, publication year range), sort key
+///   (including publication date) and direction, and offset or cursor
+///   pagination.
+/// * `book_repo` - Repository that runs the joined listing query.
+///
+/// # Returns
+///
+/// A [`BookPage`] with the books on the page, the total number of matches,
+/// and the cursor for the next page.
+///
+/// # Errors
+///
+/// Returns [`DomainError::Parse`] for a malformed cursor, or
+/// [`DomainError::Database`] on query failures.
+pub async fn query_books(
+    query: BookQuery,
+    book_repo: &Arc<dyn BookRepository>,
+) -> Result<BookPage, DomainError> {
+    book_repo.query(&query).await
 }
 
 /// Reads and returns the full HTML content of an EPUB file.
@@ -301,7 +704,7 @@ pub async fn read_book(path: &str, file_type: &str) -> Result<BookContent, Domai
             Ok(BookContent::Epub(html))
         }
         "pdf" => {
-            let page = pdf_handler::read_pdf_page(path, 0)
+            let page = pdf_handler::read_pdf_page(path, 0, &PdfRenderOptions::default())
                 .await
                 .map_err(|e| DomainError::Parse(e.to_string()))?;
             Ok(BookContent::Pdf(page))
@@ -310,29 +713,342 @@ pub async fn read_book(path: &str, file_type: &str) -> Result<BookContent, Domai
     }
 }
 
-/// Removes a book from the library by ID.
+/// Removes a book from the library by moving it to the trash.
+///
+/// The book keeps its bookmarks, annotations and reading progress until the
+/// trash is emptied or the book is purged after the retention period (see
+/// [`purge_expired_trash`]). Use [`restore_book`] to bring it back.
 ///
-/// Associated records (bookmarks, annotations, reading progress, book-author
-/// links) are cascade-deleted by SQLite foreign keys.
+/// Depending on `mode` the file is kept, moved to the operating system's
+/// trash or deleted; its path is added to the ignored files either way so
+/// [`scan_directory`] does not import it again.
 ///
 /// # Arguments
 ///
 /// * `find_id` - The book's database ID.
-/// * `book_repo` - Repository for deleting the book record.
+/// * `mode` - What to do with the book's file.
+/// * `book_repo` - Repository for trashing the book record.
+/// * `ignored_file_repo` - Repository for ignoring the book's file.
 ///
 /// # Errors
 ///
-/// Delegates to the repository; returns [`DomainError::Database`] on query
-/// failures. No error is returned if the ID does not exist.
+/// Returns [`DomainError::BookNotFound`] if the book does not exist,
+/// [`DomainError::File`] if the file cannot be moved or deleted (the book is
+/// then left in the library), or [`DomainError::Database`] on query failures.
 pub async fn remove_book(
     find_id: i32,
+    mode: RemoveMode,
     book_repo: &Arc<dyn BookRepository>,
+    ignored_file_repo: &Arc<dyn IgnoredFileRepository>,
 ) -> Result<(), DomainError> {
-    book_repo.delete(find_id).await
+    let book = book_repo
+        .find_by_id(find_id)
+        .await?
+        .ok_or(DomainError::BookNotFound(find_id))?;
+
+    if let Some(path) = book.file_path.as_deref() {
+        match mode {
+            RemoveMode::KeepFile => {}
+            RemoveMode::TrashFile => removal_handler::move_to_trash(path)
+                .await
+                .map_err(|e| DomainError::File(e.to_string()))?,
+            RemoveMode::DeleteFile => removal_handler::delete_file(path)
+                .await
+                .map_err(|e| DomainError::File(e.to_string()))?,
+        }
+        ignored_file_repo.add(path).await?;
+    }
+
+    book_repo.trash(find_id).await
+}
+
+/// Lists the books in the trash, most recently removed first.
+///
+/// Books past the retention period are purged first.
+///
+/// # Arguments
+///
+/// * `book_repo` - Repository for listing and purging trashed books.
+///
+/// # Returns
+///
+/// A [`BookDto`] per trashed book, with `deleted_at` set.
+///
+/// # Errors
+///
+/// Delegates to the repositories; returns [`DomainError::Database`] on
+/// failure.
+pub async fn list_trash(book_repo: &Arc<dyn BookRepository>) -> Result<Vec<BookDto>, DomainError> {
+    purge_expired_trash(book_repo).await?;
+    book_repo.query_trashed().await
+}
+
+/// Moves a book out of the trash back into the library and stops ignoring
+/// its file.
+///
+/// A book whose file is gone (deleted or moved to the operating system's
+/// trash by [`remove_book`], or removed outside the app) stays in the trash.
+///
+/// # Arguments
+///
+/// * `find_id` - The book's database ID.
+/// * `book_repo` - Repository for restoring the book record.
+/// * `ignored_file_repo` - Repository for un-ignoring the book's file.
+///
+/// # Errors
+///
+/// Returns [`DomainError::BookNotFound`] if the book does not exist,
+/// [`DomainError::File`] if its file no longer exists, or
+/// [`DomainError::Database`] on query failures.
+pub async fn restore_book(
+    find_id: i32,
+    book_repo: &Arc<dyn BookRepository>,
+    ignored_file_repo: &Arc<dyn IgnoredFileRepository>,
+) -> Result<(), DomainError> {
+    let book = book_repo
+        .find_by_id(find_id)
+        .await?
+        .ok_or(DomainError::BookNotFound(find_id))?;
+    if let Some(path) = book.file_path.as_deref()
+        && !tokio::fs::try_exists(path).await.unwrap_or(false)
+    {
+        return Err(DomainError::File(format!(
+            "Cannot restore the book: its file no longer exists at {}",
+            path
+        )));
+    }
+
+    book_repo.restore(find_id).await?;
+    if let Some(path) = book.file_path.as_deref() {
+        ignored_file_repo.remove(path).await?;
+    }
+    Ok(())
+}
+
+/// Lists the files that directory scans skip, most recently ignored first.
+///
+/// # Arguments
+///
+/// * `ignored_file_repo` - Repository for listing the ignored files.
+///
+/// # Errors
+///
+/// Delegates to the repository; returns [`DomainError::Database`] on query
+/// failures.
+pub async fn list_ignored_files(
+    ignored_file_repo: &Arc<dyn IgnoredFileRepository>,
+) -> Result<Vec<IgnoredFile>, DomainError> {
+    ignored_file_repo.find_all().await
+}
+
+/// Stops ignoring a file, so the next directory scan imports it again.
+///
+/// # Arguments
+///
+/// * `file_path` - The ignored path.
+/// * `ignored_file_repo` - Repository for un-ignoring the file.
+///
+/// # Errors
+///
+/// Returns [`DomainError::NotFound`] if the path is not ignored, or
+/// [`DomainError::Database`] on query failures.
+pub async fn unignore_file(
+    file_path: &str,
+    ignored_file_repo: &Arc<dyn IgnoredFileRepository>,
+) -> Result<(), DomainError> {
+    if !ignored_file_repo.remove(file_path).await? {
+        return Err(DomainError::NotFound);
+    }
+    Ok(())
+}
+
+/// Permanently deletes every book in the trash.
+///
+/// Bookmarks, annotations and reading progress of the deleted books are
+/// cascade-deleted by SQLite foreign keys. The files stay on disk.
+///
+/// # Arguments
+///
+/// * `book_repo` - Repository for deleting the book records.
+///
+/// # Returns
+///
+/// The number of books deleted.
+///
+/// # Errors
+///
+/// Delegates to the repository; returns [`DomainError::Database`] on query
+/// failures.
+pub async fn empty_trash(book_repo: &Arc<dyn BookRepository>) -> Result<usize, DomainError> {
+    book_repo.purge_trash(None).await
+}
+
+/// Permanently deletes books that have been in the trash longer than the
+/// retention period.
+///
+/// The period is read from the `TRASH_RETENTION_DAYS` environment variable,
+/// defaulting to [`DEFAULT_TRASH_RETENTION_DAYS`]. A value of `0` or less
+/// turns automatic purging off. Called at startup and when the trash is
+/// listed.
+///
+/// # Arguments
+///
+/// * `book_repo` - Repository for deleting the book records.
+///
+/// # Returns
+///
+/// The number of books deleted.
+///
+/// # Errors
+///
+/// Delegates to the repository; returns [`DomainError::Database`] on query
+/// failures.
+pub async fn purge_expired_trash(
+    book_repo: &Arc<dyn BookRepository>,
+) -> Result<usize, DomainError> {
+    let days = trash_retention_days();
+    if days <= 0 {
+        return Ok(0);
+    }
+    let cutoff = (Utc::now() - Duration::days(days))
+        .format("%Y-%m-%d %H:%M:%S")
+        .to_string();
+    book_repo.purge_trash(Some(&cutoff)).await
+}
+
+/// Returns the number of days trashed books are kept.
+///
+/// Read from the `TRASH_RETENTION_DAYS` environment variable, defaulting to
+/// [`DEFAULT_TRASH_RETENTION_DAYS`] when unset or not a number.
+pub fn trash_retention_days() -> i64 {
+    dotenvy::dotenv().ok();
+    std::env::var("TRASH_RETENTION_DAYS")
+        .ok()
+        .and_then(|value| value.trim().parse().ok())
+        .unwrap_or(DEFAULT_TRASH_RETENTION_DAYS)
+}
+
+/// Re-reads a book's checksum from its file and, when the file changed,
+/// re-anchors the book's annotations and stores the new checksum.
+///
+/// A file replaced by a new version, such as a corrected edition saved over
+/// the old one, keeps its path but changes its checksum, and the stored
+/// positions of its highlights may no longer point at their text. The
+/// checksum is only stored once re-anchoring succeeded, so a failed attempt
+/// is retried by the next refresh.
+///
+/// # Arguments
+///
+/// * `book_id` - The book's database ID.
+/// * `book_repo` - Repository for loading and updating the book record.
+/// * `annotation_repo` - Repository for the annotations to re-anchor.
+///
+/// # Returns
+///
+/// The [`ReanchorReport`] of
+/// [`reanchor_annotations`](crate::application::annotation::reanchor_annotations)
+/// when the checksum of an EPUB changed, otherwise `None`.
+///
+/// # Errors
+///
+/// Returns [`DomainError::BookNotFound`] if the book does not exist,
+/// [`DomainError::File`] if it has no file or the file cannot be read,
+/// [`DomainError::Parse`] if the new file cannot be parsed, or
+/// [`DomainError::Database`] on failure.
+pub async fn refresh_checksum(
+    book_id: i32,
+    book_repo: &Arc<dyn BookRepository>,
+    annotation_repo: &Arc<dyn AnnotationRepository>,
+) -> Result<Option<ReanchorReport>, DomainError> {
+    let book = book_repo
+        .find_by_id(book_id)
+        .await?
+        .ok_or(DomainError::BookNotFound(book_id))?;
+    let path = book
+        .file_path
+        .ok_or_else(|| DomainError::File("No file path for book".into()))?;
+
+    let checksum = compute_checksum(&path)
+        .await
+        .map_err(|e| DomainError::File(e.to_string()))?;
+    if book.checksum.as_deref() == Some(checksum.as_str()) {
+        return Ok(None);
+    }
+
+    let report = match book.file_type.as_deref() {
+        Some("epub") => Some(
+            crate::application::annotation::reanchor_annotations(
+                book_id,
+                book_repo,
+                annotation_repo,
+            )
+            .await?,
+        ),
+        _ => None,
+    };
+    book_repo
+        .update(
+            book_id,
+            UpdateBook {
+                checksum: Some(checksum),
+                ..Default::default()
+            },
+        )
+        .await?;
+    Ok(report)
+}
+
+/// Re-reads a book's checksum from its file and stores it, without
+/// re-anchoring annotations.
+///
+/// For rewrites that leave the book's content documents untouched, such as
+/// [`write_metadata_to_file`](crate::application::service::book_service::write_metadata_to_file),
+/// which only changes the OPF package document and cover.
+///
+/// # Arguments
+///
+/// * `book_id` - The book's database ID.
+/// * `book_repo` - Repository for loading and updating the book record.
+///
+/// # Errors
+///
+/// Returns [`DomainError::BookNotFound`] if the book does not exist,
+/// [`DomainError::File`] if it has no file or the file cannot be read, or
+/// [`DomainError::Database`] on failure.
+pub async fn store_checksum(
+    book_id: i32,
+    book_repo: &Arc<dyn BookRepository>,
+) -> Result<(), DomainError> {
+    let book = book_repo
+        .find_by_id(book_id)
+        .await?
+        .ok_or(DomainError::BookNotFound(book_id))?;
+    let path = book
+        .file_path
+        .ok_or_else(|| DomainError::File("No file path for book".into()))?;
+
+    let checksum = compute_checksum(&path)
+        .await
+        .map_err(|e| DomainError::File(e.to_string()))?;
+    book_repo
+        .update(
+            book_id,
+            UpdateBook {
+                checksum: Some(checksum),
+                ..Default::default()
+            },
+        )
+        .await?;
+    Ok(())
 }
 
 /// Recursively scans a directory for EPUB and PDF files and imports them.
 ///
+/// Files on the ignored list (see [`remove_book`]) are skipped. Files
+/// already in the library are not imported again; their checksum is
+/// refreshed with [`refresh_checksum`], which re-anchors the annotations of
+/// a file that changed.
+///
 /// # Arguments
 ///
 /// * `dir_path` - Directory to scan recursively for ebook files.
@@ -340,22 +1056,43 @@ pub async fn remove_book(
 /// * `author_repo` - Repository for finding or creating authors.
 /// * `book_author_repo` - Repository for linking authors to books.
 /// * `publisher_repo` - Repository for finding or creating publishers.
+/// * `ignored_file_repo` - Repository for the files to skip.
+/// * `history_repo` - Repository for recording the imported metadata.
+/// * `annotation_repo` - Repository for re-anchoring annotations.
 ///
 /// # Returns
 ///
-/// A list of error messages for files that failed to import. Successfully
-/// imported books are silently added to the library.
+/// A [`ScanReport`] with the error messages for files that failed to import
+/// or refresh, and the re-anchoring results of the files that changed.
+/// Successfully imported books are silently added to the library.
 ///
 /// # Errors
 ///
 /// Returns [`DomainError::File`] when the directory cannot be scanned.
+#[allow(clippy::too_many_arguments)]
 pub async fn scan_directory(
     dir_path: &Path,
     book_repo: &Arc<dyn BookRepository>,
     author_repo: &Arc<dyn AuthorRepository>,
     book_author_repo: &Arc<dyn BookAuthorRepository>,
     publisher_repo: &Arc<dyn PublisherRepository>,
-) -> Result<Vec<String>, DomainError> {
+    ignored_file_repo: &Arc<dyn IgnoredFileRepository>,
+    history_repo: &Arc<dyn MetadataHistoryRepository>,
+    annotation_repo: &Arc<dyn AnnotationRepository>,
+) -> Result<ScanReport, DomainError> {
+    let ignored: HashSet<String> = ignored_file_repo
+        .find_all()
+        .await?
+        .into_iter()
+        .map(|file| file.file_path)
+        .collect();
+    let library: HashMap<String, i32> = book_repo
+        .find_all()
+        .await?
+        .into_iter()
+        .filter_map(|book| Some((book.file_path?, book.id)))
+        .collect();
+
     let epub_paths = epub_handler::scan_epubs(dir_path.to_path_buf())
         .await
         .map_err(|e| DomainError::File(e.to_string()))?;
@@ -364,14 +1101,30 @@ pub async fn scan_directory(
         .map_err(|e| DomainError::File(e.to_string()))?;
 
     let mut errors = Vec::new();
+    let mut reanchored = Vec::new();
 
     for path in epub_paths.iter().chain(pdf_paths.iter()) {
+        if ignored.contains(path.to_string_lossy().as_ref()) {
+            continue;
+        }
+        if let Some(&book_id) = library.get(path.to_string_lossy().as_ref()) {
+            match refresh_checksum(book_id, book_repo, annotation_repo).await {
+                Ok(Some(report)) => reanchored.push(report),
+                Ok(None) => {}
+                Err(e) => {
+                    errors.push(format!("{:?}: {}", path.file_name().unwrap_or_default(), e));
+                }
+            }
+            continue;
+        }
         if let Err(e) = import_book(
             path,
             book_repo,
             author_repo,
             book_author_repo,
             publisher_repo,
+            ignored_file_repo,
+            history_repo,
         )
         .await
         {
@@ -384,5 +1137,5 @@ pub async fn scan_directory(
         }
     }
 
-    Ok(errors)
+    Ok(ScanReport { errors, reanchored })
 }
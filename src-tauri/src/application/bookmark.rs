@@ -13,11 +13,14 @@ use crate::domain::repository::*;
 ///
 /// # Errors
 ///
-/// Delegates to the repository; returns [`DomainError::Database`] on failure.
+/// Returns [`DomainError::InvalidInput`] when the position fails
+/// [`Locator::validate`](crate::domain::models::locator::Locator::validate),
+/// or [`DomainError::Database`] on failure.
 pub async fn add_bookmark(
     bookmark: NewBookmark,
     bookmark_repo: &Arc<dyn BookmarkRepository>,
 ) -> Result<(), DomainError> {
+    bookmark.position.validate()?;
     bookmark_repo.insert(bookmark).await
 }
 
@@ -0,0 +1,316 @@
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::Arc;
+
+use crate::application::book::get_book;
+use crate::domain::dto::duplicate_dto::{DuplicateGroup, DuplicateMatch};
+use crate::domain::error::DomainError;
+use crate::domain::models::book::Book;
+use crate::domain::models::duplicate::{
+    CANDIDATE_TITLE_SIMILARITY, DEFAULT_DUPLICATE_THRESHOLD, DuplicateSignals,
+    fingerprint_similarity, length_similarity, normalize_text, normalize_title, similarity,
+    text_fingerprint,
+};
+use crate::domain::models::isbn::Isbn;
+use crate::domain::repository::*;
+use crate::infrastructure::file_handlers::ContentProfile;
+use crate::infrastructure::file_handlers::epub_handler;
+use crate::infrastructure::file_handlers::pdf_handler;
+
+/// Title words shorter than this are not used to pick candidate pairs.
+const MIN_BLOCKING_WORD_LEN: usize = 4;
+
+/// Title words found in more books than this ("history", "guide") are too
+/// common to pick candidate pairs; every pair in a block is scored, so the
+/// cost grows with the square of the block size.
+const MAX_BLOCKING_WORD_BOOKS: usize = 50;
+
+/// The normalized properties of a book compared without opening its file.
+struct CompareKey {
+    title: String,
+    isbn: Option<String>,
+}
+
+/// Finds books that look like copies of the same file.
+///
+/// Only books of the same file type are compared, and books already linked
+/// into the same work are skipped (files of one work are meant to differ).
+/// Pairs sharing an ISBN, or a title word of at least four letters with a
+/// similar enough title overall, are scored on title, author, ISBN, page or
+/// spine count and a fingerprint of the opening text. Words in more than
+/// [`MAX_BLOCKING_WORD_BOOKS`] titles are not used for pairing; a book whose
+/// words are all that common is paired by its whole title instead. Authors
+/// are compared by their credited names, not the editable sort key. Files
+/// that cannot be read are scored without the last two signals.
+///
+/// # Arguments
+///
+/// * `min_score` - Score from `0.0` to `1.0` a pair needs to be reported;
+///   defaults to [`DEFAULT_DUPLICATE_THRESHOLD`].
+/// * `book_repo` - Repository for listing books.
+/// * `author_repo` - Repository for resolving author names.
+/// * `publisher_repo` - Repository for resolving publisher names.
+/// * `reading_progress_repo` - Repository used to suggest which book to keep.
+///
+/// # Returns
+///
+/// Groups of suspected duplicates (pairs sharing a book are joined into one
+/// group), best match first.
+///
+/// # Errors
+///
+/// Returns [`DomainError::InvalidInput`] if `min_score` is outside `0.0` to
+/// `1.0`, or [`DomainError::Database`] on query failures.
+pub async fn find_duplicates(
+    min_score: Option<f32>,
+    book_repo: &Arc<dyn BookRepository>,
+    author_repo: &Arc<dyn AuthorRepository>,
+    publisher_repo: &Arc<dyn PublisherRepository>,
+    reading_progress_repo: &Arc<dyn ReadingProgressRepository>,
+) -> Result<Vec<DuplicateGroup>, DomainError> {
+    let threshold = min_score.unwrap_or(DEFAULT_DUPLICATE_THRESHOLD);
+    if !(0.0..=1.0).contains(&threshold) {
+        return Err(DomainError::InvalidInput(
+            "Minimum score must be between 0 and 1".into(),
+        ));
+    }
+
+    let books = book_repo.find_all().await?;
+    let keys: Vec<CompareKey> = books
+        .iter()
+        .map(|book| CompareKey {
+            title: normalize_title(&book.title, book.language.as_deref()),
+            isbn: book
+                .isbn
+                .as_deref()
+                .and_then(|i| Isbn::parse(i).ok())
+                .map(|isbn| isbn.to_isbn13()),
+        })
+        .collect();
+
+    // Index books by ISBN and by title word so only plausible pairs are
+    // scored instead of every pair in the library.
+    let title_words: Vec<BTreeSet<&str>> = keys
+        .iter()
+        .map(|key| {
+            key.title
+                .split(' ')
+                .filter(|word| word.chars().count() >= MIN_BLOCKING_WORD_LEN)
+                .collect()
+        })
+        .collect();
+    let mut word_books: HashMap<&str, usize> = HashMap::new();
+    for word in title_words.iter().flatten() {
+        *word_books.entry(*word).or_default() += 1;
+    }
+
+    let mut blocks: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+    for (idx, (key, words)) in keys.iter().zip(title_words).enumerate() {
+        if let Some(isbn) = &key.isbn {
+            blocks
+                .entry(format!("isbn:{}", isbn))
+                .or_default()
+                .push(idx);
+        }
+        let words: Vec<&str> = words
+            .into_iter()
+            .filter(|word| word_books[word] <= MAX_BLOCKING_WORD_BOOKS)
+            .collect();
+        if words.is_empty() {
+            blocks
+                .entry(format!("title:{}", key.title))
+                .or_default()
+                .push(idx);
+        }
+        for word in words {
+            blocks
+                .entry(format!("word:{}", word))
+                .or_default()
+                .push(idx);
+        }
+    }
+    let mut pairs: BTreeSet<(usize, usize)> = BTreeSet::new();
+    for members in blocks.values() {
+        for (n, &left) in members.iter().enumerate() {
+            for &right in &members[n + 1..] {
+                pairs.insert((left.min(right), left.max(right)));
+            }
+        }
+    }
+
+    let mut authors: HashMap<i32, Option<String>> = HashMap::new();
+    let mut profiles: HashMap<i32, Option<(u32, Option<u64>)>> = HashMap::new();
+    let mut matches: Vec<DuplicateMatch> = Vec::new();
+    for (left, right) in pairs {
+        let (a, b) = (&books[left], &books[right]);
+        if a.file_type != b.file_type || (a.work_id.is_some() && a.work_id == b.work_id) {
+            continue;
+        }
+
+        let (key_a, key_b) = (&keys[left], &keys[right]);
+        let title = similarity(&key_a.title, &key_b.title);
+        let isbn = match (&key_a.isbn, &key_b.isbn) {
+            (Some(x), Some(y)) => Some(if x == y { 1.0 } else { 0.0 }),
+            _ => None,
+        };
+        if title < CANDIDATE_TITLE_SIMILARITY && isbn != Some(1.0) {
+            continue;
+        }
+        for book in [a, b] {
+            if let Entry::Vacant(entry) = authors.entry(book.id) {
+                entry.insert(author_key(book.id, author_repo).await?);
+            }
+        }
+        let author = match (&authors[&a.id], &authors[&b.id]) {
+            (Some(x), Some(y)) => Some(similarity(x, y)),
+            _ => None,
+        };
+
+        for book in [a, b] {
+            if let Entry::Vacant(entry) = profiles.entry(book.id) {
+                let profile = read_profile(book).await;
+                entry.insert(profile.map(|p| (p.units, text_fingerprint(&p.text))));
+            }
+        }
+        let (profile_a, profile_b) = (profiles[&a.id], profiles[&b.id]);
+        let length = match (profile_a, profile_b) {
+            (Some((x, _)), Some((y, _))) => length_similarity(x, y),
+            _ => None,
+        };
+        let text = match (profile_a, profile_b) {
+            (Some((_, Some(x))), Some((_, Some(y)))) => Some(fingerprint_similarity(x, y)),
+            _ => None,
+        };
+
+        let signals = DuplicateSignals {
+            title,
+            author,
+            isbn,
+            length,
+            text,
+        };
+        let score = signals.score();
+        if score >= threshold {
+            matches.push(DuplicateMatch {
+                book_id: a.id,
+                other_id: b.id,
+                score,
+                signals,
+            });
+        }
+    }
+    matches.sort_by(|x, y| y.score.total_cmp(&x.score));
+
+    // Join pairs that share a book into groups, in order of their best match.
+    let mut groups: Vec<Vec<i32>> = Vec::new();
+    for m in &matches {
+        let first = groups.iter().position(|g| g.contains(&m.book_id));
+        let second = groups.iter().position(|g| g.contains(&m.other_id));
+        match (first, second) {
+            (Some(x), Some(y)) if x != y => {
+                let moved = groups.remove(x.max(y));
+                groups[x.min(y)].extend(moved);
+            }
+            (Some(_), Some(_)) => {}
+            (Some(x), None) => groups[x].push(m.other_id),
+            (None, Some(y)) => groups[y].push(m.book_id),
+            (None, None) => groups.push(vec![m.book_id, m.other_id]),
+        }
+    }
+
+    let mut result = Vec::with_capacity(groups.len());
+    for mut ids in groups {
+        ids.sort_unstable();
+
+        let mut suggested_target_id = ids[0];
+        let mut last_read: Option<String> = None;
+        let mut dtos = Vec::with_capacity(ids.len());
+        for &id in &ids {
+            if let Some(read_at) = reading_progress_repo
+                .find_by_book(id)
+                .await?
+                .and_then(|p| p.last_read_at)
+                && last_read.as_ref().is_none_or(|latest| read_at > *latest)
+            {
+                suggested_target_id = id;
+                last_read = Some(read_at);
+            }
+            if let Some(dto) = get_book(id, book_repo, author_repo, publisher_repo).await? {
+                dtos.push(dto);
+            }
+        }
+
+        result.push(DuplicateGroup {
+            books: dtos,
+            matches: matches
+                .iter()
+                .filter(|m| ids.contains(&m.book_id))
+                .cloned()
+                .collect(),
+            suggested_target_id,
+        });
+    }
+
+    Ok(result)
+}
+
+/// Merges duplicate books into the one to keep.
+///
+/// Bookmarks, annotations and tags of the duplicates move to the target,
+/// the most recently read reading progress is kept, and the duplicates are
+/// deleted. Their files stay on disk.
+///
+/// # Arguments
+///
+/// * `source_ids` - Duplicates to merge away.
+/// * `target_id` - The book that remains.
+/// * `book_repo` - Repository for merging books.
+///
+/// # Errors
+///
+/// Returns [`DomainError::InvalidInput`] if there is nothing to merge,
+/// [`DomainError::BookNotFound`] if the target does not exist, or
+/// [`DomainError::Database`] on failure.
+pub async fn merge_duplicates(
+    source_ids: &[i32],
+    target_id: i32,
+    book_repo: &Arc<dyn BookRepository>,
+) -> Result<(), DomainError> {
+    if source_ids.iter().all(|&id| id == target_id) {
+        return Err(DomainError::InvalidInput(
+            "Select at least one book other than the merge target".into(),
+        ));
+    }
+    book_repo.merge(source_ids, target_id).await
+}
+
+/// Returns the normalized names of a book's credited authors, sorted so the
+/// credit order does not matter, or `None` when it has none besides the
+/// "Unknown Author" placeholder.
+async fn author_key(
+    book_id: i32,
+    author_repo: &Arc<dyn AuthorRepository>,
+) -> Result<Option<String>, DomainError> {
+    let mut names: Vec<String> = author_repo
+        .get_contributors_by_book(book_id)
+        .await?
+        .into_iter()
+        .filter(|c| c.is_author() && c.name != "Unknown Author")
+        .map(|c| normalize_text(&c.name))
+        .filter(|name| !name.is_empty())
+        .collect();
+    names.sort();
+    Ok((!names.is_empty()).then(|| names.join(" ")))
+}
+
+/// Reads a book's content profile, or `None` when the file is missing or
+/// cannot be parsed.
+async fn read_profile(book: &Book) -> Option<ContentProfile> {
+    let path = book.file_path.as_deref()?;
+    let profile = match book.file_type.as_deref()? {
+        "epub" => epub_handler::read_content_profile(path).await,
+        "pdf" => pdf_handler::read_content_profile(path).await,
+        _ => return None,
+    };
+    profile.ok()
+}
@@ -0,0 +1,248 @@
+use std::sync::Arc;
+
+use crate::application::book::get_book;
+use crate::application::service::book_service::update_book_metadata;
+use crate::domain::dto::book_dto::BookDto;
+use crate::domain::error::DomainError;
+use crate::domain::models::metadata_change::*;
+use crate::domain::repository::*;
+
+/// Returns the metadata edit history of a book.
+///
+/// # Arguments
+///
+/// * `book_id` - The book's database ID.
+/// * `book_repo` - Repository for checking that the book exists.
+/// * `history_repo` - Repository for loading the recorded changes.
+///
+/// # Returns
+///
+/// The book's change sets, newest first, each with its field changes.
+///
+/// # Errors
+///
+/// Returns [`DomainError::BookNotFound`] when no book has the given ID, or
+/// [`DomainError::Database`] on failure.
+pub async fn get_metadata_history(
+    book_id: i32,
+    book_repo: &Arc<dyn BookRepository>,
+    history_repo: &Arc<dyn MetadataHistoryRepository>,
+) -> Result<Vec<MetadataChangeSet>, DomainError> {
+    book_repo
+        .find_by_id(book_id)
+        .await?
+        .ok_or(DomainError::BookNotFound(book_id))?;
+    history_repo.find_by_book(book_id).await
+}
+
+/// Sets one recorded field back to its value before the change.
+///
+/// The old value goes through [`update_book_metadata`] like a manual edit,
+/// so it is validated again and the revert is itself recorded as a
+/// [`ChangeSource::User`] change.
+///
+/// # Arguments
+///
+/// * `change_id` - The database ID of the field change to revert.
+/// * `book_repo` - Repository for updating the book.
+/// * `author_repo` - Repository for resolving author names.
+/// * `publisher_repo` - Repository for resolving the publisher name.
+/// * `history_repo` - Repository for loading and recording changes.
+///
+/// # Returns
+///
+/// The updated book as a [`BookDto`].
+///
+/// # Errors
+///
+/// Returns [`DomainError::NotFound`] when no change has the given ID,
+/// [`DomainError::InvalidInput`] when the change was a file write-back or the
+/// old value no longer passes validation, [`DomainError::BookNotFound`] when
+/// the book is gone, or [`DomainError::Database`] on failure.
+pub async fn revert_metadata_change(
+    change_id: i32,
+    book_repo: &Arc<dyn BookRepository>,
+    author_repo: &Arc<dyn AuthorRepository>,
+    publisher_repo: &Arc<dyn PublisherRepository>,
+    history_repo: &Arc<dyn MetadataHistoryRepository>,
+) -> Result<BookDto, DomainError> {
+    let set = history_repo
+        .find_set_of_change(change_id)
+        .await?
+        .ok_or(DomainError::NotFound)?;
+    let change = set
+        .changes
+        .iter()
+        .find(|c| c.id == change_id)
+        .ok_or(DomainError::NotFound)?;
+
+    revert(
+        &set,
+        std::slice::from_ref(change),
+        book_repo,
+        author_repo,
+        publisher_repo,
+        history_repo,
+    )
+    .await
+}
+
+/// Sets every field of a recorded change set back to its value before the
+/// change, as one edit.
+///
+/// # Arguments
+///
+/// * `change_set_id` - The database ID of the change set to revert.
+/// * `book_repo` - Repository for updating the book.
+/// * `author_repo` - Repository for resolving author names.
+/// * `publisher_repo` - Repository for resolving the publisher name.
+/// * `history_repo` - Repository for loading and recording changes.
+///
+/// # Returns
+///
+/// The updated book as a [`BookDto`].
+///
+/// # Errors
+///
+/// Returns [`DomainError::NotFound`] when no change set has the given ID,
+/// otherwise the same errors as [`revert_metadata_change`].
+pub async fn revert_metadata_change_set(
+    change_set_id: i32,
+    book_repo: &Arc<dyn BookRepository>,
+    author_repo: &Arc<dyn AuthorRepository>,
+    publisher_repo: &Arc<dyn PublisherRepository>,
+    history_repo: &Arc<dyn MetadataHistoryRepository>,
+) -> Result<BookDto, DomainError> {
+    let set = history_repo
+        .find_set(change_set_id)
+        .await?
+        .ok_or(DomainError::NotFound)?;
+
+    revert(
+        &set,
+        &set.changes,
+        book_repo,
+        author_repo,
+        publisher_repo,
+        history_repo,
+    )
+    .await
+}
+
+/// Returns the tracked metadata of a book.
+pub(crate) fn metadata_snapshot(book: &BookDto) -> MetadataSnapshot {
+    MetadataSnapshot {
+        title: Some(book.title.clone()),
+        authors: book.authors.clone(),
+        publisher: book.publisher.clone(),
+        published_date: book.published_date.clone(),
+        isbn: book.isbn.clone(),
+        language: book.language.clone(),
+        description: book.description.clone(),
+        series: book.series.clone(),
+        series_index: book.series_index,
+        tags: book.tags.clone(),
+        title_sort: book.title_sort.clone(),
+        author_sort: book.author_sort.clone(),
+    }
+}
+
+/// Takes a snapshot of each of the given books, for
+/// [`record_snapshot_changes`] to compare against after an edit that
+/// touches several books at once.
+pub(crate) async fn snapshot_books(
+    book_ids: &[i32],
+    book_repo: &Arc<dyn BookRepository>,
+    author_repo: &Arc<dyn AuthorRepository>,
+    publisher_repo: &Arc<dyn PublisherRepository>,
+) -> Result<Vec<(i32, MetadataSnapshot)>, DomainError> {
+    let mut snapshots = Vec::with_capacity(book_ids.len());
+    for &book_id in book_ids {
+        if let Some(book) = get_book(book_id, book_repo, author_repo, publisher_repo).await? {
+            snapshots.push((book_id, metadata_snapshot(&book)));
+        }
+    }
+    Ok(snapshots)
+}
+
+/// Records, for each snapshot taken by [`snapshot_books`], the fields that
+/// have changed since.
+pub(crate) async fn record_snapshot_changes(
+    before: Vec<(i32, MetadataSnapshot)>,
+    source: ChangeSource,
+    book_repo: &Arc<dyn BookRepository>,
+    author_repo: &Arc<dyn AuthorRepository>,
+    publisher_repo: &Arc<dyn PublisherRepository>,
+    history_repo: &Arc<dyn MetadataHistoryRepository>,
+) -> Result<(), DomainError> {
+    for (book_id, snapshot) in before {
+        if let Some(book) = get_book(book_id, book_repo, author_repo, publisher_repo).await? {
+            let changes = snapshot.diff(&metadata_snapshot(&book));
+            history_repo.record(book_id, source, &changes).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Applies the old values of `changes`, all taken from `set`.
+async fn revert(
+    set: &MetadataChangeSet,
+    changes: &[MetadataChange],
+    book_repo: &Arc<dyn BookRepository>,
+    author_repo: &Arc<dyn AuthorRepository>,
+    publisher_repo: &Arc<dyn PublisherRepository>,
+    history_repo: &Arc<dyn MetadataHistoryRepository>,
+) -> Result<BookDto, DomainError> {
+    // The old values of a write-back are what the file held, not what the
+    // library stored, so there is nothing in the library to go back to.
+    if set.source == ChangeSource::FileWriteback {
+        return Err(DomainError::InvalidInput(
+            "Metadata written to the file cannot be reverted".into(),
+        ));
+    }
+
+    update_book_metadata(
+        set.book_id,
+        revert_patch(changes)?,
+        ChangeSource::User,
+        book_repo,
+        author_repo,
+        publisher_repo,
+        history_repo,
+    )
+    .await
+}
+
+/// Builds the patch that sets each changed field back to its old value. An
+/// empty old value becomes an empty string, which clears the field.
+fn revert_patch(changes: &[MetadataChange]) -> Result<BookMetadataPatch, DomainError> {
+    let mut patch = BookMetadataPatch::default();
+
+    for change in changes {
+        let old = change.old_value.clone();
+        match change.field.as_str() {
+            FIELD_TITLE => patch.title = Some(old.unwrap_or_default()),
+            FIELD_AUTHORS => patch.authors = Some(split_list(old.as_deref())),
+            FIELD_PUBLISHER => patch.publisher = Some(old.unwrap_or_default()),
+            FIELD_PUBLISHED_DATE => patch.published_date = Some(old.unwrap_or_default()),
+            FIELD_ISBN => patch.isbn = Some(old.unwrap_or_default()),
+            FIELD_LANGUAGE => patch.language = Some(old.unwrap_or_default()),
+            FIELD_DESCRIPTION => patch.description = Some(old.unwrap_or_default()),
+            FIELD_SERIES => patch.series = Some(old.unwrap_or_default()),
+            FIELD_SERIES_INDEX => match old {
+                Some(index) => {
+                    patch.series_index = Some(index.parse().map_err(|_| {
+                        DomainError::InvalidInput(format!("Invalid series index \"{}\"", index))
+                    })?);
+                }
+                None => patch.clear_series_index = true,
+            },
+            FIELD_TAGS => patch.tags = Some(split_list(old.as_deref())),
+            FIELD_TITLE_SORT => patch.title_sort = Some(old.unwrap_or_default()),
+            FIELD_AUTHOR_SORT => patch.author_sort = Some(old.unwrap_or_default()),
+            _ => {}
+        }
+    }
+
+    Ok(patch)
+}
@@ -0,0 +1,285 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use crate::domain::error::DomainError;
+use crate::domain::models::cfi::{Cfi, CfiPoint, CfiStep, CfiTarget, DomPosition};
+use crate::domain::models::locator::{
+    EpubLocation, Locator, LocatorTarget, LocatorText, PdfLocation,
+};
+use crate::domain::models::text_quote::TextQuote;
+use crate::domain::repository::*;
+use crate::infrastructure::file_handlers::epub_cfi::{self, EpubSpine, SpineDocument};
+use crate::infrastructure::file_handlers::pdf_handler;
+
+/// Resolves an EPUB CFI against a book's content documents.
+///
+/// The spine item is found by the `idref` asserted on the itemref step when
+/// the book has it, otherwise by the step's index; inside the document, ID
+/// assertions likewise take precedence over step indexes.
+///
+/// # Arguments
+///
+/// * `book_id` - The book's database ID.
+/// * `cfi` - The CFI, e.g. `"epubcfi(/6/4[chap01]!/4/2/1:12)"`; ranges
+///   resolve both ends.
+/// * `book_repo` - Repository for looking up the book's file.
+///
+/// # Returns
+///
+/// The spine item and the [`DomPosition`] of the CFI (and of its end, for a
+/// range).
+///
+/// # Errors
+///
+/// Returns [`DomainError::BookNotFound`] if the book does not exist,
+/// [`DomainError::InvalidInput`] if the book is not an EPUB or the CFI is
+/// malformed or points outside the book, [`DomainError::File`] if the book
+/// has no file, [`DomainError::Parse`] if the file cannot be read, or
+/// [`DomainError::Database`] on failure.
+pub async fn resolve_epub_cfi(
+    book_id: i32,
+    cfi: &str,
+    book_repo: &Arc<dyn BookRepository>,
+) -> Result<CfiTarget, DomainError> {
+    let cfi = Cfi::parse(cfi)?;
+    let path = epub_path(book_id, book_repo).await?;
+    let spine = read_spine(&path).await?;
+
+    let spine_index = cfi
+        .itemref
+        .id
+        .as_deref()
+        .and_then(|idref| spine.items.iter().position(|item| item.idref == idref))
+        .or_else(|| cfi.spine_index())
+        .filter(|&index| index < spine.items.len())
+        .ok_or_else(|| DomainError::InvalidInput("CFI points past the last spine item".into()))?;
+    let href = spine.items[spine_index].href.clone();
+
+    let document = epub_cfi::read_spine_document(&path, &href)
+        .await
+        .map_err(|e| DomainError::Parse(e.to_string()))?;
+    let resolve = |point: &CfiPoint| {
+        document
+            .resolve(point)
+            .map_err(|e| DomainError::InvalidInput(e.to_string()))
+    };
+
+    Ok(CfiTarget {
+        spine_index,
+        start: resolve(&cfi.start)?,
+        end: cfi.end.as_ref().map(resolve).transpose()?,
+        href,
+    })
+}
+
+/// Generates an EPUB CFI for a position in a book's content documents.
+///
+/// The CFI asserts the spine item's `idref` and the IDs of the elements on
+/// its path, as Readium and Calibre do.
+///
+/// # Arguments
+///
+/// * `book_id` - The book's database ID.
+/// * `spine_index` - 0-based index of the spine item.
+/// * `start` - The position, or the start of a range.
+/// * `end` - The end of a range, in the same spine item.
+/// * `book_repo` - Repository for looking up the book's file.
+///
+/// # Returns
+///
+/// An [`EpubLocation`] with the item's href, the CFI and the progression of
+/// `start` within the item, ready to store in a locator.
+///
+/// # Errors
+///
+/// Returns [`DomainError::BookNotFound`] if the book does not exist,
+/// [`DomainError::InvalidInput`] if the book is not an EPUB or a position
+/// points outside the spine item, [`DomainError::File`] if the book has no
+/// file, [`DomainError::Parse`] if the file cannot be read, or
+/// [`DomainError::Database`] on failure.
+pub async fn generate_epub_cfi(
+    book_id: i32,
+    spine_index: usize,
+    start: DomPosition,
+    end: Option<DomPosition>,
+    book_repo: &Arc<dyn BookRepository>,
+) -> Result<EpubLocation, DomainError> {
+    let path = epub_path(book_id, book_repo).await?;
+    let spine = read_spine(&path).await?;
+    let item = spine
+        .items
+        .get(spine_index)
+        .ok_or_else(|| DomainError::InvalidInput(format!("No spine item {}", spine_index)))?;
+
+    let document = epub_cfi::read_spine_document(&path, &item.href)
+        .await
+        .map_err(|e| DomainError::Parse(e.to_string()))?;
+    epub_location(&spine, spine_index, &document, &start, end.as_ref())
+}
+
+/// Builds the [`EpubLocation`] of a position in a spine item, as
+/// [`generate_epub_cfi`] returns it.
+pub(crate) fn epub_location(
+    spine: &EpubSpine,
+    spine_index: usize,
+    document: &SpineDocument,
+    start: &DomPosition,
+    end: Option<&DomPosition>,
+) -> Result<EpubLocation, DomainError> {
+    let item = spine
+        .items
+        .get(spine_index)
+        .ok_or_else(|| DomainError::InvalidInput(format!("No spine item {}", spine_index)))?;
+    let invalid =
+        |e: Box<dyn std::error::Error + Send + Sync>| DomainError::InvalidInput(e.to_string());
+
+    let cfi = Cfi {
+        spine: CfiStep {
+            index: spine.spine_step,
+            id: None,
+        },
+        itemref: CfiStep {
+            index: (spine_index as u32 + 1) * 2,
+            id: Some(item.idref.clone()).filter(|idref| !idref.is_empty()),
+        },
+        start: document.locate(start).map_err(invalid)?,
+        end: end
+            .map(|end| document.locate(end))
+            .transpose()
+            .map_err(invalid)?,
+    };
+
+    Ok(EpubLocation {
+        href: item.href.clone(),
+        cfi: Some(cfi.to_string()),
+        progression: Some(document.progression(start).map_err(invalid)?),
+        total_progression: None,
+    })
+}
+
+/// Builds the locator of a text selection on a PDF page.
+///
+/// The selection runs from one character to another, by their index in the
+/// `chars` of the page as `read_pdf_page` returns them. The characters'
+/// boxes become the locator's quads, one per line, and the selected text and
+/// up to 32 characters on each side its text context.
+///
+/// # Arguments
+///
+/// * `book_id` - The book's database ID.
+/// * `page_index` - 0-based page index.
+/// * `first_char` - Index of the first selected character.
+/// * `last_char` - Index of the last selected character.
+/// * `book_repo` - Repository for looking up the book's file.
+///
+/// # Returns
+///
+/// A PDF [`Locator`] with quads in PDF user space, ready to store as an
+/// annotation's start and end positions.
+///
+/// # Errors
+///
+/// Returns [`DomainError::BookNotFound`] if the book does not exist,
+/// [`DomainError::InvalidInput`] if the book is not a PDF or the characters
+/// are not on the page, [`DomainError::File`] if the book has no file,
+/// [`DomainError::Parse`] if the file or page cannot be read, or
+/// [`DomainError::Database`] on failure.
+pub async fn generate_pdf_locator(
+    book_id: i32,
+    page_index: u32,
+    first_char: usize,
+    last_char: usize,
+    book_repo: &Arc<dyn BookRepository>,
+) -> Result<Locator, DomainError> {
+    let path = book_path(
+        book_id,
+        "pdf",
+        "Only PDF books have page locators",
+        book_repo,
+    )
+    .await?;
+    let chars = pdf_handler::read_pdf_chars(&path, page_index)
+        .await
+        .map_err(|e| DomainError::Parse(e.to_string()))?;
+    if first_char > last_char || last_char >= chars.len() {
+        return Err(DomainError::InvalidInput(format!(
+            "No characters {}..={} on page {} ({} characters)",
+            first_char,
+            last_char,
+            page_index,
+            chars.len()
+        )));
+    }
+
+    // The page text, with a space between words that do not have whitespace
+    // between them, and the UTF-16 range of the selection in it.
+    let word_starts: HashSet<usize> = pdf_handler::word_ranges(&chars)
+        .into_iter()
+        .map(|word| word.start)
+        .collect();
+    let mut text = String::new();
+    let (mut start, mut end) = (0, 0);
+    for (index, c) in chars.iter().enumerate() {
+        if word_starts.contains(&index) && !text.is_empty() && !text.ends_with(char::is_whitespace)
+        {
+            text.push(' ');
+        }
+        if index == first_char {
+            start = text.encode_utf16().count();
+        }
+        text.push_str(&c.text);
+        if index == last_char {
+            end = text.encode_utf16().count();
+        }
+    }
+    let quote = TextQuote::at(&text, start, end);
+
+    let selected = chars[first_char..=last_char]
+        .iter()
+        .filter(|c| !c.text.trim().is_empty());
+    Ok(Locator {
+        target: LocatorTarget::Pdf(PdfLocation::from_text_boxes(
+            page_index,
+            selected.map(|c| [c.x, c.y, c.width, c.height]),
+        )),
+        text: Some(LocatorText {
+            before: quote.prefix,
+            highlight: Some(quote.exact).filter(|exact| !exact.is_empty()),
+            after: quote.suffix,
+        }),
+    })
+}
+
+/// Returns the file path of an EPUB book.
+async fn epub_path(
+    book_id: i32,
+    book_repo: &Arc<dyn BookRepository>,
+) -> Result<String, DomainError> {
+    book_path(book_id, "epub", "CFIs only apply to EPUB books", book_repo).await
+}
+
+/// Returns the file path of a book, which must have the given file type.
+async fn book_path(
+    book_id: i32,
+    file_type: &str,
+    wrong_type: &str,
+    book_repo: &Arc<dyn BookRepository>,
+) -> Result<String, DomainError> {
+    let book = book_repo
+        .find_by_id(book_id)
+        .await?
+        .ok_or(DomainError::BookNotFound(book_id))?;
+
+    if book.file_type.as_deref() != Some(file_type) {
+        return Err(DomainError::InvalidInput(wrong_type.into()));
+    }
+    book.file_path
+        .ok_or_else(|| DomainError::File("No file path for book".into()))
+}
+
+/// Reads the spine of an EPUB file.
+async fn read_spine(path: &str) -> Result<EpubSpine, DomainError> {
+    epub_cfi::read_spine(path)
+        .await
+        .map_err(|e| DomainError::Parse(e.to_string()))
+}
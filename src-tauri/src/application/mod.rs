@@ -4,11 +4,18 @@
 //! repository calls to fulfill a specific business operation. Repository
 //! implementations use Diesel async to persist data in SQLite.
 
+pub mod provider;
 pub mod repository;
 pub mod service;
 pub mod state;
 
 pub mod annotation;
+pub mod author;
 pub mod book;
 pub mod bookmark;
+pub mod duplicate;
+pub mod history;
+pub mod locator;
+pub mod publisher;
 pub mod reading_progress;
+pub mod work;
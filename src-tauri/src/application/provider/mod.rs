@@ -0,0 +1,84 @@
+//! External metadata providers used to look up better metadata for books.
+//!
+//! A provider returns candidate [`BookMetadata`] for a book; the metadata
+//! service shows them next to the stored values and applies the fields the
+//! user picks.
+
+pub mod open_library;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::dto::book_dto::BookDto;
+use crate::domain::error::DomainError;
+use crate::domain::models::isbn::Isbn;
+use crate::infrastructure::file_handlers::BookMetadata;
+
+/// A source of book metadata, such as an online catalogue.
+#[async_trait]
+pub trait MetadataProvider: Send + Sync {
+    /// Human-readable provider name shown next to its candidates.
+    fn name(&self) -> &str;
+
+    /// Looks up editions with the given ISBN.
+    ///
+    /// # Returns
+    ///
+    /// Candidate metadata, best match first, with cover bytes when the
+    /// provider has a cover. An unknown ISBN yields an empty vector.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Provider`] when the provider cannot be reached
+    /// or returns an unexpected response.
+    async fn search_by_isbn(&self, isbn: &Isbn) -> Result<Vec<BookMetadata>, DomainError>;
+
+    /// Searches by title and, optionally, author name.
+    ///
+    /// # Returns
+    ///
+    /// Candidate metadata, best match first, with cover bytes when the
+    /// provider has a cover.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Provider`] when the provider cannot be reached
+    /// or returns an unexpected response.
+    async fn search_by_title(
+        &self,
+        title: &str,
+        author: Option<&str>,
+    ) -> Result<Vec<BookMetadata>, DomainError>;
+}
+
+/// Candidate metadata from a provider.
+///
+/// `metadata.file_path` and `metadata.checksum` are empty, since candidates
+/// do not come from a file.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct MetadataCandidate {
+    /// Name of the provider that returned the candidate.
+    pub provider: String,
+    pub metadata: BookMetadata,
+}
+
+/// A book's stored metadata next to the candidates found for it.
+#[derive(Clone, Serialize)]
+pub struct MetadataComparison {
+    pub current: BookDto,
+    pub candidates: Vec<MetadataCandidate>,
+}
+
+/// A field of a candidate that can be applied to a book.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MetadataField {
+    Title,
+    Authors,
+    Publisher,
+    PublishedDate,
+    Isbn,
+    Language,
+    Description,
+    Cover,
+}
@@ -0,0 +1,373 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use crate::application::provider::MetadataProvider;
+use crate::domain::error::DomainError;
+use crate::domain::models::contributor::Contributor;
+use crate::domain::models::identifier::{Identifier, SCHEME_ISBN};
+use crate::domain::models::isbn::Isbn;
+use crate::domain::models::partial_date::PartialDate;
+use crate::infrastructure::file_handlers::BookMetadata;
+
+/// Default Open Library API base URL.
+const DEFAULT_BASE_URL: &str = "https://openlibrary.org";
+/// Default Open Library covers base URL.
+const DEFAULT_COVERS_URL: &str = "https://covers.openlibrary.org";
+/// Maximum number of title search results turned into candidates.
+const SEARCH_LIMIT: usize = 5;
+/// Time allowed for a whole request, so a stalled server cannot hang a
+/// lookup.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+/// Identifier scheme for Open Library edition and work keys.
+const SCHEME_OPEN_LIBRARY: &str = "openlibrary";
+
+/// Fields requested from `search.json`.
+const SEARCH_FIELDS: &str =
+    "key,title,author_name,publisher,first_publish_year,isbn,language,subject,cover_i";
+
+/// Metadata provider speaking the Open Library JSON API.
+///
+/// Any server implementing the same endpoints can be used by changing the
+/// base URLs, e.g. a local mock server in tests:
+///
+/// * `GET {base}/api/books?bibkeys=ISBN:{isbn}&format=json&jscmd=data`
+/// * `GET {base}/search.json?title=..&author=..&limit=..&fields=..`
+/// * `GET {covers}/b/id/{cover_id}-M.jpg`
+pub struct OpenLibraryProvider {
+    base_url: String,
+    covers_url: String,
+    client: reqwest::Client,
+}
+
+impl Default for OpenLibraryProvider {
+    fn default() -> Self {
+        Self::new(DEFAULT_BASE_URL, DEFAULT_COVERS_URL)
+    }
+}
+
+impl OpenLibraryProvider {
+    /// Creates a provider for the given API and covers base URLs.
+    pub fn new(base_url: &str, covers_url: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            covers_url: covers_url.trim_end_matches('/').to_string(),
+            // Like `Client::new`, this only fails when no TLS backend can be
+            // initialized.
+            client: reqwest::Client::builder()
+                .timeout(REQUEST_TIMEOUT)
+                .build()
+                .expect("Failed to build HTTP client"),
+        }
+    }
+
+    /// Creates a provider from the `OPEN_LIBRARY_URL` and
+    /// `OPEN_LIBRARY_COVERS_URL` environment variables, falling back to the
+    /// public Open Library servers.
+    pub fn from_env() -> Self {
+        let base_url =
+            std::env::var("OPEN_LIBRARY_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.to_string());
+        let covers_url = std::env::var("OPEN_LIBRARY_COVERS_URL")
+            .unwrap_or_else(|_| DEFAULT_COVERS_URL.to_string());
+        Self::new(&base_url, &covers_url)
+    }
+
+    async fn get_json<T: for<'de> Deserialize<'de>>(
+        &self,
+        url: &str,
+        query: &[(&str, &str)],
+    ) -> Result<T, DomainError> {
+        self.client
+            .get(url)
+            .query(query)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(provider_error)?
+            .json::<T>()
+            .await
+            .map_err(provider_error)
+    }
+
+    /// Downloads a cover image. Missing or unreadable covers yield `None`
+    /// rather than failing the lookup.
+    async fn fetch_cover(&self, url: &str) -> Option<(Vec<u8>, String)> {
+        let response = self
+            .client
+            .get(url)
+            .query(&[("default", "false")])
+            .send()
+            .await
+            .ok()?
+            .error_for_status()
+            .ok()?;
+        let mime_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .filter(|v| v.starts_with("image/"))
+            .unwrap_or("image/jpeg")
+            .to_string();
+        let bytes = response.bytes().await.ok()?;
+        (!bytes.is_empty()).then(|| (bytes.to_vec(), mime_type))
+    }
+}
+
+#[async_trait]
+impl MetadataProvider for OpenLibraryProvider {
+    fn name(&self) -> &str {
+        "Open Library"
+    }
+
+    async fn search_by_isbn(&self, isbn: &Isbn) -> Result<Vec<BookMetadata>, DomainError> {
+        let bibkey = format!("ISBN:{}", isbn);
+        let url = format!("{}/api/books", self.base_url);
+        let mut editions: HashMap<String, Edition> = self
+            .get_json(
+                &url,
+                &[
+                    ("bibkeys", bibkey.as_str()),
+                    ("format", "json"),
+                    ("jscmd", "data"),
+                ],
+            )
+            .await?;
+
+        let Some(edition) = editions.remove(&bibkey) else {
+            return Ok(Vec::new());
+        };
+        let cover = match edition.cover.as_ref().and_then(Cover::best) {
+            Some(url) => self.fetch_cover(url).await,
+            None => None,
+        };
+        Ok(vec![edition.into_metadata(isbn, cover)])
+    }
+
+    async fn search_by_title(
+        &self,
+        title: &str,
+        author: Option<&str>,
+    ) -> Result<Vec<BookMetadata>, DomainError> {
+        let url = format!("{}/search.json", self.base_url);
+        let limit = SEARCH_LIMIT.to_string();
+        let mut query = vec![
+            ("title", title),
+            ("limit", limit.as_str()),
+            ("fields", SEARCH_FIELDS),
+        ];
+        if let Some(author) = author {
+            query.push(("author", author));
+        }
+        let results: SearchResults = self.get_json(&url, &query).await?;
+
+        let mut candidates = Vec::new();
+        for doc in results.docs.into_iter().take(SEARCH_LIMIT) {
+            let cover = match doc.cover_i {
+                Some(id) if id > 0 => {
+                    self.fetch_cover(&format!("{}/b/id/{}-M.jpg", self.covers_url, id))
+                        .await
+                }
+                _ => None,
+            };
+            candidates.push(doc.into_metadata(cover));
+        }
+        Ok(candidates)
+    }
+}
+
+fn provider_error(err: reqwest::Error) -> DomainError {
+    DomainError::Provider(format!("Open Library: {}", err))
+}
+
+/// An edition from `/api/books?jscmd=data`.
+#[derive(Deserialize)]
+struct Edition {
+    #[serde(default)]
+    key: Option<String>,
+    title: String,
+    #[serde(default)]
+    authors: Vec<Named>,
+    #[serde(default)]
+    publishers: Vec<Named>,
+    #[serde(default)]
+    publish_date: Option<String>,
+    #[serde(default)]
+    subjects: Vec<Named>,
+    #[serde(default)]
+    cover: Option<Cover>,
+}
+
+#[derive(Deserialize)]
+struct Named {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct Cover {
+    #[serde(default)]
+    small: Option<String>,
+    #[serde(default)]
+    medium: Option<String>,
+    #[serde(default)]
+    large: Option<String>,
+}
+
+impl Cover {
+    fn best(&self) -> Option<&str> {
+        self.medium
+            .as_deref()
+            .or(self.large.as_deref())
+            .or(self.small.as_deref())
+    }
+}
+
+impl Edition {
+    fn into_metadata(self, isbn: &Isbn, cover_data: Option<(Vec<u8>, String)>) -> BookMetadata {
+        let mut identifiers = vec![Identifier::new(SCHEME_ISBN, isbn.to_isbn13())];
+        if let Some(key) = &self.key {
+            let key = key.trim_start_matches("/books/");
+            identifiers.push(Identifier::new(SCHEME_OPEN_LIBRARY, key));
+        }
+        candidate(
+            self.title,
+            self.authors.into_iter().map(|a| a.name).collect(),
+            self.publishers.into_iter().map(|p| p.name).collect(),
+            self.publish_date.as_deref().and_then(normalize_date),
+            Some(isbn.to_isbn13()),
+            self.subjects.into_iter().map(|s| s.name).collect(),
+            None,
+            identifiers,
+            cover_data,
+        )
+    }
+}
+
+/// Response of `search.json`.
+#[derive(Deserialize)]
+struct SearchResults {
+    #[serde(default)]
+    docs: Vec<SearchDoc>,
+}
+
+#[derive(Deserialize)]
+struct SearchDoc {
+    #[serde(default)]
+    key: Option<String>,
+    title: String,
+    #[serde(default)]
+    author_name: Vec<String>,
+    #[serde(default)]
+    publisher: Vec<String>,
+    #[serde(default)]
+    first_publish_year: Option<i32>,
+    #[serde(default)]
+    isbn: Vec<String>,
+    #[serde(default)]
+    language: Vec<String>,
+    #[serde(default)]
+    subject: Vec<String>,
+    #[serde(default)]
+    cover_i: Option<i64>,
+}
+
+impl SearchDoc {
+    fn into_metadata(self, cover_data: Option<(Vec<u8>, String)>) -> BookMetadata {
+        // Search results list the ISBNs of every edition; prefer an ISBN-13.
+        let isbn = self
+            .isbn
+            .iter()
+            .filter(|raw| raw.chars().filter(char::is_ascii_digit).count() == 13)
+            .chain(&self.isbn)
+            .find_map(|raw| Isbn::parse(raw).ok())
+            .map(|isbn| isbn.to_isbn13());
+
+        let mut identifiers: Vec<Identifier> = isbn
+            .iter()
+            .map(|i| Identifier::new(SCHEME_ISBN, i.clone()))
+            .collect();
+        if let Some(key) = &self.key {
+            let key = key.trim_start_matches("/works/");
+            identifiers.push(Identifier::new(SCHEME_OPEN_LIBRARY, key));
+        }
+
+        candidate(
+            self.title,
+            self.author_name,
+            self.publisher.into_iter().take(1).collect(),
+            self.first_publish_year.map(|y| y.to_string()),
+            isbn,
+            self.subject,
+            self.language.first().map(|l| language_tag(l)),
+            identifiers,
+            cover_data,
+        )
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn candidate(
+    title: String,
+    authors: Vec<String>,
+    publishers: Vec<String>,
+    published_date: Option<String>,
+    isbn: Option<String>,
+    subjects: Vec<String>,
+    language: Option<String>,
+    identifiers: Vec<Identifier>,
+    cover_data: Option<(Vec<u8>, String)>,
+) -> BookMetadata {
+    BookMetadata {
+        title,
+        contributors: authors.into_iter().map(Contributor::author).collect(),
+        published_date,
+        publishers,
+        isbn,
+        doi: None,
+        description: None,
+        subjects,
+        language,
+        rights: None,
+        modified_date: None,
+        identifiers,
+        epub_version: None,
+        file_path: String::new(),
+        cover_data,
+        checksum: String::new(),
+    }
+}
+
+/// Reduces Open Library's free-form `publish_date` ("May 4, 2019", "2019")
+/// to a partial date, or to its year when only that can be recognized.
+fn normalize_date(raw: &str) -> Option<String> {
+    if let Some(date) = PartialDate::parse(raw) {
+        return Some(date.to_string());
+    }
+    raw.split(|c: char| !c.is_ascii_digit())
+        .filter(|part| part.len() == 4)
+        .find_map(PartialDate::parse)
+        .map(|date| date.to_string())
+}
+
+/// Maps Open Library's MARC language codes to BCP 47 tags for common
+/// languages; other codes are returned unchanged.
+fn language_tag(code: &str) -> String {
+    match code {
+        "eng" => "en",
+        "fre" | "fra" => "fr",
+        "ger" | "deu" => "de",
+        "spa" => "es",
+        "ita" => "it",
+        "por" => "pt",
+        "dut" | "nld" => "nl",
+        "rus" => "ru",
+        "jpn" => "ja",
+        "chi" | "zho" => "zh",
+        "kor" => "ko",
+        "pol" => "pl",
+        "swe" => "sv",
+        other => other,
+    }
+    .to_string()
+}
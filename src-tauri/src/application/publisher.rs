@@ -0,0 +1,143 @@
+use std::sync::Arc;
+
+use crate::application::history::{record_snapshot_changes, snapshot_books};
+use crate::domain::dto::publisher_dto::PublisherSummary;
+use crate::domain::error::DomainError;
+use crate::domain::models::metadata_change::ChangeSource;
+use crate::domain::models::publisher::Publisher;
+use crate::domain::repository::*;
+
+/// Returns every publisher with the number of books referencing it.
+///
+/// # Arguments
+///
+/// * `publisher_repo` - Repository for querying publishers.
+///
+/// # Returns
+///
+/// A vector of [`PublisherSummary`] entries ordered by name.
+///
+/// # Errors
+///
+/// Delegates to the repository; returns [`DomainError::Database`] on failure.
+pub async fn list_publishers(
+    publisher_repo: &Arc<dyn PublisherRepository>,
+) -> Result<Vec<PublisherSummary>, DomainError> {
+    publisher_repo.list_with_counts().await
+}
+
+/// Renames a publisher. Surrounding whitespace is trimmed from the new name.
+/// The renamed publisher is recorded in the edit history of each of its books.
+///
+/// # Arguments
+///
+/// * `id` - The publisher's database ID.
+/// * `name` - The new name.
+/// * `book_repo` - Repository for loading the affected books.
+/// * `author_repo` - Repository for resolving author names.
+/// * `publisher_repo` - Repository for updating the publisher.
+/// * `history_repo` - Repository for recording the changes.
+///
+/// # Returns
+///
+/// The renamed [`Publisher`].
+///
+/// # Errors
+///
+/// Returns [`DomainError::InvalidInput`] if the name is empty or already used
+/// by another publisher, [`DomainError::NotFound`] if the publisher does not
+/// exist, or [`DomainError::Database`] on failure.
+pub async fn rename_publisher(
+    id: i32,
+    name: &str,
+    book_repo: &Arc<dyn BookRepository>,
+    author_repo: &Arc<dyn AuthorRepository>,
+    publisher_repo: &Arc<dyn PublisherRepository>,
+    history_repo: &Arc<dyn MetadataHistoryRepository>,
+) -> Result<Publisher, DomainError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(DomainError::InvalidInput(
+            "Publisher name cannot be empty".into(),
+        ));
+    }
+    let book_ids = publisher_repo.find_book_ids(&[id]).await?;
+    let before = snapshot_books(&book_ids, book_repo, author_repo, publisher_repo).await?;
+    let renamed = publisher_repo.rename(id, name).await?;
+    record_snapshot_changes(
+        before,
+        ChangeSource::User,
+        book_repo,
+        author_repo,
+        publisher_repo,
+        history_repo,
+    )
+    .await?;
+    Ok(renamed)
+}
+
+/// Merges duplicate publishers into one, re-pointing their books to the
+/// target and deleting the duplicates.
+///
+/// The change is recorded in the edit history of each book of a merged
+/// publisher.
+///
+/// # Arguments
+///
+/// * `source_ids` - Publishers to merge away.
+/// * `target_id` - The publisher that remains.
+/// * `book_repo` - Repository for loading the affected books.
+/// * `author_repo` - Repository for resolving author names.
+/// * `publisher_repo` - Repository for merging publishers.
+/// * `history_repo` - Repository for recording the changes.
+///
+/// # Errors
+///
+/// Returns [`DomainError::InvalidInput`] if there is nothing to merge,
+/// [`DomainError::NotFound`] if the target does not exist, or
+/// [`DomainError::Database`] on failure.
+pub async fn merge_publishers(
+    source_ids: &[i32],
+    target_id: i32,
+    book_repo: &Arc<dyn BookRepository>,
+    author_repo: &Arc<dyn AuthorRepository>,
+    publisher_repo: &Arc<dyn PublisherRepository>,
+    history_repo: &Arc<dyn MetadataHistoryRepository>,
+) -> Result<(), DomainError> {
+    if source_ids.iter().all(|&id| id == target_id) {
+        return Err(DomainError::InvalidInput(
+            "Select at least one publisher other than the merge target".into(),
+        ));
+    }
+    let book_ids = publisher_repo.find_book_ids(source_ids).await?;
+    let before = snapshot_books(&book_ids, book_repo, author_repo, publisher_repo).await?;
+    publisher_repo.merge(source_ids, target_id).await?;
+    record_snapshot_changes(
+        before,
+        ChangeSource::User,
+        book_repo,
+        author_repo,
+        publisher_repo,
+        history_repo,
+    )
+    .await
+}
+
+/// Deletes publishers that no book references.
+///
+/// # Arguments
+///
+/// * `publisher_repo` - Repository for deleting publishers.
+///
+/// # Returns
+///
+/// The number of deleted publishers.
+///
+/// # Errors
+///
+/// Delegates to the repository; returns [`DomainError::Database`] on failure.
+pub async fn delete_orphan_publishers(
+    publisher_repo: &Arc<dyn PublisherRepository>,
+) -> Result<usize, DomainError> {
+    publisher_repo.delete_orphans().await
+}
@@ -38,10 +38,13 @@ pub async fn get_progress(
 ///
 /// # Errors
 ///
-/// Delegates to the repository; returns [`DomainError::Database`] on failure.
+/// Returns [`DomainError::InvalidInput`] when the position fails
+/// [`Locator::validate`](crate::domain::models::locator::Locator::validate),
+/// or [`DomainError::Database`] on failure.
 pub async fn update_progress(
     progress: NewReadingProgress,
     reading_progress_repo: &Arc<dyn ReadingProgressRepository>,
 ) -> Result<(), DomainError> {
+    progress.current_position.validate()?;
     reading_progress_repo.upsert(progress).await
 }
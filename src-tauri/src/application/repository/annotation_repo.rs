@@ -1,10 +1,11 @@
 use async_trait::async_trait;
+use chrono::Utc;
 use diesel::prelude::*;
 use diesel_async::{AsyncConnection, RunQueryDsl};
 
 use crate::domain::error::DomainError;
 use crate::domain::models::annotation::Annotation;
-use crate::domain::repository::{AnnotationRepository, NewAnnotation};
+use crate::domain::repository::{AnnotationAnchor, AnnotationRepository, NewAnnotation};
 use crate::infrastructure::database::database::{connect_from_pool, lock_db};
 use crate::infrastructure::database::models::annotation::{AnnotationRow, NewAnnotationRow};
 use crate::infrastructure::database::models::schema::annotations;
@@ -43,11 +44,13 @@ impl AnnotationRepository for AnnotationRepoImpl {
         let _db_lock = lock_db();
         let mut conn = connect_from_pool().await?;
 
+        let start_position = annotation.start_position.to_stored();
+        let end_position = annotation.end_position.to_stored();
         let new_row = NewAnnotationRow {
             book_id: annotation.book_id,
             chapter_title: annotation.chapter_title.as_deref(),
-            start_position: &annotation.start_position,
-            end_position: &annotation.end_position,
+            start_position: &start_position,
+            end_position: &end_position,
             highlighted_text: annotation.highlighted_text.as_deref(),
             note: annotation.note.as_deref(),
             color: annotation.color.as_deref(),
@@ -81,4 +84,53 @@ impl AnnotationRepository for AnnotationRepoImpl {
 
         Ok(())
     }
+
+    /// Stores the re-anchored positions and clears the review flag.
+    async fn update_anchor(
+        &self,
+        find_id: i32,
+        anchor: AnnotationAnchor,
+    ) -> Result<(), DomainError> {
+        let _db_lock = lock_db();
+        let mut conn = connect_from_pool().await?;
+
+        let now = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+        diesel::update(
+            annotations::dsl::annotations.filter(annotations::annotation_id.eq(find_id)),
+        )
+        .set((
+            annotations::start_position.eq(anchor.start_position.to_stored()),
+            annotations::end_position.eq(anchor.end_position.to_stored()),
+            annotations::highlighted_text.eq(anchor.highlighted_text),
+            annotations::needs_review.eq(false),
+            annotations::anchor_confidence.eq(Some(anchor.confidence)),
+            annotations::updated_at.eq(&now),
+        ))
+        .execute(&mut conn)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Sets the review flag and the score of the best match.
+    async fn flag_for_review(
+        &self,
+        find_id: i32,
+        confidence: Option<f32>,
+    ) -> Result<(), DomainError> {
+        let _db_lock = lock_db();
+        let mut conn = connect_from_pool().await?;
+
+        diesel::update(
+            annotations::dsl::annotations.filter(annotations::annotation_id.eq(find_id)),
+        )
+        .set((
+            annotations::needs_review.eq(true),
+            annotations::anchor_confidence.eq(confidence),
+        ))
+        .execute(&mut conn)
+        .await?;
+
+        Ok(())
+    }
 }
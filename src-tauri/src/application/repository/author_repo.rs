@@ -1,13 +1,23 @@
+use std::collections::HashMap;
+
 use async_trait::async_trait;
+use diesel::SqliteConnection;
 use diesel::prelude::*;
+use diesel::sql_query;
+use diesel::sql_types::Integer;
+use diesel_async::sync_connection_wrapper::SyncConnectionWrapper;
 use diesel_async::{AsyncConnection, RunQueryDsl};
 
+use crate::application::repository::book_repo::CLEAR_AUTHOR_PLACEHOLDER_COVERS;
+use crate::domain::dto::author_dto::AuthorSummary;
 use crate::domain::error::DomainError;
 use crate::domain::models::author::Author;
+use crate::domain::models::contributor::{Contributor, ROLE_AUTHOR};
+use crate::domain::models::sort_key;
 use crate::domain::repository::AuthorRepository;
 use crate::infrastructure::database::database::{connect_from_pool, lock_db};
-use crate::infrastructure::database::models::author::{AuthorRow, NewAuthorRow};
-use crate::infrastructure::database::models::schema::{authors, book_authors};
+use crate::infrastructure::database::models::author::{AuthorCountRow, AuthorRow, NewAuthorRow};
+use crate::infrastructure::database::models::schema::{authors, book_authors, books};
 
 /// Diesel-backed implementation of [`AuthorRepository`].
 pub struct AuthorRepoImpl;
@@ -65,17 +75,271 @@ impl AuthorRepository for AuthorRepoImpl {
         }
     }
 
-    /// Returns all authors linked to the given book via `book_authors`.
+    /// Returns the authors linked to the given book via `book_authors` in the
+    /// `aut` role, in link order.
     async fn get_authors_by_book(&self, find_book_id: i32) -> Result<Vec<Author>, DomainError> {
         let mut conn = connect_from_pool().await?;
 
         let rows = book_authors::table
             .inner_join(authors::table)
             .filter(book_authors::book_id.eq(find_book_id))
+            .filter(book_authors::role.eq(ROLE_AUTHOR))
             .select((authors::author_id, authors::name))
+            .order(diesel::dsl::sql::<diesel::sql_types::Integer>(
+                "book_authors.rowid",
+            ))
             .load::<AuthorRow>(&mut conn)
             .await?;
 
         Ok(rows.into_iter().map(Author::from).collect())
     }
+
+    /// Returns every credited contributor of the given book with role and
+    /// sort name, in link order.
+    async fn get_contributors_by_book(
+        &self,
+        find_book_id: i32,
+    ) -> Result<Vec<Contributor>, DomainError> {
+        let mut conn = connect_from_pool().await?;
+
+        let rows = book_authors::table
+            .inner_join(authors::table)
+            .filter(book_authors::book_id.eq(find_book_id))
+            .select((authors::name, book_authors::role, book_authors::sort_name))
+            .order(diesel::dsl::sql::<diesel::sql_types::Integer>(
+                "book_authors.rowid",
+            ))
+            .load::<(String, String, Option<String>)>(&mut conn)
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(name, role, sort_name)| Contributor {
+                name,
+                role,
+                sort_name,
+            })
+            .collect())
+    }
+
+    /// Returns the author by ID, or `None`.
+    async fn find_by_id(&self, find_id: i32) -> Result<Option<Author>, DomainError> {
+        let mut conn = connect_from_pool().await?;
+
+        let rows = authors::dsl::authors
+            .filter(authors::author_id.eq(find_id))
+            .limit(1)
+            .load::<AuthorRow>(&mut conn)
+            .await?;
+
+        Ok(rows.into_iter().next().map(Author::from))
+    }
+
+    /// Lists all authors with their distinct book counts in one grouped query.
+    async fn list_with_counts(&self) -> Result<Vec<AuthorSummary>, DomainError> {
+        let mut conn = connect_from_pool().await?;
+
+        let rows = sql_query(
+            "SELECT a.author_id, a.name, COUNT(DISTINCT ba.book_id) AS book_count \
+             FROM authors a \
+             LEFT JOIN book_authors ba ON ba.author_id = a.author_id \
+             GROUP BY a.author_id, a.name \
+             ORDER BY LOWER(a.name), a.author_id",
+        )
+        .load::<AuthorCountRow>(&mut conn)
+        .await?;
+
+        Ok(rows.into_iter().map(AuthorSummary::from).collect())
+    }
+
+    /// Renames an author after checking that no other author has the name.
+    async fn rename(&self, find_id: i32, new_name: &str) -> Result<Author, DomainError> {
+        let _db_lock = lock_db();
+        let mut conn = connect_from_pool().await?;
+
+        let taken = authors::dsl::authors
+            .filter(authors::name.eq(new_name))
+            .filter(authors::author_id.ne(find_id))
+            .limit(1)
+            .load::<AuthorRow>(&mut conn)
+            .await?;
+        if let Some(other) = taken.into_iter().next() {
+            return Err(DomainError::InvalidInput(format!(
+                "Author \"{}\" already exists (id {}); merge instead",
+                new_name,
+                other.author_id.unwrap_or(0)
+            )));
+        }
+
+        let updated = conn
+            .transaction(async |connection| {
+                let count =
+                    diesel::update(authors::dsl::authors.filter(authors::author_id.eq(find_id)))
+                        .set(authors::name.eq(new_name))
+                        .execute(connection)
+                        .await?;
+                sql_query(CLEAR_AUTHOR_PLACEHOLDER_COVERS)
+                    .bind::<Integer, _>(find_id)
+                    .execute(connection)
+                    .await?;
+                let book_ids = forget_credit_sort_names(connection, &[find_id]).await?;
+                refresh_author_sorts(connection, &book_ids).await?;
+                Ok::<usize, diesel::result::Error>(count)
+            })
+            .await?;
+
+        if updated == 0 {
+            return Err(DomainError::NotFound);
+        }
+
+        Ok(Author {
+            id: find_id,
+            name: new_name.to_string(),
+        })
+    }
+
+    /// Re-points `book_authors` links from each source to the target with
+    /// `UPDATE OR IGNORE`, drops the links that would have duplicated an
+    /// existing credit, and deletes the source rows.
+    async fn merge(&self, source_ids: &[i32], target_id: i32) -> Result<(), DomainError> {
+        let _db_lock = lock_db();
+        let mut conn = connect_from_pool().await?;
+
+        let target = authors::dsl::authors
+            .filter(authors::author_id.eq(target_id))
+            .limit(1)
+            .load::<AuthorRow>(&mut conn)
+            .await?;
+        if target.is_empty() {
+            return Err(DomainError::NotFound);
+        }
+
+        conn.transaction(async |connection| {
+            let sources: Vec<i32> = source_ids
+                .iter()
+                .copied()
+                .filter(|&id| id != target_id)
+                .collect();
+            let book_ids = forget_credit_sort_names(connection, &sources).await?;
+            for &source_id in &sources {
+                sql_query(CLEAR_AUTHOR_PLACEHOLDER_COVERS)
+                    .bind::<Integer, _>(source_id)
+                    .execute(connection)
+                    .await?;
+                sql_query("UPDATE OR IGNORE book_authors SET author_id = ? WHERE author_id = ?")
+                    .bind::<Integer, _>(target_id)
+                    .bind::<Integer, _>(source_id)
+                    .execute(connection)
+                    .await?;
+                diesel::delete(book_authors::table.filter(book_authors::author_id.eq(source_id)))
+                    .execute(connection)
+                    .await?;
+                diesel::delete(authors::dsl::authors.filter(authors::author_id.eq(source_id)))
+                    .execute(connection)
+                    .await?;
+            }
+            refresh_author_sorts(connection, &book_ids).await?;
+            Ok::<(), diesel::result::Error>(())
+        })
+        .await?;
+
+        Ok(())
+    }
+
+    /// Returns the distinct `book_id`s of the authors' `book_authors` links.
+    async fn find_book_ids(&self, ids: &[i32]) -> Result<Vec<i32>, DomainError> {
+        let mut conn = connect_from_pool().await?;
+
+        let book_ids = book_authors::table
+            .filter(book_authors::author_id.eq_any(ids))
+            .select(book_authors::book_id)
+            .distinct()
+            .order(book_authors::book_id)
+            .load::<i32>(&mut conn)
+            .await?;
+
+        Ok(book_ids)
+    }
+
+    /// Deletes authors with no `book_authors` links.
+    async fn delete_orphans(&self) -> Result<usize, DomainError> {
+        let _db_lock = lock_db();
+        let mut conn = connect_from_pool().await?;
+
+        let deleted = conn
+            .transaction(async |connection| {
+                let count = sql_query(
+                    "DELETE FROM authors \
+                     WHERE author_id NOT IN (SELECT author_id FROM book_authors)",
+                )
+                .execute(connection)
+                .await?;
+                Ok::<usize, diesel::result::Error>(count)
+            })
+            .await?;
+
+        Ok(deleted)
+    }
+}
+
+/// Drops the `file-as` sort names of the authors' credits, which spell the
+/// name being renamed or merged away.
+///
+/// # Returns
+///
+/// The IDs of the books the authors are credited on.
+async fn forget_credit_sort_names(
+    connection: &mut SyncConnectionWrapper<SqliteConnection>,
+    author_ids: &[i32],
+) -> QueryResult<Vec<i32>> {
+    let book_ids = book_authors::table
+        .filter(book_authors::author_id.eq_any(author_ids))
+        .select(book_authors::book_id)
+        .distinct()
+        .load::<i32>(connection)
+        .await?;
+    diesel::update(book_authors::table.filter(book_authors::author_id.eq_any(author_ids)))
+        .set(book_authors::sort_name.eq(None::<String>))
+        .execute(connection)
+        .await?;
+    Ok(book_ids)
+}
+
+/// Derives `books.author_sort` of the given books again from their credits,
+/// as import does.
+async fn refresh_author_sorts(
+    connection: &mut SyncConnectionWrapper<SqliteConnection>,
+    book_ids: &[i32],
+) -> QueryResult<()> {
+    let credits = book_authors::table
+        .inner_join(authors::table)
+        .filter(book_authors::book_id.eq_any(book_ids))
+        .select((
+            book_authors::book_id,
+            authors::name,
+            book_authors::role,
+            book_authors::sort_name,
+        ))
+        .order(diesel::dsl::sql::<Integer>("book_authors.rowid"))
+        .load::<(i32, String, String, Option<String>)>(connection)
+        .await?;
+
+    let mut contributors: HashMap<i32, Vec<Contributor>> = HashMap::new();
+    for (book_id, name, role, sort_name) in credits {
+        contributors.entry(book_id).or_default().push(Contributor {
+            name,
+            role,
+            sort_name,
+        });
+    }
+    for &book_id in book_ids {
+        let author_sort = contributors
+            .get(&book_id)
+            .and_then(|credits| sort_key::authors_sort(credits));
+        diesel::update(books::table.filter(books::book_id.eq(book_id)))
+            .set(books::author_sort.eq(author_sort))
+            .execute(connection)
+            .await?;
+    }
+    Ok(())
 }
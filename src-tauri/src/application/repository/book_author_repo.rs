@@ -1,7 +1,7 @@
 use async_trait::async_trait;
 
 use crate::domain::error::DomainError;
-use crate::domain::repository::BookAuthorRepository;
+use crate::domain::repository::{BookAuthorRepository, NewBookAuthor};
 use crate::infrastructure::database::database::{connect_from_pool, lock_db};
 use crate::infrastructure::database::models::book_author::BookAuthorRow;
 use crate::infrastructure::database::models::schema::book_authors;
@@ -24,14 +24,16 @@ impl Default for BookAuthorRepoImpl {
 
 #[async_trait]
 impl BookAuthorRepository for BookAuthorRepoImpl {
-    /// Creates a book-author link in the `book_authors` join table.
-    async fn link(&self, find_book_id: i32, find_author_id: i32) -> Result<(), DomainError> {
+    /// Creates a book-author link with its role in the `book_authors` join table.
+    async fn link(&self, find_book_id: i32, author: &NewBookAuthor) -> Result<(), DomainError> {
         let _db_lock = lock_db();
         let mut conn = connect_from_pool().await?;
 
         let row = BookAuthorRow {
             book_id: find_book_id,
-            author_id: find_author_id,
+            author_id: author.author_id,
+            role: author.role.clone(),
+            sort_name: author.sort_name.clone(),
         };
 
         conn.transaction(async |connection| {
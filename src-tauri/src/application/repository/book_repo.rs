@@ -1,16 +1,47 @@
 use async_trait::async_trait;
+use base64::{Engine as _, engine::general_purpose};
+use chrono::{NaiveDate, Utc};
 use diesel::prelude::*;
+use diesel::query_builder::{BoxedSqlQuery, SqlQuery};
 use diesel::sql_query;
-use diesel::sql_types::Integer;
+use diesel::sql_types::{BigInt, Integer, Text};
+use diesel::sqlite::Sqlite;
 use diesel_async::{AsyncConnection, RunQueryDsl};
 
+use crate::application::repository::work_repo::{DELETE_EMPTY_WORKS, UNLINK_SINGLE_BOOK_WORKS};
+use crate::domain::dto::book_dto::{BookDto, BookFormat, BookPage};
 use crate::domain::error::DomainError;
 use crate::domain::models::book::Book;
-use crate::domain::repository::{BookRepository, NewBook, UpdateBook};
+use crate::domain::models::contributor::ROLE_AUTHOR;
+use crate::domain::models::identifier::{Identifier, SCHEME_ISBN};
+use crate::domain::models::isbn::Isbn;
+use crate::domain::models::partial_date::PartialDate;
+use crate::domain::repository::{
+    BookMetadataChanges, BookQuery, BookRepository, BookSortKey, NewBook, NewBookAuthor,
+    Pagination, ReadingStatus, SortDirection, UpdateBook,
+};
 use crate::infrastructure::database::database::{connect_from_pool, lock_db};
-use crate::infrastructure::database::models::book::{BookRow, NewBookRow, UpdateBookRow};
+use crate::infrastructure::database::models::book::{
+    BookListingRow, BookRow, NewBookRow, UpdateBookMetadataRow, UpdateBookRow, join_colors,
+};
 use crate::infrastructure::database::models::book_author::BookAuthorRow;
-use crate::infrastructure::database::models::schema::{book_authors, books};
+use crate::infrastructure::database::models::schema::{
+    book_authors, book_identifiers, book_subjects, book_tags, books,
+};
+
+/// Forgets a book's generated placeholder cover, palette and BlurHash, so
+/// the next cover request draws it again with the current title and author.
+/// Binds the `book_id`.
+pub(crate) const CLEAR_PLACEHOLDER_COVER: &str = "UPDATE books SET cover_image_path = NULL, \
+    cover_is_placeholder = 0, dominant_colors = NULL, blurhash = NULL \
+    WHERE book_id = ? AND cover_is_placeholder = 1";
+
+/// [`CLEAR_PLACEHOLDER_COVER`] for every book credited to an author. Binds
+/// the `author_id`.
+pub(crate) const CLEAR_AUTHOR_PLACEHOLDER_COVERS: &str = "UPDATE books SET \
+    cover_image_path = NULL, cover_is_placeholder = 0, dominant_colors = NULL, blurhash = NULL \
+    WHERE cover_is_placeholder = 1 \
+    AND book_id IN (SELECT book_id FROM book_authors WHERE author_id = ?)";
 
 /// Helper for retrieving the last inserted row ID via `last_insert_rowid()`.
 #[derive(QueryableByName)]
@@ -19,6 +50,203 @@ struct LastInsertRow {
     book_id: i32,
 }
 
+/// A positional bind parameter for the dynamically-built listing query.
+enum BindValue {
+    Text(String),
+    Int(i64),
+}
+
+/// Helper for reading a single `COUNT(*)` result.
+#[derive(QueryableByName)]
+struct CountRow {
+    #[diesel(sql_type = BigInt)]
+    count: i64,
+}
+
+/// Helper for reading one file of a work.
+#[derive(QueryableByName)]
+struct FormatRow {
+    #[diesel(sql_type = Integer)]
+    book_id: i32,
+    #[diesel(sql_type = Text)]
+    file_type: String,
+}
+
+/// `FROM` clause shared by the listing query and its count fallback.
+const LISTING_FROM: &str = "FROM books b \
+    LEFT JOIN works w ON w.work_id = b.work_id \
+    LEFT JOIN publishers p ON p.publisher_id = b.publisher_id \
+    LEFT JOIN reading_progress rp ON rp.book_id = b.book_id";
+
+/// Returns the `SELECT` list shared by the listing queries: the `books`
+/// columns, the aggregated files of the work, tags, subjects, identifiers
+/// and contributors, and the publisher name. Expects [`LISTING_FROM`].
+fn listing_columns() -> String {
+    format!(
+        "b.book_id, b.title, b.published_date, b.publisher_id, b.isbn, \
+         b.file_type, b.file_path, b.cover_image_path, b.cover_is_placeholder, \
+         b.checksum, b.added_at, b.dominant_colors, b.blurhash, b.language, \
+         b.description, b.series, b.series_index, b.rights, b.modified_date, b.epub_version, \
+         b.published_date_normalized, b.title_sort, b.author_sort, b.work_id, b.deleted_at, \
+         (SELECT GROUP_CONCAT(wb.book_id || char(30) || wb.file_type, char(31) \
+         ORDER BY {}) FROM books wb WHERE wb.book_id = b.book_id \
+         OR (wb.work_id = b.work_id AND wb.deleted_at IS NULL)) AS format_data, \
+         (SELECT GROUP_CONCAT(bt.tag, char(31) ORDER BY bt.rowid) FROM book_tags bt \
+         WHERE bt.book_id = b.book_id) AS tag_data, \
+         (SELECT GROUP_CONCAT(bs.subject, char(31) ORDER BY bs.rowid) \
+         FROM book_subjects bs WHERE bs.book_id = b.book_id) AS subject_data, \
+         (SELECT GROUP_CONCAT(bi.scheme || char(30) || bi.value, char(31) \
+         ORDER BY bi.rowid) FROM book_identifiers bi \
+         WHERE bi.book_id = b.book_id) AS identifier_data, \
+         (SELECT GROUP_CONCAT( \
+         a.name || char(30) || ba.role || char(30) || COALESCE(ba.sort_name, ''), \
+         char(31) ORDER BY ba.rowid) FROM book_authors ba \
+         JOIN authors a ON a.author_id = ba.author_id \
+         WHERE ba.book_id = b.book_id) AS contributor_data, \
+         p.name AS publisher_name",
+        format_order("wb"),
+    )
+}
+
+/// Returns the `ORDER BY` terms ranking the files of a work joined as `w`:
+/// the preferred book, then EPUBs, then the earliest imported file.
+fn format_order(alias: &str) -> String {
+    format!(
+        "{a}.book_id = w.preferred_book_id DESC, {a}.file_type = 'epub' DESC, {a}.book_id",
+        a = alias
+    )
+}
+
+/// Separates the sort key from the book ID inside a listing cursor.
+const CURSOR_SEPARATOR: char = '\u{1f}';
+
+/// Returns the SQL expression a listing is ordered by.
+///
+/// Expressions never evaluate to `NULL` so that keyset comparisons on the
+/// cursor stay total.
+fn sort_expression(sort: BookSortKey) -> &'static str {
+    match sort {
+        BookSortKey::Title => "LOWER(COALESCE(b.title_sort, b.title))",
+        BookSortKey::Author => {
+            "COALESCE(LOWER(b.author_sort), \
+             (SELECT MIN(LOWER(COALESCE(ba.sort_name, a.name))) FROM book_authors ba \
+             JOIN authors a ON a.author_id = ba.author_id \
+             WHERE ba.book_id = b.book_id AND ba.role = 'aut'), '')"
+        }
+        BookSortKey::AddedAt => "COALESCE(b.added_at, '')",
+        BookSortKey::LastRead => "COALESCE(rp.last_read_at, '')",
+        BookSortKey::Published => "COALESCE(b.published_date_normalized, '')",
+    }
+}
+
+/// Normalizes a publication date to the sortable `YYYY[-MM[-DD]]` form.
+fn normalized_date(date: &str) -> Option<String> {
+    PartialDate::parse(date).map(|d| d.to_string())
+}
+
+/// Encodes the position after `row` as an opaque, URL-safe cursor.
+fn encode_cursor(row: &BookListingRow) -> String {
+    general_purpose::URL_SAFE_NO_PAD.encode(format!(
+        "{}{}{}",
+        row.sort_key, CURSOR_SEPARATOR, row.book_id
+    ))
+}
+
+/// Decodes a cursor produced by [`encode_cursor`] into `(sort_key, book_id)`.
+fn decode_cursor(cursor: &str) -> Result<(String, i64), DomainError> {
+    let invalid = || DomainError::Parse(format!("Invalid cursor: {}", cursor));
+    let bytes = general_purpose::URL_SAFE_NO_PAD
+        .decode(cursor)
+        .map_err(|_| invalid())?;
+    let decoded = String::from_utf8(bytes).map_err(|_| invalid())?;
+    let (key, id) = decoded.rsplit_once(CURSOR_SEPARATOR).ok_or_else(invalid)?;
+    let id = id.parse::<i64>().map_err(|_| invalid())?;
+    Ok((key.to_string(), id))
+}
+
+/// Wraps raw SQL into a boxed query with its positional binds applied in order.
+fn bind_all(sql: String, binds: Vec<BindValue>) -> BoxedSqlQuery<'static, Sqlite, SqlQuery> {
+    let mut query = sql_query(sql).into_boxed::<Sqlite>();
+    for bind in binds {
+        query = match bind {
+            BindValue::Text(value) => query.bind::<Text, _>(value),
+            BindValue::Int(value) => query.bind::<BigInt, _>(value),
+        };
+    }
+    query
+}
+
+/// Builds the `WHERE` conditions for a listing's filters, appending their
+/// bind values to `binds`.
+fn filter_conditions(query: &BookQuery, binds: &mut Vec<BindValue>) -> String {
+    let filter = &query.filter;
+    let mut sql = String::new();
+
+    if let Some(author) = &filter.author {
+        sql.push_str(
+            " AND EXISTS (SELECT 1 FROM book_authors ba \
+             JOIN authors a ON a.author_id = ba.author_id \
+             WHERE ba.book_id = b.book_id AND a.name LIKE ?)",
+        );
+        binds.push(BindValue::Text(format!("%{}%", author)));
+    }
+    if let Some(publisher) = &filter.publisher {
+        sql.push_str(" AND p.name LIKE ?");
+        binds.push(BindValue::Text(format!("%{}%", publisher)));
+    }
+    if let Some(file_type) = &filter.file_type {
+        sql.push_str(" AND b.file_type = ?");
+        binds.push(BindValue::Text(file_type.to_lowercase()));
+    }
+    if let Some(after) = &filter.added_after {
+        sql.push_str(" AND b.added_at >= ?");
+        binds.push(BindValue::Text(after.clone()));
+    }
+    if let Some(before) = &filter.added_before {
+        // A date alone includes the whole day.
+        if NaiveDate::parse_from_str(before, "%Y-%m-%d").is_ok() {
+            sql.push_str(" AND date(b.added_at) <= ?");
+        } else {
+            sql.push_str(" AND b.added_at <= ?");
+        }
+        binds.push(BindValue::Text(before.clone()));
+    }
+    match filter.status {
+        Some(ReadingStatus::Unread) => {
+            sql.push_str(" AND COALESCE(rp.progress_percentage, 0) <= 0");
+        }
+        Some(ReadingStatus::Reading) => {
+            sql.push_str(" AND rp.progress_percentage > 0 AND rp.progress_percentage < 100");
+        }
+        Some(ReadingStatus::Finished) => {
+            sql.push_str(" AND rp.progress_percentage >= 100");
+        }
+        None => {}
+    }
+    if let Some(language) = &filter.language {
+        sql.push_str(" AND (LOWER(b.language) = LOWER(?) OR LOWER(b.language) LIKE LOWER(?))");
+        binds.push(BindValue::Text(language.clone()));
+        binds.push(BindValue::Text(format!("{}-%", language)));
+    }
+    if let Some(year) = filter.published_from {
+        sql.push_str(" AND CAST(SUBSTR(b.published_date_normalized, 1, 4) AS INTEGER) >= ?");
+        binds.push(BindValue::Int(i64::from(year)));
+    }
+    if let Some(year) = filter.published_to {
+        sql.push_str(" AND CAST(SUBSTR(b.published_date_normalized, 1, 4) AS INTEGER) <= ?");
+        binds.push(BindValue::Int(i64::from(year)));
+    }
+    if let Some(subject) = &filter.subject {
+        sql.push_str(
+            " AND EXISTS (SELECT 1 FROM book_subjects bs \
+             WHERE bs.book_id = b.book_id AND bs.subject = ? COLLATE NOCASE)",
+        );
+        binds.push(BindValue::Text(subject.clone()));
+    }
+
+    sql
+}
+
 /// Diesel-backed implementation of [`BookRepository`].
 pub struct BookRepoImpl;
 
@@ -35,13 +263,14 @@ impl BookRepoImpl {
 
     /// Imports a new book with author and publisher links in a single transaction.
     ///
-    /// Inserts the book row, retrieves its generated ID, and links all authors
-    /// via the `book_authors` join table. The publisher link is set in a
+    /// Inserts the book row, retrieves its generated ID, links all authors
+    /// via the `book_authors` join table, and stores its subjects and
+    /// identifiers. The publisher link is set in a
     /// separate update after the transaction (since it requires the book ID).
     pub async fn import_with_links(
         &self,
         book: NewBook,
-        author_ids: &[(i32, String)],
+        authors: &[NewBookAuthor],
         publisher_id: Option<i32>,
     ) -> Result<Book, DomainError> {
         let _db_lock = lock_db();
@@ -56,6 +285,18 @@ impl BookRepoImpl {
             file_path: &book.file_path,
             cover_image_path: book.cover_image_path.as_deref(),
             checksum: book.checksum.as_deref(),
+            dominant_colors: join_colors(&book.dominant_colors),
+            blurhash: book.blurhash.as_deref(),
+            language: book.language.as_deref(),
+            description: book.description.as_deref(),
+            rights: book.rights.as_deref(),
+            modified_date: book.modified_date.as_deref(),
+            epub_version: book.epub_version.as_deref(),
+            published_date_normalized: book.published_date.as_deref().and_then(normalized_date),
+            series: book.series.as_deref(),
+            series_index: book.series_index,
+            title_sort: book.title_sort.as_deref(),
+            author_sort: book.author_sort.as_deref(),
         };
         
         let book_id = conn.transaction(async |connection| {
@@ -70,11 +311,13 @@ impl BookRepoImpl {
                 .await?;
             let book_id = result.book_id;
 
-            // Link all authors in the same transaction
-            for (author_id, _author_name) in author_ids {
+            // Link all contributors in the same transaction
+            for author in authors {
                 let link = BookAuthorRow {
                     book_id,
-                    author_id: *author_id,
+                    author_id: author.author_id,
+                    role: author.role.clone(),
+                    sort_name: author.sort_name.clone(),
                 };
                 diesel::insert_into(book_authors::table)
                     .values(&link)
@@ -82,6 +325,27 @@ impl BookRepoImpl {
                     .await?;
             }
 
+            for subject in &book.subjects {
+                diesel::insert_or_ignore_into(book_subjects::table)
+                    .values((
+                        book_subjects::book_id.eq(book_id),
+                        book_subjects::subject.eq(subject),
+                    ))
+                    .execute(connection)
+                    .await?;
+            }
+
+            for identifier in &book.identifiers {
+                diesel::insert_or_ignore_into(book_identifiers::table)
+                    .values((
+                        book_identifiers::book_id.eq(book_id),
+                        book_identifiers::scheme.eq(&identifier.scheme),
+                        book_identifiers::value.eq(&identifier.value),
+                    ))
+                    .execute(connection)
+                    .await?;
+            }
+
             Ok::<i32, diesel::result::Error>(book_id)
         })
             .await?;
@@ -106,15 +370,43 @@ impl BookRepoImpl {
 
         Ok(Book::from(row))
     }
+
+    /// Sets or clears a book's `deleted_at` timestamp.
+    async fn set_deleted_at(
+        &self,
+        find_id: i32,
+        deleted_at: Option<String>,
+    ) -> Result<(), DomainError> {
+        let _db_lock = lock_db();
+        let mut conn = connect_from_pool().await?;
+
+        let updated = conn
+            .transaction(async |connection| {
+                let updated = diesel::update(books::dsl::books.filter(books::book_id.eq(find_id)))
+                    .set(books::deleted_at.eq(deleted_at))
+                    .execute(connection)
+                    .await?;
+                Ok::<usize, diesel::result::Error>(updated)
+            })
+            .await?;
+        if updated == 0 {
+            return Err(DomainError::BookNotFound(find_id));
+        }
+
+        Ok(())
+    }
 }
 
 #[async_trait]
 impl BookRepository for BookRepoImpl {
-    /// Returns all books from the `books` table.
+    /// Returns all books from the `books` table that are not in the trash.
     async fn find_all(&self) -> Result<Vec<Book>, DomainError> {
         let mut conn = connect_from_pool().await?;
 
-        let rows = books::dsl::books.load::<BookRow>(&mut conn).await?;
+        let rows = books::dsl::books
+            .filter(books::deleted_at.is_null())
+            .load::<BookRow>(&mut conn)
+            .await?;
 
         Ok(rows.into_iter().map(Book::from).collect())
     }
@@ -148,6 +440,18 @@ impl BookRepository for BookRepoImpl {
             file_path: &book.file_path,
             cover_image_path: book.cover_image_path.as_deref(),
             checksum: book.checksum.as_deref(),
+            dominant_colors: join_colors(&book.dominant_colors),
+            blurhash: book.blurhash.as_deref(),
+            language: book.language.as_deref(),
+            description: book.description.as_deref(),
+            rights: book.rights.as_deref(),
+            modified_date: book.modified_date.as_deref(),
+            epub_version: book.epub_version.as_deref(),
+            published_date_normalized: book.published_date.as_deref().and_then(normalized_date),
+            series: book.series.as_deref(),
+            series_index: book.series_index,
+            title_sort: book.title_sort.as_deref(),
+            author_sort: book.author_sort.as_deref(),
         };
 
         let id = conn
@@ -181,6 +485,10 @@ impl BookRepository for BookRepoImpl {
             file_path: book.file_path.as_deref(),
             cover_image_path: book.cover_image_path.as_deref(),
             checksum: book.checksum.as_deref(),
+            dominant_colors: book.dominant_colors.as_deref().and_then(join_colors),
+            blurhash: book.blurhash.as_deref(),
+            published_date_normalized: book.published_date.as_deref().map(normalized_date),
+            cover_is_placeholder: book.cover_is_placeholder,
         };
 
         conn.transaction(async |connection| {
@@ -195,7 +503,8 @@ impl BookRepository for BookRepoImpl {
         Ok(())
     }
 
-    /// Deletes a book by ID. Cascade deletes handle associated records.
+    /// Permanently deletes a book by ID. Cascade deletes handle associated
+    /// records.
     async fn delete(&self, find_id: i32) -> Result<(), DomainError> {
         let _db_lock = lock_db();
         let mut conn = connect_from_pool().await?;
@@ -204,6 +513,153 @@ impl BookRepository for BookRepoImpl {
             diesel::delete(books::dsl::books.filter(books::book_id.eq(find_id)))
                 .execute(connection)
                 .await?;
+            // The book's work may now hold a single file.
+            sql_query(UNLINK_SINGLE_BOOK_WORKS)
+                .execute(connection)
+                .await?;
+            sql_query(DELETE_EMPTY_WORKS).execute(connection).await?;
+            Ok::<(), diesel::result::Error>(())
+        })
+        .await?;
+
+        Ok(())
+    }
+
+    /// Sets `deleted_at` to the current time; the book keeps its work.
+    async fn trash(&self, find_id: i32) -> Result<(), DomainError> {
+        let now = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+        self.set_deleted_at(find_id, Some(now)).await
+    }
+
+    /// Clears `deleted_at`.
+    async fn restore(&self, find_id: i32) -> Result<(), DomainError> {
+        self.set_deleted_at(find_id, None).await
+    }
+
+    /// Returns the books with a `deleted_at`, newest first, resolved in one
+    /// query with the same aggregated columns as [`query`](Self::query).
+    async fn query_trashed(&self) -> Result<Vec<BookDto>, DomainError> {
+        let mut conn = connect_from_pool().await?;
+
+        let rows = sql_query(format!(
+            "SELECT {}, b.deleted_at AS sort_key, COUNT(*) OVER () AS total_count \
+             {} WHERE b.deleted_at IS NOT NULL \
+             ORDER BY b.deleted_at DESC, b.book_id DESC",
+            listing_columns(),
+            LISTING_FROM,
+        ))
+        .load::<BookListingRow>(&mut conn)
+        .await?;
+
+        Ok(rows.iter().map(BookListingRow::to_dto).collect())
+    }
+
+    /// Deletes trashed books (trashed before the cutoff, if given) in one
+    /// transaction, then dissolves works left with a single file.
+    async fn purge_trash(&self, older_than: Option<&str>) -> Result<usize, DomainError> {
+        let _db_lock = lock_db();
+        let mut conn = connect_from_pool().await?;
+
+        let deleted = conn
+            .transaction(async |connection| {
+                let deleted = match older_than {
+                    Some(cutoff) => {
+                        diesel::delete(
+                            books::dsl::books
+                                .filter(books::deleted_at.is_not_null())
+                                .filter(books::deleted_at.lt(cutoff)),
+                        )
+                        .execute(connection)
+                        .await?
+                    }
+                    None => {
+                        diesel::delete(books::dsl::books.filter(books::deleted_at.is_not_null()))
+                            .execute(connection)
+                            .await?
+                    }
+                };
+                if deleted > 0 {
+                    sql_query(UNLINK_SINGLE_BOOK_WORKS)
+                        .execute(connection)
+                        .await?;
+                    sql_query(DELETE_EMPTY_WORKS).execute(connection).await?;
+                }
+                Ok::<usize, diesel::result::Error>(deleted)
+            })
+            .await?;
+
+        Ok(deleted)
+    }
+
+    /// Re-points the sources' user data to the target with `UPDATE` statements
+    /// before deleting them, so the cascade only removes what was not moved.
+    async fn merge(&self, source_ids: &[i32], target_id: i32) -> Result<(), DomainError> {
+        let _db_lock = lock_db();
+        let mut conn = connect_from_pool().await?;
+
+        let target = books::dsl::books
+            .filter(books::book_id.eq(target_id))
+            .count()
+            .get_result::<i64>(&mut conn)
+            .await?;
+        if target == 0 {
+            return Err(DomainError::BookNotFound(target_id));
+        }
+
+        conn.transaction(async |connection| {
+            for &source_id in source_ids.iter().filter(|&&id| id != target_id) {
+                // Keep whichever progress was read last; `UPDATE OR IGNORE`
+                // leaves the source's row to the cascade when the target's
+                // own progress is kept.
+                sql_query(
+                    "DELETE FROM reading_progress WHERE book_id = ? AND EXISTS \
+                     (SELECT 1 FROM reading_progress s WHERE s.book_id = ? \
+                     AND COALESCE(s.last_read_at, '') > \
+                     COALESCE(reading_progress.last_read_at, ''))",
+                )
+                .bind::<Integer, _>(target_id)
+                .bind::<Integer, _>(source_id)
+                .execute(connection)
+                .await?;
+                for statement in [
+                    "UPDATE OR IGNORE reading_progress SET book_id = ? WHERE book_id = ?",
+                    "UPDATE bookmarks SET book_id = ? WHERE book_id = ?",
+                    "UPDATE annotations SET book_id = ? WHERE book_id = ?",
+                    "UPDATE OR IGNORE book_tags SET book_id = ? WHERE book_id = ?",
+                ] {
+                    sql_query(statement)
+                        .bind::<Integer, _>(target_id)
+                        .bind::<Integer, _>(source_id)
+                        .execute(connection)
+                        .await?;
+                }
+
+                sql_query(
+                    "UPDATE books SET work_id = (SELECT work_id FROM books WHERE book_id = ?) \
+                     WHERE book_id = ? AND work_id IS NULL",
+                )
+                .bind::<Integer, _>(source_id)
+                .bind::<Integer, _>(target_id)
+                .execute(connection)
+                .await?;
+                sql_query(
+                    "UPDATE works SET preferred_book_id = ? WHERE preferred_book_id = ? \
+                     AND work_id = (SELECT work_id FROM books WHERE book_id = ?)",
+                )
+                .bind::<Integer, _>(target_id)
+                .bind::<Integer, _>(source_id)
+                .bind::<Integer, _>(target_id)
+                .execute(connection)
+                .await?;
+
+                diesel::delete(books::dsl::books.filter(books::book_id.eq(source_id)))
+                    .execute(connection)
+                    .await?;
+            }
+            sql_query(UNLINK_SINGLE_BOOK_WORKS)
+                .execute(connection)
+                .await?;
+            sql_query(DELETE_EMPTY_WORKS).execute(connection).await?;
             Ok::<(), diesel::result::Error>(())
         })
         .await?;
@@ -226,12 +682,38 @@ impl BookRepository for BookRepoImpl {
         }
     }
 
-    /// Searches books by title using a LIKE query (case-insensitive).
+    /// Finds a book by its ISBN column or an `isbn` identifier in either form.
+    async fn find_by_isbn(&self, isbn: &Isbn) -> Result<Vec<Book>, DomainError> {
+        let mut conn = connect_from_pool().await?;
+
+        let forms: Vec<String> = std::iter::once(isbn.to_isbn13())
+            .chain(isbn.to_isbn10())
+            .collect();
+        let identified = book_identifiers::table
+            .filter(book_identifiers::scheme.eq(SCHEME_ISBN))
+            .filter(book_identifiers::value.eq_any(forms.clone()))
+            .select(book_identifiers::book_id.nullable());
+
+        let rows = books::dsl::books
+            .filter(
+                books::isbn
+                    .eq_any(forms)
+                    .or(books::book_id.eq_any(identified)),
+            )
+            .order(books::book_id)
+            .load::<BookRow>(&mut conn)
+            .await?;
+        Ok(rows.into_iter().map(Book::from).collect())
+    }
+
+    /// Searches books by title using a LIKE query (case-insensitive),
+    /// skipping books in the trash.
     async fn search_by_title(&self, title_query: &str) -> Result<Vec<Book>, DomainError> {
         let mut conn = connect_from_pool().await?;
 
         let rows = books::dsl::books
             .filter(books::title.like(format!("%{}%", title_query)))
+            .filter(books::deleted_at.is_null())
             .load::<BookRow>(&mut conn)
             .await?;
 
@@ -241,9 +723,266 @@ impl BookRepository for BookRepoImpl {
     async fn import_with_links(
         &self,
         book: NewBook,
-        author_ids: &[(i32, String)],
+        authors: &[NewBookAuthor],
         publisher_id: Option<i32>,
     ) -> Result<Book, DomainError> {
-        self.import_with_links(book, author_ids, publisher_id).await
+        self.import_with_links(book, authors, publisher_id).await
+    }
+
+    /// Updates the `books` columns, then replaces the `aut` links and the
+    /// tags when requested, all inside one transaction.
+    async fn update_with_links(
+        &self,
+        find_id: i32,
+        changes: BookMetadataChanges,
+    ) -> Result<(), DomainError> {
+        let _db_lock = lock_db();
+        let mut conn = connect_from_pool().await?;
+
+        let exists = books::dsl::books
+            .filter(books::book_id.eq(find_id))
+            .count()
+            .get_result::<i64>(&mut conn)
+            .await?;
+        if exists == 0 {
+            return Err(DomainError::BookNotFound(find_id));
+        }
+
+        let update_row = UpdateBookMetadataRow {
+            title: changes.title.as_deref(),
+            published_date: changes.published_date.as_ref().map(|v| v.as_deref()),
+            publisher_id: changes.publisher_id,
+            isbn: changes.isbn.as_ref().map(|v| v.as_deref()),
+            language: changes.language.as_ref().map(|v| v.as_deref()),
+            description: changes.description.as_ref().map(|v| v.as_deref()),
+            series: changes.series.as_ref().map(|v| v.as_deref()),
+            series_index: changes.series_index,
+            published_date_normalized: changes
+                .published_date
+                .as_ref()
+                .map(|v| v.as_deref().and_then(normalized_date)),
+            title_sort: changes.title_sort.as_ref().map(|v| v.as_deref()),
+            author_sort: changes.author_sort.as_ref().map(|v| v.as_deref()),
+        };
+
+        conn.transaction(async |connection| {
+            if !update_row.is_empty() {
+                diesel::update(books::dsl::books.filter(books::book_id.eq(find_id)))
+                    .set(&update_row)
+                    .execute(connection)
+                    .await?;
+            }
+
+            if let Some(authors) = &changes.authors {
+                diesel::delete(
+                    book_authors::table
+                        .filter(book_authors::book_id.eq(find_id))
+                        .filter(book_authors::role.eq(ROLE_AUTHOR)),
+                )
+                .execute(connection)
+                .await?;
+                for author in authors {
+                    let link = BookAuthorRow {
+                        book_id: find_id,
+                        author_id: author.author_id,
+                        role: author.role.clone(),
+                        sort_name: author.sort_name.clone(),
+                    };
+                    diesel::insert_into(book_authors::table)
+                        .values(&link)
+                        .execute(connection)
+                        .await?;
+                }
+            }
+
+            // The placeholder cover prints the title and first author.
+            if changes.title.is_some() || changes.authors.is_some() {
+                sql_query(CLEAR_PLACEHOLDER_COVER)
+                    .bind::<Integer, _>(find_id)
+                    .execute(connection)
+                    .await?;
+            }
+
+            if let Some(tags) = &changes.tags {
+                diesel::delete(book_tags::table.filter(book_tags::book_id.eq(find_id)))
+                    .execute(connection)
+                    .await?;
+                for tag in tags {
+                    diesel::insert_into(book_tags::table)
+                        .values((book_tags::book_id.eq(find_id), book_tags::tag.eq(tag)))
+                        .execute(connection)
+                        .await?;
+                }
+            }
+
+            Ok::<(), diesel::result::Error>(())
+        })
+        .await?;
+
+        Ok(())
+    }
+
+    /// Returns the book's tags ordered by insertion (`rowid`).
+    async fn get_tags(&self, find_book_id: i32) -> Result<Vec<String>, DomainError> {
+        let mut conn = connect_from_pool().await?;
+
+        let tags = book_tags::table
+            .filter(book_tags::book_id.eq(find_book_id))
+            .select(book_tags::tag)
+            .order(diesel::dsl::sql::<Integer>("book_tags.rowid"))
+            .load::<String>(&mut conn)
+            .await?;
+
+        Ok(tags)
+    }
+
+    /// Returns the book's subjects ordered by insertion (`rowid`).
+    async fn get_subjects(&self, find_book_id: i32) -> Result<Vec<String>, DomainError> {
+        let mut conn = connect_from_pool().await?;
+
+        let subjects = book_subjects::table
+            .filter(book_subjects::book_id.eq(find_book_id))
+            .select(book_subjects::subject)
+            .order(diesel::dsl::sql::<Integer>("book_subjects.rowid"))
+            .load::<String>(&mut conn)
+            .await?;
+
+        Ok(subjects)
+    }
+
+    /// Returns the book's identifiers ordered by insertion (`rowid`).
+    async fn get_identifiers(&self, find_book_id: i32) -> Result<Vec<Identifier>, DomainError> {
+        let mut conn = connect_from_pool().await?;
+
+        let rows = book_identifiers::table
+            .filter(book_identifiers::book_id.eq(find_book_id))
+            .select((book_identifiers::scheme, book_identifiers::value))
+            .order(diesel::dsl::sql::<Integer>("book_identifiers.rowid"))
+            .load::<(String, String)>(&mut conn)
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(scheme, value)| Identifier::new(scheme, value))
+            .collect())
+    }
+
+    /// Returns the files of the book's work in [`format_order`], or the book
+    /// alone when it has no work. Trashed files of the work are left out.
+    async fn get_formats(&self, find_book_id: i32) -> Result<Vec<BookFormat>, DomainError> {
+        let mut conn = connect_from_pool().await?;
+
+        let rows = sql_query(format!(
+            "SELECT fb.book_id, fb.file_type FROM books b \
+             JOIN books fb ON fb.book_id = b.book_id \
+             OR (fb.work_id = b.work_id AND fb.deleted_at IS NULL) \
+             LEFT JOIN works w ON w.work_id = b.work_id \
+             WHERE b.book_id = ? ORDER BY {}",
+            format_order("fb")
+        ))
+        .bind::<Integer, _>(find_book_id)
+        .load::<FormatRow>(&mut conn)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| BookFormat {
+                book_id: row.book_id,
+                file_type: row.file_type,
+            })
+            .collect())
+    }
+
+    /// Runs the filtered listing as one query over the books outside the
+    /// trash: contributors are aggregated with
+    /// `GROUP_CONCAT`, publisher and progress are left-joined, and the total
+    /// match count comes from a `COUNT(*) OVER ()` window evaluated before
+    /// the cursor condition and `LIMIT` are applied.
+    ///
+    /// Books of a work are ranked with a `ROW_NUMBER()` window partitioned by
+    /// work (books without one form their own partition) and only the first
+    /// matching file of each work is kept before counting.
+    async fn query(&self, query: &BookQuery) -> Result<BookPage, DomainError> {
+        let mut conn = connect_from_pool().await?;
+        let mut binds = Vec::new();
+
+        let mut sql = format!(
+            "SELECT * FROM (SELECT *, COUNT(*) OVER () AS total_count FROM ( \
+             SELECT {}, {} AS sort_key, \
+             ROW_NUMBER() OVER (PARTITION BY COALESCE(b.work_id, -b.book_id) \
+             ORDER BY {}) AS work_rank \
+             {} WHERE b.deleted_at IS NULL{}) WHERE work_rank = 1) WHERE 1 = 1",
+            listing_columns(),
+            sort_expression(query.sort),
+            format_order("b"),
+            LISTING_FROM,
+            filter_conditions(query, &mut binds),
+        );
+
+        let (order, after_op) = match query.direction {
+            SortDirection::Asc => ("ASC", ">"),
+            SortDirection::Desc => ("DESC", "<"),
+        };
+
+        let (limit, offset) = match &query.pagination {
+            None => (-1, 0),
+            Some(Pagination::Offset { offset, limit }) => (*limit, *offset),
+            Some(Pagination::Cursor { after, limit }) => {
+                if let Some(cursor) = after {
+                    let (key, id) = decode_cursor(cursor)?;
+                    sql.push_str(&format!(
+                        " AND (sort_key {op} ? OR (sort_key = ? AND book_id {op} ?))",
+                        op = after_op
+                    ));
+                    binds.push(BindValue::Text(key.clone()));
+                    binds.push(BindValue::Text(key));
+                    binds.push(BindValue::Int(id));
+                }
+                (*limit, 0)
+            }
+        };
+
+        sql.push_str(&format!(
+            " ORDER BY sort_key {order}, book_id {order} LIMIT ? OFFSET ?",
+            order = order
+        ));
+        binds.push(BindValue::Int(limit));
+        binds.push(BindValue::Int(offset));
+
+        let rows = bind_all(sql, binds)
+            .load::<BookListingRow>(&mut conn)
+            .await?;
+
+        let total = match rows.first() {
+            Some(row) => row.total_count,
+            None if query.pagination.is_none() => 0,
+            // Past the last page the window has no rows to report on.
+            None => {
+                let mut count_binds = Vec::new();
+                let count_sql = format!(
+                    "SELECT COUNT(DISTINCT COALESCE(b.work_id, -b.book_id)) AS count \
+                     {} WHERE b.deleted_at IS NULL{}",
+                    LISTING_FROM,
+                    filter_conditions(query, &mut count_binds),
+                );
+                bind_all(count_sql, count_binds)
+                    .get_result::<CountRow>(&mut conn)
+                    .await?
+                    .count
+            }
+        };
+
+        let next_cursor = match rows.last() {
+            Some(last) if limit > 0 && rows.len() as i64 == limit => Some(encode_cursor(last)),
+            _ => None,
+        };
+
+        let books = rows.iter().map(BookListingRow::to_dto).collect();
+
+        Ok(BookPage {
+            books,
+            total,
+            next_cursor,
+        })
     }
 }
@@ -43,11 +43,12 @@ impl BookmarkRepository for BookmarkRepoImpl {
         let _db_lock = lock_db();
         let mut conn = connect_from_pool().await?;
 
+        let position = bookmark.position.to_stored();
         let new_row = NewBookmarkRow {
             book_id: bookmark.book_id,
             chapter_title: bookmark.chapter_title.as_deref(),
             page_number: bookmark.page_number,
-            position: &bookmark.position,
+            position: &position,
         };
 
         conn.transaction(async |connection| {
@@ -0,0 +1,77 @@
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel_async::{AsyncConnection, RunQueryDsl};
+
+use crate::domain::error::DomainError;
+use crate::domain::models::ignored_file::IgnoredFile;
+use crate::domain::repository::IgnoredFileRepository;
+use crate::infrastructure::database::database::{connect_from_pool, lock_db};
+use crate::infrastructure::database::models::ignored_file::{IgnoredFileRow, NewIgnoredFileRow};
+use crate::infrastructure::database::models::schema::ignored_files;
+
+/// Diesel-backed implementation of [`IgnoredFileRepository`].
+pub struct IgnoredFileRepoImpl;
+
+impl IgnoredFileRepoImpl {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for IgnoredFileRepoImpl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl IgnoredFileRepository for IgnoredFileRepoImpl {
+    /// Returns all rows of `ignored_files`, newest first.
+    async fn find_all(&self) -> Result<Vec<IgnoredFile>, DomainError> {
+        let mut conn = connect_from_pool().await?;
+
+        let rows = ignored_files::dsl::ignored_files
+            .order(ignored_files::ignored_file_id.desc())
+            .load::<IgnoredFileRow>(&mut conn)
+            .await?;
+
+        Ok(rows.into_iter().map(IgnoredFile::from).collect())
+    }
+
+    /// Inserts the path with `INSERT OR IGNORE` against its unique index.
+    async fn add(&self, file_path: &str) -> Result<(), DomainError> {
+        let _db_lock = lock_db();
+        let mut conn = connect_from_pool().await?;
+
+        conn.transaction(async |connection| {
+            diesel::insert_or_ignore_into(ignored_files::table)
+                .values(&NewIgnoredFileRow { file_path })
+                .execute(connection)
+                .await?;
+            Ok::<(), diesel::result::Error>(())
+        })
+        .await?;
+
+        Ok(())
+    }
+
+    /// Deletes the row for the path, if any.
+    async fn remove(&self, file_path: &str) -> Result<bool, DomainError> {
+        let _db_lock = lock_db();
+        let mut conn = connect_from_pool().await?;
+
+        let deleted = conn
+            .transaction(async |connection| {
+                let deleted = diesel::delete(
+                    ignored_files::dsl::ignored_files
+                        .filter(ignored_files::file_path.eq(file_path)),
+                )
+                .execute(connection)
+                .await?;
+                Ok::<usize, diesel::result::Error>(deleted)
+            })
+            .await?;
+
+        Ok(deleted > 0)
+    }
+}
@@ -0,0 +1,161 @@
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::sql_query;
+use diesel::sql_types::Integer;
+use diesel_async::{AsyncConnection, RunQueryDsl};
+
+use crate::domain::error::DomainError;
+use crate::domain::models::metadata_change::{
+    ChangeSource, FieldChange, MetadataChange, MetadataChangeSet,
+};
+use crate::domain::repository::MetadataHistoryRepository;
+use crate::infrastructure::database::database::{connect_from_pool, lock_db};
+use crate::infrastructure::database::models::metadata_change::{
+    MetadataChangeRow, MetadataChangeSetRow, NewMetadataChangeRow, NewMetadataChangeSetRow,
+};
+use crate::infrastructure::database::models::schema::{metadata_change_sets, metadata_changes};
+
+/// Helper for retrieving the last inserted row ID via `last_insert_rowid()`.
+#[derive(QueryableByName)]
+struct LastInsertRow {
+    #[diesel(sql_type = Integer)]
+    change_set_id: i32,
+}
+
+/// Diesel-backed implementation of [`MetadataHistoryRepository`].
+pub struct MetadataHistoryRepoImpl;
+
+impl MetadataHistoryRepoImpl {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Loads the changes of the given sets and attaches them, in the order
+    /// they were recorded.
+    async fn with_changes(
+        &self,
+        rows: Vec<MetadataChangeSetRow>,
+    ) -> Result<Vec<MetadataChangeSet>, DomainError> {
+        let mut conn = connect_from_pool().await?;
+
+        let set_ids: Vec<i32> = rows.iter().filter_map(|row| row.change_set_id).collect();
+        let change_rows = metadata_changes::dsl::metadata_changes
+            .filter(metadata_changes::change_set_id.eq_any(set_ids))
+            .order(metadata_changes::change_id)
+            .load::<MetadataChangeRow>(&mut conn)
+            .await?;
+
+        let mut sets: Vec<MetadataChangeSet> =
+            rows.into_iter().map(MetadataChangeSet::from).collect();
+        for row in change_rows {
+            if let Some(set) = sets.iter_mut().find(|s| s.id == row.change_set_id) {
+                set.changes.push(MetadataChange::from(row));
+            }
+        }
+        Ok(sets)
+    }
+}
+
+impl Default for MetadataHistoryRepoImpl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl MetadataHistoryRepository for MetadataHistoryRepoImpl {
+    /// Inserts the set row and its change rows in one transaction.
+    async fn record(
+        &self,
+        book_id: i32,
+        source: ChangeSource,
+        changes: &[FieldChange],
+    ) -> Result<Option<i32>, DomainError> {
+        if changes.is_empty() {
+            return Ok(None);
+        }
+
+        let _db_lock = lock_db();
+        let mut conn = connect_from_pool().await?;
+
+        let set_id = conn
+            .transaction(async |connection| {
+                diesel::insert_into(metadata_change_sets::table)
+                    .values(&NewMetadataChangeSetRow {
+                        book_id,
+                        source: source.as_str(),
+                    })
+                    .execute(connection)
+                    .await?;
+                let set_id = sql_query("SELECT last_insert_rowid() AS change_set_id")
+                    .get_result::<LastInsertRow>(connection)
+                    .await?
+                    .change_set_id;
+
+                let rows: Vec<NewMetadataChangeRow> = changes
+                    .iter()
+                    .map(|change| NewMetadataChangeRow {
+                        change_set_id: set_id,
+                        field: change.field,
+                        old_value: change.old_value.as_deref(),
+                        new_value: change.new_value.as_deref(),
+                    })
+                    .collect();
+                diesel::insert_into(metadata_changes::table)
+                    .values(&rows)
+                    .execute(connection)
+                    .await?;
+
+                Ok::<i32, diesel::result::Error>(set_id)
+            })
+            .await?;
+
+        Ok(Some(set_id))
+    }
+
+    /// Loads the book's sets newest first, then their changes in one query.
+    async fn find_by_book(&self, find_book_id: i32) -> Result<Vec<MetadataChangeSet>, DomainError> {
+        let mut conn = connect_from_pool().await?;
+
+        let rows = metadata_change_sets::dsl::metadata_change_sets
+            .filter(metadata_change_sets::book_id.eq(find_book_id))
+            .order(metadata_change_sets::change_set_id.desc())
+            .load::<MetadataChangeSetRow>(&mut conn)
+            .await?;
+
+        self.with_changes(rows).await
+    }
+
+    /// Returns the set by ID with its changes, or `None`.
+    async fn find_set(&self, find_id: i32) -> Result<Option<MetadataChangeSet>, DomainError> {
+        let mut conn = connect_from_pool().await?;
+
+        let rows = metadata_change_sets::dsl::metadata_change_sets
+            .filter(metadata_change_sets::change_set_id.eq(find_id))
+            .limit(1)
+            .load::<MetadataChangeSetRow>(&mut conn)
+            .await?;
+
+        Ok(self.with_changes(rows).await?.into_iter().next())
+    }
+
+    /// Looks up the change's `change_set_id`, then loads that set.
+    async fn find_set_of_change(
+        &self,
+        change_id: i32,
+    ) -> Result<Option<MetadataChangeSet>, DomainError> {
+        let mut conn = connect_from_pool().await?;
+
+        let set_id = metadata_changes::dsl::metadata_changes
+            .filter(metadata_changes::change_id.eq(change_id))
+            .select(metadata_changes::change_set_id)
+            .first::<i32>(&mut conn)
+            .await
+            .optional()?;
+
+        match set_id {
+            Some(set_id) => self.find_set(set_id).await,
+            None => Ok(None),
+        }
+    }
+}
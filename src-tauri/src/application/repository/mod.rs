@@ -5,5 +5,8 @@ pub mod author_repo;
 pub mod book_author_repo;
 pub mod book_repo;
 pub mod bookmark_repo;
+pub mod ignored_file_repo;
+pub mod metadata_history_repo;
 pub mod publisher_repo;
 pub mod reading_progress_repo;
+pub mod work_repo;
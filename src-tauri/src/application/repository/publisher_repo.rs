@@ -1,13 +1,15 @@
 use async_trait::async_trait;
 use diesel::prelude::*;
+use diesel::sql_query;
 use diesel_async::{AsyncConnection, RunQueryDsl};
 
+use crate::domain::dto::publisher_dto::PublisherSummary;
 use crate::domain::error::DomainError;
 use crate::domain::models::publisher::Publisher;
 use crate::domain::repository::PublisherRepository;
 use crate::infrastructure::database::database::{connect_from_pool, lock_db};
-use crate::infrastructure::database::models::publisher::PublisherRow;
-use crate::infrastructure::database::models::schema::publishers;
+use crate::infrastructure::database::models::publisher::{PublisherCountRow, PublisherRow};
+use crate::infrastructure::database::models::schema::{books, publishers};
 
 /// Diesel-backed implementation of [`PublisherRepository`].
 pub struct PublisherRepoImpl;
@@ -78,4 +80,130 @@ impl PublisherRepository for PublisherRepoImpl {
             DomainError::Database("Failed to retrieve newly created publisher".into())
         })
     }
+
+    /// Lists all publishers with their book counts in one grouped query.
+    async fn list_with_counts(&self) -> Result<Vec<PublisherSummary>, DomainError> {
+        let mut conn = connect_from_pool().await?;
+
+        let rows = sql_query(
+            "SELECT p.publisher_id, p.name, COUNT(b.book_id) AS book_count \
+             FROM publishers p \
+             LEFT JOIN books b ON b.publisher_id = p.publisher_id \
+             GROUP BY p.publisher_id, p.name \
+             ORDER BY LOWER(p.name), p.publisher_id",
+        )
+        .load::<PublisherCountRow>(&mut conn)
+        .await?;
+
+        Ok(rows.into_iter().map(PublisherSummary::from).collect())
+    }
+
+    /// Renames a publisher after checking that no other publisher has the name.
+    async fn rename(&self, find_id: i32, new_name: &str) -> Result<Publisher, DomainError> {
+        let _db_lock = lock_db();
+        let mut conn = connect_from_pool().await?;
+
+        let taken = publishers::dsl::publishers
+            .filter(publishers::name.eq(new_name))
+            .filter(publishers::publisher_id.ne(find_id))
+            .limit(1)
+            .load::<PublisherRow>(&mut conn)
+            .await?;
+        if let Some(other) = taken.into_iter().next() {
+            return Err(DomainError::InvalidInput(format!(
+                "Publisher \"{}\" already exists (id {}); merge instead",
+                new_name,
+                other.publisher_id.unwrap_or(0)
+            )));
+        }
+
+        let updated = conn
+            .transaction(async |connection| {
+                let count = diesel::update(
+                    publishers::dsl::publishers.filter(publishers::publisher_id.eq(find_id)),
+                )
+                .set(publishers::name.eq(new_name))
+                .execute(connection)
+                .await?;
+                Ok::<usize, diesel::result::Error>(count)
+            })
+            .await?;
+
+        if updated == 0 {
+            return Err(DomainError::NotFound);
+        }
+
+        Ok(Publisher {
+            id: find_id,
+            name: new_name.to_string(),
+        })
+    }
+
+    /// Re-points `books.publisher_id` from each source to the target and
+    /// deletes the source rows.
+    async fn merge(&self, source_ids: &[i32], target_id: i32) -> Result<(), DomainError> {
+        let _db_lock = lock_db();
+        let mut conn = connect_from_pool().await?;
+
+        let target = publishers::dsl::publishers
+            .filter(publishers::publisher_id.eq(target_id))
+            .limit(1)
+            .load::<PublisherRow>(&mut conn)
+            .await?;
+        if target.is_empty() {
+            return Err(DomainError::NotFound);
+        }
+
+        conn.transaction(async |connection| {
+            for &source_id in source_ids.iter().filter(|&&id| id != target_id) {
+                diesel::update(books::dsl::books.filter(books::publisher_id.eq(source_id)))
+                    .set(books::publisher_id.eq(target_id))
+                    .execute(connection)
+                    .await?;
+                diesel::delete(
+                    publishers::dsl::publishers.filter(publishers::publisher_id.eq(source_id)),
+                )
+                .execute(connection)
+                .await?;
+            }
+            Ok::<(), diesel::result::Error>(())
+        })
+        .await?;
+
+        Ok(())
+    }
+
+    /// Returns the IDs of the books pointing at the publishers.
+    async fn find_book_ids(&self, ids: &[i32]) -> Result<Vec<i32>, DomainError> {
+        let mut conn = connect_from_pool().await?;
+
+        let book_ids = books::dsl::books
+            .filter(books::publisher_id.eq_any(ids))
+            .select(books::book_id)
+            .order(books::book_id)
+            .load::<Option<i32>>(&mut conn)
+            .await?;
+
+        Ok(book_ids.into_iter().flatten().collect())
+    }
+
+    /// Deletes publishers that no book references.
+    async fn delete_orphans(&self) -> Result<usize, DomainError> {
+        let _db_lock = lock_db();
+        let mut conn = connect_from_pool().await?;
+
+        let deleted = conn
+            .transaction(async |connection| {
+                let count = sql_query(
+                    "DELETE FROM publishers WHERE publisher_id NOT IN \
+                     (SELECT publisher_id FROM books WHERE publisher_id IS NOT NULL)",
+                )
+                .execute(connection)
+                .await?;
+                Ok::<usize, diesel::result::Error>(count)
+            })
+            .await?;
+
+        Ok(deleted)
+    }
 }
@@ -54,12 +54,13 @@ impl ReadingProgressRepository for ReadingProgressRepoImpl {
         let _db_lock = lock_db();
         let mut conn = connect_from_pool().await?;
         let now = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+        let current_position = progress.current_position.to_stored();
 
         conn.transaction(async |connection| {
             diesel::insert_into(reading_progress::table)
                 .values(&NewReadingProgressRow {
                     book_id: progress.book_id,
-                    current_position: &progress.current_position,
+                    current_position: &current_position,
                     chapter_title: progress.chapter_title.as_deref(),
                     page_number: progress.page_number,
                     progress_percentage: progress.progress_percentage,
@@ -67,7 +68,7 @@ impl ReadingProgressRepository for ReadingProgressRepoImpl {
                 .on_conflict(reading_progress::book_id)
                 .do_update()
                 .set((
-                    reading_progress::current_position.eq(&progress.current_position),
+                    reading_progress::current_position.eq(&current_position),
                     reading_progress::chapter_title.eq(&progress.chapter_title),
                     reading_progress::page_number.eq(progress.page_number),
                     reading_progress::progress_percentage.eq(progress.progress_percentage),
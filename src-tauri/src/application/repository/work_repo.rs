@@ -0,0 +1,202 @@
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::sql_query;
+use diesel::sql_types::Integer;
+use diesel_async::{AsyncConnection, RunQueryDsl};
+
+use crate::domain::error::DomainError;
+use crate::domain::models::work::Work;
+use crate::domain::repository::WorkRepository;
+use crate::infrastructure::database::database::{connect_from_pool, lock_db};
+use crate::infrastructure::database::models::schema::{books, works};
+use crate::infrastructure::database::models::work::{NewWorkRow, WorkRow};
+
+/// Clears `books.work_id` on books whose work holds no other file.
+pub(crate) const UNLINK_SINGLE_BOOK_WORKS: &str = "UPDATE books SET work_id = NULL \
+    WHERE work_id IN (SELECT work_id FROM books WHERE work_id IS NOT NULL \
+    GROUP BY work_id HAVING COUNT(*) < 2)";
+
+/// Deletes works that no book belongs to.
+pub(crate) const DELETE_EMPTY_WORKS: &str = "DELETE FROM works WHERE work_id NOT IN \
+    (SELECT work_id FROM books WHERE work_id IS NOT NULL)";
+
+/// Helper for retrieving the last inserted row ID via `last_insert_rowid()`.
+#[derive(QueryableByName)]
+struct LastInsertRow {
+    #[diesel(sql_type = Integer)]
+    work_id: i32,
+}
+
+/// Diesel-backed implementation of [`WorkRepository`].
+pub struct WorkRepoImpl;
+
+impl WorkRepoImpl {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for WorkRepoImpl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl WorkRepository for WorkRepoImpl {
+    /// Returns the work by ID, or `None`.
+    async fn find_by_id(&self, find_id: i32) -> Result<Option<Work>, DomainError> {
+        let mut conn = connect_from_pool().await?;
+
+        let rows = works::dsl::works
+            .filter(works::work_id.eq(find_id))
+            .limit(1)
+            .load::<WorkRow>(&mut conn)
+            .await?;
+        Ok(rows.into_iter().next().map(Work::from))
+    }
+
+    /// Points every given book, and every book of the works being merged
+    /// away, at the first existing work or a newly inserted one.
+    async fn link(&self, book_ids: &[i32], title: &str) -> Result<Work, DomainError> {
+        let _db_lock = lock_db();
+        let mut conn = connect_from_pool().await?;
+
+        let rows = books::dsl::books
+            .filter(books::book_id.eq_any(book_ids))
+            .select((books::book_id, books::work_id))
+            .load::<(Option<i32>, Option<i32>)>(&mut conn)
+            .await?;
+
+        let mut existing: Vec<i32> = Vec::new();
+        for &id in book_ids {
+            let (_, work_id) = rows
+                .iter()
+                .find(|(book_id, _)| *book_id == Some(id))
+                .ok_or(DomainError::BookNotFound(id))?;
+            if let Some(work_id) = work_id
+                && !existing.contains(work_id)
+            {
+                existing.push(*work_id);
+            }
+        }
+
+        let work_id = conn
+            .transaction(async |connection| {
+                let work_id = match existing.first() {
+                    Some(&work_id) => work_id,
+                    None => {
+                        diesel::insert_into(works::table)
+                            .values(&NewWorkRow { title })
+                            .execute(connection)
+                            .await?;
+                        sql_query("SELECT last_insert_rowid() AS work_id")
+                            .get_result::<LastInsertRow>(connection)
+                            .await?
+                            .work_id
+                    }
+                };
+
+                for &other in existing.iter().skip(1) {
+                    diesel::update(books::dsl::books.filter(books::work_id.eq(other)))
+                        .set(books::work_id.eq(work_id))
+                        .execute(connection)
+                        .await?;
+                }
+                diesel::update(books::dsl::books.filter(books::book_id.eq_any(book_ids)))
+                    .set(books::work_id.eq(work_id))
+                    .execute(connection)
+                    .await?;
+                sql_query(DELETE_EMPTY_WORKS).execute(connection).await?;
+
+                Ok::<i32, diesel::result::Error>(work_id)
+            })
+            .await?;
+
+        let row = works::dsl::works
+            .filter(works::work_id.eq(work_id))
+            .first::<WorkRow>(&mut conn)
+            .await?;
+        Ok(Work::from(row))
+    }
+
+    /// Clears the book's `work_id` (and the work's preference for it), then
+    /// dissolves works left with fewer than two files.
+    async fn unlink(&self, find_book_id: i32) -> Result<(), DomainError> {
+        let _db_lock = lock_db();
+        let mut conn = connect_from_pool().await?;
+
+        let work_id = books::dsl::books
+            .filter(books::book_id.eq(find_book_id))
+            .select(books::work_id)
+            .first::<Option<i32>>(&mut conn)
+            .await
+            .optional()?
+            .ok_or(DomainError::BookNotFound(find_book_id))?;
+        let Some(work_id) = work_id else {
+            return Ok(());
+        };
+
+        conn.transaction(async |connection| {
+            diesel::update(books::dsl::books.filter(books::book_id.eq(find_book_id)))
+                .set(books::work_id.eq(None::<i32>))
+                .execute(connection)
+                .await?;
+            diesel::update(
+                works::dsl::works
+                    .filter(works::work_id.eq(work_id))
+                    .filter(works::preferred_book_id.eq(find_book_id)),
+            )
+            .set(works::preferred_book_id.eq(None::<i32>))
+            .execute(connection)
+            .await?;
+            sql_query(UNLINK_SINGLE_BOOK_WORKS)
+                .execute(connection)
+                .await?;
+            sql_query(DELETE_EMPTY_WORKS).execute(connection).await?;
+            Ok::<(), diesel::result::Error>(())
+        })
+        .await?;
+
+        Ok(())
+    }
+
+    /// Stores the preferred book after checking it belongs to the work.
+    async fn set_preferred(&self, find_id: i32, book_id: i32) -> Result<(), DomainError> {
+        let _db_lock = lock_db();
+        let mut conn = connect_from_pool().await?;
+
+        let work = works::dsl::works
+            .filter(works::work_id.eq(find_id))
+            .limit(1)
+            .load::<WorkRow>(&mut conn)
+            .await?;
+        if work.is_empty() {
+            return Err(DomainError::NotFound);
+        }
+
+        let member = books::dsl::books
+            .filter(books::book_id.eq(book_id))
+            .filter(books::work_id.eq(find_id))
+            .count()
+            .get_result::<i64>(&mut conn)
+            .await?;
+        if member == 0 {
+            return Err(DomainError::InvalidInput(format!(
+                "Book {} is not part of work {}",
+                book_id, find_id
+            )));
+        }
+
+        conn.transaction(async |connection| {
+            diesel::update(works::dsl::works.filter(works::work_id.eq(find_id)))
+                .set(works::preferred_book_id.eq(book_id))
+                .execute(connection)
+                .await?;
+            Ok::<(), diesel::result::Error>(())
+        })
+        .await?;
+
+        Ok(())
+    }
+}
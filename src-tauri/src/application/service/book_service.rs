@@ -1,5 +1,22 @@
-use crate::domain::repository::BookRepository;
+use crate::application::history::metadata_snapshot;
+use crate::application::provider::{
+    MetadataCandidate, MetadataComparison, MetadataField, MetadataProvider,
+};
+use crate::domain::dto::book_dto::BookDto;
+use crate::domain::error::DomainError;
+use crate::domain::models::contributor::{Contributor, ROLE_AUTHOR};
+use crate::domain::models::isbn::Isbn;
+use crate::domain::models::metadata_change::{ChangeSource, MetadataSnapshot};
+use crate::domain::models::partial_date::PartialDate;
+use crate::domain::models::sort_key;
+use crate::domain::repository::{
+    AuthorRepository, BookMetadataChanges, BookMetadataPatch, BookRepository,
+    MetadataHistoryRepository, NewBookAuthor, PublisherRepository, UpdateBook,
+};
 use crate::infrastructure::file_handlers::BookMetadata;
+use crate::infrastructure::file_handlers::cover_handler;
+use crate::infrastructure::file_handlers::epub_handler;
+use crate::infrastructure::file_handlers::epub_writer::{self, EpubMetadataUpdate};
 use std::sync::Arc;
 
 /// Re-parses the original ebook file for a book and returns fresh metadata.
@@ -98,48 +115,556 @@ pub async fn list_metadata(
     Ok(all_metadata)
 }
 
-/// Updates metadata fields for a book found by title search.
+/// Applies a metadata patch to the book with the given ID.
 ///
-/// Only the provided (non-`None`) fields are updated. The book is located
-/// by searching for the first title match.
+/// The patch is validated and normalized first (trimmed text, a real
+/// `YYYY[-MM[-DD]]` date, ISBN checked and stored in its ISBN-13 form,
+/// de-duplicated tags). Author names and the publisher are resolved with
+/// `find_or_create`; authors keep their existing sort names when they were
+/// already credited. The title and author sort keys are derived again when
+/// the title, language or authors change, unless the patch sets them. Column
+/// changes, author links and tags are then written in one transaction, and
+/// the fields that changed are recorded in the book's edit history.
 ///
 /// # Arguments
 ///
-/// * `book_name` - Substring to search book titles against.
-/// * `title` - New title, or `None` to keep existing.
-/// * `published_date` - New publication date, or `None` to keep existing.
-/// * `isbn` - New ISBN, or `None` to keep existing.
-/// * `book_repo` - Repository for searching and updating the book.
+/// * `book_id` - The book's database ID.
+/// * `patch` - Fields to change; `None` fields are left as they are.
+/// * `source` - What made the change, as recorded in the history.
+/// * `book_repo` - Repository for updating the book.
+/// * `author_repo` - Repository for resolving author names.
+/// * `publisher_repo` - Repository for resolving the publisher name.
+/// * `history_repo` - Repository for recording the change.
+///
+/// # Returns
+///
+/// The updated book as a [`BookDto`].
 ///
 /// # Errors
 ///
-/// Returns [`DomainError::NotFound`] when no book matches the title search.
-pub async fn update_metadata(
-    book_name: &str,
-    title: Option<&str>,
-    published_date: Option<&str>,
-    isbn: Option<&str>,
+/// Returns [`DomainError::BookNotFound`] when no book has the given ID,
+/// [`DomainError::InvalidInput`] when a field fails validation, or
+/// [`DomainError::Database`] on failure.
+pub async fn update_book_metadata(
+    book_id: i32,
+    patch: BookMetadataPatch,
+    source: ChangeSource,
     book_repo: &Arc<dyn BookRepository>,
-) -> Result<(), crate::domain::error::DomainError> {
-    let books = book_repo.search_by_title(book_name).await?;
-    let book = books
+    author_repo: &Arc<dyn AuthorRepository>,
+    publisher_repo: &Arc<dyn PublisherRepository>,
+    history_repo: &Arc<dyn MetadataHistoryRepository>,
+) -> Result<BookDto, DomainError> {
+    let current = book_repo
+        .find_by_id(book_id)
+        .await?
+        .ok_or(DomainError::BookNotFound(book_id))?;
+    let before =
+        crate::application::book::get_book(book_id, book_repo, author_repo, publisher_repo)
+            .await?
+            .ok_or(DomainError::BookNotFound(book_id))?;
+
+    let mut changes = BookMetadataChanges::default();
+    let mut new_authors: Option<Vec<Contributor>> = None;
+
+    if let Some(title) = patch.title {
+        let title = title.trim();
+        if title.is_empty() {
+            return Err(DomainError::InvalidInput("Title cannot be empty".into()));
+        }
+        changes.title = Some(title.to_string());
+    }
+
+    if let Some(names) = patch.authors {
+        let existing = author_repo.get_contributors_by_book(book_id).await?;
+        let mut links: Vec<NewBookAuthor> = Vec::new();
+        let mut credits: Vec<Contributor> = Vec::new();
+        for name in names {
+            let name = name.trim();
+            if name.is_empty() {
+                return Err(DomainError::InvalidInput(
+                    "Author names cannot be empty".into(),
+                ));
+            }
+            let author = author_repo.find_or_create(name).await?;
+            if links.iter().any(|l| l.author_id == author.id) {
+                continue;
+            }
+            let sort_name = existing
+                .iter()
+                .find(|c| c.is_author() && c.name == author.name)
+                .and_then(|c| c.sort_name.clone());
+            credits.push(Contributor {
+                name: author.name.clone(),
+                role: ROLE_AUTHOR.to_string(),
+                sort_name: sort_name.clone(),
+            });
+            links.push(NewBookAuthor {
+                author_id: author.id,
+                role: ROLE_AUTHOR.to_string(),
+                sort_name,
+            });
+        }
+        changes.authors = Some(links);
+        new_authors = Some(credits);
+    }
+
+    if let Some(publisher) = patch.publisher {
+        changes.publisher_id = Some(match clearable(publisher) {
+            Some(name) => Some(publisher_repo.find_or_create(&name).await?.id),
+            None => None,
+        });
+    }
+
+    if let Some(date) = patch.published_date {
+        let date = clearable(date);
+        if let Some(d) = &date {
+            PartialDate::parse_strict(d)?;
+        }
+        changes.published_date = Some(date);
+    }
+
+    if let Some(isbn) = patch.isbn {
+        changes.isbn = Some(match clearable(isbn) {
+            Some(raw) => Some(Isbn::parse(&raw)?.to_isbn13()),
+            None => None,
+        });
+    }
+
+    if let Some(language) = patch.language {
+        let language = clearable(language);
+        if let Some(l) = &language {
+            validate_language(l)?;
+        }
+        changes.language = Some(language);
+    }
+
+    if let Some(description) = patch.description {
+        changes.description = Some(clearable(description));
+    }
+
+    if let Some(series) = patch.series {
+        let series = clearable(series);
+        if series.is_none() {
+            changes.series_index = Some(None);
+        }
+        changes.series = Some(series);
+    }
+
+    if let Some(index) = patch.series_index {
+        if !index.is_finite() || index < 0.0 {
+            return Err(DomainError::InvalidInput(format!(
+                "Series index must be a non-negative number, got {}",
+                index
+            )));
+        }
+        if changes.series == Some(None) {
+            return Err(DomainError::InvalidInput(
+                "Series index cannot be set while clearing the series".into(),
+            ));
+        }
+        changes.series_index = Some(Some(index));
+    }
+
+    if patch.clear_series_index {
+        if patch.series_index.is_some() {
+            return Err(DomainError::InvalidInput(
+                "Series index cannot be set and cleared at once".into(),
+            ));
+        }
+        changes.series_index = Some(None);
+    }
+
+    if let Some(tags) = patch.tags {
+        let mut unique: Vec<String> = Vec::new();
+        for tag in tags {
+            let tag = tag.trim();
+            if !tag.is_empty() && !unique.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+                unique.push(tag.to_string());
+            }
+        }
+        changes.tags = Some(unique);
+    }
+
+    let derive_title_sort = match patch.title_sort.map(clearable) {
+        Some(Some(key)) => {
+            changes.title_sort = Some(Some(key));
+            false
+        }
+        Some(None) => true,
+        None => changes.title.is_some() || changes.language.is_some(),
+    };
+    if derive_title_sort {
+        let title = changes.title.as_deref().unwrap_or(&current.title);
+        let language = match &changes.language {
+            Some(language) => language.as_deref(),
+            None => current.language.as_deref(),
+        };
+        changes.title_sort = Some(Some(sort_key::title_sort(title, language)));
+    }
+
+    let derive_author_sort = match patch.author_sort.map(clearable) {
+        Some(Some(key)) => {
+            changes.author_sort = Some(Some(key));
+            false
+        }
+        Some(None) => true,
+        None => new_authors.is_some(),
+    };
+    if derive_author_sort {
+        let credits = match new_authors {
+            Some(credits) => credits,
+            None => author_repo.get_contributors_by_book(book_id).await?,
+        };
+        changes.author_sort = Some(sort_key::authors_sort(&credits));
+    }
+
+    book_repo.update_with_links(book_id, changes).await?;
+
+    let after = crate::application::book::get_book(book_id, book_repo, author_repo, publisher_repo)
+        .await?
+        .ok_or(DomainError::BookNotFound(book_id))?;
+    let changed = metadata_snapshot(&before).diff(&metadata_snapshot(&after));
+    history_repo.record(book_id, source, &changed).await?;
+
+    Ok(after)
+}
+
+/// Writes a book's stored metadata back into its EPUB file.
+///
+/// Rewrites title, contributors (with `file-as` sort names), publisher,
+/// publication date, ISBN and series in the EPUB's OPF package document,
+/// and optionally replaces the cover with the cached cover image. All other
+/// archive entries are left untouched. The stored checksum is recomputed
+/// afterwards with [`store_checksum`](crate::application::book::store_checksum)
+/// so duplicate detection keeps matching the file on disk. The content
+/// documents are not changed, so annotations are not re-anchored.
+///
+/// Placeholder values assigned at import ("Unknown Author", "Unknown
+/// Publisher") are not written to the file, and neither is a generated
+/// placeholder cover: only cover art from the file or a metadata provider is
+/// embedded.
+///
+/// The written fields whose values differ from what the file held before are
+/// recorded in the book's edit history as a [`ChangeSource::FileWriteback`]
+/// change. The series is not read from files, so it is not recorded.
+///
+/// # Arguments
+///
+/// * `book_id` - The book's database ID.
+/// * `include_cover` - Whether to embed the cached cover image as well,
+///   unless it is a generated placeholder.
+/// * `book_repo` - Repository for loading and updating the book record.
+/// * `author_repo` - Repository for resolving contributors.
+/// * `publisher_repo` - Repository for resolving the publisher name.
+/// * `history_repo` - Repository for recording the write-back.
+///
+/// # Returns
+///
+/// The book as a [`BookDto`], with its updated checksum.
+///
+/// # Errors
+///
+/// Returns [`DomainError::BookNotFound`] when no book has the given ID,
+/// [`DomainError::InvalidInput`] when the book is not an EPUB,
+/// [`DomainError::File`] when the file cannot be rewritten or checksummed,
+/// or [`DomainError::Database`] on failure.
+pub async fn write_metadata_to_file(
+    book_id: i32,
+    include_cover: bool,
+    book_repo: &Arc<dyn BookRepository>,
+    author_repo: &Arc<dyn AuthorRepository>,
+    publisher_repo: &Arc<dyn PublisherRepository>,
+    history_repo: &Arc<dyn MetadataHistoryRepository>,
+) -> Result<BookDto, DomainError> {
+    let book = book_repo
+        .find_by_id(book_id)
+        .await?
+        .ok_or(DomainError::BookNotFound(book_id))?;
+
+    if book.file_type.as_deref() != Some("epub") {
+        return Err(DomainError::InvalidInput(
+            "Metadata can only be written to EPUB files".into(),
+        ));
+    }
+    let path = book
+        .file_path
+        .clone()
+        .ok_or_else(|| DomainError::File("No file path for book".into()))?;
+
+    let contributors: Vec<Contributor> = author_repo
+        .get_contributors_by_book(book_id)
+        .await?
         .into_iter()
-        .next()
-        .ok_or(crate::domain::error::DomainError::NotFound)?;
-
-    book_repo
-        .update(
-            book.id,
-            crate::domain::repository::UpdateBook {
-                title: title.map(|s| s.to_string()),
-                published_date: published_date.map(|s| s.to_string()),
-                publisher_id: None,
-                isbn: isbn.map(|s| s.to_string()),
-                file_type: None,
-                file_path: None,
-                cover_image_path: None,
-                checksum: None,
-            },
-        )
+        .filter(|c| c.name != "Unknown Author")
+        .collect();
+    let publisher = match book.publisher_id {
+        Some(id) => publisher_repo
+            .find_by_id(id)
+            .await?
+            .map(|p| p.name)
+            .filter(|name| name != "Unknown Publisher"),
+        None => None,
+    };
+
+    let cover = match book.cover_image_path.as_deref() {
+        Some(cached) if include_cover && !book.cover_is_placeholder => {
+            let bytes = tokio::fs::read(cached)
+                .await
+                .map_err(|e| DomainError::File(e.to_string()))?;
+            let media_type = image::guess_format(&bytes)
+                .map_err(|e| DomainError::File(e.to_string()))?
+                .to_mime_type()
+                .to_string();
+            Some((bytes, media_type))
+        }
+        _ => None,
+    };
+
+    // An unreadable file is treated as having held no metadata.
+    let in_file = epub_handler::parse_epub_meta(path.clone()).await.ok();
+    let before = MetadataSnapshot {
+        title: in_file.as_ref().map(|m| m.title.clone()),
+        authors: in_file
+            .as_ref()
+            .map(|m| author_names(&m.contributors))
+            .unwrap_or_default(),
+        publisher: in_file.as_ref().and_then(|m| m.publishers.first().cloned()),
+        published_date: in_file.as_ref().and_then(|m| m.published_date.clone()),
+        isbn: in_file.and_then(|m| m.isbn),
+        ..Default::default()
+    };
+    let written = MetadataSnapshot {
+        title: Some(book.title.clone()),
+        authors: author_names(&contributors),
+        publisher: publisher.clone(),
+        published_date: book.published_date.clone(),
+        isbn: book.isbn.clone(),
+        ..Default::default()
+    };
+
+    let update = EpubMetadataUpdate {
+        title: book.title,
+        contributors,
+        publisher,
+        published_date: book.published_date,
+        isbn: book.isbn,
+        series: book.series,
+        series_index: book.series_index,
+        cover,
+    };
+    epub_writer::write_epub_metadata(path.clone(), update)
         .await
+        .map_err(|e| DomainError::File(e.to_string()))?;
+
+    history_repo
+        .record(book_id, ChangeSource::FileWriteback, &before.diff(&written))
+        .await?;
+    crate::application::book::store_checksum(book_id, book_repo).await?;
+
+    crate::application::book::get_book(book_id, book_repo, author_repo, publisher_repo)
+        .await?
+        .ok_or(DomainError::BookNotFound(book_id))
+}
+
+/// Looks up candidate metadata for a book with a metadata provider.
+///
+/// Searches by the book's ISBN first. When the book has no ISBN, or the
+/// provider does not know it, falls back to a title search narrowed by the
+/// first credited author (the "Unknown Author" placeholder is not sent).
+///
+/// # Arguments
+///
+/// * `book_id` - The book's database ID.
+/// * `book_repo` - Repository for loading the book.
+/// * `author_repo` - Repository for resolving contributors.
+/// * `publisher_repo` - Repository for resolving the publisher name.
+/// * `provider` - The metadata provider to query.
+///
+/// # Returns
+///
+/// A [`MetadataComparison`] holding the stored book next to the candidates,
+/// best match first.
+///
+/// # Errors
+///
+/// Returns [`DomainError::BookNotFound`] when no book has the given ID,
+/// [`DomainError::Provider`] when the provider request fails, or
+/// [`DomainError::Database`] on failure.
+pub async fn find_metadata_candidates(
+    book_id: i32,
+    book_repo: &Arc<dyn BookRepository>,
+    author_repo: &Arc<dyn AuthorRepository>,
+    publisher_repo: &Arc<dyn PublisherRepository>,
+    provider: &Arc<dyn MetadataProvider>,
+) -> Result<MetadataComparison, DomainError> {
+    let current =
+        crate::application::book::get_book(book_id, book_repo, author_repo, publisher_repo)
+            .await?
+            .ok_or(DomainError::BookNotFound(book_id))?;
+
+    let mut found = Vec::new();
+    if let Some(isbn) = current.isbn.as_deref().and_then(|i| Isbn::parse(i).ok()) {
+        found = provider.search_by_isbn(&isbn).await?;
+    }
+    if found.is_empty() {
+        let author = current
+            .authors
+            .first()
+            .map(String::as_str)
+            .filter(|name| *name != "Unknown Author");
+        found = provider.search_by_title(&current.title, author).await?;
+    }
+
+    let candidates = found
+        .into_iter()
+        .map(|metadata| MetadataCandidate {
+            provider: provider.name().to_string(),
+            metadata,
+        })
+        .collect();
+    Ok(MetadataComparison {
+        current,
+        candidates,
+    })
+}
+
+/// Applies the chosen fields of a provider candidate to a book.
+///
+/// Text fields go through [`update_book_metadata`] and get the same
+/// validation as manual edits; fields the candidate leaves empty are
+/// skipped rather than cleared. A chosen cover is applied afterwards,
+/// replacing the cached cover image, dominant colors and BlurHash via
+/// [`UpdateBook`].
+///
+/// # Arguments
+///
+/// * `book_id` - The book's database ID.
+/// * `candidate` - Candidate metadata as returned by
+///   [`find_metadata_candidates`].
+/// * `fields` - The fields to copy from the candidate.
+/// * `book_repo` - Repository for updating the book.
+/// * `author_repo` - Repository for resolving author names.
+/// * `publisher_repo` - Repository for resolving the publisher name.
+/// * `history_repo` - Repository for recording the text field changes.
+///
+/// # Returns
+///
+/// The updated book as a [`BookDto`].
+///
+/// # Errors
+///
+/// Returns [`DomainError::BookNotFound`] when no book has the given ID,
+/// [`DomainError::InvalidInput`] when a chosen field fails validation,
+/// [`DomainError::File`] when the cover cannot be cached, or
+/// [`DomainError::Database`] on failure.
+pub async fn apply_metadata_candidate(
+    book_id: i32,
+    candidate: BookMetadata,
+    fields: Vec<MetadataField>,
+    book_repo: &Arc<dyn BookRepository>,
+    author_repo: &Arc<dyn AuthorRepository>,
+    publisher_repo: &Arc<dyn PublisherRepository>,
+    history_repo: &Arc<dyn MetadataHistoryRepository>,
+) -> Result<BookDto, DomainError> {
+    let book = book_repo
+        .find_by_id(book_id)
+        .await?
+        .ok_or(DomainError::BookNotFound(book_id))?;
+
+    let mut patch = BookMetadataPatch::default();
+    for field in &fields {
+        match field {
+            MetadataField::Title => patch.title = Some(candidate.title.clone()),
+            MetadataField::Authors => {
+                let authors: Vec<String> = candidate
+                    .contributors
+                    .iter()
+                    .filter(|c| c.is_author())
+                    .map(|c| c.name.clone())
+                    .collect();
+                if !authors.is_empty() {
+                    patch.authors = Some(authors);
+                }
+            }
+            MetadataField::Publisher => patch.publisher = candidate.publishers.first().cloned(),
+            MetadataField::PublishedDate => patch.published_date = candidate.published_date.clone(),
+            MetadataField::Isbn => patch.isbn = candidate.isbn.clone(),
+            MetadataField::Language => patch.language = candidate.language.clone(),
+            MetadataField::Description => patch.description = candidate.description.clone(),
+            MetadataField::Cover => {}
+        }
+    }
+
+    update_book_metadata(
+        book_id,
+        patch,
+        ChangeSource::Provider,
+        book_repo,
+        author_repo,
+        publisher_repo,
+        history_repo,
+    )
+    .await?;
+
+    if fields.contains(&MetadataField::Cover)
+        && let Some((bytes, _mime)) = candidate.cover_data.filter(|(b, _)| !b.is_empty())
+    {
+        let checksum = book.checksum.clone().unwrap_or_else(|| book.id.to_string());
+        let cached_path = cover_handler::cache_cover(&checksum, &bytes)
+            .await
+            .map_err(|e| DomainError::File(e.to_string()))?;
+        let mut update = UpdateBook {
+            cover_image_path: Some(cached_path.to_string_lossy().to_string()),
+            cover_is_placeholder: Some(false),
+            ..Default::default()
+        };
+        if let Ok(palette) = cover_handler::extract_cover_palette(bytes).await {
+            update.dominant_colors = Some(palette.dominant_colors);
+            update.blurhash = Some(palette.blurhash);
+        }
+        book_repo.update(book_id, update).await?;
+    }
+
+    crate::application::book::get_book(book_id, book_repo, author_repo, publisher_repo)
+        .await?
+        .ok_or(DomainError::BookNotFound(book_id))
+}
+
+/// Returns the names of the `aut` contributors, in credit order.
+fn author_names(contributors: &[Contributor]) -> Vec<String> {
+    contributors
+        .iter()
+        .filter(|c| c.is_author())
+        .map(|c| c.name.clone())
+        .collect()
+}
+
+/// Trims a submitted value, mapping an empty result to `None` (clear).
+fn clearable(value: String) -> Option<String> {
+    let trimmed = value.trim();
+    if trimmed.is_empty() {
+        None
+    } else {
+        Some(trimmed.to_string())
+    }
+}
+
+/// Checks the shape of a BCP 47 language tag: a 2-3 letter primary subtag
+/// followed by optional 1-8 character alphanumeric subtags.
+fn validate_language(tag: &str) -> Result<(), DomainError> {
+    let mut subtags = tag.split('-');
+    let primary_ok = subtags
+        .next()
+        .is_some_and(|p| (2..=3).contains(&p.len()) && p.bytes().all(|b| b.is_ascii_alphabetic()));
+    let rest_ok =
+        subtags.all(|s| (1..=8).contains(&s.len()) && s.bytes().all(|b| b.is_ascii_alphanumeric()));
+
+    if primary_ok && rest_ok {
+        Ok(())
+    } else {
+        Err(DomainError::InvalidInput(format!(
+            "Invalid language tag \"{}\": expected e.g. \"en\" or \"pt-BR\"",
+            tag
+        )))
+    }
 }
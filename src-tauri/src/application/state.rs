@@ -1,10 +1,11 @@
+use crate::application::provider::MetadataProvider;
 use crate::domain::repository::*;
 use std::sync::Arc;
 
 /// Application-wide dependency container.
 ///
-/// Holds `Arc`-wrapped trait objects for every repository, enabling
-/// dependency injection and testability. Passed to Tauri commands as
+/// Holds `Arc`-wrapped trait objects for every repository and the metadata
+/// provider, enabling dependency injection and testability. Passed to Tauri commands as
 /// `State<'_, AppState>`.
 pub struct AppState {
     pub book_repo: Arc<dyn BookRepository>,
@@ -14,4 +15,8 @@ pub struct AppState {
     pub bookmark_repo: Arc<dyn BookmarkRepository>,
     pub annotation_repo: Arc<dyn AnnotationRepository>,
     pub reading_progress_repo: Arc<dyn ReadingProgressRepository>,
+    pub work_repo: Arc<dyn WorkRepository>,
+    pub ignored_file_repo: Arc<dyn IgnoredFileRepository>,
+    pub metadata_history_repo: Arc<dyn MetadataHistoryRepository>,
+    pub metadata_provider: Arc<dyn MetadataProvider>,
 }
@@ -0,0 +1,242 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use crate::application::book::get_book;
+use crate::domain::dto::book_dto::BookDto;
+use crate::domain::dto::work_dto::{WorkDto, WorkMatch, WorkSuggestion};
+use crate::domain::error::DomainError;
+use crate::domain::models::book::Book;
+use crate::domain::models::isbn::Isbn;
+use crate::domain::models::work::{Work, work_key};
+use crate::domain::repository::*;
+
+/// Suggests books to link into works.
+///
+/// Books are grouped first by ISBN (compared in ISBN-13 form), then by
+/// [`work_key`], i.e. normalized title and author. Groups whose books
+/// already share one work, and groups repeating the books of an earlier
+/// suggestion, are left out.
+///
+/// # Arguments
+///
+/// * `book_repo` - Repository for listing books.
+/// * `author_repo` - Repository for resolving author names.
+/// * `publisher_repo` - Repository for resolving publisher names.
+///
+/// # Returns
+///
+/// The suggestions, ISBN matches first.
+///
+/// # Errors
+///
+/// Delegates to the repositories; returns [`DomainError::Database`] on
+/// failure.
+pub async fn suggest_work_links(
+    book_repo: &Arc<dyn BookRepository>,
+    author_repo: &Arc<dyn AuthorRepository>,
+    publisher_repo: &Arc<dyn PublisherRepository>,
+) -> Result<Vec<WorkSuggestion>, DomainError> {
+    let books = book_repo.find_all().await?;
+
+    let mut by_isbn: BTreeMap<String, Vec<&Book>> = BTreeMap::new();
+    let mut by_title: BTreeMap<String, Vec<&Book>> = BTreeMap::new();
+    for book in &books {
+        if let Some(isbn) = book.isbn.as_deref().and_then(|i| Isbn::parse(i).ok()) {
+            by_isbn.entry(isbn.to_isbn13()).or_default().push(book);
+        }
+        if let Some(key) = work_key(
+            &book.title,
+            book.language.as_deref(),
+            book.author_sort.as_deref(),
+        ) {
+            by_title.entry(key).or_default().push(book);
+        }
+    }
+
+    let groups = by_isbn
+        .into_values()
+        .map(|group| (WorkMatch::Isbn, group))
+        .chain(
+            by_title
+                .into_values()
+                .map(|group| (WorkMatch::TitleAuthor, group)),
+        );
+
+    let mut suggested: Vec<Vec<i32>> = Vec::new();
+    let mut suggestions = Vec::new();
+    for (reason, group) in groups {
+        if group.len() < 2 {
+            continue;
+        }
+        let work_id = group[0].work_id;
+        if work_id.is_some() && group.iter().all(|b| b.work_id == work_id) {
+            continue;
+        }
+
+        let mut ids: Vec<i32> = group.iter().map(|b| b.id).collect();
+        ids.sort_unstable();
+        if suggested.contains(&ids) {
+            continue;
+        }
+
+        let mut dtos: Vec<BookDto> = Vec::with_capacity(ids.len());
+        for &id in &ids {
+            if let Some(dto) = get_book(id, book_repo, author_repo, publisher_repo).await? {
+                dtos.push(dto);
+            }
+        }
+        suggested.push(ids);
+        suggestions.push(WorkSuggestion {
+            reason,
+            books: dtos,
+        });
+    }
+
+    Ok(suggestions)
+}
+
+/// Links books into one work.
+///
+/// # Arguments
+///
+/// * `book_ids` - The books to link; duplicates are ignored. A new work is
+///   titled after the first book.
+/// * `work_repo` - Repository for linking the books.
+/// * `book_repo` - Repository for looking up the books and their formats.
+///
+/// # Returns
+///
+/// The [`WorkDto`] the books now belong to.
+///
+/// # Errors
+///
+/// Returns [`DomainError::InvalidInput`] if fewer than two distinct books
+/// are given, [`DomainError::BookNotFound`] if a book does not exist, or
+/// [`DomainError::Database`] on failure.
+pub async fn link_books(
+    book_ids: &[i32],
+    work_repo: &Arc<dyn WorkRepository>,
+    book_repo: &Arc<dyn BookRepository>,
+) -> Result<WorkDto, DomainError> {
+    let mut ids: Vec<i32> = Vec::with_capacity(book_ids.len());
+    for &id in book_ids {
+        if !ids.contains(&id) {
+            ids.push(id);
+        }
+    }
+    if ids.len() < 2 {
+        return Err(DomainError::InvalidInput(
+            "Select at least two books to link".into(),
+        ));
+    }
+
+    let first = book_repo
+        .find_by_id(ids[0])
+        .await?
+        .ok_or(DomainError::BookNotFound(ids[0]))?;
+    let work = work_repo.link(&ids, &first.title).await?;
+    to_work_dto(work, first.id, book_repo).await
+}
+
+/// Removes a book from its work. A work left with a single file is
+/// dissolved.
+///
+/// # Arguments
+///
+/// * `book_id` - The book to unlink.
+/// * `work_repo` - Repository for unlinking the book.
+///
+/// # Errors
+///
+/// Returns [`DomainError::BookNotFound`] if the book does not exist, or
+/// [`DomainError::Database`] on failure.
+pub async fn unlink_book(
+    book_id: i32,
+    work_repo: &Arc<dyn WorkRepository>,
+) -> Result<(), DomainError> {
+    work_repo.unlink(book_id).await
+}
+
+/// Sets the file the reader opens for a work.
+///
+/// # Arguments
+///
+/// * `work_id` - The work's database ID.
+/// * `book_id` - The preferred file; must belong to the work.
+/// * `work_repo` - Repository for updating the work.
+/// * `book_repo` - Repository for listing the work's formats.
+///
+/// # Returns
+///
+/// The updated [`WorkDto`], with the preferred file listed first.
+///
+/// # Errors
+///
+/// Returns [`DomainError::NotFound`] if the work does not exist,
+/// [`DomainError::InvalidInput`] if the book is not part of it, or
+/// [`DomainError::Database`] on failure.
+pub async fn set_preferred_format(
+    work_id: i32,
+    book_id: i32,
+    work_repo: &Arc<dyn WorkRepository>,
+    book_repo: &Arc<dyn BookRepository>,
+) -> Result<WorkDto, DomainError> {
+    work_repo.set_preferred(work_id, book_id).await?;
+    let work = work_repo
+        .find_by_id(work_id)
+        .await?
+        .ok_or(DomainError::NotFound)?;
+    to_work_dto(work, book_id, book_repo).await
+}
+
+/// Returns the file the reader should open for a book.
+///
+/// For a book that belongs to a work this is the work's preferred file:
+/// the one chosen with [`set_preferred_format`], otherwise an EPUB, then
+/// the earliest imported file. Other books resolve to themselves.
+///
+/// # Arguments
+///
+/// * `book_id` - Any file of the work.
+/// * `book_repo` - Repository for looking up the book and its formats.
+/// * `author_repo` - Repository for resolving author names.
+/// * `publisher_repo` - Repository for resolving publisher names.
+///
+/// # Returns
+///
+/// The preferred file as a [`BookDto`].
+///
+/// # Errors
+///
+/// Returns [`DomainError::BookNotFound`] if the book does not exist, or
+/// [`DomainError::Database`] on failure.
+pub async fn get_preferred_book(
+    book_id: i32,
+    book_repo: &Arc<dyn BookRepository>,
+    author_repo: &Arc<dyn AuthorRepository>,
+    publisher_repo: &Arc<dyn PublisherRepository>,
+) -> Result<BookDto, DomainError> {
+    let preferred = book_repo
+        .get_formats(book_id)
+        .await?
+        .first()
+        .map(|f| f.book_id)
+        .unwrap_or(book_id);
+    get_book(preferred, book_repo, author_repo, publisher_repo)
+        .await?
+        .ok_or(DomainError::BookNotFound(book_id))
+}
+
+/// Builds a [`WorkDto`] from a work and the formats of one of its books.
+async fn to_work_dto(
+    work: Work,
+    member_id: i32,
+    book_repo: &Arc<dyn BookRepository>,
+) -> Result<WorkDto, DomainError> {
+    Ok(WorkDto {
+        id: work.id,
+        title: work.title,
+        preferred_book_id: work.preferred_book_id,
+        formats: book_repo.get_formats(member_id).await?,
+    })
+}
@@ -0,0 +1,11 @@
+use serde::Serialize;
+
+/// An author with the number of books they are credited on, for the author
+/// management view.
+#[derive(Debug, Clone, Serialize)]
+pub struct AuthorSummary {
+    pub id: i32,
+    pub name: String,
+    /// Number of distinct books linked to this author in any role.
+    pub book_count: i64,
+}
@@ -1,15 +1,32 @@
 use serde::Serialize;
 
+use crate::domain::models::contributor::Contributor;
+use crate::domain::models::identifier::Identifier;
+use crate::domain::models::partial_date::PartialDate;
+
 /// Data transfer object for books sent to the frontend.
 ///
-/// Combines a [`Book`](crate::domain::models::book::Book) with its first author
-/// and publisher names for convenient rendering in the UI.
+/// Combines a [`Book`](crate::domain::models::book::Book) with its contributors
+/// and publisher name for convenient rendering in the UI. `author` is kept as
+/// the first credited author for callers that only show one name.
 #[derive(Debug, Clone, Serialize)]
 pub struct BookDto {
     pub id: i32,
     pub title: String,
+    /// Title used for sorting, without a leading article.
+    pub title_sort: Option<String>,
     pub author: Option<String>,
+    /// Names of all contributors credited in the `aut` role, in link order.
+    pub authors: Vec<String>,
+    /// Authors used for sorting, as `Surname, Given Names` joined with `" & "`.
+    pub author_sort: Option<String>,
+    /// Every credited contributor, including editors, translators and
+    /// illustrators, with their role and sort name.
+    pub contributors: Vec<Contributor>,
+    /// Publication date as originally written.
     pub published_date: Option<String>,
+    /// `published_date` parsed to year, month or day precision.
+    pub published: Option<PartialDate>,
     pub publisher: Option<String>,
     pub isbn: Option<String>,
     pub file_type: Option<String>,
@@ -17,21 +34,76 @@ pub struct BookDto {
     pub cover_image_path: Option<String>,
     pub checksum: Option<String>,
     pub added_at: Option<String>,
+    pub dominant_colors: Vec<String>,
+    pub blurhash: Option<String>,
+    pub language: Option<String>,
+    pub description: Option<String>,
+    pub series: Option<String>,
+    pub series_index: Option<f32>,
+    pub tags: Vec<String>,
+    pub rights: Option<String>,
+    pub modified_date: Option<String>,
+    pub epub_version: Option<String>,
+    pub subjects: Vec<String>,
+    /// Every identifier stored for the book, typed by scheme.
+    pub identifiers: Vec<Identifier>,
+    /// The work this file belongs to, if it is linked with other formats.
+    pub work_id: Option<i32>,
+    /// Every file of the book's work, preferred first; only the book itself
+    /// when it belongs to no work.
+    pub formats: Vec<BookFormat>,
+    /// When the book was moved to the trash; `None` for books in the library.
+    pub deleted_at: Option<String>,
+}
+
+/// One file of a work, as listed in [`BookDto::formats`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BookFormat {
+    pub book_id: i32,
+    /// File format (e.g., `"epub"`, `"pdf"`).
+    pub file_type: String,
+}
+
+/// A page of books returned by a filtered, sorted listing.
+#[derive(Debug, Clone, Serialize)]
+pub struct BookPage {
+    /// Books on this page, in the requested order.
+    pub books: Vec<BookDto>,
+    /// Number of books matching the filters across all pages.
+    pub total: i64,
+    /// Opaque cursor for the following page, or `None` on the last page.
+    pub next_cursor: Option<String>,
 }
 
 impl BookDto {
-    /// Constructs a `BookDto` from a domain [`Book`](crate::domain::models::book::Book) and
-    /// resolved author/publisher names.
+    /// Constructs a `BookDto` from a domain [`Book`](crate::domain::models::book::Book),
+    /// its contributors, the resolved publisher name, its tags, subjects,
+    /// identifiers and the formats of its work.
     pub fn new(
         book: &crate::domain::models::book::Book,
-        author: Option<String>,
+        contributors: Vec<Contributor>,
         publisher: Option<String>,
+        tags: Vec<String>,
+        subjects: Vec<String>,
+        identifiers: Vec<Identifier>,
+        formats: Vec<BookFormat>,
     ) -> Self {
+        let authors: Vec<String> = contributors
+            .iter()
+            .filter(|c| c.is_author())
+            .map(|c| c.name.clone())
+            .collect();
+
         Self {
             id: book.id,
             title: book.title.clone(),
-            author,
+            title_sort: book.title_sort.clone(),
+            author: authors.first().cloned(),
+            authors,
+            author_sort: book.author_sort.clone(),
+            contributors,
             published_date: book.published_date.clone(),
+            published: book.published,
             publisher,
             isbn: book.isbn.clone(),
             file_type: book.file_type.clone(),
@@ -39,6 +111,21 @@ impl BookDto {
             cover_image_path: book.cover_image_path.clone(),
             checksum: book.checksum.clone(),
             added_at: book.added_at.clone(),
+            dominant_colors: book.dominant_colors.clone(),
+            blurhash: book.blurhash.clone(),
+            language: book.language.clone(),
+            description: book.description.clone(),
+            series: book.series.clone(),
+            series_index: book.series_index,
+            tags,
+            rights: book.rights.clone(),
+            modified_date: book.modified_date.clone(),
+            epub_version: book.epub_version.clone(),
+            subjects,
+            identifiers,
+            work_id: book.work_id,
+            formats,
+            deleted_at: book.deleted_at.clone(),
         }
     }
 }
@@ -0,0 +1,28 @@
+use serde::Serialize;
+
+use crate::domain::dto::book_dto::BookDto;
+use crate::domain::models::duplicate::DuplicateSignals;
+
+/// A scored pair of books that look like copies of the same file.
+#[derive(Debug, Clone, Serialize)]
+pub struct DuplicateMatch {
+    pub book_id: i32,
+    pub other_id: i32,
+    /// Weighted score from `0.0` to `1.0`.
+    pub score: f32,
+    /// The per-property similarities the score was computed from.
+    pub signals: DuplicateSignals,
+}
+
+/// Books suspected to be duplicates of each other, for review before
+/// merging.
+#[derive(Debug, Clone, Serialize)]
+pub struct DuplicateGroup {
+    /// The suspected duplicates, in import order.
+    pub books: Vec<BookDto>,
+    /// Every pair in the group that scored above the threshold, best first.
+    pub matches: Vec<DuplicateMatch>,
+    /// The book suggested to keep: the most recently read one, otherwise the
+    /// earliest imported.
+    pub suggested_target_id: i32,
+}
@@ -1,3 +1,7 @@
 //! Data transfer objects for presentation to the frontend.
 
+pub mod author_dto;
 pub mod book_dto;
+pub mod duplicate_dto;
+pub mod publisher_dto;
+pub mod work_dto;
@@ -0,0 +1,11 @@
+use serde::Serialize;
+
+/// A publisher with the number of books referencing it, for the publisher
+/// management view.
+#[derive(Debug, Clone, Serialize)]
+pub struct PublisherSummary {
+    pub id: i32,
+    pub name: String,
+    /// Number of books whose `publisher_id` points at this publisher.
+    pub book_count: i64,
+}
@@ -0,0 +1,33 @@
+use serde::Serialize;
+
+use crate::domain::dto::book_dto::{BookDto, BookFormat};
+
+/// A work with its files, for the work management view.
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkDto {
+    pub id: i32,
+    pub title: String,
+    /// The file chosen explicitly as preferred, if any.
+    pub preferred_book_id: Option<i32>,
+    /// Every file of the work; the first one is what the reader opens.
+    pub formats: Vec<BookFormat>,
+}
+
+/// Why books were suggested as formats of the same work.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WorkMatch {
+    /// The books share an ISBN.
+    Isbn,
+    /// The books share a normalized title and author.
+    TitleAuthor,
+}
+
+/// Books that look like formats or editions of the same work but are not
+/// linked yet.
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkSuggestion {
+    pub reason: WorkMatch,
+    /// The books to link, in import order.
+    pub books: Vec<BookDto>,
+}
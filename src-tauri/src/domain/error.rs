@@ -15,7 +15,7 @@ pub enum DomainError {
     #[error("Book not found: {0}")]
     BookNotFound(i32),
 
-    /// A book with the same checksum already exists (duplicate import).
+    /// A book with the same checksum or ISBN already exists (duplicate import).
     #[error("Duplicate book: {0}")]
     DuplicateBook(String),
 
@@ -31,6 +31,16 @@ pub enum DomainError {
     #[error("Parse error: {0}")]
     Parse(String),
 
+    /// A request was rejected because its input is invalid or conflicts with
+    /// existing data (e.g., renaming an author to a name already in use).
+    #[error("Invalid input: {0}")]
+    InvalidInput(String),
+
+    /// An external metadata provider could not be reached or returned an
+    /// unexpected response.
+    #[error("Metadata provider error: {0}")]
+    Provider(String),
+
     /// A generic not-found error for non-book lookups.
     #[error("Not found")]
     NotFound,
@@ -1,5 +1,9 @@
+use serde::Serialize;
+
+use crate::domain::models::locator::Locator;
+
 /// A highlighted text annotation with an optional note.
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct Annotation {
     /// Auto-generated primary key.
     pub id: i32,
@@ -7,18 +11,65 @@ pub struct Annotation {
     pub book_id: i32,
     /// Title of the chapter containing the annotation, if known.
     pub chapter_title: Option<String>,
-    /// Start position of the highlighted text (format depends on file type).
-    pub start_position: String,
-    /// End position of the highlighted text.
-    pub end_position: String,
+    /// Start of the highlighted text.
+    pub start_position: Locator,
+    /// End of the highlighted text.
+    pub end_position: Locator,
     /// The actual highlighted text content, if captured.
     pub highlighted_text: Option<String>,
     /// User-written note attached to the highlight.
     pub note: Option<String>,
     /// Color identifier for the highlight (e.g., `"yellow"`, `"#FF0000"`).
     pub color: Option<String>,
+    /// Set when re-anchoring after the book's file changed could not find
+    /// the highlighted text again confidently; the positions are then the
+    /// old ones.
+    pub needs_review: bool,
+    /// Score of the last re-anchoring, from `0.0` to `1.0`; `None` when the
+    /// annotation was never re-anchored or its text was not found.
+    pub anchor_confidence: Option<f32>,
     /// ISO 8601 timestamp of when the annotation was created.
     pub created_at: Option<String>,
     /// ISO 8601 timestamp of the last modification.
     pub updated_at: Option<String>,
 }
+
+/// What re-anchoring did with an annotation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReanchorOutcome {
+    /// The highlighted text is still where the annotation points.
+    Unchanged,
+    /// The highlighted text was found elsewhere and the annotation moved to
+    /// it.
+    Moved,
+    /// The highlighted text was not found, or not confidently; the
+    /// annotation kept its positions and was flagged.
+    NeedsReview,
+}
+
+/// The result of re-anchoring one annotation.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReanchoredAnnotation {
+    pub annotation_id: i32,
+    pub outcome: ReanchorOutcome,
+    /// Score of the best match, from `0.0` to `1.0`; `None` when nothing
+    /// matched or the annotation has no text to look for.
+    pub confidence: Option<f32>,
+}
+
+/// The result of re-anchoring a book's annotations.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReanchorReport {
+    pub book_id: i32,
+    pub annotations: Vec<ReanchoredAnnotation>,
+}
+
+/// The result of exporting a PDF book's annotations into a copy.
+#[derive(Debug, Clone, Serialize)]
+pub struct AnnotationExport {
+    /// Number of annotations written into the copy.
+    pub written: usize,
+    /// IDs of the highlights left out because they span more than two pages.
+    pub skipped: Vec<i32>,
+}
@@ -1,5 +1,5 @@
 /// An author of a book.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct Author {
     /// Auto-generated primary key.
     pub id: i32,
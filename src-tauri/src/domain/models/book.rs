@@ -1,3 +1,5 @@
+use crate::domain::models::partial_date::PartialDate;
+
 /// A book in the user's library.
 #[derive(Debug, Clone)]
 pub struct Book {
@@ -5,7 +7,7 @@ pub struct Book {
     pub id: i32,
     /// The book's title extracted from ebook metadata.
     pub title: String,
-    /// Publication date string from ebook metadata, if available.
+    /// Publication date as found in the ebook metadata or entered by the user.
     pub published_date: Option<String>,
     /// Foreign key to the [`Publisher`] record, if known.
     pub publisher_id: Option<i32>,
@@ -17,8 +19,43 @@ pub struct Book {
     pub file_path: Option<String>,
     /// Path to a locally cached cover image, if any.
     pub cover_image_path: Option<String>,
+    /// Whether the cached cover is a generated placeholder rather than cover
+    /// art from the file or a metadata provider.
+    pub cover_is_placeholder: bool,
     /// SHA-256 checksum of the ebook file, used for duplicate detection.
     pub checksum: Option<String>,
     /// ISO 8601 timestamp of when the book was imported.
     pub added_at: Option<String>,
+    /// Dominant cover colors as `#rrggbb` strings, most dominant first.
+    pub dominant_colors: Vec<String>,
+    /// BlurHash of the cover image for instant placeholders.
+    pub blurhash: Option<String>,
+    /// BCP 47 language tag (e.g., `"en"`, `"pt-BR"`), if known.
+    pub language: Option<String>,
+    /// Publisher's or user's description of the book.
+    pub description: Option<String>,
+    /// Name of the series the book belongs to, if any.
+    pub series: Option<String>,
+    /// Position within the series; fractional values allow novellas between
+    /// volumes (e.g., `2.5`).
+    pub series_index: Option<f32>,
+    /// Copyright or license statement (`dc:rights`), if any.
+    pub rights: Option<String>,
+    /// When the file's content was last modified according to its metadata.
+    pub modified_date: Option<String>,
+    /// EPUB specification version of the file (e.g., `"2.0"`, `"3.0"`).
+    pub epub_version: Option<String>,
+    /// `published_date` parsed to year, month or day precision.
+    pub published: Option<PartialDate>,
+    /// Title used for sorting, without a leading article (e.g., `"Hobbit"`).
+    pub title_sort: Option<String>,
+    /// Authors used for sorting, as `Surname, Given Names` joined with `" & "`
+    /// (e.g., `"Le Guin, Ursula K."`).
+    pub author_sort: Option<String>,
+    /// The [`Work`](crate::domain::models::work::Work) this file is a format
+    /// or edition of, if it has been linked to one.
+    pub work_id: Option<i32>,
+    /// When the book was moved to the trash (`YYYY-MM-DD HH:MM:SS`, UTC);
+    /// `None` while it is in the library.
+    pub deleted_at: Option<String>,
 }
@@ -1,3 +1,5 @@
+use crate::domain::models::locator::Locator;
+
 /// A user-created bookmark within a book.
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct Bookmark {
@@ -9,8 +11,8 @@ pub struct Bookmark {
     pub chapter_title: Option<String>,
     /// Page number for PDF books, if applicable.
     pub page_number: Option<i32>,
-    /// Position identifier (e.g., EPUB CFI, byte offset) marking the bookmark location.
-    pub position: String,
+    /// Where the bookmark was placed.
+    pub position: Locator,
     /// ISO 8601 timestamp of when the bookmark was created.
     pub created_at: Option<String>,
 }
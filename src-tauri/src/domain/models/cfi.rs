@@ -0,0 +1,399 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::domain::error::DomainError;
+
+/// Characters escaped with `^` inside CFI assertions.
+const SPECIAL_CHARS: &str = "^[](),;=";
+
+/// An EPUB Canonical Fragment Identifier pointing into a spine item, e.g.
+/// `epubcfi(/6/4[chap01]!/4[body01]/10[para05]/3:10)`, or a range such as
+/// `epubcfi(/6/4[chap01]!/4/10,/1:3,/3:8)`.
+///
+/// Steps count child nodes the way the CFI specification does: elements get
+/// even indexes (2 for the first child element) and the text before, between
+/// and after them odd ones. Character offsets count UTF-16 code units, as
+/// browsers and the readers producing CFIs (Readium, epub.js, Calibre) do.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cfi {
+    /// Step to the `spine` element in the package document; `/6` when the
+    /// package lists metadata, manifest and spine in the usual order.
+    pub spine: CfiStep,
+    /// Step to the spine `itemref`, `(spine_index + 1) * 2`, asserting the
+    /// item's `idref`.
+    pub itemref: CfiStep,
+    /// The position, or the start of a range.
+    pub start: CfiPoint,
+    /// The end of a range.
+    pub end: Option<CfiPoint>,
+}
+
+/// One step of a CFI path, e.g. `/4[body01]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CfiStep {
+    pub index: u32,
+    /// ID assertion: the `id` of the element the step leads to.
+    pub id: Option<String>,
+}
+
+/// A path through a content document, from its root element.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CfiPoint {
+    pub steps: Vec<CfiStep>,
+    pub offset: Option<CfiOffset>,
+}
+
+/// A character offset into the text node the path ends on, e.g.
+/// `:10[before,after;s=b]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CfiOffset {
+    pub chars: u32,
+    /// Text location assertion: the text just before the offset.
+    pub before: Option<String>,
+    /// Text location assertion: the text just after the offset.
+    pub after: Option<String>,
+    /// Side bias, for an offset where rendering can split the text.
+    pub side: Option<CfiSide>,
+}
+
+/// Side bias of a [`CfiOffset`] (`;s=b` or `;s=a`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CfiSide {
+    Before,
+    After,
+}
+
+/// A position in a spine item's document, in the terms the reader uses.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DomPosition {
+    /// Path from the root element: the 0-based index of each child element
+    /// along the way, counting elements only.
+    pub element_path: Vec<usize>,
+    /// Offset into the element's text content (the text of all its
+    /// descendants) in UTF-16 code units, or `None` for the element itself.
+    pub offset: Option<usize>,
+}
+
+/// Where a CFI points in a book.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CfiTarget {
+    /// 0-based index of the spine item.
+    pub spine_index: usize,
+    /// Href of the spine item, relative to the package document.
+    pub href: String,
+    /// The position, or the start of a range.
+    pub start: DomPosition,
+    /// The end of a range.
+    pub end: Option<DomPosition>,
+}
+
+impl Cfi {
+    /// Parses a CFI.
+    ///
+    /// # Arguments
+    ///
+    /// * `raw` - The CFI, e.g. `"epubcfi(/6/4[chap01]!/4/2/1:12)"`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::InvalidInput`] when the value is not a CFI, or
+    /// when it does not step through the spine into a content document (two
+    /// package steps and `!`). Temporal and spatial offsets and indirection
+    /// into nested documents are not supported.
+    pub fn parse(raw: &str) -> Result<Self, DomainError> {
+        let raw = raw.trim();
+        let invalid = |reason: &str| {
+            DomainError::InvalidInput(format!("Invalid CFI \"{}\": {}", raw, reason))
+        };
+
+        let body = raw
+            .strip_prefix("epubcfi(")
+            .and_then(|rest| rest.strip_suffix(')'))
+            .ok_or_else(|| invalid("expected epubcfi(...)"))?;
+        Parser {
+            chars: body.chars().collect(),
+            pos: 0,
+        }
+        .cfi()
+        .map_err(invalid)
+    }
+
+    /// The 0-based spine index the `itemref` step leads to, or `None` when
+    /// the step is not an element step.
+    pub fn spine_index(&self) -> Option<usize> {
+        match self.itemref.index {
+            index if index >= 2 && index % 2 == 0 => Some(index as usize / 2 - 1),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Cfi {
+    /// Formats the CFI; a range shares the steps its ends have in common.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("epubcfi(")?;
+        write_step(f, &self.spine)?;
+        write_step(f, &self.itemref)?;
+        f.write_str("!")?;
+
+        match &self.end {
+            None => write_point(f, &self.start.steps, self.start.offset.as_ref())?,
+            Some(end) => {
+                // Each end keeps at least one step of its own.
+                let shared = self
+                    .start
+                    .steps
+                    .iter()
+                    .zip(&end.steps)
+                    .take_while(|(a, b)| a == b)
+                    .count()
+                    .min(self.start.steps.len().saturating_sub(1))
+                    .min(end.steps.len().saturating_sub(1));
+                for step in &self.start.steps[..shared] {
+                    write_step(f, step)?;
+                }
+                f.write_str(",")?;
+                write_point(f, &self.start.steps[shared..], self.start.offset.as_ref())?;
+                f.write_str(",")?;
+                write_point(f, &end.steps[shared..], end.offset.as_ref())?;
+            }
+        }
+        f.write_str(")")
+    }
+}
+
+impl FromStr for Cfi {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+fn write_step(f: &mut fmt::Formatter<'_>, step: &CfiStep) -> fmt::Result {
+    write!(f, "/{}", step.index)?;
+    if let Some(id) = &step.id {
+        write!(f, "[{}]", escape(id))?;
+    }
+    Ok(())
+}
+
+fn write_point(
+    f: &mut fmt::Formatter<'_>,
+    steps: &[CfiStep],
+    offset: Option<&CfiOffset>,
+) -> fmt::Result {
+    for step in steps {
+        write_step(f, step)?;
+    }
+    let Some(offset) = offset else {
+        return Ok(());
+    };
+    write!(f, ":{}", offset.chars)?;
+    if offset.before.is_some() || offset.after.is_some() || offset.side.is_some() {
+        f.write_str("[")?;
+        f.write_str(&escape(offset.before.as_deref().unwrap_or_default()))?;
+        if let Some(after) = &offset.after {
+            write!(f, ",{}", escape(after))?;
+        }
+        match offset.side {
+            Some(CfiSide::Before) => f.write_str(";s=b")?,
+            Some(CfiSide::After) => f.write_str(";s=a")?,
+            None => {}
+        }
+        f.write_str("]")?;
+    }
+    Ok(())
+}
+
+/// Escapes the characters with a meaning in CFI syntax.
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if SPECIAL_CHARS.contains(c) {
+            escaped.push('^');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// The content of a bracketed assertion: comma-separated values, then
+/// `;`-separated parameters.
+#[derive(Default)]
+struct Assertion {
+    values: Vec<String>,
+    params: Vec<String>,
+}
+
+impl Assertion {
+    fn value(&self, index: usize) -> Option<String> {
+        self.values.get(index).filter(|v| !v.is_empty()).cloned()
+    }
+}
+
+/// Recursive-descent parser over the part of a CFI inside `epubcfi(...)`.
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek();
+        self.pos += 1;
+        c
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        let matched = self.peek() == Some(c);
+        if matched {
+            self.pos += 1;
+        }
+        matched
+    }
+
+    fn cfi(&mut self) -> Result<Cfi, &'static str> {
+        let [spine, itemref] = <[CfiStep; 2]>::try_from(self.steps()?)
+            .map_err(|_| "expected a spine step and an itemref step")?;
+        if !self.eat('!') {
+            return Err("expected ! after the itemref step");
+        }
+
+        let mut start = self.point()?;
+        let end = if self.eat(',') {
+            if start.offset.is_some() {
+                return Err("the common path of a range cannot have an offset");
+            }
+            let parent = std::mem::take(&mut start.steps);
+            let local_start = self.point()?;
+            if !self.eat(',') {
+                return Err("expected the end of the range");
+            }
+            let local_end = self.point()?;
+            if local_start == CfiPoint::default() || local_end == CfiPoint::default() {
+                return Err("empty range end");
+            }
+            start = CfiPoint {
+                steps: [parent.clone(), local_start.steps].concat(),
+                offset: local_start.offset,
+            };
+            Some(CfiPoint {
+                steps: [parent, local_end.steps].concat(),
+                offset: local_end.offset,
+            })
+        } else {
+            None
+        };
+
+        match self.peek() {
+            None => Ok(Cfi {
+                spine,
+                itemref,
+                start,
+                end,
+            }),
+            Some('!') => Err("indirection into nested documents is not supported"),
+            Some(_) => Err("unexpected characters"),
+        }
+    }
+
+    fn point(&mut self) -> Result<CfiPoint, &'static str> {
+        let steps = self.steps()?;
+        let offset = self.offset()?;
+        Ok(CfiPoint { steps, offset })
+    }
+
+    fn steps(&mut self) -> Result<Vec<CfiStep>, &'static str> {
+        let mut steps = Vec::new();
+        while self.eat('/') {
+            let index = self.integer()?;
+            if index == 0 {
+                return Err("step indexes start at 1");
+            }
+            let id = self.assertion()?.and_then(|a| a.value(0));
+            steps.push(CfiStep { index, id });
+        }
+        Ok(steps)
+    }
+
+    fn offset(&mut self) -> Result<Option<CfiOffset>, &'static str> {
+        match self.peek() {
+            Some(':') => {
+                self.pos += 1;
+                let chars = self.integer()?;
+                let assertion = self.assertion()?.unwrap_or_default();
+                let side = assertion
+                    .params
+                    .iter()
+                    .find_map(|p| p.strip_prefix("s="))
+                    .and_then(|side| match side {
+                        "b" => Some(CfiSide::Before),
+                        "a" => Some(CfiSide::After),
+                        _ => None,
+                    });
+                Ok(Some(CfiOffset {
+                    chars,
+                    before: assertion.value(0),
+                    after: assertion.value(1),
+                    side,
+                }))
+            }
+            Some('~' | '@') => Err("temporal and spatial offsets are not supported"),
+            _ => Ok(None),
+        }
+    }
+
+    fn integer(&mut self) -> Result<u32, &'static str> {
+        let start = self.pos;
+        while matches!(self.peek(), Some('0'..='9')) {
+            self.pos += 1;
+        }
+        if start == self.pos {
+            return Err("expected a number");
+        }
+        self.chars[start..self.pos]
+            .iter()
+            .collect::<String>()
+            .parse()
+            .map_err(|_| "number out of range")
+    }
+
+    /// Reads a bracketed assertion if one follows, unescaping `^`.
+    fn assertion(&mut self) -> Result<Option<Assertion>, &'static str> {
+        if !self.eat('[') {
+            return Ok(None);
+        }
+        let mut assertion = Assertion::default();
+        let mut current = String::new();
+        let mut in_params = false;
+        loop {
+            match self.next().ok_or("unterminated assertion")? {
+                ']' => break,
+                '^' => current.push(self.next().ok_or("unterminated assertion")?),
+                ',' if !in_params => assertion.values.push(std::mem::take(&mut current)),
+                ';' => {
+                    let part = std::mem::take(&mut current);
+                    if in_params {
+                        assertion.params.push(part);
+                    } else {
+                        assertion.values.push(part);
+                        in_params = true;
+                    }
+                }
+                c => current.push(c),
+            }
+        }
+        if in_params {
+            assertion.params.push(current);
+        } else {
+            assertion.values.push(current);
+        }
+        Ok(Some(assertion))
+    }
+}
@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+
+/// MARC relator code for a primary author.
+pub const ROLE_AUTHOR: &str = "aut";
+/// MARC relator code for a generic contributor, used when no role is given.
+pub const ROLE_CONTRIBUTOR: &str = "ctb";
+
+/// A person credited on a book, with their role and sort form.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Contributor {
+    /// Display name as it appears in the book's metadata.
+    pub name: String,
+    /// MARC relator code (e.g., `"aut"`, `"edt"`, `"trl"`, `"ill"`).
+    pub role: String,
+    /// Sort form of the name (EPUB `file-as`), e.g. `"Tolkien, J. R. R."`.
+    pub sort_name: Option<String>,
+}
+
+impl Contributor {
+    /// Creates a primary author with no explicit sort name.
+    pub fn author(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            role: ROLE_AUTHOR.to_string(),
+            sort_name: None,
+        }
+    }
+
+    /// Returns `true` if this contributor is credited as an author.
+    pub fn is_author(&self) -> bool {
+        self.role == ROLE_AUTHOR
+    }
+}
@@ -0,0 +1,168 @@
+use serde::Serialize;
+
+use crate::domain::models::sort_key;
+
+/// Score at or above which two books are reported as suspected duplicates.
+pub const DEFAULT_DUPLICATE_THRESHOLD: f32 = 0.8;
+
+/// Title similarity below which a pair without a shared ISBN is not scored.
+pub const CANDIDATE_TITLE_SIMILARITY: f32 = 0.6;
+
+/// Number of words of a book's text used for its fingerprint.
+pub const FINGERPRINT_WORDS: usize = 2000;
+
+/// Relative weight of each signal in [`DuplicateSignals::score`].
+const TITLE_WEIGHT: f32 = 3.0;
+const AUTHOR_WEIGHT: f32 = 2.0;
+const ISBN_WEIGHT: f32 = 3.0;
+const LENGTH_WEIGHT: f32 = 1.0;
+const TEXT_WEIGHT: f32 = 3.0;
+
+/// How closely two books match on each compared property, from `0.0` (no
+/// match) to `1.0` (identical). A signal is `None` when one of the books
+/// lacks the property, and is then left out of the score.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct DuplicateSignals {
+    /// Similarity of the normalized titles.
+    pub title: f32,
+    /// Similarity of the author sort keys.
+    pub author: Option<f32>,
+    /// `1.0` for the same ISBN, `0.0` for different ones.
+    pub isbn: Option<f32>,
+    /// Ratio of the shorter to the longer page or spine count.
+    pub length: Option<f32>,
+    /// Similarity of the text fingerprints.
+    pub text: Option<f32>,
+}
+
+impl DuplicateSignals {
+    /// Combines the available signals into a weighted average.
+    ///
+    /// Titles, ISBNs and text weigh three times as much as the page or spine
+    /// count, authors twice as much.
+    pub fn score(&self) -> f32 {
+        let signals = [
+            (Some(self.title), TITLE_WEIGHT),
+            (self.author, AUTHOR_WEIGHT),
+            (self.isbn, ISBN_WEIGHT),
+            (self.length, LENGTH_WEIGHT),
+            (self.text, TEXT_WEIGHT),
+        ];
+        let (total, weights) = signals
+            .iter()
+            .filter_map(|(value, weight)| value.map(|v| (v * weight, *weight)))
+            .fold((0.0, 0.0), |(total, weights), (v, w)| {
+                (total + v, weights + w)
+            });
+        total / weights
+    }
+}
+
+/// Reduces a title to lowercase words for comparison: the leading article
+/// is dropped and punctuation is removed.
+///
+/// # Arguments
+///
+/// * `title` - The book's title.
+/// * `language` - BCP 47 language tag used to drop a leading article.
+pub fn normalize_title(title: &str, language: Option<&str>) -> String {
+    normalize_text(&sort_key::title_sort(title, language))
+}
+
+/// Lowercases text and keeps only letters and digits, with single spaces
+/// between words.
+pub fn normalize_text(text: &str) -> String {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Returns the Dice coefficient of the character bigrams of two strings.
+///
+/// Both strings are compared as given, so callers normalize them first.
+/// Two empty strings are identical; one empty string matches nothing.
+pub fn similarity(a: &str, b: &str) -> f32 {
+    if a == b {
+        return 1.0;
+    }
+    let bigrams = |s: &str| -> Vec<(char, char)> {
+        let chars: Vec<char> = s.chars().collect();
+        chars.windows(2).map(|w| (w[0], w[1])).collect()
+    };
+    let left = bigrams(a);
+    let mut right = bigrams(b);
+    if left.is_empty() || right.is_empty() {
+        return 0.0;
+    }
+
+    let total = left.len() + right.len();
+    let mut shared = 0;
+    for bigram in left {
+        if let Some(pos) = right.iter().position(|&other| other == bigram) {
+            right.swap_remove(pos);
+            shared += 1;
+        }
+    }
+    (2 * shared) as f32 / total as f32
+}
+
+/// Returns the ratio of the smaller to the larger count, or `None` when
+/// either count is zero.
+pub fn length_similarity(a: u32, b: u32) -> Option<f32> {
+    if a == 0 || b == 0 {
+        return None;
+    }
+    Some(a.min(b) as f32 / a.max(b) as f32)
+}
+
+/// Computes a 64-bit SimHash of a text's word trigrams.
+///
+/// Texts that share most of their wording get fingerprints differing in few
+/// bits, so re-packaged copies of a book stay close while different books
+/// land about half the bits apart. Only the first [`FINGERPRINT_WORDS`]
+/// words are used.
+///
+/// # Returns
+///
+/// The fingerprint, or `None` when the text has fewer than three words.
+pub fn text_fingerprint(text: &str) -> Option<u64> {
+    let normalized = normalize_text(text);
+    let words: Vec<&str> = normalized.split(' ').take(FINGERPRINT_WORDS).collect();
+    if words.len() < 3 {
+        return None;
+    }
+
+    let mut counts = [0i32; 64];
+    for shingle in words.windows(3) {
+        let hash = fnv1a(shingle.join(" ").as_bytes());
+        for (bit, count) in counts.iter_mut().enumerate() {
+            if hash & (1 << bit) != 0 {
+                *count += 1;
+            } else {
+                *count -= 1;
+            }
+        }
+    }
+
+    Some(
+        counts
+            .iter()
+            .enumerate()
+            .filter(|(_, count)| **count > 0)
+            .fold(0u64, |fingerprint, (bit, _)| fingerprint | (1 << bit)),
+    )
+}
+
+/// Returns how many of the 64 fingerprint bits agree, as a fraction.
+pub fn fingerprint_similarity(a: u64, b: u64) -> f32 {
+    1.0 - (a ^ b).count_ones() as f32 / 64.0
+}
+
+/// 64-bit FNV-1a hash; stable across builds, unlike the standard hasher.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
@@ -0,0 +1,205 @@
+use std::fmt;
+use std::path::{Component, Path};
+
+use regex::Regex;
+use serde::Serialize;
+
+use crate::domain::error::DomainError;
+use crate::domain::models::isbn::Isbn;
+
+/// Placeholders recognized in a pattern, with the regex each one matches.
+/// `{ignore}` matches any text without extracting it.
+const PLACEHOLDERS: [(&str, &str); 7] = [
+    ("title", "[^/]+?"),
+    ("author", "[^/]+?"),
+    ("series", "[^/]+?"),
+    ("index", r"\d+(?:\.\d+)?"),
+    ("year", r"\d{4}"),
+    ("isbn", r"[0-9Xx][0-9Xx -]{8,15}[0-9Xx]"),
+    ("ignore", "[^/]*?"),
+];
+
+/// A pattern describing how book metadata is encoded in file and folder
+/// names, e.g. `{author} - {title} ({year})` or
+/// `{author}/{series}/{index} - {title}`.
+///
+/// Each `/`-separated segment matches one trailing path component; the last
+/// segment matches the file name without its extension. Underscores in file
+/// and folder names are read as spaces, and a run of spaces in the pattern
+/// matches any run of whitespace.
+#[derive(Debug, Clone)]
+pub struct FilenamePattern {
+    pattern: String,
+    regex: Regex,
+    segments: usize,
+}
+
+/// Metadata extracted from a path by a [`FilenamePattern`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct FilenameMetadata {
+    pub title: Option<String>,
+    /// Authors from `{author}`, split on `&`, `and` and `;`.
+    pub authors: Vec<String>,
+    pub series: Option<String>,
+    pub series_index: Option<f32>,
+    pub year: Option<u16>,
+    /// ISBN-13 form of a valid `{isbn}`.
+    pub isbn: Option<String>,
+}
+
+impl FilenamePattern {
+    /// Compiles a pattern.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - Literal text and `{title}`, `{author}`, `{series}`,
+    ///   `{index}`, `{year}`, `{isbn}` or `{ignore}` placeholders, with `/`
+    ///   separating folder levels.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::InvalidInput`] for an unknown, repeated or
+    /// unclosed placeholder, an empty segment, or a pattern without any
+    /// placeholder that extracts a field.
+    pub fn parse(pattern: &str) -> Result<Self, DomainError> {
+        let invalid = |reason: String| {
+            DomainError::InvalidInput(format!(
+                "Invalid filename pattern \"{}\": {}",
+                pattern, reason
+            ))
+        };
+
+        let pattern = pattern.trim().trim_matches('/');
+        let mut source = String::from("^");
+        let mut used: Vec<&str> = Vec::new();
+        let mut literal_space = false;
+        let mut rest = pattern;
+
+        if pattern.split('/').any(|segment| segment.trim().is_empty()) {
+            return Err(invalid("empty path segment".into()));
+        }
+
+        while let Some(c) = rest.chars().next() {
+            if c == '{' {
+                let end = rest
+                    .find('}')
+                    .ok_or_else(|| invalid("unclosed \"{\"".into()))?;
+                let name = rest[1..end].trim().to_ascii_lowercase();
+                let &(name, expr) = PLACEHOLDERS
+                    .iter()
+                    .find(|(n, _)| *n == name)
+                    .ok_or_else(|| invalid(format!("unknown placeholder {{{}}}", name)))?;
+                if name == "ignore" {
+                    source.push_str(expr);
+                } else {
+                    if used.contains(&name) {
+                        return Err(invalid(format!("{{{}}} is used more than once", name)));
+                    }
+                    used.push(name);
+                    source.push_str(&format!("(?P<{}>{})", name, expr));
+                }
+                literal_space = false;
+                rest = &rest[end + 1..];
+                continue;
+            }
+
+            if c.is_whitespace() {
+                if !literal_space {
+                    source.push_str(r"\s+");
+                }
+                literal_space = true;
+            } else {
+                source.push_str(&regex::escape(&c.to_string()));
+                literal_space = false;
+            }
+            rest = &rest[c.len_utf8()..];
+        }
+        source.push('$');
+
+        if used.is_empty() {
+            return Err(invalid("no placeholder to extract".into()));
+        }
+        let regex = Regex::new(&source).map_err(|e| invalid(e.to_string()))?;
+
+        Ok(Self {
+            pattern: pattern.to_string(),
+            segments: pattern.split('/').count(),
+            regex,
+        })
+    }
+
+    /// The pattern as written, without surrounding whitespace or slashes.
+    pub fn as_str(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Matches the pattern against the trailing components of `path`.
+    ///
+    /// # Returns
+    ///
+    /// The extracted fields, or `None` when the path has fewer components
+    /// than the pattern has segments or does not match.
+    pub fn extract(&self, path: &Path) -> Option<FilenameMetadata> {
+        let stem = path.file_stem()?.to_string_lossy().to_string();
+        let mut components: Vec<String> = path
+            .parent()
+            .into_iter()
+            .flat_map(|parent| parent.components())
+            .filter_map(|c| match c {
+                Component::Normal(name) => Some(name.to_string_lossy().to_string()),
+                _ => None,
+            })
+            .rev()
+            .take(self.segments - 1)
+            .collect();
+        if components.len() + 1 < self.segments {
+            return None;
+        }
+        components.reverse();
+        components.push(stem);
+        let subject = components
+            .iter()
+            .map(|c| c.replace('_', " ").trim().to_string())
+            .collect::<Vec<_>>()
+            .join("/");
+
+        let caps = self.regex.captures(&subject)?;
+        let text = |name: &str| {
+            caps.name(name)
+                .map(|m| m.as_str().split_whitespace().collect::<Vec<_>>().join(" "))
+                .filter(|v| !v.is_empty())
+        };
+
+        Some(FilenameMetadata {
+            title: text("title"),
+            authors: text("author")
+                .map(|a| split_authors(&a))
+                .unwrap_or_default(),
+            series: text("series"),
+            series_index: text("index").and_then(|i| i.parse().ok()),
+            year: text("year")
+                .and_then(|y| y.parse().ok())
+                .filter(|y| *y >= 1000),
+            isbn: text("isbn")
+                .and_then(|i| Isbn::parse(&i).ok())
+                .map(|i| i.to_isbn13()),
+        })
+    }
+}
+
+impl fmt::Display for FilenamePattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.pattern)
+    }
+}
+
+/// Splits an `{author}` value such as `Terry Pratchett & Neil Gaiman`.
+fn split_authors(value: &str) -> Vec<String> {
+    let separators = Regex::new(r"\s+(?:&|and)\s+|\s*;\s*").expect("valid regex");
+    separators
+        .split(value)
+        .map(str::trim)
+        .filter(|a| !a.is_empty())
+        .map(str::to_string)
+        .collect()
+}
@@ -0,0 +1,140 @@
+use serde::{Deserialize, Serialize};
+
+/// Scheme for International Standard Book Numbers (ISBN-10 or ISBN-13).
+pub const SCHEME_ISBN: &str = "isbn";
+/// Scheme for Amazon Standard Identification Numbers.
+pub const SCHEME_ASIN: &str = "asin";
+/// Scheme for UUIDs, commonly used as EPUB unique identifiers.
+pub const SCHEME_UUID: &str = "uuid";
+/// Scheme for Digital Object Identifiers.
+pub const SCHEME_DOI: &str = "doi";
+/// Scheme for identifiers that are URLs or other URIs.
+pub const SCHEME_URI: &str = "uri";
+/// Scheme for identifiers whose kind could not be determined.
+pub const SCHEME_OTHER: &str = "other";
+
+/// An identifier of a book typed by scheme, e.g. an ISBN or a DOI.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Identifier {
+    /// Lowercase scheme name (`"isbn"`, `"asin"`, `"uuid"`, `"doi"`, `"uri"`,
+    /// `"other"`, or a declared scheme such as `"calibre"`).
+    pub scheme: String,
+    /// The identifier with any scheme prefix (`urn:isbn:`, `doi:`) removed.
+    pub value: String,
+}
+
+impl Identifier {
+    /// Creates an identifier with an explicit scheme.
+    pub fn new(scheme: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            scheme: scheme.into(),
+            value: value.into(),
+        }
+    }
+
+    /// Determines the scheme of a raw identifier string.
+    ///
+    /// A URN or scheme prefix in the value wins (`urn:isbn:`, `urn:uuid:`,
+    /// `doi:`, `https://doi.org/`, `asin:`), then the declared scheme (EPUB 2
+    /// `opf:scheme` or EPUB 3 `identifier-type`), then the value's shape.
+    ///
+    /// # Arguments
+    ///
+    /// * `raw` - The identifier as it appears in the file.
+    /// * `declared_scheme` - The scheme declared alongside it, if any.
+    pub fn classify(raw: &str, declared_scheme: Option<&str>) -> Self {
+        let raw = raw.trim();
+        let lower = raw.to_ascii_lowercase();
+
+        const PREFIXES: [(&str, &str); 11] = [
+            ("urn:isbn:", SCHEME_ISBN),
+            ("isbn:", SCHEME_ISBN),
+            ("urn:uuid:", SCHEME_UUID),
+            ("uuid:", SCHEME_UUID),
+            ("urn:doi:", SCHEME_DOI),
+            ("doi:", SCHEME_DOI),
+            ("https://doi.org/", SCHEME_DOI),
+            ("http://dx.doi.org/", SCHEME_DOI),
+            ("urn:asin:", SCHEME_ASIN),
+            ("asin:", SCHEME_ASIN),
+            ("amazon:", SCHEME_ASIN),
+        ];
+        for (prefix, scheme) in PREFIXES {
+            if lower.starts_with(prefix) {
+                return Self::typed(scheme, raw[prefix.len()..].trim());
+            }
+        }
+
+        if let Some(declared) = declared_scheme.map(|s| s.trim().to_ascii_lowercase())
+            && !declared.is_empty()
+        {
+            let scheme = match declared.as_str() {
+                "isbn" | "isbn-10" | "isbn-13" => SCHEME_ISBN,
+                "asin" | "amazon" | "mobi-asin" => SCHEME_ASIN,
+                "uuid" => SCHEME_UUID,
+                "doi" => SCHEME_DOI,
+                "uri" | "url" => SCHEME_URI,
+                other => return Self::new(other, raw),
+            };
+            return Self::typed(scheme, raw);
+        }
+
+        let compact: String = raw.chars().filter(|c| !matches!(c, '-' | ' ')).collect();
+        let scheme = if is_uuid(raw) {
+            SCHEME_UUID
+        } else if is_isbn_shaped(&compact) {
+            SCHEME_ISBN
+        } else if lower.starts_with("10.") && lower.contains('/') {
+            SCHEME_DOI
+        } else if compact.len() == 10
+            && compact.starts_with("B0")
+            && compact
+                .chars()
+                .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+        {
+            SCHEME_ASIN
+        } else if lower.starts_with("http://") || lower.starts_with("https://") {
+            SCHEME_URI
+        } else {
+            SCHEME_OTHER
+        };
+        Self::typed(scheme, raw)
+    }
+
+    /// Builds an identifier, compacting ISBNs to digits (and a trailing `X`).
+    fn typed(scheme: &str, value: &str) -> Self {
+        let value = if scheme == SCHEME_ISBN {
+            value
+                .chars()
+                .filter(|c| !matches!(c, '-' | ' '))
+                .map(|c| c.to_ascii_uppercase())
+                .collect()
+        } else {
+            value.to_string()
+        };
+        Self::new(scheme, value)
+    }
+}
+
+/// Returns `true` for the canonical 8-4-4-4-12 hexadecimal UUID form.
+fn is_uuid(value: &str) -> bool {
+    let groups: Vec<&str> = value.split('-').collect();
+    groups.len() == 5
+        && groups
+            .iter()
+            .zip([8, 4, 4, 4, 12])
+            .all(|(g, len)| g.len() == len && g.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+/// Returns `true` for 13 digits, or 9 digits followed by a digit or `X`.
+fn is_isbn_shaped(compact: &str) -> bool {
+    let bytes = compact.as_bytes();
+    match bytes.len() {
+        13 => bytes.iter().all(u8::is_ascii_digit),
+        10 => {
+            bytes[..9].iter().all(u8::is_ascii_digit)
+                && (bytes[9].is_ascii_digit() || bytes[9] == b'X' || bytes[9] == b'x')
+        }
+        _ => false,
+    }
+}
@@ -0,0 +1,12 @@
+use serde::Serialize;
+
+/// A file the user removed from the library, skipped by directory scans.
+#[derive(Debug, Clone, Serialize)]
+pub struct IgnoredFile {
+    /// Auto-generated primary key.
+    pub id: i32,
+    /// Path of the file as it was stored on the removed book.
+    pub file_path: String,
+    /// ISO 8601 timestamp of when the file was ignored.
+    pub ignored_at: Option<String>,
+}
@@ -0,0 +1,146 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::domain::error::DomainError;
+
+/// A validated International Standard Book Number.
+///
+/// Always held in its ISBN-13 form so that the ISBN-10 and ISBN-13 of the
+/// same edition compare equal. Parsing accepts either length, with or
+/// without hyphens, spaces and a `urn:isbn:` / `ISBN` prefix.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Isbn(String);
+
+impl Isbn {
+    /// Parses and validates an ISBN-10 or ISBN-13.
+    ///
+    /// # Arguments
+    ///
+    /// * `raw` - The ISBN as entered or found in a file, e.g.
+    ///   `"urn:isbn:978-0-306-40615-7"` or `"0-306-40615-2"`.
+    ///
+    /// # Returns
+    ///
+    /// The ISBN converted to its 13-digit form.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::InvalidInput`] when the value does not have 10
+    /// or 13 digits (an ISBN-10 may end in `X`), when an ISBN-13 does not
+    /// start with `978` or `979`, or when the check digit does not match.
+    pub fn parse(raw: &str) -> Result<Self, DomainError> {
+        let compact = compact(strip_prefix(raw.trim()));
+        let invalid = |reason: &str| {
+            DomainError::InvalidInput(format!("Invalid ISBN \"{}\": {}", raw.trim(), reason))
+        };
+
+        let digits: Vec<u32> = compact
+            .chars()
+            .enumerate()
+            .map(|(i, c)| match c {
+                'X' if i == 9 && compact.len() == 10 => Some(10),
+                c => c.to_digit(10),
+            })
+            .collect::<Option<_>>()
+            .ok_or_else(|| invalid("expected 10 or 13 digits"))?;
+
+        match digits.len() {
+            10 => {
+                let sum: u32 = digits
+                    .iter()
+                    .enumerate()
+                    .map(|(i, d)| d * (10 - i as u32))
+                    .sum();
+                if sum % 11 != 0 {
+                    return Err(invalid("check digit does not match"));
+                }
+                let body = format!("978{}", &compact[..9]);
+                let check = isbn13_check_digit(&body);
+                Ok(Self(format!("{}{}", body, check)))
+            }
+            13 => {
+                if !compact.starts_with("978") && !compact.starts_with("979") {
+                    return Err(invalid("ISBN-13 must start with 978 or 979"));
+                }
+                if isbn13_check_digit(&compact[..12]) != digits[12] {
+                    return Err(invalid("check digit does not match"));
+                }
+                Ok(Self(compact))
+            }
+            _ => Err(invalid("expected 10 or 13 digits")),
+        }
+    }
+
+    /// The 13-digit form, without hyphens.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the 13-digit form, without hyphens.
+    pub fn to_isbn13(&self) -> String {
+        self.0.clone()
+    }
+
+    /// Returns the 10-digit form, or `None` for `979` ISBNs, which have no
+    /// ISBN-10 equivalent.
+    pub fn to_isbn10(&self) -> Option<String> {
+        let body = self.0.strip_prefix("978")?.get(..9)?;
+        let sum: u32 = body
+            .chars()
+            .filter_map(|c| c.to_digit(10))
+            .enumerate()
+            .map(|(i, d)| d * (10 - i as u32))
+            .sum();
+        let check = match (11 - sum % 11) % 11 {
+            10 => 'X',
+            d => char::from_digit(d, 10).unwrap_or('0'),
+        };
+        Some(format!("{}{}", body, check))
+    }
+}
+
+impl fmt::Display for Isbn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl FromStr for Isbn {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+/// Removes a leading `urn:isbn:`, `isbn:`, `ISBN-13:` or `ISBN ` label.
+fn strip_prefix(value: &str) -> &str {
+    const PREFIXES: [&str; 4] = ["urn:isbn:", "isbn-13", "isbn-10", "isbn"];
+    let lower = value.to_ascii_lowercase();
+    for prefix in PREFIXES {
+        if lower.starts_with(prefix) {
+            return value[prefix.len()..].trim_start_matches([':', ' ']);
+        }
+    }
+    value
+}
+
+/// Drops hyphens and whitespace and uppercases a trailing `x`.
+fn compact(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| *c != '-' && !c.is_whitespace())
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
+
+/// Computes the ISBN-13 check digit for the first 12 digits.
+fn isbn13_check_digit(first_twelve: &str) -> u32 {
+    let sum: u32 = first_twelve
+        .chars()
+        .filter_map(|c| c.to_digit(10))
+        .enumerate()
+        .map(|(i, d)| if i % 2 == 0 { d } else { d * 3 })
+        .sum();
+    (10 - sum % 10) % 10
+}
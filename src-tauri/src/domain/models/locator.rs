@@ -0,0 +1,228 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::error::DomainError;
+use crate::domain::models::cfi::Cfi;
+
+/// A position in a book, modelled on the Readium locator.
+///
+/// Stored as JSON in the `position` columns of bookmarks, annotations and
+/// reading progress, e.g.
+/// `{"type":"epub","href":"text/ch03.xhtml","cfi":"epubcfi(/6/8!/4/2/1:12)","progression":0.25}`
+/// or `{"type":"pdf","page_index":41,"quads":[...]}`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Locator {
+    #[serde(flatten)]
+    pub target: LocatorTarget,
+    /// The text at and around the position, for finding it again when the
+    /// structural part no longer matches.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<LocatorText>,
+}
+
+/// The format-specific part of a [`Locator`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum LocatorTarget {
+    Epub(EpubLocation),
+    Pdf(PdfLocation),
+    /// A position stored before locators existed (a reader scroll offset or
+    /// page number), kept verbatim because it cannot be interpreted.
+    Legacy {
+        value: String,
+    },
+}
+
+/// A position within an EPUB spine item.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EpubLocation {
+    /// Href of the spine item, relative to the package document.
+    pub href: String,
+    /// EPUB Canonical Fragment Identifier, e.g. `epubcfi(/6/4!/4/2/1:0)`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cfi: Option<String>,
+    /// Position within the spine item, from 0.0 to 1.0.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub progression: Option<f64>,
+    /// Position within the whole book, from 0.0 to 1.0.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_progression: Option<f64>,
+}
+
+/// A position on a PDF page.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PdfLocation {
+    /// 0-based page index, as taken by `read_pdf_page`.
+    pub page_index: u32,
+    /// Areas on the page, e.g. the lines of a highlight; empty for the
+    /// whole page.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub quads: Vec<Quad>,
+}
+
+/// A quadrilateral in PDF user space (points, origin at the bottom-left of
+/// the page), corners in `QuadPoints` order: upper-left, upper-right,
+/// lower-left, lower-right.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Quad {
+    pub points: [PdfPoint; 4],
+}
+
+/// A point in PDF user space.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PdfPoint {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl PdfLocation {
+    /// Builds the location of a text selection from the boxes of its text
+    /// spans, in reading order. Consecutive boxes on the same line are merged
+    /// into one quad, as PDF viewers draw highlights.
+    ///
+    /// # Arguments
+    ///
+    /// * `page_index` - 0-based page index.
+    /// * `boxes` - `[x, y, width, height]` of each span in PDF user space,
+    ///   `(x, y)` being the bottom-left corner.
+    pub fn from_text_boxes(page_index: u32, boxes: impl IntoIterator<Item = [f32; 4]>) -> Self {
+        let mut lines: Vec<[f32; 4]> = Vec::new();
+        for [x, y, width, height] in boxes {
+            let (right, top) = (x + width, y + height);
+            if let Some(line) = lines.last_mut() {
+                // Same line when the boxes overlap by half the smaller height.
+                let overlap = top.min(line[3]) - y.max(line[1]);
+                if overlap >= 0.5 * height.min(line[3] - line[1]) {
+                    *line = [
+                        line[0].min(x),
+                        line[1].min(y),
+                        line[2].max(right),
+                        line[3].max(top),
+                    ];
+                    continue;
+                }
+            }
+            lines.push([x, y, right, top]);
+        }
+
+        PdfLocation {
+            page_index,
+            quads: lines
+                .into_iter()
+                .map(|[left, bottom, right, top]| {
+                    Quad::from_rect(left, bottom, right - left, top - bottom)
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Quad {
+    /// The quad of an axis-aligned box whose bottom-left corner is `(x, y)`.
+    pub fn from_rect(x: f32, y: f32, width: f32, height: f32) -> Self {
+        let (right, top) = (x + width, y + height);
+        Quad {
+            points: [
+                PdfPoint { x, y: top },
+                PdfPoint { x: right, y: top },
+                PdfPoint { x, y },
+                PdfPoint { x: right, y },
+            ],
+        }
+    }
+
+    /// The smallest axis-aligned box containing the quad, as a PDF rectangle
+    /// `[left, bottom, right, top]`.
+    pub fn bounds(&self) -> [f32; 4] {
+        self.points.iter().fold(
+            [
+                f32::INFINITY,
+                f32::INFINITY,
+                f32::NEG_INFINITY,
+                f32::NEG_INFINITY,
+            ],
+            |[left, bottom, right, top], p| {
+                [left.min(p.x), bottom.min(p.y), right.max(p.x), top.max(p.y)]
+            },
+        )
+    }
+}
+
+/// Text context of a [`Locator`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LocatorText {
+    /// Text just before the position.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub before: Option<String>,
+    /// The selected text, for ranges.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub highlight: Option<String>,
+    /// Text just after the position.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+}
+
+impl Locator {
+    /// Reads a locator from its stored JSON form. A value that is not a
+    /// locator is returned as [`LocatorTarget::Legacy`], so rows written
+    /// outside the app stay readable.
+    pub fn from_stored(value: &str) -> Self {
+        serde_json::from_str(value).unwrap_or_else(|_| Locator {
+            target: LocatorTarget::Legacy {
+                value: value.to_string(),
+            },
+            text: None,
+        })
+    }
+
+    /// Returns the JSON form stored in the database.
+    pub fn to_stored(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    /// Checks that the locator can point somewhere: a non-empty href, a
+    /// well-formed CFI (see [`Cfi::parse`]), progressions from 0.0 to 1.0 and
+    /// finite quad coordinates.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::InvalidInput`] naming the first invalid part.
+    pub fn validate(&self) -> Result<(), DomainError> {
+        match &self.target {
+            LocatorTarget::Epub(epub) => {
+                if epub.href.trim().is_empty() {
+                    return Err(DomainError::InvalidInput(
+                        "EPUB locator needs the href of a spine item".into(),
+                    ));
+                }
+                if let Some(cfi) = &epub.cfi {
+                    Cfi::parse(cfi)?;
+                }
+                for progression in [epub.progression, epub.total_progression]
+                    .into_iter()
+                    .flatten()
+                {
+                    if !(0.0..=1.0).contains(&progression) {
+                        return Err(DomainError::InvalidInput(format!(
+                            "Progression must be between 0 and 1, got {}",
+                            progression
+                        )));
+                    }
+                }
+            }
+            LocatorTarget::Pdf(pdf) => {
+                let finite = pdf
+                    .quads
+                    .iter()
+                    .flat_map(|quad| quad.points)
+                    .all(|p| p.x.is_finite() && p.y.is_finite());
+                if !finite {
+                    return Err(DomainError::InvalidInput(
+                        "PDF locator quads must have finite coordinates".into(),
+                    ));
+                }
+            }
+            LocatorTarget::Legacy { .. } => {}
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,162 @@
+use serde::{Deserialize, Serialize};
+
+/// Names of the metadata fields tracked in a book's edit history.
+pub const FIELD_TITLE: &str = "title";
+pub const FIELD_AUTHORS: &str = "authors";
+pub const FIELD_PUBLISHER: &str = "publisher";
+pub const FIELD_PUBLISHED_DATE: &str = "published_date";
+pub const FIELD_ISBN: &str = "isbn";
+pub const FIELD_LANGUAGE: &str = "language";
+pub const FIELD_DESCRIPTION: &str = "description";
+pub const FIELD_SERIES: &str = "series";
+pub const FIELD_SERIES_INDEX: &str = "series_index";
+pub const FIELD_TAGS: &str = "tags";
+pub const FIELD_TITLE_SORT: &str = "title_sort";
+pub const FIELD_AUTHOR_SORT: &str = "author_sort";
+
+/// Separator between the entries of list fields (`authors`, `tags`) in a
+/// recorded value. Names and tags are single-line, so it never occurs in an
+/// entry.
+pub const LIST_SEPARATOR: char = '\n';
+
+/// What made a metadata change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeSource {
+    /// An edit in the app: the metadata editor, author and publisher
+    /// management, or reverting an earlier change.
+    User,
+    /// Fields applied from a metadata provider candidate.
+    Provider,
+    /// Metadata read from the file when the book was imported.
+    Import,
+    /// Stored metadata written into the book's file; the recorded old values
+    /// are the ones the file held before.
+    FileWriteback,
+}
+
+impl ChangeSource {
+    /// Returns the name stored in the database.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChangeSource::User => "user",
+            ChangeSource::Provider => "provider",
+            ChangeSource::Import => "import",
+            ChangeSource::FileWriteback => "file_writeback",
+        }
+    }
+
+    /// Parses a stored name, or returns `None` for an unknown one.
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "user" => Some(ChangeSource::User),
+            "provider" => Some(ChangeSource::Provider),
+            "import" => Some(ChangeSource::Import),
+            "file_writeback" => Some(ChangeSource::FileWriteback),
+            _ => None,
+        }
+    }
+}
+
+/// A change of one metadata field, before it is recorded.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    /// One of the `FIELD_*` names.
+    pub field: &'static str,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+}
+
+/// A recorded change of one metadata field.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MetadataChange {
+    /// Auto-generated primary key.
+    pub id: i32,
+    /// One of the `FIELD_*` names.
+    pub field: String,
+    /// The value before the change; `None` when the field was empty. List
+    /// fields hold their entries separated by [`LIST_SEPARATOR`].
+    pub old_value: Option<String>,
+    /// The value after the change; `None` when the field was cleared.
+    pub new_value: Option<String>,
+}
+
+/// The field changes made to one book by a single edit.
+#[derive(Debug, Clone, Serialize)]
+pub struct MetadataChangeSet {
+    /// Auto-generated primary key.
+    pub id: i32,
+    pub book_id: i32,
+    pub source: ChangeSource,
+    /// ISO 8601 timestamp of when the change was made.
+    pub changed_at: Option<String>,
+    pub changes: Vec<MetadataChange>,
+}
+
+/// The tracked metadata of a book at one point in time, compared before and
+/// after an edit to find what changed.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MetadataSnapshot {
+    pub title: Option<String>,
+    pub authors: Vec<String>,
+    pub publisher: Option<String>,
+    pub published_date: Option<String>,
+    pub isbn: Option<String>,
+    pub language: Option<String>,
+    pub description: Option<String>,
+    pub series: Option<String>,
+    pub series_index: Option<f32>,
+    pub tags: Vec<String>,
+    pub title_sort: Option<String>,
+    pub author_sort: Option<String>,
+}
+
+impl MetadataSnapshot {
+    /// Returns every tracked field with its value in recorded form.
+    pub fn fields(&self) -> Vec<(&'static str, Option<String>)> {
+        vec![
+            (FIELD_TITLE, self.title.clone()),
+            (FIELD_AUTHORS, join_list(&self.authors)),
+            (FIELD_PUBLISHER, self.publisher.clone()),
+            (FIELD_PUBLISHED_DATE, self.published_date.clone()),
+            (FIELD_ISBN, self.isbn.clone()),
+            (FIELD_LANGUAGE, self.language.clone()),
+            (FIELD_DESCRIPTION, self.description.clone()),
+            (FIELD_SERIES, self.series.clone()),
+            (FIELD_SERIES_INDEX, self.series_index.map(|i| i.to_string())),
+            (FIELD_TAGS, join_list(&self.tags)),
+            (FIELD_TITLE_SORT, self.title_sort.clone()),
+            (FIELD_AUTHOR_SORT, self.author_sort.clone()),
+        ]
+    }
+
+    /// Lists the fields whose values differ in `after`, in field order.
+    pub fn diff(&self, after: &MetadataSnapshot) -> Vec<FieldChange> {
+        self.fields()
+            .into_iter()
+            .zip(after.fields())
+            .filter(|((_, old), (_, new))| old != new)
+            .map(|((field, old_value), (_, new_value))| FieldChange {
+                field,
+                old_value,
+                new_value,
+            })
+            .collect()
+    }
+}
+
+/// Splits a recorded list value into its entries.
+pub fn split_list(value: Option<&str>) -> Vec<String> {
+    value
+        .map(|v| v.split(LIST_SEPARATOR).map(str::to_string).collect())
+        .unwrap_or_default()
+}
+
+/// Joins list entries into their recorded form; an empty list is `None`.
+fn join_list(entries: &[String]) -> Option<String> {
+    if entries.is_empty() {
+        None
+    } else {
+        Some(entries.join(&LIST_SEPARATOR.to_string()))
+    }
+}
@@ -4,5 +4,18 @@ pub mod annotation;
 pub mod author;
 pub mod book;
 pub mod bookmark;
+pub mod cfi;
+pub mod contributor;
+pub mod duplicate;
+pub mod filename_pattern;
+pub mod identifier;
+pub mod ignored_file;
+pub mod isbn;
+pub mod locator;
+pub mod metadata_change;
+pub mod partial_date;
 pub mod publisher;
 pub mod reading_progress;
+pub mod sort_key;
+pub mod text_quote;
+pub mod work;
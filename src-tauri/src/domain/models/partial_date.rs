@@ -0,0 +1,120 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::domain::error::DomainError;
+
+/// A date known to year, month or day precision, such as a publication date.
+///
+/// Displays (and is stored) as `YYYY`, `YYYY-MM` or `YYYY-MM-DD`, which sort
+/// correctly as text: a year sorts before any month of that year.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct PartialDate {
+    pub year: u16,
+    pub month: Option<u8>,
+    /// Only present when `month` is.
+    pub day: Option<u8>,
+}
+
+impl PartialDate {
+    /// Parses a date as found in ebook metadata, keeping as much precision as
+    /// is valid.
+    ///
+    /// Accepts ISO 8601 dates and timestamps (`2019`, `2019-05`,
+    /// `2019-05-04T10:22:11+02:00`; the date is taken as written, without
+    /// time zone conversion) and PDF dates (`D:20190504102211+02'00'`). An
+    /// out-of-range month or day is dropped rather than rejecting the year.
+    ///
+    /// # Returns
+    ///
+    /// `None` when no year from 1000 to 9999 can be read. Earlier years are
+    /// treated as placeholders, like calibre's `0101-01-01` for "unknown".
+    pub fn parse(raw: &str) -> Option<Self> {
+        let raw = raw.trim();
+        let raw = raw.strip_prefix("D:").unwrap_or(raw);
+
+        // Split into numeric components: `2019-05-04...` or `20190504...`.
+        let date_part = raw.split(['T', ' ']).next().unwrap_or(raw);
+        let (year, month, day) = if date_part.contains('-') {
+            let mut parts = date_part.split('-');
+            (parts.next(), parts.next(), parts.next())
+        } else {
+            let digits_end = date_part
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(date_part.len());
+            let digits = &date_part[..digits_end];
+            (digits.get(0..4), digits.get(4..6), digits.get(6..8))
+        };
+
+        let number = |part: Option<&str>, width: usize| {
+            part.filter(|p| p.len() == width && p.bytes().all(|b| b.is_ascii_digit()))
+                .and_then(|p| p.parse::<u16>().ok())
+        };
+        let year = number(year, 4).filter(|y| *y >= 1000)?;
+        let month = number(month, 2)
+            .filter(|m| (1..=12).contains(m))
+            .map(|m| m as u8);
+        let day = month.and_then(|m| {
+            number(day, 2)
+                .filter(|d| *d >= 1 && *d <= days_in_month(year, m) as u16)
+                .map(|d| d as u8)
+        });
+
+        Some(Self { year, month, day })
+    }
+
+    /// Parses user input, which must be exactly `YYYY`, `YYYY-MM` or
+    /// `YYYY-MM-DD` naming a real date.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::InvalidInput`] for any other format, a year
+    /// outside 1000–9999, or a month or day that does not exist.
+    pub fn parse_strict(raw: &str) -> Result<Self, DomainError> {
+        let invalid = || {
+            DomainError::InvalidInput(format!(
+                "Invalid date \"{}\": expected YYYY, YYYY-MM or YYYY-MM-DD",
+                raw
+            ))
+        };
+
+        let parts: Vec<&str> = raw.split('-').collect();
+        let widths = [4, 2, 2];
+        if parts.len() > 3
+            || parts
+                .iter()
+                .zip(widths)
+                .any(|(p, w)| p.len() != w || !p.bytes().all(|b| b.is_ascii_digit()))
+        {
+            return Err(invalid());
+        }
+
+        let parsed = Self::parse(raw).ok_or_else(invalid)?;
+        let precision = 1 + parsed.month.is_some() as usize + parsed.day.is_some() as usize;
+        if precision != parts.len() {
+            return Err(invalid());
+        }
+        Ok(parsed)
+    }
+}
+
+impl fmt::Display for PartialDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}", self.year)?;
+        if let Some(month) = self.month {
+            write!(f, "-{:02}", month)?;
+            if let Some(day) = self.day {
+                write!(f, "-{:02}", day)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if (year % 4 == 0 && year % 100 != 0) || year % 400 == 0 => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
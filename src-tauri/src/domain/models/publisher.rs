@@ -1,5 +1,5 @@
 /// A book publisher.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct Publisher {
     /// Auto-generated primary key.
     pub id: i32,
@@ -1,3 +1,5 @@
+use crate::domain::models::locator::Locator;
+
 /// Reading progress tracking for a book.
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct ReadingProgress {
@@ -5,8 +7,8 @@ pub struct ReadingProgress {
     pub id: i32,
     /// The book this progress record belongs to.
     pub book_id: i32,
-    /// Current position identifier (format depends on file type).
-    pub current_position: String,
+    /// Current reading position.
+    pub current_position: Locator,
     /// Title of the current chapter, if known.
     pub chapter_title: Option<String>,
     /// Current page number for PDF books, if applicable.
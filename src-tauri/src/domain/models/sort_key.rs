@@ -0,0 +1,116 @@
+use crate::domain::models::contributor::Contributor;
+
+/// Lowercase name particles that belong to the surname, as in "Le Guin" or
+/// "van Gogh".
+const SURNAME_PARTICLES: [&str; 16] = [
+    "da", "das", "de", "del", "della", "der", "des", "di", "du", "la", "le", "st.", "ten", "ter",
+    "van", "von",
+];
+
+/// Generational suffixes kept after the given names.
+const NAME_SUFFIXES: [&str; 8] = ["jr", "jr.", "sr", "sr.", "ii", "iii", "iv", "phd"];
+
+/// Derives the "Surname, Given Names" sort form of a personal name.
+///
+/// Particles before the surname stay with it ("Ursula K. Le Guin" becomes
+/// "Le Guin, Ursula K.") and suffixes follow the given names ("Martin
+/// Luther King Jr." becomes "King, Martin Luther, Jr."). Names that already
+/// contain a comma or are a single word are returned unchanged.
+pub fn author_sort(name: &str) -> String {
+    let name = name.split_whitespace().collect::<Vec<_>>().join(" ");
+    if name.contains(',') {
+        return name;
+    }
+
+    let mut words: Vec<&str> = name.split(' ').collect();
+    let suffix = match words.last() {
+        Some(last) if words.len() > 2 && NAME_SUFFIXES.contains(&last.to_lowercase().as_str()) => {
+            words.pop()
+        }
+        _ => None,
+    };
+    if words.len() < 2 {
+        return name;
+    }
+
+    let mut surname_start = words.len() - 1;
+    while surname_start > 1
+        && SURNAME_PARTICLES.contains(&words[surname_start - 1].to_lowercase().as_str())
+    {
+        surname_start -= 1;
+    }
+
+    let surname = words[surname_start..].join(" ");
+    let given = words[..surname_start].join(" ");
+    match suffix {
+        Some(suffix) => format!("{}, {}, {}", surname, given, suffix),
+        None => format!("{}, {}", surname, given),
+    }
+}
+
+/// Derives a book's author sort key from its credits.
+///
+/// Each `aut` credit contributes its EPUB `file-as` sort name when present,
+/// otherwise [`author_sort`] of its name; the keys are joined with `" & "`
+/// in credit order. The "Unknown Author" placeholder is skipped.
+///
+/// # Returns
+///
+/// The joined key, or `None` when the book has no named author.
+pub fn authors_sort(contributors: &[Contributor]) -> Option<String> {
+    let keys: Vec<String> = contributors
+        .iter()
+        .filter(|c| c.is_author() && c.name != "Unknown Author")
+        .map(|c| c.sort_name.clone().unwrap_or_else(|| author_sort(&c.name)))
+        .collect();
+    (!keys.is_empty()).then(|| keys.join(" & "))
+}
+
+/// Derives the sort form of a title by dropping a leading article of the
+/// book's language ("The Hobbit" sorts as "Hobbit", "L'Étranger" as
+/// "Étranger").
+///
+/// # Arguments
+///
+/// * `title` - The book's title.
+/// * `language` - BCP 47 language tag; English articles are used when it is
+///   `None` or has no known articles.
+pub fn title_sort(title: &str, language: Option<&str>) -> String {
+    let title = title.trim();
+    let primary = language
+        .and_then(|l| l.split(['-', '_']).next())
+        .map(str::to_lowercase);
+    let (articles, elided): (&[&str], &[&str]) = match primary.as_deref() {
+        Some("fr") => (&["le", "la", "les", "un", "une"], &["l"]),
+        Some("de") => (&["der", "die", "das", "ein", "eine"], &[]),
+        Some("es") => (&["el", "la", "los", "las", "un", "una"], &[]),
+        Some("it") => (
+            &["il", "lo", "la", "i", "gli", "le", "un", "uno", "una"],
+            &["l", "un"],
+        ),
+        Some("pt") => (&["o", "a", "os", "as", "um", "uma"], &[]),
+        Some("nl") => (&["de", "het", "een"], &[]),
+        _ => (&["the", "a", "an"], &[]),
+    };
+
+    for article in elided {
+        for apostrophe in ['\'', '\u{2019}'] {
+            let prefix = format!("{}{}", article, apostrophe);
+            let rest = title
+                .get(..prefix.len())
+                .filter(|head| head.to_lowercase() == prefix)
+                .and_then(|_| title.get(prefix.len()..))
+                .map(str::trim_start);
+            if let Some(rest) = rest.filter(|r| !r.is_empty()) {
+                return rest.to_string();
+            }
+        }
+    }
+    if let Some((first, rest)) = title.split_once(char::is_whitespace) {
+        let rest = rest.trim_start();
+        if !rest.is_empty() && articles.contains(&first.to_lowercase().as_str()) {
+            return rest.to_string();
+        }
+    }
+    title.to_string()
+}
@@ -0,0 +1,323 @@
+use crate::domain::models::duplicate::similarity;
+
+/// Score at or above which a re-anchored annotation is moved without
+/// asking the user to check it.
+pub const REANCHOR_CONFIDENCE: f32 = 0.75;
+
+/// Fraction of a quote's characters that may differ in an approximate
+/// match.
+const MAX_ERROR_RATE: f32 = 0.25;
+
+/// Quotes longer than this are matched approximately by their first and
+/// last [`EDGE_CHARS`] characters, which keeps the search linear in the
+/// length of the text.
+const MAX_FUZZY_CHARS: usize = 256;
+const EDGE_CHARS: usize = 64;
+
+/// Characters of context compared, and captured by [`TextQuote::at`], on
+/// each side of a quote.
+const CONTEXT_CHARS: usize = 32;
+
+/// Share of a match's score that depends on its context rather than on the
+/// quote itself.
+const CONTEXT_WEIGHT: f32 = 0.2;
+
+/// Most candidate matches returned for one text.
+const MAX_CANDIDATES: usize = 16;
+
+/// A passage to find again in a text, with the text around it, like the
+/// text quote selector of the W3C Web Annotation model.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TextQuote {
+    /// The quoted text.
+    pub exact: String,
+    /// Text just before the quote.
+    pub prefix: Option<String>,
+    /// Text just after the quote.
+    pub suffix: Option<String>,
+}
+
+/// Where a [`TextQuote`] was found in a text.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QuoteMatch {
+    /// Start of the matched text, in UTF-16 code units.
+    pub start: usize,
+    /// End of the matched text (exclusive), in UTF-16 code units.
+    pub end: usize,
+    /// How closely the text and its context match the quote, from `0.0` to
+    /// `1.0`.
+    pub score: f32,
+}
+
+impl TextQuote {
+    /// Finds the places in a text that match the quote.
+    ///
+    /// Text is compared case-insensitively, with runs of whitespace
+    /// collapsed, soft hyphens dropped and typographic quotes and dashes
+    /// folded into their ASCII forms, so an edition that only reflows or
+    /// re-typesets a passage still matches exactly. When there is no exact
+    /// match, passages within an edit distance of a quarter of the quote's
+    /// length are returned. The prefix and suffix only weigh on the score,
+    /// so a quote whose surroundings were rewritten is still found.
+    ///
+    /// # Returns
+    ///
+    /// Non-overlapping matches, best first; empty when the quote is blank
+    /// or nothing in the text is close enough.
+    pub fn find_in(&self, text: &str) -> Vec<QuoteMatch> {
+        let text = Normalized::new(text);
+        let exact = Normalized::new(self.exact.trim()).chars;
+        if exact.is_empty() || exact.len() > text.chars.len() {
+            return Vec::new();
+        }
+
+        let occurrences: Vec<(usize, usize, f32)> = text
+            .chars
+            .windows(exact.len())
+            .enumerate()
+            .filter(|(_, window)| *window == exact.as_slice())
+            .map(|(start, _)| (start, start + exact.len(), 1.0))
+            .collect();
+        let spans = if occurrences.is_empty() {
+            approximate_spans(&exact, &text.chars)
+        } else {
+            occurrences
+        };
+
+        let prefix = self.prefix.as_deref().map(|prefix| {
+            let chars = Normalized::new(prefix.trim()).chars;
+            chars[chars.len().saturating_sub(CONTEXT_CHARS)..].to_vec()
+        });
+        let suffix = self.suffix.as_deref().map(|suffix| {
+            let mut chars = Normalized::new(suffix.trim()).chars;
+            chars.truncate(CONTEXT_CHARS);
+            chars
+        });
+        let mut matches: Vec<QuoteMatch> = spans
+            .into_iter()
+            .map(|(start, end, quote_score)| {
+                let mut context = Vec::new();
+                if let Some(prefix) = prefix.as_ref().filter(|p| !p.is_empty()) {
+                    let before = text.chars[..start]
+                        .strip_suffix(&[' '])
+                        .unwrap_or(&text.chars[..start]);
+                    let before = &before[before.len().saturating_sub(prefix.len())..];
+                    context.push(compare(prefix, before));
+                }
+                if let Some(suffix) = suffix.as_ref().filter(|s| !s.is_empty()) {
+                    let after = text.chars[end..]
+                        .strip_prefix(&[' '])
+                        .unwrap_or(&text.chars[end..]);
+                    let after = &after[..suffix.len().min(after.len())];
+                    context.push(compare(suffix, after));
+                }
+                let context_score = match context.len() {
+                    0 => 1.0,
+                    n => context.iter().sum::<f32>() / n as f32,
+                };
+                QuoteMatch {
+                    start: text.starts[start],
+                    end: text.ends[end - 1],
+                    score: quote_score * (1.0 - CONTEXT_WEIGHT + CONTEXT_WEIGHT * context_score),
+                }
+            })
+            .collect();
+        matches.sort_by(|a, b| b.score.total_cmp(&a.score));
+        matches.truncate(MAX_CANDIDATES);
+        matches
+    }
+
+    /// Captures the quote between two positions of a text, with up to 32
+    /// characters of context on each side. Whitespace runs are collapsed.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text.
+    /// * `start` - Start of the quote, in UTF-16 code units.
+    /// * `end` - End of the quote (exclusive), in UTF-16 code units.
+    pub fn at(text: &str, start: usize, end: usize) -> Self {
+        let mut before = String::new();
+        let mut exact = String::new();
+        let mut after = String::new();
+        let mut offset = 0;
+        for c in text.chars() {
+            let part = if offset < start {
+                &mut before
+            } else if offset < end {
+                &mut exact
+            } else {
+                &mut after
+            };
+            part.push(c);
+            offset += c.len_utf16();
+        }
+
+        let collapse = |s: &str| s.split_whitespace().collect::<Vec<_>>().join(" ");
+        let before: Vec<char> = collapse(&before).chars().collect();
+        let after = collapse(&after);
+        let non_empty = |s: String| Some(s).filter(|s| !s.is_empty());
+        Self {
+            exact: collapse(&exact),
+            prefix: non_empty(
+                before[before.len().saturating_sub(CONTEXT_CHARS)..]
+                    .iter()
+                    .collect(),
+            ),
+            suffix: non_empty(after.chars().take(CONTEXT_CHARS).collect()),
+        }
+    }
+}
+
+/// Text prepared for matching, with the UTF-16 span each character came
+/// from.
+struct Normalized {
+    chars: Vec<char>,
+    starts: Vec<usize>,
+    ends: Vec<usize>,
+}
+
+impl Normalized {
+    fn new(text: &str) -> Self {
+        let mut normalized = Self {
+            chars: Vec::new(),
+            starts: Vec::new(),
+            ends: Vec::new(),
+        };
+        let mut offset = 0;
+        for c in text.chars() {
+            let start = offset;
+            offset += c.len_utf16();
+            let folded = match c {
+                // Soft hyphen and zero-width space: invisible hyphenation
+                // and line-break hints.
+                '\u{ad}' | '\u{200b}' => continue,
+                c if c.is_whitespace() => {
+                    if normalized.chars.last() == Some(&' ') {
+                        continue;
+                    }
+                    ' '
+                }
+                '\u{2018}' | '\u{2019}' | '\u{201a}' | '\u{201b}' | '\u{2032}' => '\'',
+                '\u{201c}' | '\u{201d}' | '\u{201e}' | '\u{201f}' | '\u{2033}' => '"',
+                '\u{2010}'..='\u{2015}' | '\u{2212}' => '-',
+                c => c.to_lowercase().next().unwrap_or(c),
+            };
+            normalized.chars.push(folded);
+            normalized.starts.push(start);
+            normalized.ends.push(offset);
+        }
+        normalized
+    }
+}
+
+fn compare(expected: &[char], found: &[char]) -> f32 {
+    let expected: String = expected.iter().collect();
+    let found: String = found.iter().collect();
+    similarity(expected.trim(), found.trim())
+}
+
+/// Returns the passages of `text` within the allowed edit distance of
+/// `pattern`, as `(start, end, score)` character spans.
+fn approximate_spans(pattern: &[char], text: &[char]) -> Vec<(usize, usize, f32)> {
+    let max_errors = |len: usize| (len as f32 * MAX_ERROR_RATE) as usize;
+    let score = |errors: usize, len: usize| 1.0 - errors as f32 / len as f32;
+
+    if pattern.len() <= MAX_FUZZY_CHARS {
+        return distinct(approximate_matches(
+            pattern,
+            text,
+            max_errors(pattern.len()),
+        ))
+        .into_iter()
+        .map(|(start, end, errors)| (start, end, score(errors, pattern.len())))
+        .collect();
+    }
+
+    // Find where the quote starts by its head, then where it ends by its
+    // tail, in the stretch a quote of its length could cover.
+    let head = &pattern[..EDGE_CHARS];
+    let tail = &pattern[pattern.len() - EDGE_CHARS..];
+    let reach = pattern.len() + max_errors(pattern.len());
+    distinct(approximate_matches(head, text, max_errors(EDGE_CHARS)))
+        .into_iter()
+        .filter_map(|(start, _, head_errors)| {
+            let stretch = &text[start..(start + reach).min(text.len())];
+            let (_, end, tail_errors) = approximate_matches(tail, stretch, max_errors(EDGE_CHARS))
+                .into_iter()
+                .min_by_key(|&(_, end, errors)| (errors, end.abs_diff(pattern.len())))?;
+            Some((
+                start,
+                start + end,
+                score(head_errors + tail_errors, 2 * EDGE_CHARS),
+            ))
+        })
+        .collect()
+}
+
+/// Sellers' approximate string matching with Ukkonen's cut-off: returns
+/// every `(start, end, errors)` span of `text` that `pattern` can be turned
+/// into with at most `max_errors` insertions, deletions or substitutions.
+fn approximate_matches(
+    pattern: &[char],
+    text: &[char],
+    max_errors: usize,
+) -> Vec<(usize, usize, usize)> {
+    let m = pattern.len();
+    // `cost[i]`: fewest edits from `pattern[..i]` to a passage ending at the
+    // current character; `start[i]`: where that passage starts.
+    let mut cost: Vec<usize> = (0..=m).collect();
+    let mut start = vec![0; m + 1];
+    // Last row whose cost is within `max_errors`; the rows below it are
+    // not computed.
+    let mut active = max_errors.min(m);
+    let mut found = Vec::new();
+
+    for (j, &c) in text.iter().enumerate() {
+        let (mut diagonal_cost, mut diagonal_start) = (0, j);
+        start[0] = j + 1;
+        let last = (active + 1).min(m);
+        for i in 1..=last {
+            let (old_cost, old_start) = if i > active {
+                (max_errors + 1, j)
+            } else {
+                (cost[i], start[i])
+            };
+            let mut best = (
+                diagonal_cost + usize::from(pattern[i - 1] != c),
+                diagonal_start,
+            );
+            if old_cost + 1 < best.0 {
+                best = (old_cost + 1, old_start);
+            }
+            if cost[i - 1] + 1 < best.0 {
+                best = (cost[i - 1] + 1, start[i - 1]);
+            }
+            (diagonal_cost, diagonal_start) = (old_cost, old_start);
+            (cost[i], start[i]) = best;
+        }
+
+        active = last;
+        while active > 0 && cost[active] > max_errors {
+            active -= 1;
+        }
+        if active == m {
+            found.push((start[m], j + 1, cost[m]));
+        }
+    }
+    found
+}
+
+/// Keeps the best of overlapping spans, fewest errors first.
+fn distinct(mut spans: Vec<(usize, usize, usize)>) -> Vec<(usize, usize, usize)> {
+    spans.sort_by_key(|&(start, end, errors)| (errors, start, end));
+    let mut kept: Vec<(usize, usize, usize)> = Vec::new();
+    for span in spans {
+        if kept
+            .iter()
+            .all(|other| span.1 <= other.0 || span.0 >= other.1)
+        {
+            kept.push(span);
+        }
+    }
+    kept
+}
@@ -0,0 +1,57 @@
+use serde::Serialize;
+
+use crate::domain::models::sort_key;
+
+/// A work: one book held as several files, such as an EPUB and a PDF of the
+/// same title or two editions of it.
+#[derive(Debug, Clone, Serialize)]
+pub struct Work {
+    /// Auto-generated primary key.
+    pub id: i32,
+    /// Display title, taken from the first linked book.
+    pub title: String,
+    /// The file the reader opens for this work. When `None`, an EPUB is
+    /// preferred, then the earliest imported file.
+    pub preferred_book_id: Option<i32>,
+    /// ISO 8601 timestamp of when the work was created.
+    pub created_at: Option<String>,
+}
+
+/// Derives the key under which files of the same work are suggested for
+/// linking.
+///
+/// The title is reduced to its [`sort_key::title_sort`] form without a
+/// subtitle (text after `:`), and both title and author sort key are
+/// lowercased with everything but letters and digits removed, so "The
+/// Dispossessed: An Ambiguous Utopia" by "Le Guin, Ursula K." and
+/// "Dispossessed" by "Le Guin, Ursula K" share a key.
+///
+/// # Arguments
+///
+/// * `title` - The book's title.
+/// * `language` - BCP 47 language tag used to drop a leading article.
+/// * `author_sort` - The book's author sort key, if it has named authors.
+///
+/// # Returns
+///
+/// The key, or `None` when the title has no letters or digits.
+pub fn work_key(title: &str, language: Option<&str>, author_sort: Option<&str>) -> Option<String> {
+    let simplify = |text: &str| -> String {
+        text.chars()
+            .filter(|c| c.is_alphanumeric())
+            .flat_map(char::to_lowercase)
+            .collect()
+    };
+
+    let title = sort_key::title_sort(title, language);
+    let main_title = title.split(':').next().unwrap_or(&title);
+    let title_key = simplify(main_title);
+    if title_key.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "{}|{}",
+        title_key,
+        simplify(author_sort.unwrap_or_default())
+    ))
+}
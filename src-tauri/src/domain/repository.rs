@@ -1,12 +1,23 @@
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
 
+use crate::domain::dto::author_dto::AuthorSummary;
+use crate::domain::dto::book_dto::{BookDto, BookFormat, BookPage};
+use crate::domain::dto::publisher_dto::PublisherSummary;
 use crate::domain::error::DomainError;
 use crate::domain::models::annotation::Annotation;
 use crate::domain::models::author::Author;
 use crate::domain::models::book::Book;
 use crate::domain::models::bookmark::Bookmark;
+use crate::domain::models::contributor::Contributor;
+use crate::domain::models::identifier::Identifier;
+use crate::domain::models::ignored_file::IgnoredFile;
+use crate::domain::models::isbn::Isbn;
+use crate::domain::models::locator::Locator;
+use crate::domain::models::metadata_change::{ChangeSource, FieldChange, MetadataChangeSet};
 use crate::domain::models::publisher::Publisher;
 use crate::domain::models::reading_progress::ReadingProgress;
+use crate::domain::models::work::Work;
 
 /// Input data for creating a new book record.
 pub struct NewBook {
@@ -18,9 +29,25 @@ pub struct NewBook {
     pub file_path: String,
     pub cover_image_path: Option<String>,
     pub checksum: Option<String>,
+    pub dominant_colors: Vec<String>,
+    pub blurhash: Option<String>,
+    pub language: Option<String>,
+    pub description: Option<String>,
+    pub rights: Option<String>,
+    pub modified_date: Option<String>,
+    pub epub_version: Option<String>,
+    pub series: Option<String>,
+    pub series_index: Option<f32>,
+    pub title_sort: Option<String>,
+    pub author_sort: Option<String>,
+    /// Subject headings, stored in `book_subjects`.
+    pub subjects: Vec<String>,
+    /// Typed identifiers, stored in `book_identifiers`.
+    pub identifiers: Vec<Identifier>,
 }
 
 /// Partial update input for an existing book. Only `Some` fields are applied.
+#[derive(Default)]
 pub struct UpdateBook {
     pub title: Option<String>,
     pub published_date: Option<String>,
@@ -29,7 +56,160 @@ pub struct UpdateBook {
     pub file_type: Option<String>,
     pub file_path: Option<String>,
     pub cover_image_path: Option<String>,
+    pub cover_is_placeholder: Option<bool>,
     pub checksum: Option<String>,
+    pub dominant_colors: Option<Vec<String>>,
+    pub blurhash: Option<String>,
+}
+
+/// Metadata edits submitted by the user for a single book.
+///
+/// `None` leaves a field unchanged. For optional text fields (`publisher`,
+/// `published_date`, `isbn`, `language`, `description`, `series`) an empty
+/// or whitespace-only string clears the stored value; clearing `series` also
+/// clears `series_index`, and `clear_series_index` clears the index alone.
+/// `authors` and `tags` replace the existing lists.
+/// `title_sort` and `author_sort` override the derived sort keys; an empty
+/// string derives them again from the title, language and authors.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct BookMetadataPatch {
+    pub title: Option<String>,
+    pub authors: Option<Vec<String>>,
+    pub publisher: Option<String>,
+    pub published_date: Option<String>,
+    pub isbn: Option<String>,
+    pub language: Option<String>,
+    pub description: Option<String>,
+    pub series: Option<String>,
+    pub series_index: Option<f32>,
+    pub clear_series_index: bool,
+    pub tags: Option<Vec<String>>,
+    pub title_sort: Option<String>,
+    pub author_sort: Option<String>,
+}
+
+/// Validated metadata changes for [`BookRepository::update_with_links`].
+///
+/// The outer `Option` selects whether a field changes; for nullable columns
+/// `Some(None)` clears the value.
+#[derive(Default)]
+pub struct BookMetadataChanges {
+    pub title: Option<String>,
+    pub published_date: Option<Option<String>>,
+    pub publisher_id: Option<Option<i32>>,
+    pub isbn: Option<Option<String>>,
+    pub language: Option<Option<String>>,
+    pub description: Option<Option<String>>,
+    pub series: Option<Option<String>>,
+    pub series_index: Option<Option<f32>>,
+    pub title_sort: Option<Option<String>>,
+    pub author_sort: Option<Option<String>>,
+    /// Replaces every `aut` link when set; links in other roles are kept.
+    pub authors: Option<Vec<NewBookAuthor>>,
+    /// Replaces every tag when set.
+    pub tags: Option<Vec<String>>,
+}
+
+/// Input data for linking an existing author to a book in a given role.
+pub struct NewBookAuthor {
+    pub author_id: i32,
+    /// MARC relator code, e.g. `"aut"` or `"trl"`.
+    pub role: String,
+    /// Sort form of the name for this credit (EPUB `file-as`).
+    pub sort_name: Option<String>,
+}
+
+/// Reading state of a book, derived from its reading progress record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReadingStatus {
+    /// Never opened, or progress is still at 0%.
+    Unread,
+    /// Progress between 0% and 100%.
+    Reading,
+    /// Progress at 100%.
+    Finished,
+}
+
+/// Column a book listing is ordered by.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BookSortKey {
+    /// Title sort key, falling back to the title (case-insensitive).
+    #[default]
+    Title,
+    /// Author sort key, falling back to the alphabetically first linked
+    /// author name (case-insensitive).
+    Author,
+    /// Import timestamp.
+    AddedAt,
+    /// Last time the book was opened; never-read books sort first.
+    LastRead,
+    /// Publication date at its known precision (a bare year sorts before
+    /// its months); undated books sort first.
+    Published,
+}
+
+/// Sort direction for book listings.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortDirection {
+    #[default]
+    Asc,
+    Desc,
+}
+
+/// Filters applied to a book listing. `None` fields are ignored.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct BookFilter {
+    /// Substring matched against any linked author's name.
+    pub author: Option<String>,
+    /// Substring matched against the publisher's name.
+    pub publisher: Option<String>,
+    /// Exact file type (e.g., `"epub"`, `"pdf"`).
+    pub file_type: Option<String>,
+    /// Only books added at or after this `YYYY-MM-DD[ HH:MM:SS]` timestamp.
+    pub added_after: Option<String>,
+    /// Only books added at or before this `YYYY-MM-DD[ HH:MM:SS]` timestamp;
+    /// a date alone includes books added during that day.
+    pub added_before: Option<String>,
+    /// Only books in the given reading state.
+    pub status: Option<ReadingStatus>,
+    /// Language tag; `"en"` also matches regional variants such as `"en-GB"`.
+    pub language: Option<String>,
+    /// Subject matched exactly (case-insensitive) against any of the book's
+    /// subjects.
+    pub subject: Option<String>,
+    /// Only books published in or after this year.
+    pub published_from: Option<i32>,
+    /// Only books published in or before this year.
+    pub published_to: Option<i32>,
+}
+
+/// Page selection for a book listing.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Pagination {
+    /// Skip `offset` rows and return at most `limit`.
+    Offset { offset: i64, limit: i64 },
+    /// Return at most `limit` rows after the opaque cursor from a previous
+    /// [`BookPage::next_cursor`], or from the start when `after` is `None`.
+    Cursor { after: Option<String>, limit: i64 },
+}
+
+/// Filter, sort and pagination options for [`BookRepository::query`].
+///
+/// The default query returns every book ordered by title.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct BookQuery {
+    pub filter: BookFilter,
+    pub sort: BookSortKey,
+    pub direction: SortDirection,
+    /// `None` returns all matching books in one page.
+    pub pagination: Option<Pagination>,
 }
 
 /// Persistence operations for books.
@@ -37,6 +217,8 @@ pub struct UpdateBook {
 pub trait BookRepository: Send + Sync {
     /// Returns all books in the library.
     ///
+    /// Books in the trash are left out.
+    ///
     /// # Returns
     ///
     /// A vector of all [`Book`] records.
@@ -91,10 +273,11 @@ pub trait BookRepository: Send + Sync {
     /// Returns [`DomainError::Database`] on update failure.
     async fn update(&self, id: i32, book: UpdateBook) -> Result<(), DomainError>;
 
-    /// Deletes a book by ID.
+    /// Permanently deletes a book by ID.
     ///
     /// Related records (bookmarks, annotations, progress, author links) are
-    /// cascade-deleted by SQLite foreign keys.
+    /// cascade-deleted by SQLite foreign keys. A work left with a single
+    /// file is dissolved.
     ///
     /// # Arguments
     ///
@@ -105,6 +288,81 @@ pub trait BookRepository: Send + Sync {
     /// Returns [`DomainError::Database`] on query failure.
     async fn delete(&self, id: i32) -> Result<(), DomainError>;
 
+    /// Moves a book to the trash by stamping its `deleted_at` with the
+    /// current time.
+    ///
+    /// Trashed books keep their bookmarks, annotations and progress but are
+    /// left out of listings and searches.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The book's database ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::BookNotFound`] if the book does not exist, or
+    /// [`DomainError::Database`] on failure.
+    async fn trash(&self, id: i32) -> Result<(), DomainError>;
+
+    /// Takes a book out of the trash by clearing its `deleted_at`.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The book's database ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::BookNotFound`] if the book does not exist, or
+    /// [`DomainError::Database`] on failure.
+    async fn restore(&self, id: i32) -> Result<(), DomainError>;
+
+    /// Returns the books in the trash as DTOs, most recently trashed first.
+    ///
+    /// Authors, publisher, tags, subjects, identifiers and formats are
+    /// resolved in the same single SQL query as [`query`](Self::query).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on query failure.
+    async fn query_trashed(&self) -> Result<Vec<BookDto>, DomainError>;
+
+    /// Permanently deletes trashed books.
+    ///
+    /// Works left with a single file are dissolved, as with
+    /// [`delete`](Self::delete).
+    ///
+    /// # Arguments
+    ///
+    /// * `older_than` - Only books trashed before this `YYYY-MM-DD HH:MM:SS`
+    ///   timestamp are deleted; `None` empties the whole trash.
+    ///
+    /// # Returns
+    ///
+    /// The number of books deleted.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on failure.
+    async fn purge_trash(&self, older_than: Option<&str>) -> Result<usize, DomainError>;
+
+    /// Merges duplicate books into a target book.
+    ///
+    /// Moves the bookmarks, annotations and tags of the source books to the
+    /// target, keeps the most recently read reading progress, and lets the
+    /// target take a source's place in its work when the target has none.
+    /// The source books are then deleted. Runs in a single transaction.
+    ///
+    /// # Arguments
+    ///
+    /// * `source_ids` - Books to merge away. The target's own ID is ignored.
+    /// * `target_id` - The book that remains.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::BookNotFound`] if the target does not exist, or
+    /// [`DomainError::Database`] on failure.
+    async fn merge(&self, source_ids: &[i32], target_id: i32) -> Result<(), DomainError>;
+
     /// Returns the book matching the given SHA-256 checksum.
     ///
     /// Used for duplicate detection during import.
@@ -122,6 +380,25 @@ pub trait BookRepository: Send + Sync {
     /// Returns [`DomainError::Database`] on query failure.
     async fn find_by_checksum(&self, checksum: &str) -> Result<Option<Book>, DomainError>;
 
+    /// Finds the books with an ISBN.
+    ///
+    /// Used for duplicate detection during import. Matches the stored
+    /// `isbn` column as well as `isbn` identifiers in either their ISBN-10 or
+    /// ISBN-13 form. Several formats of the same edition may share an ISBN.
+    ///
+    /// # Arguments
+    ///
+    /// * `isbn` - The validated ISBN to look for.
+    ///
+    /// # Returns
+    ///
+    /// A (possibly empty) vector of matching [`Book`] records.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on query failure.
+    async fn find_by_isbn(&self, isbn: &Isbn) -> Result<Vec<Book>, DomainError>;
+
     /// Returns books whose title contains the given query string.
     ///
     /// Uses a SQL `LIKE` query (`%query%`) for substring matching. Case
@@ -140,15 +417,41 @@ pub trait BookRepository: Send + Sync {
     /// Returns [`DomainError::Database`] on query failure.
     async fn search_by_title(&self, title: &str) -> Result<Vec<Book>, DomainError>;
 
+    /// Returns a filtered, sorted page of books resolved to DTOs.
+    ///
+    /// Author names, publisher name and reading progress are joined in a
+    /// single SQL query, so the cost does not grow with the number of books
+    /// on the page.
+    ///
+    /// Books linked into a work are listed once: the matching file that comes
+    /// first in the work's format order stands for the work, with every
+    /// file of the work in [`BookDto::formats`](crate::domain::dto::book_dto::BookDto::formats).
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - Filters, sort order and pagination to apply.
+    ///
+    /// # Returns
+    ///
+    /// A [`BookPage`] with the matching books, the total number of entries
+    /// across all pages, and a cursor for the next page when one exists.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Parse`] for a malformed cursor, or
+    /// [`DomainError::Database`] on query failure.
+    async fn query(&self, query: &BookQuery) -> Result<BookPage, DomainError>;
+
     /// Imports a new book with author and publisher links in a single transaction.
     ///
-    /// Inserts the book, links each author via the join table, and associates
-    /// the publisher. Returns the fully-hydrated [`Book`] with its generated ID.
+    /// Inserts the book, links each contributor via the join table in the
+    /// given order, and associates the publisher. Returns the fully-hydrated
+    /// [`Book`] with its generated ID.
     ///
     /// # Arguments
     ///
     /// * `book` - The new book data.
-    /// * `author_ids` - Slice of `(author_id, author_name)` tuples to link.
+    /// * `authors` - Contributor links (author, role, sort name) to create.
     /// * `publisher_id` - Optional publisher to associate.
     ///
     /// # Returns
@@ -163,9 +466,83 @@ pub trait BookRepository: Send + Sync {
     async fn import_with_links(
         &self,
         book: NewBook,
-        author_ids: &[(i32, String)],
+        authors: &[NewBookAuthor],
         publisher_id: Option<i32>,
     ) -> Result<Book, DomainError>;
+
+    /// Applies metadata changes, author links and tags in a single
+    /// transaction.
+    ///
+    /// A change of the title or authors drops a generated placeholder cover
+    /// (with its palette and BlurHash), as it prints both.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The book's database ID.
+    /// * `changes` - Column changes plus optional replacement author links
+    ///   and tags.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::BookNotFound`] if no book has the given ID, or
+    /// [`DomainError::Database`] if any statement fails (nothing is applied).
+    async fn update_with_links(
+        &self,
+        id: i32,
+        changes: BookMetadataChanges,
+    ) -> Result<(), DomainError>;
+
+    /// Returns the tags of the given book in insertion order.
+    ///
+    /// # Arguments
+    ///
+    /// * `book_id` - The book's database ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on query failure.
+    async fn get_tags(&self, book_id: i32) -> Result<Vec<String>, DomainError>;
+
+    /// Returns the subjects of the given book in insertion order.
+    ///
+    /// # Arguments
+    ///
+    /// * `book_id` - The book's database ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on query failure.
+    async fn get_subjects(&self, book_id: i32) -> Result<Vec<String>, DomainError>;
+
+    /// Returns the typed identifiers of the given book in insertion order.
+    ///
+    /// # Arguments
+    ///
+    /// * `book_id` - The book's database ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on query failure.
+    async fn get_identifiers(&self, book_id: i32) -> Result<Vec<Identifier>, DomainError>;
+
+    /// Returns every file of the given book's work, preferred first.
+    ///
+    /// The preferred file is the work's `preferred_book_id`, else an EPUB,
+    /// else the earliest imported file.
+    ///
+    /// # Arguments
+    ///
+    /// * `book_id` - The book's database ID.
+    ///
+    /// # Returns
+    ///
+    /// The work's [`BookFormat`]s, or only the book's own format when it
+    /// belongs to no work.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on query failure.
+    async fn get_formats(&self, book_id: i32) -> Result<Vec<BookFormat>, DomainError>;
 }
 
 /// Persistence operations for authors.
@@ -186,7 +563,7 @@ pub trait AuthorRepository: Send + Sync {
     /// Returns [`DomainError::Database`] on query or insert failure.
     async fn find_or_create(&self, name: &str) -> Result<Author, DomainError>;
 
-    /// Returns all authors linked to the given book.
+    /// Returns the authors linked to the given book in the `aut` role.
     ///
     /// # Arguments
     ///
@@ -194,12 +571,121 @@ pub trait AuthorRepository: Send + Sync {
     ///
     /// # Returns
     ///
-    /// A vector of [`Author`] entities linked via `book_authors`.
+    /// A vector of [`Author`] entities linked via `book_authors`, in link
+    /// order.
     ///
     /// # Errors
     ///
     /// Returns [`DomainError::Database`] on query failure.
     async fn get_authors_by_book(&self, book_id: i32) -> Result<Vec<Author>, DomainError>;
+
+    /// Returns every contributor credited on the given book, in any role.
+    ///
+    /// # Arguments
+    ///
+    /// * `book_id` - The book's database ID.
+    ///
+    /// # Returns
+    ///
+    /// A vector of [`Contributor`]s with their role and sort name, in link
+    /// order.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on query failure.
+    async fn get_contributors_by_book(&self, book_id: i32)
+    -> Result<Vec<Contributor>, DomainError>;
+
+    /// Returns the author with the given ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The author's database ID.
+    ///
+    /// # Returns
+    ///
+    /// `Some(Author)` if found, `None` otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on query failure.
+    async fn find_by_id(&self, id: i32) -> Result<Option<Author>, DomainError>;
+
+    /// Returns every author with the number of books they are linked to,
+    /// ordered by name (case-insensitive).
+    ///
+    /// # Returns
+    ///
+    /// A vector of [`AuthorSummary`] entries, including authors with no books.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on query failure.
+    async fn list_with_counts(&self) -> Result<Vec<AuthorSummary>, DomainError>;
+
+    /// Renames an author, dropping the generated placeholder covers of their
+    /// books, which print the old name, and the `file-as` sort names of
+    /// their credits. The books' `author_sort` is derived again in the same
+    /// transaction.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The author's database ID.
+    /// * `name` - The new name.
+    ///
+    /// # Returns
+    ///
+    /// The renamed [`Author`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::NotFound`] if no author has the given ID,
+    /// [`DomainError::InvalidInput`] if another author already uses `name`
+    /// (merge them instead), or [`DomainError::Database`] on failure.
+    async fn rename(&self, id: i32, name: &str) -> Result<Author, DomainError>;
+
+    /// Merges authors into a target author.
+    ///
+    /// Re-points every `book_authors` link of the source authors to the
+    /// target, dropping links the target already has in the same role, then
+    /// deletes the source authors. The generated placeholder covers of the
+    /// source authors' books and the `file-as` sort names of their credits
+    /// are dropped, and the books' `author_sort` is derived again. Runs in a
+    /// single transaction.
+    ///
+    /// # Arguments
+    ///
+    /// * `source_ids` - Authors to merge away. The target's own ID is ignored.
+    /// * `target_id` - The author that remains.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::NotFound`] if the target does not exist, or
+    /// [`DomainError::Database`] on failure.
+    async fn merge(&self, source_ids: &[i32], target_id: i32) -> Result<(), DomainError>;
+
+    /// Returns the IDs of the books credited to any of the given authors,
+    /// in any role.
+    ///
+    /// # Arguments
+    ///
+    /// * `ids` - The authors' database IDs.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on query failure.
+    async fn find_book_ids(&self, ids: &[i32]) -> Result<Vec<i32>, DomainError>;
+
+    /// Deletes authors that no book links to.
+    ///
+    /// # Returns
+    ///
+    /// The number of deleted authors.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on failure.
+    async fn delete_orphans(&self) -> Result<usize, DomainError>;
 }
 
 /// Persistence operations for publishers.
@@ -234,22 +720,274 @@ pub trait PublisherRepository: Send + Sync {
     ///
     /// Returns [`DomainError::Database`] on query or insert failure.
     async fn find_or_create(&self, name: &str) -> Result<Publisher, DomainError>;
+
+    /// Returns every publisher with the number of books referencing it,
+    /// ordered by name (case-insensitive).
+    ///
+    /// # Returns
+    ///
+    /// A vector of [`PublisherSummary`] entries, including unused publishers.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on query failure.
+    async fn list_with_counts(&self) -> Result<Vec<PublisherSummary>, DomainError>;
+
+    /// Renames a publisher.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The publisher's database ID.
+    /// * `name` - The new name.
+    ///
+    /// # Returns
+    ///
+    /// The renamed [`Publisher`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::NotFound`] if no publisher has the given ID,
+    /// [`DomainError::InvalidInput`] if another publisher already uses
+    /// `name` (merge them instead), or [`DomainError::Database`] on failure.
+    async fn rename(&self, id: i32, name: &str) -> Result<Publisher, DomainError>;
+
+    /// Merges publishers into a target publisher.
+    ///
+    /// Re-points `books.publisher_id` from the source publishers to the
+    /// target, then deletes the source publishers. Runs in a single
+    /// transaction.
+    ///
+    /// # Arguments
+    ///
+    /// * `source_ids` - Publishers to merge away. The target's own ID is ignored.
+    /// * `target_id` - The publisher that remains.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::NotFound`] if the target does not exist, or
+    /// [`DomainError::Database`] on failure.
+    async fn merge(&self, source_ids: &[i32], target_id: i32) -> Result<(), DomainError>;
+
+    /// Returns the IDs of the books published by any of the given
+    /// publishers.
+    ///
+    /// # Arguments
+    ///
+    /// * `ids` - The publishers' database IDs.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on query failure.
+    async fn find_book_ids(&self, ids: &[i32]) -> Result<Vec<i32>, DomainError>;
+
+    /// Deletes publishers that no book references.
+    ///
+    /// # Returns
+    ///
+    /// The number of deleted publishers.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on failure.
+    async fn delete_orphans(&self) -> Result<usize, DomainError>;
+}
+
+/// Persistence operations for works, which group several book files
+/// (formats or editions) of the same book.
+#[async_trait]
+pub trait WorkRepository: Send + Sync {
+    /// Returns the work with the given ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The work's database ID.
+    ///
+    /// # Returns
+    ///
+    /// `Some(Work)` if found, `None` otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on query failure.
+    async fn find_by_id(&self, id: i32) -> Result<Option<Work>, DomainError>;
+
+    /// Links books into one work.
+    ///
+    /// When some of the books already belong to works, the first of those
+    /// works is kept and the books of the others are moved into it (the
+    /// emptied works are deleted). Otherwise a new work titled `title` is
+    /// created. Runs in a single transaction.
+    ///
+    /// # Arguments
+    ///
+    /// * `book_ids` - The books to link, in the order given by the user.
+    /// * `title` - Title for a newly created work.
+    ///
+    /// # Returns
+    ///
+    /// The work the books now belong to.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::BookNotFound`] if a book does not exist, or
+    /// [`DomainError::Database`] on failure (nothing is applied).
+    async fn link(&self, book_ids: &[i32], title: &str) -> Result<Work, DomainError>;
+
+    /// Removes a book from its work.
+    ///
+    /// A work left with a single book is dissolved. Books that belong to no
+    /// work are left as they are.
+    ///
+    /// # Arguments
+    ///
+    /// * `book_id` - The book's database ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::BookNotFound`] if the book does not exist, or
+    /// [`DomainError::Database`] on failure.
+    async fn unlink(&self, book_id: i32) -> Result<(), DomainError>;
+
+    /// Sets the file the reader opens for a work.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The work's database ID.
+    /// * `book_id` - A book of the work.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::NotFound`] if the work does not exist,
+    /// [`DomainError::InvalidInput`] if the book is not part of it, or
+    /// [`DomainError::Database`] on failure.
+    async fn set_preferred(&self, id: i32, book_id: i32) -> Result<(), DomainError>;
+}
+
+/// Persistence operations for the files that directory scans skip.
+#[async_trait]
+pub trait IgnoredFileRepository: Send + Sync {
+    /// Returns every ignored file, most recently ignored first.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on query failure.
+    async fn find_all(&self) -> Result<Vec<IgnoredFile>, DomainError>;
+
+    /// Adds a path to the ignored files. Ignoring a path twice keeps the
+    /// first entry.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_path` - The file's path as stored on its book.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on insert failure.
+    async fn add(&self, file_path: &str) -> Result<(), DomainError>;
+
+    /// Removes a path from the ignored files, so scans import it again.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_path` - The ignored path.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the path was ignored, `false` otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on delete failure.
+    async fn remove(&self, file_path: &str) -> Result<bool, DomainError>;
+}
+
+/// Persistence operations for the metadata edit history of books.
+#[async_trait]
+pub trait MetadataHistoryRepository: Send + Sync {
+    /// Records the field changes of one edit as a change set.
+    ///
+    /// # Arguments
+    ///
+    /// * `book_id` - The edited book's database ID.
+    /// * `source` - What made the edit.
+    /// * `changes` - The changed fields. Nothing is recorded when empty.
+    ///
+    /// # Returns
+    ///
+    /// The new change set's ID, or `None` when there was nothing to record.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on insert failure (nothing is
+    /// recorded).
+    async fn record(
+        &self,
+        book_id: i32,
+        source: ChangeSource,
+        changes: &[FieldChange],
+    ) -> Result<Option<i32>, DomainError>;
+
+    /// Returns a book's change sets with their changes, newest first.
+    ///
+    /// # Arguments
+    ///
+    /// * `book_id` - The book's database ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on query failure.
+    async fn find_by_book(&self, book_id: i32) -> Result<Vec<MetadataChangeSet>, DomainError>;
+
+    /// Returns a change set with its changes.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The change set's database ID.
+    ///
+    /// # Returns
+    ///
+    /// `Some(MetadataChangeSet)` if found, `None` otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on query failure.
+    async fn find_set(&self, id: i32) -> Result<Option<MetadataChangeSet>, DomainError>;
+
+    /// Returns the change set a field change belongs to.
+    ///
+    /// # Arguments
+    ///
+    /// * `change_id` - The field change's database ID.
+    ///
+    /// # Returns
+    ///
+    /// `Some(MetadataChangeSet)` with all of its changes if found, `None`
+    /// otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on query failure.
+    async fn find_set_of_change(
+        &self,
+        change_id: i32,
+    ) -> Result<Option<MetadataChangeSet>, DomainError>;
 }
 
 /// Persistence operations for the book-author many-to-many join table.
 #[async_trait]
 pub trait BookAuthorRepository: Send + Sync {
-    /// Creates a link between a book and an author.
+    /// Creates a link between a book and an author in the given role.
     ///
     /// # Arguments
     ///
     /// * `book_id` - The book's database ID.
-    /// * `author_id` - The author's database ID.
+    /// * `author` - The author, role and sort name to link.
     ///
     /// # Errors
     ///
-    /// Returns [`DomainError::Database`] on insert failure.
-    async fn link(&self, book_id: i32, author_id: i32) -> Result<(), DomainError>;
+    /// Returns [`DomainError::Database`] on insert failure, including when the
+    /// author is already linked in the same role.
+    async fn link(&self, book_id: i32, author: &NewBookAuthor) -> Result<(), DomainError>;
 }
 
 /// Persistence operations for bookmarks.
@@ -298,7 +1036,7 @@ pub struct NewBookmark {
     pub book_id: i32,
     pub chapter_title: Option<String>,
     pub page_number: Option<i32>,
-    pub position: String,
+    pub position: Locator,
 }
 
 /// Persistence operations for annotations (highlights with optional notes).
@@ -340,19 +1078,53 @@ pub trait AnnotationRepository: Send + Sync {
     ///
     /// Returns [`DomainError::Database`] on query failure.
     async fn delete(&self, id: i32) -> Result<(), DomainError>;
+
+    /// Moves an annotation to where re-anchoring found its text, clearing
+    /// its review flag.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The annotation's database ID.
+    /// * `anchor` - The new positions and text, with the match's score.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on query failure.
+    async fn update_anchor(&self, id: i32, anchor: AnnotationAnchor) -> Result<(), DomainError>;
+
+    /// Flags an annotation whose text re-anchoring could not find again
+    /// confidently, leaving its positions as they are.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The annotation's database ID.
+    /// * `confidence` - Score of the best match, if anything matched.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on query failure.
+    async fn flag_for_review(&self, id: i32, confidence: Option<f32>) -> Result<(), DomainError>;
 }
 
 /// Input data for creating a new annotation.
 pub struct NewAnnotation {
     pub book_id: i32,
     pub chapter_title: Option<String>,
-    pub start_position: String,
-    pub end_position: String,
+    pub start_position: Locator,
+    pub end_position: Locator,
     pub highlighted_text: Option<String>,
     pub note: Option<String>,
     pub color: Option<String>,
 }
 
+/// New location of an annotation, found by re-anchoring its text.
+pub struct AnnotationAnchor {
+    pub start_position: Locator,
+    pub end_position: Locator,
+    pub highlighted_text: Option<String>,
+    pub confidence: f32,
+}
+
 /// Persistence operations for reading progress.
 #[async_trait]
 pub trait ReadingProgressRepository: Send + Sync {
@@ -391,7 +1163,7 @@ pub trait ReadingProgressRepository: Send + Sync {
 /// Input data for creating or updating reading progress.
 pub struct NewReadingProgress {
     pub book_id: i32,
-    pub current_position: String,
+    pub current_position: Locator,
     pub chapter_title: Option<String>,
     pub page_number: Option<i32>,
     pub progress_percentage: Option<f32>,
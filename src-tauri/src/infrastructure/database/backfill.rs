@@ -0,0 +1,196 @@
+//! Fills columns added by migrations for the rows that already exist.
+//!
+//! Values derived by domain code (ISBNs, parsed dates, sort keys) are computed here
+//! with that same code rather than re-implemented in SQL, so migrated rows
+//! match newly imported ones.
+//!
+//! Each backfill fills its rows and records its migration in
+//! `completed_backfills` in one transaction, and runs at every start-up
+//! until that row exists. A backfill interrupted by a crash or a failing row
+//! therefore leaves nothing half converted and is retried. Backfills only
+//! fill values that are still missing or not yet normalized, so running one
+//! over rows the application already wrote changes nothing.
+
+use std::collections::HashMap;
+
+use diesel::SqliteConnection;
+use diesel::prelude::*;
+
+use crate::domain::models::contributor::Contributor;
+use crate::domain::models::isbn::Isbn;
+use crate::domain::models::partial_date::PartialDate;
+use crate::domain::models::sort_key;
+use crate::infrastructure::database::models::schema::{
+    authors, book_authors, book_identifiers, books, completed_backfills,
+};
+
+/// Version of the migration normalizing `books.isbn`.
+const ISBN_MIGRATION: &str = "0006";
+/// Version of the migration adding `books.published_date_normalized`.
+const PUBLISHED_DATE_MIGRATION: &str = "0007";
+/// Version of the migration adding `books.title_sort` and `books.author_sort`.
+const SORT_KEYS_MIGRATION: &str = "0008";
+
+/// A book's ID, title, language, title sort key and author sort key.
+type SortKeyRow = (
+    Option<i32>,
+    String,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+);
+
+/// Runs the backfills that have not completed yet.
+///
+/// Called after the migrations are applied, so every backfilled column
+/// exists.
+///
+/// # Arguments
+///
+/// * `conn` - Connection to the migrated database.
+///
+/// # Errors
+///
+/// Returns the Diesel error of the first failing query. The failing
+/// backfill is rolled back and runs again on the next call.
+pub fn run_backfills(conn: &mut SqliteConnection) -> QueryResult<()> {
+    run_once(conn, ISBN_MIGRATION, backfill_isbns)?;
+    run_once(conn, PUBLISHED_DATE_MIGRATION, backfill_published_dates)?;
+    run_once(conn, SORT_KEYS_MIGRATION, backfill_sort_keys)?;
+    Ok(())
+}
+
+/// Runs `backfill` and records `migration` as completed in one transaction,
+/// unless it is recorded already.
+fn run_once(
+    conn: &mut SqliteConnection,
+    migration: &str,
+    backfill: fn(&mut SqliteConnection) -> QueryResult<()>,
+) -> QueryResult<()> {
+    conn.transaction(|conn| {
+        let done = completed_backfills::table
+            .find(migration)
+            .count()
+            .get_result::<i64>(conn)?;
+        if done > 0 {
+            return Ok(());
+        }
+        backfill(conn)?;
+        diesel::insert_into(completed_backfills::table)
+            .values(completed_backfills::migration.eq(migration))
+            .execute(conn)?;
+        Ok(())
+    })
+}
+
+/// Replaces every stored ISBN with its [`Isbn::parse`] ISBN-13 form, as an
+/// import stores it. A non-blank value `Isbn::parse` rejects is kept as an
+/// identifier with scheme `other`, and a rejected value is cleared from
+/// `isbn`.
+fn backfill_isbns(conn: &mut SqliteConnection) -> QueryResult<()> {
+    let rows: Vec<(Option<i32>, Option<String>)> = books::table
+        .filter(books::isbn.is_not_null())
+        .select((books::book_id, books::isbn))
+        .load(conn)?;
+
+    for (id, raw) in rows {
+        let (Some(id), Some(raw)) = (id, raw) else {
+            continue;
+        };
+        let normalized = match Isbn::parse(&raw) {
+            Ok(isbn) => Some(isbn.to_isbn13()),
+            Err(_) if raw.trim().is_empty() => None,
+            Err(_) => {
+                diesel::insert_or_ignore_into(book_identifiers::table)
+                    .values((
+                        book_identifiers::book_id.eq(id),
+                        book_identifiers::scheme.eq("other"),
+                        book_identifiers::value.eq(raw.trim()),
+                    ))
+                    .execute(conn)?;
+                None
+            }
+        };
+        if normalized.as_deref() != Some(raw.as_str()) {
+            diesel::update(books::table.filter(books::book_id.eq(id)))
+                .set(books::isbn.eq(normalized))
+                .execute(conn)?;
+        }
+    }
+    Ok(())
+}
+
+/// Sets `published_date_normalized` of every book with a `published_date`
+/// but no normalized form to its [`PartialDate::parse`] form.
+fn backfill_published_dates(conn: &mut SqliteConnection) -> QueryResult<()> {
+    let rows: Vec<(Option<i32>, Option<String>)> = books::table
+        .filter(books::published_date.is_not_null())
+        .filter(books::published_date_normalized.is_null())
+        .select((books::book_id, books::published_date))
+        .load(conn)?;
+
+    for (id, date) in rows {
+        let normalized = date
+            .as_deref()
+            .and_then(PartialDate::parse)
+            .map(|d| d.to_string());
+        diesel::update(books::table.filter(books::book_id.eq(id)))
+            .set(books::published_date_normalized.eq(normalized))
+            .execute(conn)?;
+    }
+    Ok(())
+}
+
+/// Sets a missing `title_sort` or `author_sort` as an import would:
+/// [`sort_key::title_sort`] of the title in the book's language and
+/// [`sort_key::authors_sort`] of its credits in link order. Keys already set,
+/// including ones the user edited, are kept.
+fn backfill_sort_keys(conn: &mut SqliteConnection) -> QueryResult<()> {
+    let rows: Vec<SortKeyRow> = books::table
+        .filter(books::title_sort.is_null().or(books::author_sort.is_null()))
+        .select((
+            books::book_id,
+            books::title,
+            books::language,
+            books::title_sort,
+            books::author_sort,
+        ))
+        .load(conn)?;
+    let credits: Vec<(i32, String, String, Option<String>)> = book_authors::table
+        .inner_join(authors::table)
+        .select((
+            book_authors::book_id,
+            authors::name,
+            book_authors::role,
+            book_authors::sort_name,
+        ))
+        .order(diesel::dsl::sql::<diesel::sql_types::Integer>(
+            "book_authors.rowid",
+        ))
+        .load(conn)?;
+
+    let mut contributors: HashMap<i32, Vec<Contributor>> = HashMap::new();
+    for (book_id, name, role, sort_name) in credits {
+        contributors.entry(book_id).or_default().push(Contributor {
+            name,
+            role,
+            sort_name,
+        });
+    }
+
+    for (id, title, language, title_sort, author_sort) in rows {
+        let title_sort =
+            title_sort.unwrap_or_else(|| sort_key::title_sort(&title, language.as_deref()));
+        let author_sort = author_sort.or_else(|| {
+            id.and_then(|id| contributors.get(&id))
+                .and_then(|credits| sort_key::authors_sort(credits))
+        });
+        diesel::update(books::table.filter(books::book_id.eq(id)))
+            .set((
+                books::title_sort.eq(title_sort),
+                books::author_sort.eq(author_sort),
+            ))
+            .execute(conn)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,2 @@
+ALTER TABLE books DROP COLUMN blurhash;
+ALTER TABLE books DROP COLUMN dominant_colors;
@@ -0,0 +1,2 @@
+ALTER TABLE books ADD COLUMN dominant_colors TEXT;
+ALTER TABLE books ADD COLUMN blurhash TEXT;
@@ -0,0 +1,15 @@
+CREATE TABLE book_authors_new (
+    book_id   INTEGER NOT NULL,
+    author_id INTEGER NOT NULL,
+    role      TEXT NOT NULL DEFAULT 'aut',
+    sort_name TEXT,
+    PRIMARY KEY (book_id, author_id, role),
+    FOREIGN KEY (book_id)   REFERENCES books(book_id)      ON DELETE CASCADE,
+    FOREIGN KEY (author_id) REFERENCES authors(author_id) ON DELETE CASCADE
+);
+
+INSERT INTO book_authors_new (book_id, author_id)
+SELECT book_id, author_id FROM book_authors ORDER BY rowid;
+
+DROP TABLE book_authors;
+ALTER TABLE book_authors_new RENAME TO book_authors;
@@ -0,0 +1,7 @@
+DROP INDEX IF EXISTS idx_book_tags_tag;
+DROP TABLE IF EXISTS book_tags;
+
+ALTER TABLE books DROP COLUMN series_index;
+ALTER TABLE books DROP COLUMN series;
+ALTER TABLE books DROP COLUMN description;
+ALTER TABLE books DROP COLUMN language;
@@ -0,0 +1,13 @@
+ALTER TABLE books ADD COLUMN language TEXT;
+ALTER TABLE books ADD COLUMN description TEXT;
+ALTER TABLE books ADD COLUMN series TEXT;
+ALTER TABLE books ADD COLUMN series_index REAL;
+
+CREATE TABLE book_tags (
+    book_id INTEGER NOT NULL,
+    tag     TEXT NOT NULL,
+    PRIMARY KEY (book_id, tag),
+    FOREIGN KEY (book_id) REFERENCES books(book_id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_book_tags_tag ON book_tags(tag);
@@ -0,0 +1,9 @@
+DROP INDEX IF EXISTS idx_book_identifiers_value;
+DROP TABLE IF EXISTS book_identifiers;
+DROP INDEX IF EXISTS idx_book_subjects_subject;
+DROP TABLE IF EXISTS book_subjects;
+DROP INDEX IF EXISTS idx_books_language;
+
+ALTER TABLE books DROP COLUMN epub_version;
+ALTER TABLE books DROP COLUMN modified_date;
+ALTER TABLE books DROP COLUMN rights;
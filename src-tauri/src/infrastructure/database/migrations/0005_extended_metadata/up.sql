@@ -0,0 +1,24 @@
+ALTER TABLE books ADD COLUMN rights TEXT;
+ALTER TABLE books ADD COLUMN modified_date TEXT;
+ALTER TABLE books ADD COLUMN epub_version TEXT;
+
+CREATE INDEX idx_books_language ON books(language);
+
+CREATE TABLE book_subjects (
+    book_id INTEGER NOT NULL,
+    subject TEXT NOT NULL,
+    PRIMARY KEY (book_id, subject),
+    FOREIGN KEY (book_id) REFERENCES books(book_id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_book_subjects_subject ON book_subjects(subject);
+
+CREATE TABLE book_identifiers (
+    book_id INTEGER NOT NULL,
+    scheme  TEXT NOT NULL,
+    value   TEXT NOT NULL,
+    PRIMARY KEY (book_id, scheme, value),
+    FOREIGN KEY (book_id) REFERENCES books(book_id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_book_identifiers_value ON book_identifiers(scheme, value);
@@ -0,0 +1,3 @@
+-- Normalized ISBNs cannot be restored to their original spelling; only the
+-- index is removed.
+DROP INDEX IF EXISTS idx_books_isbn;
@@ -0,0 +1,5 @@
+-- Existing ISBNs are normalized in Rust with Isbn::parse after the
+-- migrations run (infrastructure::database::backfill), so they get exactly
+-- the values new imports do. Values it rejects move to book_identifiers.
+
+CREATE INDEX idx_books_isbn ON books(isbn);
@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS idx_books_published_date_normalized;
+
+ALTER TABLE books DROP COLUMN published_date_normalized;
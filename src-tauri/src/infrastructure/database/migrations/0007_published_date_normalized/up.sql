@@ -0,0 +1,7 @@
+ALTER TABLE books ADD COLUMN published_date_normalized TEXT;
+
+-- Existing rows are filled in Rust with PartialDate::parse after the
+-- migrations run (infrastructure::database::backfill), so they get exactly
+-- the values new imports do.
+
+CREATE INDEX idx_books_published_date_normalized ON books(published_date_normalized);
@@ -0,0 +1,3 @@
+ALTER TABLE books DROP COLUMN author_sort;
+
+ALTER TABLE books DROP COLUMN title_sort;
@@ -0,0 +1,6 @@
+ALTER TABLE books ADD COLUMN title_sort TEXT;
+ALTER TABLE books ADD COLUMN author_sort TEXT;
+
+-- Existing rows are filled in Rust with sort_key::title_sort and
+-- sort_key::authors_sort after the migrations run
+-- (infrastructure::database::backfill), so they sort like new imports.
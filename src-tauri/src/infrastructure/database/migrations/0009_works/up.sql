@@ -0,0 +1,14 @@
+CREATE TABLE works (
+    work_id           INTEGER PRIMARY KEY AUTOINCREMENT,
+    title             TEXT NOT NULL,
+    preferred_book_id INTEGER,
+    created_at        TEXT DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (preferred_book_id) REFERENCES books(book_id) ON DELETE SET NULL
+);
+
+-- Books of the same work (other formats or editions) share a work_id.
+-- Kept without a foreign key so the column can be dropped again; the
+-- repository dissolves works that are left with fewer than two books.
+ALTER TABLE books ADD COLUMN work_id INTEGER;
+
+CREATE INDEX idx_books_work_id ON books(work_id);
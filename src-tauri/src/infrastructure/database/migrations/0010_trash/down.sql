@@ -0,0 +1,14 @@
+-- Trashed books would reappear in the library, so they are purged first,
+-- dissolving works left with a single book.
+DELETE FROM books WHERE deleted_at IS NOT NULL;
+
+UPDATE books SET work_id = NULL
+WHERE work_id IN (SELECT work_id FROM books WHERE work_id IS NOT NULL
+                  GROUP BY work_id HAVING COUNT(*) < 2);
+
+DELETE FROM works
+WHERE work_id NOT IN (SELECT work_id FROM books WHERE work_id IS NOT NULL);
+
+DROP INDEX IF EXISTS idx_books_deleted_at;
+
+ALTER TABLE books DROP COLUMN deleted_at;
@@ -0,0 +1,5 @@
+-- Removed books stay in the trash, with their bookmarks, annotations and
+-- progress, until they are restored or purged. NULL for books in the library.
+ALTER TABLE books ADD COLUMN deleted_at TEXT;
+
+CREATE INDEX idx_books_deleted_at ON books(deleted_at);
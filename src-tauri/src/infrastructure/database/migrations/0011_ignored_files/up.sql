@@ -0,0 +1,7 @@
+-- Files the user removed from the library; directory scans skip them so
+-- they are not imported again.
+CREATE TABLE ignored_files (
+    ignored_file_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    file_path       TEXT NOT NULL UNIQUE,
+    ignored_at      TEXT DEFAULT CURRENT_TIMESTAMP
+);
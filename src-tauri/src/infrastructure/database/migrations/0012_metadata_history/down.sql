@@ -0,0 +1,3 @@
+DROP TABLE metadata_changes;
+
+DROP TABLE metadata_change_sets;
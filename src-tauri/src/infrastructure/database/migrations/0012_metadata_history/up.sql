@@ -0,0 +1,23 @@
+-- One row per edit of a book's metadata, with the source that made it.
+CREATE TABLE metadata_change_sets (
+    change_set_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    book_id       INTEGER NOT NULL,
+    source        TEXT NOT NULL,
+    changed_at    TEXT DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (book_id) REFERENCES books(book_id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_metadata_change_sets_book_id ON metadata_change_sets(book_id);
+
+-- The fields an edit changed. List fields (authors, tags) hold their
+-- entries separated by newlines.
+CREATE TABLE metadata_changes (
+    change_id     INTEGER PRIMARY KEY AUTOINCREMENT,
+    change_set_id INTEGER NOT NULL,
+    field         TEXT NOT NULL,
+    old_value     TEXT,
+    new_value     TEXT,
+    FOREIGN KEY (change_set_id) REFERENCES metadata_change_sets(change_set_id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_metadata_changes_change_set_id ON metadata_changes(change_set_id);
@@ -0,0 +1,24 @@
+-- EPUB locators have no older form and are left as JSON.
+UPDATE bookmarks
+SET position = CASE json_extract(position, '$.type')
+    WHEN 'legacy' THEN json_extract(position, '$.value')
+    WHEN 'pdf' THEN CAST(json_extract(position, '$.page_index') + 1 AS TEXT)
+    ELSE position
+END
+WHERE json_valid(position);
+
+UPDATE annotations
+SET start_position = json_extract(start_position, '$.value')
+WHERE json_valid(start_position) AND json_extract(start_position, '$.type') = 'legacy';
+
+UPDATE annotations
+SET end_position = json_extract(end_position, '$.value')
+WHERE json_valid(end_position) AND json_extract(end_position, '$.type') = 'legacy';
+
+UPDATE reading_progress
+SET current_position = CASE json_extract(current_position, '$.type')
+    WHEN 'legacy' THEN json_extract(current_position, '$.value')
+    WHEN 'pdf' THEN CAST(json_extract(current_position, '$.page_index') + 1 AS TEXT)
+    ELSE current_position
+END
+WHERE json_valid(current_position);
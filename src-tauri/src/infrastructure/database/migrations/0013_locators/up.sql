@@ -0,0 +1,28 @@
+-- Positions are now JSON locators (see domain::models::locator). Page numbers
+-- stored by the PDF reader (1-based) become page indexes; any other value that
+-- is not already a locator is kept verbatim as a legacy position.
+UPDATE bookmarks
+SET position = json_object('type', 'pdf', 'page_index', CAST(position AS INTEGER) - 1)
+WHERE book_id IN (SELECT book_id FROM books WHERE file_type = 'pdf')
+  AND position GLOB '[1-9]*' AND NOT position GLOB '*[^0-9]*';
+
+UPDATE reading_progress
+SET current_position = json_object('type', 'pdf', 'page_index', CAST(current_position AS INTEGER) - 1)
+WHERE book_id IN (SELECT book_id FROM books WHERE file_type = 'pdf')
+  AND current_position GLOB '[1-9]*' AND NOT current_position GLOB '*[^0-9]*';
+
+UPDATE bookmarks
+SET position = json_object('type', 'legacy', 'value', position)
+WHERE position NOT LIKE '{%';
+
+UPDATE annotations
+SET start_position = json_object('type', 'legacy', 'value', start_position)
+WHERE start_position NOT LIKE '{%';
+
+UPDATE annotations
+SET end_position = json_object('type', 'legacy', 'value', end_position)
+WHERE end_position NOT LIKE '{%';
+
+UPDATE reading_progress
+SET current_position = json_object('type', 'legacy', 'value', current_position)
+WHERE current_position NOT LIKE '{%';
@@ -0,0 +1,2 @@
+ALTER TABLE annotations DROP COLUMN anchor_confidence;
+ALTER TABLE annotations DROP COLUMN needs_review;
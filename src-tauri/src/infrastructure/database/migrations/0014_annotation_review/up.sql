@@ -0,0 +1,5 @@
+-- Re-anchoring after a book's file changes flags the annotations whose
+-- highlighted text it could not find again confidently, and records the
+-- score of its best match.
+ALTER TABLE annotations ADD COLUMN needs_review BOOLEAN NOT NULL DEFAULT 0;
+ALTER TABLE annotations ADD COLUMN anchor_confidence REAL;
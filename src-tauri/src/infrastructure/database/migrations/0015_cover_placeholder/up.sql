@@ -0,0 +1,3 @@
+-- Generated placeholder covers are cached like real cover art; the flag keeps
+-- them from being written into the book's file.
+ALTER TABLE books ADD COLUMN cover_is_placeholder BOOLEAN NOT NULL DEFAULT 0;
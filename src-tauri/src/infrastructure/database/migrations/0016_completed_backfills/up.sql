@@ -0,0 +1,7 @@
+-- Each Rust backfill (infrastructure/database/backfill.rs) records its
+-- migration here in the transaction that fills the rows, so one interrupted
+-- by a crash or error runs again on the next start-up.
+CREATE TABLE completed_backfills (
+    migration    TEXT PRIMARY KEY NOT NULL,
+    completed_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
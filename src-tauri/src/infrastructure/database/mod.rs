@@ -1,5 +1,6 @@
 //! SQLite database pool, Diesel ORM models, and schema.
 
+pub mod backfill;
 #[allow(clippy::module_inception)]
 pub mod database;
 pub mod models;
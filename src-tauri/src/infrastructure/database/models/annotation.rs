@@ -1,3 +1,4 @@
+use crate::domain::models::locator::Locator;
 use crate::infrastructure::database::models::schema::annotations;
 use diesel::prelude::*;
 
@@ -17,6 +18,8 @@ pub struct AnnotationRow {
     pub color: Option<String>,
     pub created_at: Option<String>,
     pub updated_at: Option<String>,
+    pub needs_review: bool,
+    pub anchor_confidence: Option<f32>,
 }
 
 /// Insertable row for creating a new annotation record.
@@ -39,13 +42,15 @@ impl From<AnnotationRow> for crate::domain::models::annotation::Annotation {
             id: row.annotation_id.unwrap_or(0),
             book_id: row.book_id,
             chapter_title: row.chapter_title,
-            start_position: row.start_position,
-            end_position: row.end_position,
+            start_position: Locator::from_stored(&row.start_position),
+            end_position: Locator::from_stored(&row.end_position),
             highlighted_text: row.highlighted_text,
             note: row.note,
             color: row.color,
             created_at: row.created_at,
             updated_at: row.updated_at,
+            needs_review: row.needs_review,
+            anchor_confidence: row.anchor_confidence,
         }
     }
 }
@@ -27,3 +27,27 @@ impl From<AuthorRow> for crate::domain::models::author::Author {
         }
     }
 }
+
+/// Row returned by the author listing query: an author plus the number of
+/// distinct books linked to them.
+#[derive(QueryableByName, Debug)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct AuthorCountRow {
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub author_id: i32,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub name: String,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub book_count: i64,
+}
+
+/// Converts an `AuthorCountRow` into an [`AuthorSummary`](crate::domain::dto::author_dto::AuthorSummary).
+impl From<AuthorCountRow> for crate::domain::dto::author_dto::AuthorSummary {
+    fn from(row: AuthorCountRow) -> Self {
+        crate::domain::dto::author_dto::AuthorSummary {
+            id: row.author_id,
+            name: row.name,
+            book_count: row.book_count,
+        }
+    }
+}
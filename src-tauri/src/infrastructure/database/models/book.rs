@@ -1,3 +1,4 @@
+use crate::domain::models::partial_date::PartialDate;
 use crate::infrastructure::database::models::schema::books;
 use diesel::prelude::*;
 
@@ -17,6 +18,27 @@ pub struct BookRow {
     pub cover_image_path: Option<String>,
     pub checksum: Option<String>,
     pub added_at: Option<String>,
+    pub dominant_colors: Option<String>,
+    pub blurhash: Option<String>,
+    pub language: Option<String>,
+    pub description: Option<String>,
+    pub series: Option<String>,
+    pub series_index: Option<f32>,
+    pub rights: Option<String>,
+    pub modified_date: Option<String>,
+    pub epub_version: Option<String>,
+    /// `published_date` as `YYYY[-MM[-DD]]`, for sorting and filtering.
+    pub published_date_normalized: Option<String>,
+    /// Title without a leading article, for sorting.
+    pub title_sort: Option<String>,
+    /// Authors as `Surname, Given Names`, joined with `&`, for sorting.
+    pub author_sort: Option<String>,
+    /// The work this file is a format or edition of, if linked.
+    pub work_id: Option<i32>,
+    /// When the book was moved to the trash; `NULL` while in the library.
+    pub deleted_at: Option<String>,
+    /// Whether `cover_image_path` is a generated placeholder.
+    pub cover_is_placeholder: bool,
 }
 
 /// Insertable row for creating a new book record.
@@ -31,6 +53,18 @@ pub struct NewBookRow<'a> {
     pub file_path: &'a str,
     pub cover_image_path: Option<&'a str>,
     pub checksum: Option<&'a str>,
+    pub dominant_colors: Option<String>,
+    pub blurhash: Option<&'a str>,
+    pub language: Option<&'a str>,
+    pub description: Option<&'a str>,
+    pub rights: Option<&'a str>,
+    pub modified_date: Option<&'a str>,
+    pub epub_version: Option<&'a str>,
+    pub published_date_normalized: Option<String>,
+    pub series: Option<&'a str>,
+    pub series_index: Option<f32>,
+    pub title_sort: Option<&'a str>,
+    pub author_sort: Option<&'a str>,
 }
 
 /// Partial update row for modifying an existing book.
@@ -45,6 +79,279 @@ pub struct UpdateBookRow<'a> {
     pub file_path: Option<&'a str>,
     pub cover_image_path: Option<&'a str>,
     pub checksum: Option<&'a str>,
+    pub dominant_colors: Option<String>,
+    pub blurhash: Option<&'a str>,
+    pub published_date_normalized: Option<Option<String>>,
+    pub cover_is_placeholder: Option<bool>,
+}
+
+/// Changeset for user metadata edits.
+///
+/// Unlike [`UpdateBookRow`], nullable columns use `Option<Option<_>>` so an
+/// edit can clear them: `None` skips the column, `Some(None)` sets `NULL`.
+#[derive(AsChangeset, PartialEq, Debug)]
+#[diesel(table_name = books)]
+pub struct UpdateBookMetadataRow<'a> {
+    pub title: Option<&'a str>,
+    pub published_date: Option<Option<&'a str>>,
+    pub publisher_id: Option<Option<i32>>,
+    pub isbn: Option<Option<&'a str>>,
+    pub language: Option<Option<&'a str>>,
+    pub description: Option<Option<&'a str>>,
+    pub series: Option<Option<&'a str>>,
+    pub series_index: Option<Option<f32>>,
+    pub published_date_normalized: Option<Option<String>>,
+    pub title_sort: Option<Option<&'a str>>,
+    pub author_sort: Option<Option<&'a str>>,
+}
+
+impl UpdateBookMetadataRow<'_> {
+    /// Returns `true` if no column would be changed. Diesel rejects empty
+    /// changesets, so callers skip the `UPDATE` in that case.
+    pub fn is_empty(&self) -> bool {
+        self.title.is_none()
+            && self.published_date.is_none()
+            && self.publisher_id.is_none()
+            && self.isbn.is_none()
+            && self.language.is_none()
+            && self.description.is_none()
+            && self.series.is_none()
+            && self.series_index.is_none()
+            && self.published_date_normalized.is_none()
+            && self.title_sort.is_none()
+            && self.author_sort.is_none()
+    }
+}
+
+/// Row returned by the joined book listing query.
+///
+/// Carries the `books` columns plus the aggregated contributors, publisher
+/// name, last-read timestamp, the active sort key and the total match count.
+#[derive(QueryableByName, Debug)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct BookListingRow {
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub book_id: i32,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub title: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub published_date: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Integer>)]
+    pub publisher_id: Option<i32>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub isbn: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub file_type: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub file_path: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub cover_image_path: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Bool)]
+    pub cover_is_placeholder: bool,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub checksum: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub added_at: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub dominant_colors: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub blurhash: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub language: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub description: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub series: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Float>)]
+    pub series_index: Option<f32>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub rights: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub modified_date: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub epub_version: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub published_date_normalized: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub title_sort: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub author_sort: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Integer>)]
+    pub work_id: Option<i32>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub deleted_at: Option<String>,
+    /// The book and the other files of its work outside the trash, preferred
+    /// first, separated by `\u{1f}`; each entry is `book_id` and `file_type`
+    /// separated by `\u{1e}`.
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub format_data: Option<String>,
+    /// Tags in insertion order, separated by `\u{1f}`.
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub tag_data: Option<String>,
+    /// Subjects in insertion order, separated by `\u{1f}`.
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub subject_data: Option<String>,
+    /// Identifiers in insertion order, separated by `\u{1f}`; each entry is
+    /// `scheme` and `value` separated by `\u{1e}`.
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub identifier_data: Option<String>,
+    /// Linked contributors in link order, separated by `\u{1f}`; each entry is
+    /// `name`, `role` and `sort_name` (empty when unset) separated by `\u{1e}`.
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub contributor_data: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub publisher_name: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub sort_key: String,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub total_count: i64,
+}
+
+impl BookListingRow {
+    /// Splits the aggregated `contributor_data` column into contributors.
+    pub fn contributors(&self) -> Vec<crate::domain::models::contributor::Contributor> {
+        self.contributor_data
+            .as_deref()
+            .map(|data| {
+                data.split('\u{1f}')
+                    .map(|entry| {
+                        let mut parts = entry.split('\u{1e}');
+                        let name = parts.next().unwrap_or_default().to_string();
+                        let role = parts.next().unwrap_or_default().to_string();
+                        let sort_name = parts.next().filter(|s| !s.is_empty()).map(String::from);
+                        crate::domain::models::contributor::Contributor {
+                            name,
+                            role,
+                            sort_name,
+                        }
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Splits the aggregated `tag_data` column into tags.
+    pub fn tags(&self) -> Vec<String> {
+        self.tag_data
+            .as_deref()
+            .map(|data| data.split('\u{1f}').map(String::from).collect())
+            .unwrap_or_default()
+    }
+
+    /// Splits the aggregated `subject_data` column into subjects.
+    pub fn subjects(&self) -> Vec<String> {
+        self.subject_data
+            .as_deref()
+            .map(|data| data.split('\u{1f}').map(String::from).collect())
+            .unwrap_or_default()
+    }
+
+    /// Splits the aggregated `identifier_data` column into identifiers.
+    pub fn identifiers(&self) -> Vec<crate::domain::models::identifier::Identifier> {
+        self.identifier_data
+            .as_deref()
+            .map(|data| {
+                data.split('\u{1f}')
+                    .filter_map(|entry| entry.split_once('\u{1e}'))
+                    .map(|(scheme, value)| {
+                        crate::domain::models::identifier::Identifier::new(scheme, value)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Splits the aggregated `format_data` column into the formats of the
+    /// book's work, or returns the book's own format when it has no work.
+    pub fn formats(&self) -> Vec<crate::domain::dto::book_dto::BookFormat> {
+        match self.format_data.as_deref() {
+            Some(data) => data
+                .split('\u{1f}')
+                .filter_map(|entry| entry.split_once('\u{1e}'))
+                .filter_map(|(id, file_type)| {
+                    Some(crate::domain::dto::book_dto::BookFormat {
+                        book_id: id.parse().ok()?,
+                        file_type: file_type.to_string(),
+                    })
+                })
+                .collect(),
+            None => vec![crate::domain::dto::book_dto::BookFormat {
+                book_id: self.book_id,
+                file_type: self.file_type.clone(),
+            }],
+        }
+    }
+
+    /// Converts the `books` columns of this row into a domain
+    /// [`Book`](crate::domain::models::book::Book).
+    pub fn to_book(&self) -> crate::domain::models::book::Book {
+        crate::domain::models::book::Book {
+            id: self.book_id,
+            title: self.title.clone(),
+            published_date: self.published_date.clone(),
+            publisher_id: self.publisher_id,
+            isbn: self.isbn.clone(),
+            file_type: Some(self.file_type.clone()),
+            file_path: Some(self.file_path.clone()),
+            cover_image_path: self.cover_image_path.clone(),
+            cover_is_placeholder: self.cover_is_placeholder,
+            checksum: self.checksum.clone(),
+            added_at: self.added_at.clone(),
+            dominant_colors: split_colors(self.dominant_colors.as_deref()),
+            blurhash: self.blurhash.clone(),
+            language: self.language.clone(),
+            description: self.description.clone(),
+            series: self.series.clone(),
+            series_index: self.series_index,
+            rights: self.rights.clone(),
+            modified_date: self.modified_date.clone(),
+            epub_version: self.epub_version.clone(),
+            published: self
+                .published_date_normalized
+                .as_deref()
+                .and_then(PartialDate::parse),
+            title_sort: self.title_sort.clone(),
+            author_sort: self.author_sort.clone(),
+            work_id: self.work_id,
+            deleted_at: self.deleted_at.clone(),
+        }
+    }
+
+    /// Converts this row into a [`BookDto`](crate::domain::dto::book_dto::BookDto)
+    /// with its aggregated columns resolved.
+    pub fn to_dto(&self) -> crate::domain::dto::book_dto::BookDto {
+        crate::domain::dto::book_dto::BookDto::new(
+            &self.to_book(),
+            self.contributors(),
+            self.publisher_name.clone(),
+            self.tags(),
+            self.subjects(),
+            self.identifiers(),
+            self.formats(),
+        )
+    }
+}
+
+/// Joins a color palette into the comma-separated form stored in
+/// `books.dominant_colors`. Returns `None` for an empty palette.
+pub fn join_colors(colors: &[String]) -> Option<String> {
+    if colors.is_empty() {
+        None
+    } else {
+        Some(colors.join(","))
+    }
+}
+
+/// Splits the comma-separated `books.dominant_colors` column into a palette.
+pub fn split_colors(colors: Option<&str>) -> Vec<String> {
+    colors
+        .map(|c| {
+            c.split(',')
+                .filter(|s| !s.is_empty())
+                .map(|s| s.to_string())
+                .collect()
+        })
+        .unwrap_or_default()
 }
 
 /// Converts a domain [`Book`](crate::domain::models::book::Book) into a `BookRow`.
@@ -61,6 +368,21 @@ impl From<&crate::domain::models::book::Book> for BookRow {
             cover_image_path: book.cover_image_path.clone(),
             checksum: book.checksum.clone(),
             added_at: book.added_at.clone(),
+            dominant_colors: join_colors(&book.dominant_colors),
+            blurhash: book.blurhash.clone(),
+            language: book.language.clone(),
+            description: book.description.clone(),
+            series: book.series.clone(),
+            series_index: book.series_index,
+            rights: book.rights.clone(),
+            modified_date: book.modified_date.clone(),
+            epub_version: book.epub_version.clone(),
+            published_date_normalized: book.published.map(|d| d.to_string()),
+            title_sort: book.title_sort.clone(),
+            author_sort: book.author_sort.clone(),
+            work_id: book.work_id,
+            deleted_at: book.deleted_at.clone(),
+            cover_is_placeholder: book.cover_is_placeholder,
         }
     }
 }
@@ -77,8 +399,26 @@ impl From<BookRow> for crate::domain::models::book::Book {
             file_type: Some(row.file_type),
             file_path: Some(row.file_path),
             cover_image_path: row.cover_image_path,
+            cover_is_placeholder: row.cover_is_placeholder,
             checksum: row.checksum,
             added_at: row.added_at,
+            dominant_colors: split_colors(row.dominant_colors.as_deref()),
+            blurhash: row.blurhash,
+            language: row.language,
+            description: row.description,
+            series: row.series,
+            series_index: row.series_index,
+            rights: row.rights,
+            modified_date: row.modified_date,
+            epub_version: row.epub_version,
+            published: row
+                .published_date_normalized
+                .as_deref()
+                .and_then(PartialDate::parse),
+            title_sort: row.title_sort,
+            author_sort: row.author_sort,
+            work_id: row.work_id,
+            deleted_at: row.deleted_at,
         }
     }
 }
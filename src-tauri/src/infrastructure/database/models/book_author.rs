@@ -2,10 +2,15 @@ use crate::infrastructure::database::models::schema::book_authors;
 use diesel::prelude::*;
 
 /// Diesel row for the `book_authors` many-to-many join table.
+///
+/// The same author may be linked more than once with different `role`s
+/// (e.g., author and illustrator).
 #[derive(Queryable, Identifiable, PartialEq, Insertable, Debug)]
 #[diesel(table_name = book_authors)]
-#[diesel(primary_key(book_id, author_id))]
+#[diesel(primary_key(book_id, author_id, role))]
 pub struct BookAuthorRow {
     pub book_id: i32,
     pub author_id: i32,
+    pub role: String,
+    pub sort_name: Option<String>,
 }
@@ -1,3 +1,4 @@
+use crate::domain::models::locator::Locator;
 use crate::infrastructure::database::models::schema::bookmarks;
 use diesel::prelude::*;
 
@@ -33,7 +34,7 @@ impl From<BookmarkRow> for crate::domain::models::bookmark::Bookmark {
             book_id: row.book_id,
             chapter_title: row.chapter_title,
             page_number: row.page_number,
-            position: row.position,
+            position: Locator::from_stored(&row.position),
             created_at: row.created_at,
         }
     }
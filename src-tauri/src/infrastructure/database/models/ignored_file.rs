@@ -0,0 +1,32 @@
+use crate::infrastructure::database::models::schema::ignored_files;
+use diesel::prelude::*;
+
+/// Diesel queryable row for the `ignored_files` table.
+#[derive(Queryable, Identifiable, Selectable, PartialEq, Debug)]
+#[diesel(table_name = ignored_files)]
+#[diesel(primary_key(ignored_file_id))]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct IgnoredFileRow {
+    pub ignored_file_id: Option<i32>,
+    pub file_path: String,
+    pub ignored_at: Option<String>,
+}
+
+/// Insertable row for ignoring a file.
+#[derive(Insertable, PartialEq, Debug)]
+#[diesel(table_name = ignored_files)]
+pub struct NewIgnoredFileRow<'a> {
+    pub file_path: &'a str,
+}
+
+/// Converts an `IgnoredFileRow` into a domain
+/// [`IgnoredFile`](crate::domain::models::ignored_file::IgnoredFile).
+impl From<IgnoredFileRow> for crate::domain::models::ignored_file::IgnoredFile {
+    fn from(row: IgnoredFileRow) -> Self {
+        crate::domain::models::ignored_file::IgnoredFile {
+            id: row.ignored_file_id.unwrap_or(0),
+            file_path: row.file_path,
+            ignored_at: row.ignored_at,
+        }
+    }
+}
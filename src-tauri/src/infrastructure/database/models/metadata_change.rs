@@ -0,0 +1,73 @@
+use crate::domain::models::metadata_change::{ChangeSource, MetadataChange, MetadataChangeSet};
+use crate::infrastructure::database::models::schema::{metadata_change_sets, metadata_changes};
+use diesel::prelude::*;
+
+/// Diesel queryable row for the `metadata_change_sets` table.
+#[derive(Queryable, Identifiable, Selectable, PartialEq, Debug)]
+#[diesel(table_name = metadata_change_sets)]
+#[diesel(primary_key(change_set_id))]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct MetadataChangeSetRow {
+    pub change_set_id: Option<i32>,
+    pub book_id: i32,
+    pub source: String,
+    pub changed_at: Option<String>,
+}
+
+/// Insertable row for recording a new change set.
+#[derive(Insertable, PartialEq, Debug)]
+#[diesel(table_name = metadata_change_sets)]
+pub struct NewMetadataChangeSetRow<'a> {
+    pub book_id: i32,
+    pub source: &'a str,
+}
+
+/// Diesel queryable row for the `metadata_changes` table.
+#[derive(Queryable, Identifiable, Selectable, PartialEq, Debug)]
+#[diesel(table_name = metadata_changes)]
+#[diesel(primary_key(change_id))]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct MetadataChangeRow {
+    pub change_id: Option<i32>,
+    pub change_set_id: i32,
+    pub field: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+}
+
+/// Insertable row for recording a field change.
+#[derive(Insertable, PartialEq, Debug)]
+#[diesel(table_name = metadata_changes)]
+pub struct NewMetadataChangeRow<'a> {
+    pub change_set_id: i32,
+    pub field: &'a str,
+    pub old_value: Option<&'a str>,
+    pub new_value: Option<&'a str>,
+}
+
+/// Converts a `MetadataChangeSetRow` into a domain [`MetadataChangeSet`]
+/// without its changes. An unknown source is read as
+/// [`ChangeSource::User`].
+impl From<MetadataChangeSetRow> for MetadataChangeSet {
+    fn from(row: MetadataChangeSetRow) -> Self {
+        MetadataChangeSet {
+            id: row.change_set_id.unwrap_or(0),
+            book_id: row.book_id,
+            source: ChangeSource::parse(&row.source).unwrap_or(ChangeSource::User),
+            changed_at: row.changed_at,
+            changes: Vec::new(),
+        }
+    }
+}
+
+/// Converts a `MetadataChangeRow` into a domain [`MetadataChange`].
+impl From<MetadataChangeRow> for MetadataChange {
+    fn from(row: MetadataChangeRow) -> Self {
+        MetadataChange {
+            id: row.change_id.unwrap_or(0),
+            field: row.field,
+            old_value: row.old_value,
+            new_value: row.new_value,
+        }
+    }
+}
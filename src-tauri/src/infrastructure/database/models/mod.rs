@@ -9,6 +9,9 @@ pub mod author;
 pub mod book;
 pub mod book_author;
 pub mod bookmark;
+pub mod ignored_file;
+pub mod metadata_change;
 pub mod publisher;
 pub mod reading_progress;
 pub mod schema;
+pub mod work;
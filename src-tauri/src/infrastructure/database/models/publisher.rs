@@ -20,3 +20,27 @@ impl From<PublisherRow> for crate::domain::models::publisher::Publisher {
         }
     }
 }
+
+/// Row returned by the publisher listing query: a publisher plus the number
+/// of books referencing it.
+#[derive(QueryableByName, Debug)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct PublisherCountRow {
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub publisher_id: i32,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub name: String,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub book_count: i64,
+}
+
+/// Converts a `PublisherCountRow` into a [`PublisherSummary`](crate::domain::dto::publisher_dto::PublisherSummary).
+impl From<PublisherCountRow> for crate::domain::dto::publisher_dto::PublisherSummary {
+    fn from(row: PublisherCountRow) -> Self {
+        crate::domain::dto::publisher_dto::PublisherSummary {
+            id: row.publisher_id,
+            name: row.name,
+            book_count: row.book_count,
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use crate::domain::models::locator::Locator;
 use crate::infrastructure::database::models::schema::reading_progress;
 use diesel::prelude::*;
 
@@ -33,7 +34,7 @@ impl From<ReadingProgressRow> for crate::domain::models::reading_progress::Readi
         crate::domain::models::reading_progress::ReadingProgress {
             id: row.progress_id.unwrap_or(0),
             book_id: row.book_id,
-            current_position: row.current_position,
+            current_position: Locator::from_stored(&row.current_position),
             chapter_title: row.chapter_title,
             page_number: row.page_number,
             progress_percentage: row.progress_percentage,
@@ -15,6 +15,8 @@ diesel::table! {
         color -> Nullable<Text>,
         created_at -> Nullable<Text>,
         updated_at -> Nullable<Text>,
+        needs_review -> Bool,
+        anchor_confidence -> Nullable<Float>,
     }
 }
 
@@ -26,9 +28,33 @@ diesel::table! {
 }
 
 diesel::table! {
-    book_authors (book_id, author_id) {
+    book_authors (book_id, author_id, role) {
         book_id -> Integer,
         author_id -> Integer,
+        role -> Text,
+        sort_name -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    book_identifiers (book_id, scheme, value) {
+        book_id -> Integer,
+        scheme -> Text,
+        value -> Text,
+    }
+}
+
+diesel::table! {
+    book_subjects (book_id, subject) {
+        book_id -> Integer,
+        subject -> Text,
+    }
+}
+
+diesel::table! {
+    book_tags (book_id, tag) {
+        book_id -> Integer,
+        tag -> Text,
     }
 }
 
@@ -55,6 +81,55 @@ diesel::table! {
         cover_image_path -> Nullable<Text>,
         checksum -> Nullable<Text>,
         added_at -> Nullable<Text>,
+        dominant_colors -> Nullable<Text>,
+        blurhash -> Nullable<Text>,
+        language -> Nullable<Text>,
+        description -> Nullable<Text>,
+        series -> Nullable<Text>,
+        series_index -> Nullable<Float>,
+        rights -> Nullable<Text>,
+        modified_date -> Nullable<Text>,
+        epub_version -> Nullable<Text>,
+        published_date_normalized -> Nullable<Text>,
+        title_sort -> Nullable<Text>,
+        author_sort -> Nullable<Text>,
+        work_id -> Nullable<Integer>,
+        deleted_at -> Nullable<Text>,
+        cover_is_placeholder -> Bool,
+    }
+}
+
+diesel::table! {
+    completed_backfills (migration) {
+        migration -> Text,
+        completed_at -> Text,
+    }
+}
+
+diesel::table! {
+    ignored_files (ignored_file_id) {
+        ignored_file_id -> Nullable<Integer>,
+        file_path -> Text,
+        ignored_at -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    metadata_change_sets (change_set_id) {
+        change_set_id -> Nullable<Integer>,
+        book_id -> Integer,
+        source -> Text,
+        changed_at -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    metadata_changes (change_id) {
+        change_id -> Nullable<Integer>,
+        change_set_id -> Integer,
+        field -> Text,
+        old_value -> Nullable<Text>,
+        new_value -> Nullable<Text>,
     }
 }
 
@@ -77,19 +152,42 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    works (work_id) {
+        work_id -> Nullable<Integer>,
+        title -> Text,
+        preferred_book_id -> Nullable<Integer>,
+        created_at -> Nullable<Text>,
+    }
+}
+
 diesel::joinable!(annotations -> books (book_id));
 diesel::joinable!(book_authors -> authors (author_id));
 diesel::joinable!(book_authors -> books (book_id));
+diesel::joinable!(book_identifiers -> books (book_id));
+diesel::joinable!(book_subjects -> books (book_id));
+diesel::joinable!(book_tags -> books (book_id));
 diesel::joinable!(bookmarks -> books (book_id));
 diesel::joinable!(books -> publishers (publisher_id));
+diesel::joinable!(books -> works (work_id));
+diesel::joinable!(metadata_change_sets -> books (book_id));
+diesel::joinable!(metadata_changes -> metadata_change_sets (change_set_id));
 diesel::joinable!(reading_progress -> books (book_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
     annotations,
     authors,
     book_authors,
+    book_identifiers,
+    book_subjects,
+    book_tags,
     bookmarks,
     books,
+    completed_backfills,
+    ignored_files,
+    metadata_change_sets,
+    metadata_changes,
     publishers,
     reading_progress,
+    works,
 );
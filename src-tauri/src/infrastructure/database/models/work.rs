@@ -0,0 +1,33 @@
+use crate::infrastructure::database::models::schema::works;
+use diesel::prelude::*;
+
+/// Diesel queryable row for the `works` table.
+#[derive(Queryable, Identifiable, Selectable, PartialEq, Debug)]
+#[diesel(table_name = works)]
+#[diesel(primary_key(work_id))]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct WorkRow {
+    pub work_id: Option<i32>,
+    pub title: String,
+    pub preferred_book_id: Option<i32>,
+    pub created_at: Option<String>,
+}
+
+/// Insertable row for creating a new work.
+#[derive(Insertable, PartialEq, Debug)]
+#[diesel(table_name = works)]
+pub struct NewWorkRow<'a> {
+    pub title: &'a str,
+}
+
+/// Converts a `WorkRow` into a domain [`Work`](crate::domain::models::work::Work).
+impl From<WorkRow> for crate::domain::models::work::Work {
+    fn from(row: WorkRow) -> Self {
+        crate::domain::models::work::Work {
+            id: row.work_id.unwrap_or(0),
+            title: row.title,
+            preferred_book_id: row.preferred_book_id,
+            created_at: row.created_at,
+        }
+    }
+}
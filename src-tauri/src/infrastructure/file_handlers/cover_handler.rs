@@ -0,0 +1,561 @@
+use image::imageops::FilterType;
+use image::{ImageFormat, Rgb, RgbImage};
+use std::collections::HashMap;
+use std::io::Cursor;
+use std::path::PathBuf;
+
+/// Width of generated placeholder covers in pixels.
+pub const PLACEHOLDER_WIDTH: u32 = 400;
+/// Height of generated placeholder covers in pixels (2:3 book aspect ratio).
+pub const PLACEHOLDER_HEIGHT: u32 = 600;
+/// Width PDF covers are rendered at from the first page, in pixels.
+pub const PDF_COVER_WIDTH: u32 = PLACEHOLDER_WIDTH;
+
+/// Blank space kept between the cover edges and any text.
+const MARGIN: u32 = 32;
+/// Width of a glyph cell in font units (5 pixel columns + 1 spacing).
+const GLYPH_ADVANCE: u32 = 6;
+/// Height of a text line in font units (7 pixel rows + 3 leading).
+const LINE_HEIGHT: u32 = 10;
+/// Largest and smallest font scales tried when fitting the title.
+const MAX_TITLE_SCALE: u32 = 10;
+const MIN_TITLE_SCALE: u32 = 2;
+/// Font scale used for the author line.
+const AUTHOR_SCALE: u32 = 3;
+
+/// Side length of the thumbnail that palettes and BlurHashes are computed from.
+const ANALYSIS_SIZE: u32 = 32;
+/// Number of colors kept in a cover palette.
+const PALETTE_SIZE: usize = 5;
+/// BlurHash component counts (horizontal x vertical).
+const BLURHASH_COMPONENTS: (u32, u32) = (4, 3);
+/// Alphabet used by BlurHash's base-83 encoding.
+const BASE83_CHARS: &[u8] =
+    b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz#$%*+,-.:;=?@[]^_{|}~";
+
+/// Colors derived from a cover image for painting placeholders in the UI.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoverPalette {
+    /// Most common colors as `#rrggbb` strings, most dominant first.
+    pub dominant_colors: Vec<String>,
+    /// BlurHash string encoding a blurred preview of the cover.
+    pub blurhash: String,
+}
+
+/// Returns the directory where cover images are cached.
+///
+/// Read from the `COVER_CACHE_DIR` environment variable, defaulting to
+/// `./covers` next to the default database location.
+pub fn cover_cache_dir() -> PathBuf {
+    dotenvy::dotenv().ok();
+    PathBuf::from(std::env::var("COVER_CACHE_DIR").unwrap_or_else(|_| "./covers".to_string()))
+}
+
+/// Writes cover image bytes to the cover cache, keyed by the book checksum.
+///
+/// The file extension is derived from the image data (`png`, `jpg`, ...).
+///
+/// # Arguments
+///
+/// * `checksum` - SHA-256 checksum of the book file, used as the file stem.
+/// * `bytes` - Encoded cover image data.
+///
+/// # Returns
+///
+/// The path of the cached cover file.
+///
+/// # Errors
+///
+/// Returns [`std::io::Error`] when the cache directory cannot be created or
+/// the file cannot be written.
+pub async fn cache_cover(checksum: &str, bytes: &[u8]) -> Result<PathBuf, std::io::Error> {
+    let ext = image::guess_format(bytes)
+        .ok()
+        .and_then(|f| f.extensions_str().first().copied())
+        .unwrap_or("bin");
+
+    let dir = cover_cache_dir();
+    tokio::fs::create_dir_all(&dir).await?;
+
+    let path = dir.join(format!("{}.{}", checksum, ext));
+    tokio::fs::write(&path, bytes).await?;
+    Ok(path)
+}
+
+/// Computes the dominant color palette and BlurHash of a cover image.
+///
+/// The image is downscaled to a small thumbnail first, so the cost is
+/// independent of the cover's resolution.
+///
+/// # Arguments
+///
+/// * `bytes` - Encoded cover image data (PNG or JPEG).
+///
+/// # Returns
+///
+/// A [`CoverPalette`] with up to five dominant colors and a 4x3 BlurHash.
+///
+/// # Errors
+///
+/// Returns a boxed error when the image format is unsupported or the data
+/// cannot be decoded.
+pub async fn extract_cover_palette(
+    bytes: Vec<u8>,
+) -> Result<CoverPalette, Box<dyn std::error::Error + Send + Sync>> {
+    tokio::task::spawn_blocking(move || {
+        let image = image::load_from_memory(&bytes)?;
+        let thumbnail = image
+            .resize_exact(ANALYSIS_SIZE, ANALYSIS_SIZE, FilterType::Triangle)
+            .to_rgb8();
+
+        Ok(CoverPalette {
+            dominant_colors: dominant_colors(&thumbnail),
+            blurhash: encode_blurhash(&thumbnail, BLURHASH_COMPONENTS.0, BLURHASH_COMPONENTS.1),
+        })
+    })
+    .await?
+}
+
+/// Generates a deterministic placeholder cover for a book without cover art.
+///
+/// The background color is derived from the book's checksum so the same file
+/// always gets the same cover. The title is word-wrapped and scaled to the
+/// largest size that fits, with the author printed along the bottom edge.
+///
+/// # Arguments
+///
+/// * `title` - The book's title.
+/// * `author` - The primary author's name, if known.
+/// * `checksum` - SHA-256 checksum of the book file.
+///
+/// # Returns
+///
+/// PNG-encoded image bytes of [`PLACEHOLDER_WIDTH`] x [`PLACEHOLDER_HEIGHT`].
+///
+/// # Errors
+///
+/// Returns a boxed error when the image cannot be encoded.
+pub async fn generate_placeholder_cover(
+    title: &str,
+    author: Option<&str>,
+    checksum: &str,
+) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>> {
+    let title = title.to_string();
+    let author = author.map(|a| a.to_string());
+    let checksum = checksum.to_string();
+
+    tokio::task::spawn_blocking(move || {
+        let image = render_placeholder(&title, author.as_deref(), &checksum);
+        let mut bytes = Vec::new();
+        image.write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png)?;
+        Ok(bytes)
+    })
+    .await?
+}
+
+/// Draws the placeholder cover into an RGB image buffer.
+fn render_placeholder(title: &str, author: Option<&str>, checksum: &str) -> RgbImage {
+    let background = background_color(checksum);
+    let accent = shade(background, 1.35);
+    let foreground = Rgb([245, 245, 240]);
+
+    let mut image = RgbImage::from_pixel(PLACEHOLDER_WIDTH, PLACEHOLDER_HEIGHT, background);
+
+    // Accent band separating the title block from the author line.
+    let band_y = PLACEHOLDER_HEIGHT - MARGIN - LINE_HEIGHT * AUTHOR_SCALE * 2 - 16;
+    fill_rect(
+        &mut image,
+        MARGIN,
+        band_y,
+        PLACEHOLDER_WIDTH - 2 * MARGIN,
+        4,
+        accent,
+    );
+
+    let text_width = PLACEHOLDER_WIDTH - 2 * MARGIN;
+    let title_height = band_y - 2 * MARGIN;
+    let (scale, lines) = fit_text(&printable(title), text_width, title_height);
+    let block_height = lines.len() as u32 * LINE_HEIGHT * scale;
+    let mut y = MARGIN + title_height.saturating_sub(block_height) / 2;
+    for line in &lines {
+        draw_centered_line(&mut image, line, y, scale, foreground);
+        y += LINE_HEIGHT * scale;
+    }
+
+    if let Some(author) = author.map(printable) {
+        let max_chars = (text_width / (GLYPH_ADVANCE * AUTHOR_SCALE)) as usize;
+        let mut author_y = band_y + 16;
+        for line in wrap_words(&author, max_chars).iter().take(2) {
+            draw_centered_line(&mut image, line, author_y, AUTHOR_SCALE, foreground);
+            author_y += LINE_HEIGHT * AUTHOR_SCALE;
+        }
+    }
+
+    image
+}
+
+/// Upper-cases `text` and reduces it to characters the bitmap font can draw.
+///
+/// Cyrillic and Greek letters are transliterated to Latin ones; characters of
+/// other scripts (CJK, Arabic, ...) are dropped rather than left as blank
+/// cells, so a title in such a script draws no text at all.
+fn printable(text: &str) -> String {
+    let mut out = String::new();
+    for c in text.to_uppercase().chars() {
+        if c.is_whitespace() {
+            out.push(' ');
+        } else if glyph(c).is_some() {
+            out.push(c);
+        } else if let Some(latin) = transliterate(c) {
+            out.push_str(latin);
+        }
+    }
+    out.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Finds the largest font scale at which `text` fits in the given box.
+///
+/// Falls back to the smallest scale (truncating extra lines) for very long
+/// titles.
+fn fit_text(text: &str, width: u32, height: u32) -> (u32, Vec<String>) {
+    for scale in (MIN_TITLE_SCALE..=MAX_TITLE_SCALE).rev() {
+        let max_chars = (width / (GLYPH_ADVANCE * scale)) as usize;
+        let lines = wrap_words(text, max_chars);
+        if lines.len() as u32 * LINE_HEIGHT * scale <= height {
+            return (scale, lines);
+        }
+    }
+
+    let max_chars = (width / (GLYPH_ADVANCE * MIN_TITLE_SCALE)) as usize;
+    let max_lines = (height / (LINE_HEIGHT * MIN_TITLE_SCALE)) as usize;
+    let mut lines = wrap_words(text, max_chars);
+    lines.truncate(max_lines);
+    (MIN_TITLE_SCALE, lines)
+}
+
+/// Greedily wraps `text` into upper-cased lines of at most `max_chars`
+/// characters, hard-splitting words longer than a line.
+fn wrap_words(text: &str, max_chars: usize) -> Vec<String> {
+    let max_chars = max_chars.max(1);
+    let mut lines = Vec::new();
+    let mut current = String::new();
+
+    for word in text.split_whitespace() {
+        let word: Vec<char> = word.to_uppercase().chars().collect();
+        for chunk in word.chunks(max_chars) {
+            let chunk: String = chunk.iter().collect();
+            let needed = if current.is_empty() {
+                chunk.chars().count()
+            } else {
+                current.chars().count() + 1 + chunk.chars().count()
+            };
+            if needed > max_chars && !current.is_empty() {
+                lines.push(std::mem::take(&mut current));
+            }
+            if !current.is_empty() {
+                current.push(' ');
+            }
+            current.push_str(&chunk);
+        }
+    }
+
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+/// Draws a single line of text horizontally centered at row `y`.
+fn draw_centered_line(image: &mut RgbImage, line: &str, y: u32, scale: u32, color: Rgb<u8>) {
+    let count = line.chars().count() as u32;
+    let line_width = (count * GLYPH_ADVANCE).saturating_sub(1) * scale;
+    let mut x = PLACEHOLDER_WIDTH.saturating_sub(line_width) / 2;
+
+    for c in line.chars() {
+        if let Some(rows) = glyph(c) {
+            for (row, bits) in rows.iter().enumerate() {
+                for col in 0..5 {
+                    if bits & (0b10000 >> col) != 0 {
+                        fill_rect(
+                            image,
+                            x + col * scale,
+                            y + row as u32 * scale,
+                            scale,
+                            scale,
+                            color,
+                        );
+                    }
+                }
+            }
+        }
+        x += GLYPH_ADVANCE * scale;
+    }
+}
+
+/// Fills a rectangle, clipping it to the image bounds.
+fn fill_rect(image: &mut RgbImage, x: u32, y: u32, width: u32, height: u32, color: Rgb<u8>) {
+    let x_end = (x + width).min(image.width());
+    let y_end = (y + height).min(image.height());
+    for py in y..y_end {
+        for px in x..x_end {
+            image.put_pixel(px, py, color);
+        }
+    }
+}
+
+/// Buckets pixels into a coarse 4-bit-per-channel histogram and returns the
+/// average color of the most populated buckets.
+fn dominant_colors(image: &RgbImage) -> Vec<String> {
+    let mut buckets: HashMap<u16, (u32, [u32; 3])> = HashMap::new();
+    for pixel in image.pixels() {
+        let [r, g, b] = pixel.0;
+        let key = ((r as u16 >> 4) << 8) | ((g as u16 >> 4) << 4) | (b as u16 >> 4);
+        let entry = buckets.entry(key).or_insert((0, [0; 3]));
+        entry.0 += 1;
+        entry.1[0] += r as u32;
+        entry.1[1] += g as u32;
+        entry.1[2] += b as u32;
+    }
+
+    let mut ranked: Vec<(u16, (u32, [u32; 3]))> = buckets.into_iter().collect();
+    ranked.sort_by(|a, b| b.1.0.cmp(&a.1.0).then(a.0.cmp(&b.0)));
+
+    ranked
+        .into_iter()
+        .take(PALETTE_SIZE)
+        .map(|(_, (count, sum))| {
+            format!(
+                "#{:02x}{:02x}{:02x}",
+                sum[0] / count,
+                sum[1] / count,
+                sum[2] / count
+            )
+        })
+        .collect()
+}
+
+/// Encodes an image as a BlurHash string with the given component counts.
+///
+/// Follows the reference algorithm from <https://blurha.sh>.
+fn encode_blurhash(image: &RgbImage, components_x: u32, components_y: u32) -> String {
+    let (width, height) = image.dimensions();
+    let mut factors: Vec<[f32; 3]> = Vec::with_capacity((components_x * components_y) as usize);
+
+    for j in 0..components_y {
+        for i in 0..components_x {
+            let normalisation = if i == 0 && j == 0 { 1.0 } else { 2.0 };
+            let mut factor = [0.0f32; 3];
+            for y in 0..height {
+                for x in 0..width {
+                    let basis = normalisation
+                        * (std::f32::consts::PI * i as f32 * x as f32 / width as f32).cos()
+                        * (std::f32::consts::PI * j as f32 * y as f32 / height as f32).cos();
+                    let pixel = image.get_pixel(x, y).0;
+                    for (channel, value) in factor.iter_mut().zip(pixel) {
+                        *channel += basis * srgb_to_linear(value);
+                    }
+                }
+            }
+            let scale = 1.0 / (width * height) as f32;
+            factors.push(factor.map(|v| v * scale));
+        }
+    }
+
+    let mut hash = String::new();
+    let size_flag = (components_x - 1) + (components_y - 1) * 9;
+    encode_base83(size_flag, 1, &mut hash);
+
+    let (dc, ac) = factors.split_first().expect("at least one component");
+    let maximum_value = if ac.is_empty() {
+        encode_base83(0, 1, &mut hash);
+        1.0
+    } else {
+        let actual_max = ac
+            .iter()
+            .flat_map(|f| f.iter())
+            .fold(0.0f32, |max, v| max.max(v.abs()));
+        let quantised_max = ((actual_max * 166.0 - 0.5).floor()).clamp(0.0, 82.0) as u32;
+        encode_base83(quantised_max, 1, &mut hash);
+        (quantised_max + 1) as f32 / 166.0
+    };
+
+    let dc_value =
+        (linear_to_srgb(dc[0]) << 16) + (linear_to_srgb(dc[1]) << 8) + linear_to_srgb(dc[2]);
+    encode_base83(dc_value, 4, &mut hash);
+
+    for factor in ac {
+        let quantise = |v: f32| {
+            let scaled = (v / maximum_value).signum() * (v / maximum_value).abs().powf(0.5);
+            (scaled * 9.0 + 9.5).floor().clamp(0.0, 18.0) as u32
+        };
+        let value = quantise(factor[0]) * 19 * 19 + quantise(factor[1]) * 19 + quantise(factor[2]);
+        encode_base83(value, 2, &mut hash);
+    }
+
+    hash
+}
+
+/// Appends `value` to `out` as `length` base-83 digits.
+fn encode_base83(value: u32, length: u32, out: &mut String) {
+    for i in 1..=length {
+        let digit = (value / 83u32.pow(length - i)) % 83;
+        out.push(BASE83_CHARS[digit as usize] as char);
+    }
+}
+
+/// Converts an 8-bit sRGB channel value to linear light.
+fn srgb_to_linear(value: u8) -> f32 {
+    let v = value as f32 / 255.0;
+    if v <= 0.04045 {
+        v / 12.92
+    } else {
+        ((v + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Converts a linear light value back to an 8-bit sRGB channel value.
+fn linear_to_srgb(value: f32) -> u32 {
+    let v = value.clamp(0.0, 1.0);
+    if v <= 0.003_130_8 {
+        (v * 12.92 * 255.0 + 0.5) as u32
+    } else {
+        ((1.055 * v.powf(1.0 / 2.4) - 0.055) * 255.0 + 0.5) as u32
+    }
+}
+
+/// Derives a muted background color from the leading bytes of a checksum.
+fn background_color(checksum: &str) -> Rgb<u8> {
+    let seed = checksum
+        .get(..6)
+        .and_then(|s| u32::from_str_radix(s, 16).ok())
+        .unwrap_or_else(|| {
+            checksum
+                .bytes()
+                .fold(0u32, |acc, b| acc.wrapping_mul(31).wrapping_add(b as u32))
+        });
+
+    let hue = (seed % 360) as f32;
+    let saturation = 0.35 + ((seed >> 9) % 20) as f32 / 100.0;
+    let lightness = 0.28 + ((seed >> 14) % 10) as f32 / 100.0;
+    hsl_to_rgb(hue, saturation, lightness)
+}
+
+/// Scales each channel of `color` by `factor`, saturating at 255.
+fn shade(color: Rgb<u8>, factor: f32) -> Rgb<u8> {
+    Rgb(color.0.map(|c| (c as f32 * factor).min(255.0) as u8))
+}
+
+/// Converts an HSL color (hue in degrees, saturation/lightness in 0..=1) to RGB.
+fn hsl_to_rgb(hue: f32, saturation: f32, lightness: f32) -> Rgb<u8> {
+    let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+    let h = hue / 60.0;
+    let x = chroma * (1.0 - (h % 2.0 - 1.0).abs());
+    let (r, g, b) = match h as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = lightness - chroma / 2.0;
+    let to_u8 = |v: f32| ((v + m) * 255.0).round().clamp(0.0, 255.0) as u8;
+    Rgb([to_u8(r), to_u8(g), to_u8(b)])
+}
+
+/// Returns the Latin spelling of an upper-case Cyrillic or Greek letter.
+fn transliterate(c: char) -> Option<&'static str> {
+    let latin = match c {
+        'А' | 'Α' | 'Ά' => "A",
+        'Б' | 'Β' => "B",
+        'В' => "V",
+        'Г' | 'Ґ' | 'Γ' => "G",
+        'Д' | 'Δ' => "D",
+        'Е' | 'Э' | 'Є' | 'Ε' | 'Έ' => "E",
+        'Ё' => "YO",
+        'Ж' => "ZH",
+        'З' | 'Ζ' => "Z",
+        'И' | 'І' | 'Ι' | 'Ί' | 'Ϊ' | 'Η' | 'Ή' => "I",
+        'Ї' => "YI",
+        'Й' | 'Ы' => "Y",
+        'К' | 'Κ' => "K",
+        'Л' | 'Λ' => "L",
+        'М' | 'Μ' => "M",
+        'Н' | 'Ν' => "N",
+        'О' | 'Ο' | 'Ό' | 'Ω' | 'Ώ' => "O",
+        'П' | 'Π' => "P",
+        'Р' | 'Ρ' => "R",
+        'С' | 'Σ' => "S",
+        'Т' | 'Τ' => "T",
+        'У' | 'Υ' | 'Ύ' | 'Ϋ' => "U",
+        'Ф' | 'Φ' => "F",
+        'Х' | 'Χ' => "KH",
+        'Ц' => "TS",
+        'Ч' => "CH",
+        'Ш' => "SH",
+        'Щ' => "SHCH",
+        'Ю' => "YU",
+        'Я' => "YA",
+        'Θ' => "TH",
+        'Ξ' => "X",
+        'Ψ' => "PS",
+        'Ъ' | 'Ь' => "",
+        _ => return None,
+    };
+    Some(latin)
+}
+
+/// Returns the 5x7 bitmap for an upper-case character, one byte per row with
+/// the leftmost pixel in bit 4. Unsupported characters render as blanks.
+fn glyph(c: char) -> Option<[u8; 7]> {
+    let rows = match c {
+        'A' | 'À' | 'Á' | 'Â' | 'Ã' | 'Ä' | 'Å' => [0x0E, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x11],
+        'B' => [0x1E, 0x11, 0x11, 0x1E, 0x11, 0x11, 0x1E],
+        'C' | 'Ç' => [0x0E, 0x11, 0x10, 0x10, 0x10, 0x11, 0x0E],
+        'D' => [0x1E, 0x11, 0x11, 0x11, 0x11, 0x11, 0x1E],
+        'E' | 'È' | 'É' | 'Ê' | 'Ë' => [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x1F],
+        'F' => [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x10],
+        'G' => [0x0E, 0x11, 0x10, 0x17, 0x11, 0x11, 0x0F],
+        'H' => [0x11, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x11],
+        'I' | 'Ì' | 'Í' | 'Î' | 'Ï' => [0x0E, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0E],
+        'J' => [0x07, 0x02, 0x02, 0x02, 0x02, 0x12, 0x0C],
+        'K' => [0x11, 0x12, 0x14, 0x18, 0x14, 0x12, 0x11],
+        'L' => [0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x1F],
+        'M' => [0x11, 0x1B, 0x15, 0x15, 0x11, 0x11, 0x11],
+        'N' | 'Ñ' => [0x11, 0x11, 0x19, 0x15, 0x13, 0x11, 0x11],
+        'O' | 'Ò' | 'Ó' | 'Ô' | 'Õ' | 'Ö' | 'Ø' => [0x0E, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E],
+        'P' => [0x1E, 0x11, 0x11, 0x1E, 0x10, 0x10, 0x10],
+        'Q' => [0x0E, 0x11, 0x11, 0x11, 0x15, 0x12, 0x0D],
+        'R' => [0x1E, 0x11, 0x11, 0x1E, 0x14, 0x12, 0x11],
+        'S' => [0x0F, 0x10, 0x10, 0x0E, 0x01, 0x01, 0x1E],
+        'T' => [0x1F, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04],
+        'U' | 'Ù' | 'Ú' | 'Û' | 'Ü' => [0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E],
+        'V' => [0x11, 0x11, 0x11, 0x11, 0x11, 0x0A, 0x04],
+        'W' => [0x11, 0x11, 0x11, 0x15, 0x15, 0x15, 0x0A],
+        'X' => [0x11, 0x11, 0x0A, 0x04, 0x0A, 0x11, 0x11],
+        'Y' | 'Ý' => [0x11, 0x11, 0x11, 0x0A, 0x04, 0x04, 0x04],
+        'Z' => [0x1F, 0x01, 0x02, 0x04, 0x08, 0x10, 0x1F],
+        '0' => [0x0E, 0x11, 0x13, 0x15, 0x19, 0x11, 0x0E],
+        '1' => [0x04, 0x0C, 0x04, 0x04, 0x04, 0x04, 0x0E],
+        '2' => [0x0E, 0x11, 0x01, 0x02, 0x04, 0x08, 0x1F],
+        '3' => [0x1F, 0x02, 0x04, 0x02, 0x01, 0x11, 0x0E],
+        '4' => [0x02, 0x06, 0x0A, 0x12, 0x1F, 0x02, 0x02],
+        '5' => [0x1F, 0x10, 0x1E, 0x01, 0x01, 0x11, 0x0E],
+        '6' => [0x06, 0x08, 0x10, 0x1E, 0x11, 0x11, 0x0E],
+        '7' => [0x1F, 0x01, 0x02, 0x04, 0x08, 0x08, 0x08],
+        '8' => [0x0E, 0x11, 0x11, 0x0E, 0x11, 0x11, 0x0E],
+        '9' => [0x0E, 0x11, 0x11, 0x0F, 0x01, 0x02, 0x0C],
+        '.' => [0x00, 0x00, 0x00, 0x00, 0x00, 0x0C, 0x0C],
+        ',' => [0x00, 0x00, 0x00, 0x00, 0x0C, 0x04, 0x08],
+        '\'' | '’' => [0x04, 0x04, 0x08, 0x00, 0x00, 0x00, 0x00],
+        '-' | '–' | '—' => [0x00, 0x00, 0x00, 0x1F, 0x00, 0x00, 0x00],
+        ':' => [0x00, 0x0C, 0x0C, 0x00, 0x0C, 0x0C, 0x00],
+        '!' => [0x04, 0x04, 0x04, 0x04, 0x04, 0x00, 0x04],
+        '?' => [0x0E, 0x11, 0x01, 0x02, 0x04, 0x00, 0x04],
+        '&' => [0x0C, 0x12, 0x14, 0x08, 0x15, 0x12, 0x0D],
+        '(' => [0x02, 0x04, 0x08, 0x08, 0x08, 0x04, 0x02],
+        ')' => [0x08, 0x04, 0x02, 0x02, 0x02, 0x04, 0x08],
+        _ => return None,
+    };
+    Some(rows)
+}
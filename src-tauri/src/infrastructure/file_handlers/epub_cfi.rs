@@ -0,0 +1,523 @@
+//! Resolving and generating EPUB CFIs against the content documents of a
+//! book.
+//!
+//! Spine documents are parsed as XML, as reading systems load XHTML, so the
+//! node tree matches the one CFIs from other readers were computed on.
+
+use quick_xml::Reader;
+use quick_xml::escape::resolve_xml_entity;
+use quick_xml::events::{BytesStart, Event};
+use std::fs::File;
+use std::io::BufReader;
+use zip::ZipArchive;
+
+use crate::domain::models::cfi::{CfiOffset, CfiPoint, CfiStep, DomPosition};
+use crate::infrastructure::file_handlers::opf::{
+    read_entry_string, read_opf, resolve_href, scan_package,
+};
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// The spine of an EPUB, as CFIs address it.
+#[derive(Debug, Clone)]
+pub struct EpubSpine {
+    /// CFI step to the `spine` element of the package document.
+    pub spine_step: u32,
+    /// Spine items in reading order.
+    pub items: Vec<SpineItem>,
+}
+
+/// One spine item.
+#[derive(Debug, Clone)]
+pub struct SpineItem {
+    /// The `idref` of the `itemref`.
+    pub idref: String,
+    /// Href of the manifest item, relative to the package document; empty
+    /// when the manifest has no item with the `idref`.
+    pub href: String,
+}
+
+/// A node of a content document as CFI steps count them. Text split only by
+/// comments or processing instructions is one node.
+enum Node {
+    Element(DomElement),
+    Text(String),
+}
+
+struct DomElement {
+    id: Option<String>,
+    children: Vec<Node>,
+    /// Length of the element's text content in UTF-16 code units.
+    text_len: usize,
+}
+
+impl DomElement {
+    fn from_tag(tag: &BytesStart) -> Result<Self, BoxError> {
+        let mut id = None;
+        for attr in tag.attributes() {
+            let attr = attr?;
+            if attr.key.local_name().as_ref() == b"id" {
+                id = Some(attr.unescape_value()?.into_owned());
+            }
+        }
+        Ok(Self {
+            id,
+            children: Vec::new(),
+            text_len: 0,
+        })
+    }
+
+    fn elements(&self) -> impl Iterator<Item = &DomElement> {
+        self.children.iter().filter_map(|child| match child {
+            Node::Element(element) => Some(element),
+            Node::Text(_) => None,
+        })
+    }
+
+    fn push_text(&mut self, text: &str) {
+        match self.children.last_mut() {
+            Some(Node::Text(last)) => last.push_str(text),
+            _ => self.children.push(Node::Text(text.to_string())),
+        }
+    }
+
+    /// Returns the length of the text before the text node that follows the
+    /// `elements`-th child element (before the first element for 0), and the
+    /// length of that text node.
+    fn text_slot(&self, elements: usize) -> (usize, usize) {
+        let mut seen = 0;
+        let mut before = 0;
+        for child in &self.children {
+            match child {
+                Node::Element(_) if seen == elements => break,
+                Node::Element(element) => {
+                    seen += 1;
+                    before += element.text_len;
+                }
+                Node::Text(text) if seen == elements => return (before, utf16_len(text)),
+                Node::Text(text) => before += utf16_len(text),
+            }
+        }
+        (before, 0)
+    }
+
+    /// Returns the length of the text content before the `index`-th child
+    /// element.
+    fn text_before_element(&self, index: usize) -> usize {
+        let (before, len) = self.text_slot(index);
+        before + len
+    }
+}
+
+/// The node tree of a spine item's content document.
+pub struct SpineDocument {
+    root: DomElement,
+}
+
+impl SpineDocument {
+    /// Parses an XHTML content document.
+    ///
+    /// # Errors
+    ///
+    /// Returns a boxed error when the document is not well-formed XML or has
+    /// no root element.
+    pub fn parse(xhtml: &str) -> Result<Self, BoxError> {
+        let mut reader = Reader::from_str(xhtml);
+        let mut stack: Vec<DomElement> = Vec::new();
+        let mut root = None;
+
+        loop {
+            match reader.read_event()? {
+                Event::Start(e) => stack.push(DomElement::from_tag(&e)?),
+                Event::Empty(e) => close(&mut stack, &mut root, DomElement::from_tag(&e)?),
+                Event::End(_) => {
+                    let element = stack.pop().ok_or("unbalanced end tag")?;
+                    close(&mut stack, &mut root, element);
+                }
+                Event::Text(e) => push_text(&mut stack, &e.xml_content()?),
+                Event::CData(e) => push_text(&mut stack, &e.xml_content()?),
+                Event::GeneralRef(e) => {
+                    let text = match e.resolve_char_ref()? {
+                        Some(c) => c.to_string(),
+                        None => {
+                            let name = e.decode()?;
+                            match resolve_xml_entity(&name) {
+                                Some(text) => text.to_string(),
+                                // The one HTML entity common in EPUBs, declared
+                                // by their XHTML doctype.
+                                None if name == "nbsp" => "\u{a0}".to_string(),
+                                None => format!("&{};", name),
+                            }
+                        }
+                    };
+                    push_text(&mut stack, &text);
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+
+        let root = root.ok_or("document has no root element")?;
+        Ok(Self { root })
+    }
+
+    /// Resolves the content-document part of a CFI.
+    ///
+    /// An ID assertion that does not match the element at the step's index
+    /// takes precedence when an element with that ID exists, so CFIs survive
+    /// edits before the target. An offset past the end of the text node is
+    /// clamped to its end.
+    ///
+    /// # Errors
+    ///
+    /// Returns a boxed error when a step points past the last child or steps
+    /// into a text node.
+    pub fn resolve(&self, point: &CfiPoint) -> Result<DomPosition, BoxError> {
+        let mut element = &self.root;
+        let mut path = Vec::new();
+
+        for (i, step) in point.steps.iter().enumerate() {
+            if step.index % 2 == 1 {
+                if i + 1 < point.steps.len() {
+                    return Err("CFI steps into a text node".into());
+                }
+                let elements = step.index as usize / 2;
+                if elements > element.elements().count() {
+                    return Err(past_last_child(step));
+                }
+                let (before, len) = element.text_slot(elements);
+                let chars = point.offset.as_ref().map_or(0, |o| o.chars as usize);
+                return Ok(DomPosition {
+                    element_path: path,
+                    offset: Some(before + chars.min(len)),
+                });
+            }
+
+            let index = step.index as usize / 2 - 1;
+            let child = element.elements().nth(index);
+            let moved_id = step
+                .id
+                .as_deref()
+                .filter(|&id| child.and_then(|c| c.id.as_deref()) != Some(id));
+            match moved_id.and_then(|id| find_by_id(&self.root, id)) {
+                Some(found) => {
+                    element = self
+                        .element_at(&found)
+                        .ok_or_else(|| past_last_child(step))?;
+                    path = found;
+                }
+                None => {
+                    element = child.ok_or_else(|| past_last_child(step))?;
+                    path.push(index);
+                }
+            }
+        }
+
+        Ok(DomPosition {
+            element_path: path,
+            offset: None,
+        })
+    }
+
+    /// Builds the content-document part of a CFI for a position.
+    ///
+    /// A text offset on the boundary between two text nodes is placed at the
+    /// end of the first one. Element steps carry the element's ID as
+    /// assertion.
+    ///
+    /// # Errors
+    ///
+    /// Returns a boxed error when the element path points past the last
+    /// child or the offset past the end of the element's text.
+    pub fn locate(&self, position: &DomPosition) -> Result<CfiPoint, BoxError> {
+        let mut element = &self.root;
+        let mut steps = Vec::new();
+        for &index in &position.element_path {
+            element = element
+                .elements()
+                .nth(index)
+                .ok_or("element path points past the last child")?;
+            steps.push(element_step(index, element));
+        }
+
+        let Some(offset) = position.offset else {
+            return Ok(CfiPoint {
+                steps,
+                offset: None,
+            });
+        };
+        if offset > element.text_len {
+            return Err("offset points past the end of the element's text".into());
+        }
+        let chars = locate_text(element, offset, &mut steps);
+        Ok(CfiPoint {
+            steps,
+            offset: Some(CfiOffset {
+                chars: chars as u32,
+                before: None,
+                after: None,
+                side: None,
+            }),
+        })
+    }
+
+    /// Returns how far into the document's text a position is, from 0.0 to
+    /// 1.0.
+    ///
+    /// # Errors
+    ///
+    /// Returns a boxed error when the element path points past the last
+    /// child.
+    pub fn progression(&self, position: &DomPosition) -> Result<f64, BoxError> {
+        let before = self.text_offset(position)?;
+        Ok(match self.root.text_len {
+            0 => 0.0,
+            total => before as f64 / total as f64,
+        })
+    }
+
+    /// Returns the offset of a position in the document's [`text`](Self::text),
+    /// in UTF-16 code units.
+    ///
+    /// # Errors
+    ///
+    /// Returns a boxed error when the element path points past the last
+    /// child.
+    pub fn text_offset(&self, position: &DomPosition) -> Result<usize, BoxError> {
+        let mut element = &self.root;
+        let mut before = 0;
+        for &index in &position.element_path {
+            before += element.text_before_element(index);
+            element = element
+                .elements()
+                .nth(index)
+                .ok_or("element path points past the last child")?;
+        }
+        Ok(before + position.offset.unwrap_or(0).min(element.text_len))
+    }
+
+    /// Returns the document's text content, the text CFI offsets and
+    /// [`DomPosition`] offsets count in.
+    pub fn text(&self) -> String {
+        let mut text = String::new();
+        collect_text(&self.root, &mut text);
+        text
+    }
+
+    fn element_at(&self, path: &[usize]) -> Option<&DomElement> {
+        let mut element = &self.root;
+        for &index in path {
+            element = element.elements().nth(index)?;
+        }
+        Some(element)
+    }
+}
+
+/// Attaches a closed element to its parent, or keeps it as the root.
+fn close(stack: &mut [DomElement], root: &mut Option<DomElement>, mut element: DomElement) {
+    element.text_len = element
+        .children
+        .iter()
+        .map(|child| match child {
+            Node::Element(element) => element.text_len,
+            Node::Text(text) => utf16_len(text),
+        })
+        .sum();
+    match stack.last_mut() {
+        Some(parent) => parent.children.push(Node::Element(element)),
+        None if root.is_none() => *root = Some(element),
+        None => {}
+    }
+}
+
+/// Adds text to the open element; text outside the root element is dropped.
+fn push_text(stack: &mut [DomElement], text: &str) {
+    if let Some(parent) = stack.last_mut() {
+        parent.push_text(text);
+    }
+}
+
+fn collect_text(element: &DomElement, text: &mut String) {
+    for child in &element.children {
+        match child {
+            Node::Element(element) => collect_text(element, text),
+            Node::Text(value) => text.push_str(value),
+        }
+    }
+}
+
+fn past_last_child(step: &CfiStep) -> BoxError {
+    format!("CFI step /{} points past the last child", step.index).into()
+}
+
+fn utf16_len(text: &str) -> usize {
+    text.encode_utf16().count()
+}
+
+fn element_step(index: usize, element: &DomElement) -> CfiStep {
+    CfiStep {
+        index: (index as u32 + 1) * 2,
+        id: element.id.clone(),
+    }
+}
+
+/// Returns the element path of the element with the given ID.
+fn find_by_id(element: &DomElement, id: &str) -> Option<Vec<usize>> {
+    for (index, child) in element.elements().enumerate() {
+        if child.id.as_deref() == Some(id) {
+            return Some(vec![index]);
+        }
+        if let Some(mut path) = find_by_id(child, id) {
+            path.insert(0, index);
+            return Some(path);
+        }
+    }
+    None
+}
+
+/// Pushes the steps to the text node holding `offset` (relative to
+/// `element`'s text content) and returns the offset within that node.
+fn locate_text(element: &DomElement, mut offset: usize, steps: &mut Vec<CfiStep>) -> usize {
+    let mut elements = 0;
+    for child in &element.children {
+        match child {
+            Node::Text(text) => {
+                let len = utf16_len(text);
+                if offset <= len {
+                    steps.push(CfiStep {
+                        index: elements * 2 + 1,
+                        id: None,
+                    });
+                    return offset;
+                }
+                offset -= len;
+            }
+            Node::Element(child) => {
+                if offset <= child.text_len && child.text_len > 0 {
+                    steps.push(element_step(elements as usize, child));
+                    return locate_text(child, offset, steps);
+                }
+                offset -= child.text_len;
+                elements += 1;
+            }
+        }
+    }
+    // No text left: the (empty) text node after the last child element.
+    steps.push(CfiStep {
+        index: elements * 2 + 1,
+        id: None,
+    });
+    offset
+}
+
+/// Reads the spine of an EPUB file.
+///
+/// # Errors
+///
+/// Returns a boxed error when the archive, its container or the OPF cannot
+/// be read or parsed.
+pub async fn read_spine(path: &str) -> Result<EpubSpine, BoxError> {
+    let path = path.to_string();
+    tokio::task::spawn_blocking(move || {
+        let mut archive = ZipArchive::new(BufReader::new(File::open(&path)?))?;
+        let (_, spine) = spine_of(&mut archive)?;
+        Ok(spine)
+    })
+    .await?
+}
+
+/// Reads and parses the content document of a spine item.
+///
+/// # Arguments
+///
+/// * `path` - Absolute path to the EPUB file.
+/// * `href` - The item's href, relative to the package document.
+///
+/// # Errors
+///
+/// Returns a boxed error when the archive cannot be read, has no entry for
+/// the href, or the document is not well-formed XML.
+pub async fn read_spine_document(path: &str, href: &str) -> Result<SpineDocument, BoxError> {
+    let path = path.to_string();
+    let href = href.to_string();
+    tokio::task::spawn_blocking(move || {
+        let mut archive = ZipArchive::new(BufReader::new(File::open(&path)?))?;
+        let (opf_path, _) = read_opf(&mut archive)?;
+        item_document(&mut archive, &opf_path, &href)
+    })
+    .await?
+}
+
+/// Reads the spine of an EPUB file with the content document of every
+/// item.
+///
+/// # Returns
+///
+/// The spine and, in spine order, each item's document, or `None` for an
+/// item that is missing or not well-formed XML.
+///
+/// # Errors
+///
+/// Returns a boxed error when the archive, its container or the OPF cannot
+/// be read or parsed.
+pub async fn read_spine_documents(
+    path: &str,
+) -> Result<(EpubSpine, Vec<Option<SpineDocument>>), BoxError> {
+    let path = path.to_string();
+    tokio::task::spawn_blocking(move || {
+        let mut archive = ZipArchive::new(BufReader::new(File::open(&path)?))?;
+        let (opf_path, spine) = spine_of(&mut archive)?;
+        let documents = spine
+            .items
+            .iter()
+            .map(|item| item_document(&mut archive, &opf_path, &item.href).ok())
+            .collect();
+        Ok((spine, documents))
+    })
+    .await?
+}
+
+/// Reads the spine from an archive's package document, returning the
+/// package document's path with it.
+fn spine_of(archive: &mut ZipArchive<BufReader<File>>) -> Result<(String, EpubSpine), BoxError> {
+    let (opf_path, opf) = read_opf(archive)?;
+    let package = scan_package(&opf)?;
+
+    let items = package
+        .spine
+        .iter()
+        .map(|idref| SpineItem {
+            idref: idref.clone(),
+            href: package
+                .manifest
+                .children
+                .iter()
+                .find(|item| item.attr("id") == Some(idref.as_str()))
+                .and_then(|item| item.attr("href"))
+                .unwrap_or_default()
+                .to_string(),
+        })
+        .collect();
+    Ok((
+        opf_path,
+        EpubSpine {
+            spine_step: package.spine_step,
+            items,
+        },
+    ))
+}
+
+/// Reads and parses the content document at an href relative to the
+/// package document.
+fn item_document(
+    archive: &mut ZipArchive<BufReader<File>>,
+    opf_path: &str,
+    href: &str,
+) -> Result<SpineDocument, BoxError> {
+    let opf_dir = match opf_path.rfind('/') {
+        Some(idx) => &opf_path[..=idx],
+        None => "",
+    };
+    let xhtml = read_entry_string(archive, &resolve_href(opf_dir, href))?;
+    SpineDocument::parse(&xhtml)
+}
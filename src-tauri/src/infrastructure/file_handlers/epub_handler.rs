@@ -6,9 +6,13 @@ use std::path::{Path, PathBuf};
 use tokio::task::JoinError;
 use walkdir::WalkDir;
 
+use crate::domain::models::contributor::{Contributor, ROLE_AUTHOR, ROLE_CONTRIBUTOR};
+use crate::domain::models::identifier::{SCHEME_DOI, SCHEME_ISBN};
+use crate::domain::models::isbn::Isbn;
 use crate::infrastructure::database::database::connect_from_pool;
-use crate::infrastructure::file_handlers::BookMetadata;
+use crate::infrastructure::file_handlers::{BookMetadata, ContentProfile, PROFILE_TEXT_CHARS, opf};
 use crate::utils::file::compute_checksum;
+use crate::utils::html::sanitize_html;
 
 /// Marker struct for EPUB-specific operations.
 pub struct EpubHandler;
@@ -47,9 +51,13 @@ pub async fn scan_epubs<P: AsRef<Path> + Send + 'static>(
 
 /// Parses metadata from an EPUB file.
 ///
-/// Extracts title, authors, publishers, publication date, ISBN, cover image,
-/// and computes a SHA-256 checksum. Defaults to "Unknown Author" / "Unknown
-/// Publisher" / "Unknown Title" when metadata fields are missing.
+/// Extracts title, contributors (with MARC roles and `file-as` sort names),
+/// publishers, publication date, cover image, description (sanitized HTML),
+/// language, subjects, rights, modification date, EPUB version and every
+/// identifier typed by scheme (the first valid ISBN, in its ISBN-13 form,
+/// and the first DOI are also surfaced separately), and computes a SHA-256
+/// checksum. Defaults to "Unknown Author" / "Unknown Publisher" / "Unknown
+/// Title" when metadata fields are missing.
 ///
 /// # Arguments
 ///
@@ -78,7 +86,22 @@ pub async fn parse_epub_meta(
             .map(|t| t.value().to_string())
             .unwrap_or_else(|| "Unknown Title".to_string());
 
-        let mut authors: Vec<String> = metadata.creators().map(|c| c.value().to_string()).collect();
+        // `dc:creator` entries default to author, `dc:contributor` entries to
+        // generic contributor, unless a MARC relator role is declared.
+        let creators = metadata
+            .creators()
+            .map(|c| (c, ROLE_AUTHOR))
+            .chain(metadata.contributors().map(|c| (c, ROLE_CONTRIBUTOR)));
+        let mut contributors: Vec<Contributor> = creators
+            .map(|(c, default_role)| Contributor {
+                name: c.value().to_string(),
+                role: c
+                    .main_role()
+                    .map(|r| r.code().to_string())
+                    .unwrap_or_else(|| default_role.to_string()),
+                sort_name: c.file_as().map(|f| f.to_string()),
+            })
+            .collect();
 
         let mut publishers: Vec<String> = metadata
             .publishers()
@@ -89,16 +112,31 @@ pub async fn parse_epub_meta(
             publishers.push("Unknown Publisher".to_string());
         }
 
-        if authors.is_empty() {
-            authors.push("Unknown Author".to_string());
+        if !contributors.iter().any(Contributor::is_author) {
+            contributors.insert(0, Contributor::author("Unknown Author"));
         }
 
         let published_date = metadata.published().map(|d| d.to_string());
 
-        let isbn = metadata
-            .identifiers()
-            .find(|i| i.value().starts_with("urn:isbn:"))
-            .map(|i| i.value().to_string());
+        // rbook does not expose identifier schemes or refinements, so the
+        // remaining fields come from reading the OPF directly.
+        let package = opf::read_package_metadata(&path).unwrap_or_default();
+        let isbn = package
+            .identifiers
+            .iter()
+            .filter(|i| i.scheme == SCHEME_ISBN)
+            .find_map(|i| Isbn::parse(&i.value).ok())
+            .map(|isbn| isbn.to_isbn13());
+        let doi = package
+            .identifiers
+            .iter()
+            .find(|i| i.scheme == SCHEME_DOI)
+            .map(|i| i.value.clone());
+        let description = package
+            .description
+            .as_deref()
+            .map(sanitize_html)
+            .filter(|d| !d.is_empty());
 
         let cover_data = if let Some(cover_image) = book.manifest().cover_image() {
             let mime_type = cover_image.kind().as_str().to_string();
@@ -112,10 +150,18 @@ pub async fn parse_epub_meta(
 
         Ok(BookMetadata {
             title,
-            authors,
+            contributors,
             publishers,
             published_date,
             isbn,
+            doi,
+            description,
+            subjects: package.subjects,
+            language: package.language,
+            rights: package.rights,
+            modified_date: package.modified,
+            identifiers: package.identifiers,
+            epub_version: package.version,
             file_path: path,
             cover_data,
             checksum,
@@ -325,3 +371,56 @@ pub async fn get_cover_image_by_book_id(
         None => Ok(Vec::new()),
     }
 }
+
+/// Reads the spine length and opening text of an EPUB.
+///
+/// Text is taken from the `<body>` of the XHTML spine items in reading
+/// order until [`PROFILE_TEXT_CHARS`] characters are collected.
+///
+/// # Arguments
+///
+/// * `path` - Absolute path to the EPUB file.
+///
+/// # Returns
+///
+/// A [`ContentProfile`] whose `units` is the number of spine items.
+///
+/// # Errors
+///
+/// Returns a boxed error when the file cannot be opened or is not a valid
+/// EPUB.
+pub async fn read_content_profile(
+    path: &str,
+) -> Result<ContentProfile, Box<dyn std::error::Error + Send + Sync>> {
+    let path_str = path.to_string();
+    tokio::task::spawn_blocking(move || {
+        let epub = Epub::open(&path_str)?;
+        let body_selector = Selector::parse("body").unwrap();
+
+        let spine = epub.spine().iter().collect::<Vec<_>>();
+        let mut text = String::new();
+        for item_ref in &spine {
+            if text.len() >= PROFILE_TEXT_CHARS {
+                break;
+            }
+            if let Some(resource) = epub.manifest().by_id(item_ref.idref())
+                && resource.kind().as_str() == "application/xhtml+xml"
+                && let Ok(content) = epub.read_resource_str(resource.resource())
+            {
+                let document = Html::parse_document(&content);
+                if let Some(body) = document.select(&body_selector).next() {
+                    for chunk in body.text() {
+                        text.push_str(chunk);
+                        text.push(' ');
+                    }
+                }
+            }
+        }
+
+        Ok(ContentProfile {
+            units: spine.len() as u32,
+            text: text.chars().take(PROFILE_TEXT_CHARS).collect(),
+        })
+    })
+    .await?
+}
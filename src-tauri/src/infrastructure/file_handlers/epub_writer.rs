@@ -0,0 +1,456 @@
+use quick_xml::escape::escape;
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+use std::path::PathBuf;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::domain::models::contributor::Contributor;
+use crate::infrastructure::file_handlers::opf::{Element, read_opf, resolve_href, scan_package};
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Metadata to write into an EPUB's package document.
+///
+/// Each field describes the complete desired state: `None` or an empty list
+/// removes the corresponding elements from the OPF.
+#[derive(Debug, Clone, Default)]
+pub struct EpubMetadataUpdate {
+    /// Replaces every `dc:title`.
+    pub title: String,
+    /// Replaces every `dc:creator` / `dc:contributor`, in order. Contributors
+    /// with role `aut` are written as `dc:creator`, all others as
+    /// `dc:contributor`.
+    pub contributors: Vec<Contributor>,
+    /// Replaces every `dc:publisher`.
+    pub publisher: Option<String>,
+    /// Replaces the publication `dc:date`; dates for other events are kept.
+    pub published_date: Option<String>,
+    /// Replaces ISBN `dc:identifier`s. Other identifiers are kept.
+    pub isbn: Option<String>,
+    /// Series name, written as calibre and EPUB 3 collection metadata.
+    pub series: Option<String>,
+    /// Position within the series.
+    pub series_index: Option<f32>,
+    /// Cover image bytes and MIME type. `None` leaves the cover untouched.
+    pub cover: Option<(Vec<u8>, String)>,
+}
+
+/// Writes metadata into the OPF package document of an EPUB file.
+///
+/// Only the OPF entry (and the cover image entry, when a cover is supplied)
+/// is rewritten; every other archive entry is copied over in its original
+/// compressed form and order. Within the OPF, only the affected metadata
+/// elements and their `refines` metas are replaced. The new archive is
+/// written next to the original and then renamed over it.
+///
+/// # Arguments
+///
+/// * `path` - Absolute path to the EPUB file.
+/// * `update` - The metadata to write.
+///
+/// # Errors
+///
+/// Returns a boxed error when the archive cannot be read or written, has no
+/// `META-INF/container.xml` rootfile, or the OPF is not well-formed XML.
+pub async fn write_epub_metadata(path: String, update: EpubMetadataUpdate) -> Result<(), BoxError> {
+    tokio::task::spawn_blocking(move || write_epub_metadata_blocking(&path, &update)).await?
+}
+
+fn write_epub_metadata_blocking(path: &str, update: &EpubMetadataUpdate) -> Result<(), BoxError> {
+    let mut archive = ZipArchive::new(BufReader::new(File::open(path)?))?;
+
+    let (opf_path, opf) = read_opf(&mut archive)?;
+    let opf_dir = match opf_path.rfind('/') {
+        Some(idx) => &opf_path[..=idx],
+        None => "",
+    };
+
+    let existing: HashSet<String> = archive.file_names().map(str::to_string).collect();
+    let rewrite = rewrite_opf(&opf, opf_dir, update, &existing)?;
+
+    let tmp_path = PathBuf::from(format!("{path}.tmp"));
+    let result = (|| -> Result<(), BoxError> {
+        let mut writer = ZipWriter::new(BufWriter::new(File::create(&tmp_path)?));
+        writer.set_raw_comment(archive.comment().into())?;
+
+        for i in 0..archive.len() {
+            let entry = archive.by_index_raw(i)?;
+            let replacement = if entry.name() == opf_path {
+                Some(rewrite.opf.as_bytes())
+            } else if rewrite.cover_entry.as_deref() == Some(entry.name()) {
+                update.cover.as_ref().map(|(data, _)| data.as_slice())
+            } else {
+                None
+            };
+
+            match replacement {
+                Some(data) => {
+                    let method = match entry.compression() {
+                        CompressionMethod::Stored => CompressionMethod::Stored,
+                        _ => CompressionMethod::Deflated,
+                    };
+                    let mut options = SimpleFileOptions::default().compression_method(method);
+                    if let Some(modified) = entry.last_modified() {
+                        options = options.last_modified_time(modified);
+                    }
+                    let name = entry.name().to_string();
+                    drop(entry);
+                    writer.start_file(name, options)?;
+                    writer.write_all(data)?;
+                }
+                None => writer.raw_copy_file(entry)?,
+            }
+        }
+
+        if let (Some(name), Some((data, _))) = (&rewrite.new_cover_entry, &update.cover) {
+            let options =
+                SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+            writer.start_file(name.as_str(), options)?;
+            writer.write_all(data)?;
+        }
+
+        writer.finish()?.flush()?;
+        Ok(())
+    })();
+
+    if let Err(e) = result {
+        let _ = std::fs::remove_file(&tmp_path);
+        return Err(e);
+    }
+
+    std::fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+/// Returns an id based on `base` that is not yet in `ids`, and reserves it.
+fn fresh_id(ids: &mut HashSet<String>, base: &str) -> String {
+    let mut id = base.to_string();
+    let mut n = 1;
+    while ids.contains(&id) {
+        n += 1;
+        id = format!("{base}-{n}");
+    }
+    ids.insert(id.clone());
+    id
+}
+
+/// The rewritten OPF plus the archive entries the cover goes into.
+struct OpfRewrite {
+    opf: String,
+    /// Existing entry whose bytes are replaced by the new cover.
+    cover_entry: Option<String>,
+    /// New entry to append for the cover.
+    new_cover_entry: Option<String>,
+}
+
+fn rewrite_opf(
+    opf: &str,
+    opf_dir: &str,
+    update: &EpubMetadataUpdate,
+    existing_entries: &HashSet<String>,
+) -> Result<OpfRewrite, BoxError> {
+    let mut package = scan_package(opf)?;
+    let mut ids = std::mem::take(&mut package.ids);
+    let epub3 = package.is_epub3();
+    let metadata = &package.metadata.children;
+
+    let is_isbn = |el: &Element| {
+        el.name.ends_with("identifier")
+            && (el
+                .attr("scheme")
+                .is_some_and(|s| s.eq_ignore_ascii_case("isbn"))
+                || el.text.to_ascii_lowercase().starts_with("urn:isbn:"))
+    };
+    let series_ids: HashSet<&str> = metadata
+        .iter()
+        .filter(|el| el.attr("property") == Some("collection-type") && el.text == "series")
+        .filter_map(Element::refines)
+        .collect();
+    let unique_isbn = metadata.iter().position(|el| {
+        is_isbn(el)
+            && el.attr("id").is_some()
+            && el.attr("id") == package.unique_identifier.as_deref()
+    });
+
+    let mut replaced: Vec<usize> = Vec::new();
+    for (idx, el) in metadata.iter().enumerate() {
+        let local = el.name.rsplit(':').next().unwrap_or(&el.name);
+        let remove = match local {
+            "title" | "creator" | "contributor" => true,
+            "publisher" => true,
+            "date" => el.attr("event").is_none_or(|e| e == "publication"),
+            "identifier" => is_isbn(el) && Some(idx) != unique_isbn,
+            "meta" => {
+                matches!(
+                    el.attr("name"),
+                    Some("calibre:series" | "calibre:series_index")
+                ) || (el.attr("property") == Some("belongs-to-collection")
+                    && el.attr("id").is_some_and(|id| series_ids.contains(id)))
+                    || (epub3
+                        && el.attr("property") == Some("dcterms:modified")
+                        && el.refines().is_none())
+            }
+            _ => false,
+        };
+        if remove {
+            replaced.push(idx);
+        }
+    }
+
+    let removed_ids: HashSet<&str> = replaced
+        .iter()
+        .filter_map(|&idx| metadata[idx].attr("id"))
+        .collect();
+    for (idx, el) in metadata.iter().enumerate() {
+        if el.refines().is_some_and(|id| removed_ids.contains(id)) && !replaced.contains(&idx) {
+            replaced.push(idx);
+        }
+    }
+
+    // (start, end, replacement), applied back to front.
+    let mut edits: Vec<(usize, usize, String)> = replaced
+        .iter()
+        .map(|&idx| (metadata[idx].lead, metadata[idx].end, String::new()))
+        .collect();
+
+    let mut elements: Vec<String> = Vec::new();
+    elements.push(format!(
+        "<dc:title>{}</dc:title>",
+        escape(update.title.as_str())
+    ));
+
+    for contributor in &update.contributors {
+        let tag = if contributor.is_author() {
+            "dc:creator"
+        } else {
+            "dc:contributor"
+        };
+        let name = escape(contributor.name.as_str());
+        let role = escape(contributor.role.as_str());
+        if epub3 {
+            let id = fresh_id(
+                &mut ids,
+                if contributor.is_author() {
+                    "creator"
+                } else {
+                    "contributor"
+                },
+            );
+            elements.push(format!("<{tag} id=\"{id}\">{name}</{tag}>"));
+            elements.push(format!(
+                "<meta refines=\"#{id}\" property=\"role\" scheme=\"marc:relators\">{role}</meta>"
+            ));
+            if let Some(sort_name) = &contributor.sort_name {
+                elements.push(format!(
+                    "<meta refines=\"#{id}\" property=\"file-as\">{}</meta>",
+                    escape(sort_name.as_str())
+                ));
+            }
+        } else {
+            let file_as = contributor
+                .sort_name
+                .as_ref()
+                .map(|s| format!(" opf:file-as=\"{}\"", escape(s.as_str())))
+                .unwrap_or_default();
+            elements.push(format!(
+                "<{tag} opf:role=\"{role}\"{file_as}>{name}</{tag}>"
+            ));
+        }
+    }
+
+    if let Some(publisher) = &update.publisher {
+        elements.push(format!(
+            "<dc:publisher>{}</dc:publisher>",
+            escape(publisher.as_str())
+        ));
+    }
+
+    if let Some(date) = &update.published_date {
+        let event = if epub3 {
+            ""
+        } else {
+            " opf:event=\"publication\""
+        };
+        elements.push(format!(
+            "<dc:date{event}>{}</dc:date>",
+            escape(date.as_str())
+        ));
+    }
+
+    if let Some(isbn) = &update.isbn {
+        match unique_isbn.map(|idx| &metadata[idx]) {
+            Some(el) => {
+                let value = if el.text.to_ascii_lowercase().starts_with("urn:isbn:") {
+                    format!("urn:isbn:{isbn}")
+                } else {
+                    isbn.clone()
+                };
+                let (start, end) = el.inner.unwrap_or((el.tag_end, el.tag_end));
+                edits.push((start, end, escape(value.as_str()).into_owned()));
+            }
+            None if epub3 => {
+                elements.push(format!(
+                    "<dc:identifier>urn:isbn:{}</dc:identifier>",
+                    escape(isbn.as_str())
+                ));
+            }
+            None => {
+                elements.push(format!(
+                    "<dc:identifier opf:scheme=\"ISBN\">{}</dc:identifier>",
+                    escape(isbn.as_str())
+                ));
+            }
+        }
+    }
+
+    if let Some(series) = &update.series {
+        let series = escape(series.as_str());
+        elements.push(format!(
+            "<meta name=\"calibre:series\" content=\"{series}\"/>"
+        ));
+        if let Some(index) = update.series_index {
+            elements.push(format!(
+                "<meta name=\"calibre:series_index\" content=\"{index}\"/>"
+            ));
+        }
+        if epub3 {
+            let id = fresh_id(&mut ids, "series");
+            elements.push(format!(
+                "<meta property=\"belongs-to-collection\" id=\"{id}\">{series}</meta>"
+            ));
+            elements.push(format!(
+                "<meta refines=\"#{id}\" property=\"collection-type\">series</meta>"
+            ));
+            if let Some(index) = update.series_index {
+                elements.push(format!(
+                    "<meta refines=\"#{id}\" property=\"group-position\">{index}</meta>"
+                ));
+            }
+        }
+    }
+
+    if epub3 {
+        let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ");
+        elements.push(format!("<meta property=\"dcterms:modified\">{now}</meta>"));
+    }
+
+    let mut cover_entry = None;
+    let mut new_cover_entry = None;
+    if let Some((_, media_type)) = &update.cover {
+        let metadata = &package.metadata.children;
+        let manifest = &package.manifest.children;
+        let cover_meta = metadata
+            .iter()
+            .position(|el| el.name.ends_with("meta") && el.attr("name") == Some("cover"));
+        let cover_item = manifest
+            .iter()
+            .find(|el| {
+                el.attr("properties")
+                    .is_some_and(|p| p.split_whitespace().any(|p| p == "cover-image"))
+            })
+            .or_else(|| {
+                let id = cover_meta.and_then(|idx| metadata[idx].attr("content"))?;
+                manifest.iter().find(|el| el.attr("id") == Some(id))
+            });
+
+        match cover_item {
+            Some(item) => {
+                let href = item.attr("href").unwrap_or_default();
+                cover_entry = Some(resolve_href(opf_dir, href));
+                if item.attr("media-type") != Some(media_type.as_str()) {
+                    let tag = &opf[item.start..item.tag_end];
+                    if let Some(tag) = replace_attr(tag, "media-type", media_type) {
+                        edits.push((item.start, item.tag_end, tag));
+                    }
+                }
+            }
+            None => {
+                let ext = match media_type.as_str() {
+                    "image/jpeg" => "jpg",
+                    "image/png" => "png",
+                    "image/gif" => "gif",
+                    "image/webp" => "webp",
+                    "image/svg+xml" => "svg",
+                    _ => "img",
+                };
+                let mut href = format!("cover.{ext}");
+                let mut n = 1;
+                while existing_entries.contains(&format!("{opf_dir}{href}")) {
+                    n += 1;
+                    href = format!("cover-{n}.{ext}");
+                }
+                new_cover_entry = Some(format!("{opf_dir}{href}"));
+
+                if let Some(idx) = cover_meta
+                    && !replaced.contains(&idx)
+                {
+                    edits.push((metadata[idx].lead, metadata[idx].end, String::new()));
+                }
+
+                let id = fresh_id(&mut ids, "cover-image");
+                let properties = if epub3 {
+                    " properties=\"cover-image\""
+                } else {
+                    ""
+                };
+                let item = format!(
+                    "<item id=\"{id}\" href=\"{}\" media-type=\"{}\"{properties}/>",
+                    escape(href.as_str()),
+                    escape(media_type.as_str())
+                );
+                let indent = package.manifest.indent(opf);
+                let at = package.manifest.insert_at(opf);
+                edits.push((at, at, format!("\n{indent}{item}")));
+                elements.push(format!("<meta name=\"cover\" content=\"{id}\"/>"));
+            }
+        }
+    }
+
+    let indent = package.metadata.indent(opf);
+    let at = package.metadata.insert_at(opf);
+    let inserted: String = elements
+        .iter()
+        .map(|el| format!("\n{indent}{el}"))
+        .collect();
+    edits.push((at, at, inserted));
+
+    if !epub3 && !opf.contains("xmlns:opf=") {
+        let at = package.metadata.open_end - 1;
+        edits.push((
+            at,
+            at,
+            " xmlns:opf=\"http://www.idpf.org/2007/opf\"".to_string(),
+        ));
+    }
+
+    // Insertions at a position must land after any removal ending there, so
+    // ties are ordered with the insertion first when applying back to front.
+    edits.sort_by(|a, b| b.0.cmp(&a.0).then(b.1.cmp(&a.1)));
+    let mut opf = opf.to_string();
+    for (start, end, text) in edits {
+        opf.replace_range(start..end, &text);
+    }
+
+    Ok(OpfRewrite {
+        opf,
+        cover_entry,
+        new_cover_entry,
+    })
+}
+
+/// Replaces the value of attribute `name` in a raw start tag.
+fn replace_attr(tag: &str, name: &str, value: &str) -> Option<String> {
+    let key = format!(" {name}=");
+    let key_start = tag.find(&key)?;
+    let value_start = key_start + key.len();
+    let quote = tag[value_start..].chars().next()?;
+    let value_end = value_start + 1 + tag[value_start + 1..].find(quote)?;
+    Some(format!(
+        "{}{quote}{}{}",
+        &tag[..value_start],
+        escape(value),
+        &tag[value_end..]
+    ))
+}
@@ -1,25 +1,66 @@
+pub mod cover_handler;
+pub mod epub_cfi;
 pub mod epub_handler;
+pub mod epub_writer;
 pub mod mobi_handler;
+pub mod opf;
 pub mod pdf_handler;
+pub mod pdf_writer;
+pub mod removal_handler;
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::models::contributor::Contributor;
+use crate::domain::models::identifier::Identifier;
+
+/// Upper bound on the characters of text kept in a [`ContentProfile`].
+pub const PROFILE_TEXT_CHARS: usize = 20_000;
+
+/// Length and opening text of an ebook, used to compare files when looking
+/// for duplicates.
+#[derive(Debug, Clone, Default)]
+pub struct ContentProfile {
+    /// Number of spine items (EPUB) or pages (PDF).
+    pub units: u32,
+    /// Plain text from the start of the book, at most
+    /// [`PROFILE_TEXT_CHARS`] characters.
+    pub text: String,
+}
 
 /// Metadata extracted from an ebook file.
 ///
 /// Used by the import pipeline and the metadata service to populate book
-/// records and present metadata to the frontend.
-#[derive(Serialize, Clone)]
+/// records and present metadata to the frontend. Metadata provider candidates
+/// use the same shape and are sent back by the frontend when applied.
+#[derive(Serialize, Deserialize, Clone)]
 pub struct BookMetadata {
     /// Book title from the file's metadata.
     pub title: String,
-    /// List of author names from the file's metadata.
-    pub authors: Vec<String>,
+    /// Credited people from the file's metadata, with roles and sort names,
+    /// in document order.
+    pub contributors: Vec<Contributor>,
     /// Publication date string, if present.
     pub published_date: Option<String>,
     /// List of publisher names from the file's metadata.
     pub publishers: Vec<String>,
-    /// ISBN, if present in the file's metadata.
+    /// ISBN, if present in the file's metadata or front matter.
     pub isbn: Option<String>,
+    /// DOI, if found in the file's front matter.
+    pub doi: Option<String>,
+    /// Summary or abstract, if present. EPUB descriptions are sanitized HTML.
+    pub description: Option<String>,
+    /// Subject keywords, if present.
+    pub subjects: Vec<String>,
+    /// BCP 47 language tag of the content, if declared.
+    pub language: Option<String>,
+    /// Copyright or license statement, if present.
+    pub rights: Option<String>,
+    /// Last modification date declared in the metadata, if any.
+    pub modified_date: Option<String>,
+    /// Every identifier found in the file, typed by scheme.
+    pub identifiers: Vec<Identifier>,
+    /// EPUB specification version (`None` for other formats).
+    pub epub_version: Option<String>,
     /// Absolute path to the ebook file on disk.
     pub file_path: String,
     /// Raw cover image bytes and MIME type, if extracted.
@@ -0,0 +1,364 @@
+//! Reading the OPF package document of an EPUB archive.
+//!
+//! Shared by the EPUB parser (for metadata `rbook` does not expose) and the
+//! metadata writer (which splices replacements into the original text).
+
+use quick_xml::Reader;
+use quick_xml::escape::unescape;
+use quick_xml::events::{BytesStart, Event};
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{BufReader, Read};
+use zip::ZipArchive;
+
+use crate::domain::models::identifier::Identifier;
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Reads a text entry of the archive by name.
+pub(crate) fn read_entry_string<R: Read + std::io::Seek>(
+    archive: &mut ZipArchive<R>,
+    name: &str,
+) -> Result<String, BoxError> {
+    let mut contents = String::new();
+    archive.by_name(name)?.read_to_string(&mut contents)?;
+    Ok(contents)
+}
+
+/// Locates the OPF through `META-INF/container.xml` and returns its archive
+/// path and contents.
+pub(crate) fn read_opf<R: Read + std::io::Seek>(
+    archive: &mut ZipArchive<R>,
+) -> Result<(String, String), BoxError> {
+    let container = read_entry_string(archive, "META-INF/container.xml")?;
+    let opf_path = find_rootfile(&container)?.ok_or("container.xml has no rootfile")?;
+    let opf = read_entry_string(archive, &opf_path)?;
+    Ok((opf_path, opf))
+}
+
+/// Returns the `full-path` of the first `rootfile` in `container.xml`.
+fn find_rootfile(container: &str) -> Result<Option<String>, BoxError> {
+    let mut reader = Reader::from_str(container);
+    loop {
+        match reader.read_event()? {
+            Event::Start(e) | Event::Empty(e) if e.local_name().as_ref() == b"rootfile" => {
+                return Ok(attributes(&e)?.remove("full-path"));
+            }
+            Event::Eof => return Ok(None),
+            _ => {}
+        }
+    }
+}
+
+/// Collects the attributes of a tag keyed by their local name.
+fn attributes(tag: &BytesStart) -> Result<HashMap<String, String>, BoxError> {
+    let mut attrs = HashMap::new();
+    for attr in tag.attributes() {
+        let attr = attr?;
+        let key = String::from_utf8_lossy(attr.key.local_name().as_ref()).into_owned();
+        attrs.insert(key, attr.unescape_value()?.into_owned());
+    }
+    Ok(attrs)
+}
+
+/// A direct child of `<metadata>` or `<manifest>`, with its byte span in the OPF.
+pub(crate) struct Element {
+    pub(crate) name: String,
+    pub(crate) attrs: HashMap<String, String>,
+    pub(crate) text: String,
+    /// Start of the whitespace preceding the element.
+    pub(crate) lead: usize,
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+    /// End of the start tag.
+    pub(crate) tag_end: usize,
+    /// Span of the element's content, for non-empty elements.
+    pub(crate) inner: Option<(usize, usize)>,
+}
+
+impl Element {
+    pub(crate) fn attr(&self, name: &str) -> Option<&str> {
+        self.attrs.get(name).map(String::as_str)
+    }
+
+    pub(crate) fn refines(&self) -> Option<&str> {
+        self.attr("refines").and_then(|r| r.strip_prefix('#'))
+    }
+}
+
+/// The open/close positions of `<metadata>` or `<manifest>` and its children.
+#[derive(Default)]
+pub(crate) struct Section {
+    pub(crate) open_end: usize,
+    pub(crate) close_start: usize,
+    pub(crate) children: Vec<Element>,
+}
+
+impl Section {
+    /// Indentation used by the section's children.
+    pub(crate) fn indent<'a>(&self, opf: &'a str) -> &'a str {
+        let gap = match self.children.first() {
+            Some(first) => &opf[self.open_end..first.start],
+            None => "",
+        };
+        match gap.rfind('\n') {
+            Some(idx) => &gap[idx + 1..],
+            None => "    ",
+        }
+    }
+
+    /// Position just before the whitespace preceding the closing tag.
+    pub(crate) fn insert_at(&self, opf: &str) -> usize {
+        self.open_end + opf[self.open_end..self.close_start].trim_end().len()
+    }
+}
+
+/// The parts of an OPF package document that are read or rewritten.
+#[derive(Default)]
+pub(crate) struct Package {
+    pub(crate) version: String,
+    pub(crate) unique_identifier: Option<String>,
+    pub(crate) metadata: Section,
+    pub(crate) manifest: Section,
+    pub(crate) ids: HashSet<String>,
+    /// CFI step to `<spine>`: twice its position among the children of
+    /// `<package>`.
+    pub(crate) spine_step: u32,
+    /// The `idref` of each spine `itemref`, in reading order.
+    pub(crate) spine: Vec<String>,
+}
+
+impl Package {
+    pub(crate) fn is_epub3(&self) -> bool {
+        self.version.starts_with('3')
+    }
+}
+
+pub(crate) fn scan_package(opf: &str) -> Result<Package, BoxError> {
+    let mut package = Package::default();
+    let mut reader = Reader::from_str(opf);
+    let mut stack: Vec<String> = Vec::new();
+    let mut open: Option<Element> = None;
+    let mut package_children = 0;
+
+    loop {
+        let start = reader.buffer_position() as usize;
+        let event = reader.read_event()?;
+        let end = reader.buffer_position() as usize;
+
+        match event {
+            Event::Start(ref e) | Event::Empty(ref e) => {
+                let name = String::from_utf8_lossy(e.name().as_ref()).into_owned();
+                let local = String::from_utf8_lossy(e.local_name().as_ref()).into_owned();
+                let attrs = attributes(e)?;
+                if let Some(id) = attrs.get("id") {
+                    package.ids.insert(id.clone());
+                }
+                let is_empty = matches!(event, Event::Empty(_));
+                if stack.len() == 1 {
+                    package_children += 1;
+                }
+
+                match (stack.last().map(String::as_str), local.as_str()) {
+                    (_, "package") if stack.is_empty() => {
+                        package.version = attrs.get("version").cloned().unwrap_or_default();
+                        package.unique_identifier = attrs.get("unique-identifier").cloned();
+                    }
+                    (Some("package"), "metadata") => package.metadata.open_end = end,
+                    (Some("package"), "manifest") => package.manifest.open_end = end,
+                    (Some("package"), "spine") => package.spine_step = package_children * 2,
+                    (Some("spine"), "itemref") => {
+                        package
+                            .spine
+                            .push(attrs.get("idref").cloned().unwrap_or_default());
+                    }
+                    (Some("metadata" | "manifest"), _) if stack.len() == 2 => {
+                        let element = Element {
+                            name,
+                            attrs,
+                            text: String::new(),
+                            lead: opf[..start].trim_end().len(),
+                            start,
+                            end,
+                            tag_end: end,
+                            inner: None,
+                        };
+                        if is_empty {
+                            push_child(&mut package, &stack, element);
+                        } else {
+                            open = Some(element);
+                        }
+                    }
+                    _ => {}
+                }
+
+                if !is_empty {
+                    stack.push(local);
+                }
+            }
+            Event::End(e) => {
+                stack.pop();
+                let local = e.local_name();
+                if stack.len() == 2 {
+                    if let Some(mut element) = open.take() {
+                        let inner = &opf[element.tag_end..start];
+                        element.text = unescape(inner)
+                            .map(|t| t.into_owned())
+                            .unwrap_or_else(|_| inner.to_string())
+                            .trim()
+                            .to_string();
+                        element.inner = Some((element.tag_end, start));
+                        element.end = end;
+                        push_child(&mut package, &stack, element);
+                    }
+                } else if stack.len() == 1 {
+                    match local.as_ref() {
+                        b"metadata" => package.metadata.close_start = start,
+                        b"manifest" => package.manifest.close_start = start,
+                        _ => {}
+                    }
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    if package.metadata.close_start == 0 || package.manifest.close_start == 0 {
+        return Err("OPF has no metadata or manifest section".into());
+    }
+    Ok(package)
+}
+
+fn push_child(package: &mut Package, stack: &[String], element: Element) {
+    match stack.last().map(String::as_str) {
+        Some("metadata") => package.metadata.children.push(element),
+        Some("manifest") => package.manifest.children.push(element),
+        _ => {}
+    }
+}
+
+/// Resolves a manifest `href` against the OPF directory into an archive path.
+pub(crate) fn resolve_href(opf_dir: &str, href: &str) -> String {
+    let href = href.split('#').next().unwrap_or(href);
+    let mut decoded = Vec::with_capacity(href.len());
+    let bytes = href.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%'
+            && let Some(byte) = href
+                .get(i + 1..i + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+        {
+            decoded.push(byte);
+            i += 3;
+            continue;
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+    let href = String::from_utf8_lossy(&decoded);
+
+    let mut parts: Vec<&str> = opf_dir.split('/').filter(|p| !p.is_empty()).collect();
+    for part in href.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop();
+            }
+            part => parts.push(part),
+        }
+    }
+    parts.join("/")
+}
+
+/// Package metadata that `rbook` does not expose in full.
+#[derive(Debug, Default)]
+pub(crate) struct PackageMetadata {
+    /// The `version` attribute of `<package>` (e.g. `"2.0"`, `"3.0"`).
+    pub(crate) version: Option<String>,
+    /// First `dc:description`, unescaped (may contain HTML markup).
+    pub(crate) description: Option<String>,
+    /// First `dc:language`.
+    pub(crate) language: Option<String>,
+    /// Every `dc:subject`, in document order.
+    pub(crate) subjects: Vec<String>,
+    /// First `dc:rights`.
+    pub(crate) rights: Option<String>,
+    /// EPUB 3 `dcterms:modified`, or an EPUB 2 `dc:date` with
+    /// `opf:event="modification"`.
+    pub(crate) modified: Option<String>,
+    /// Every `dc:identifier`, typed by prefix, declared scheme or shape.
+    pub(crate) identifiers: Vec<Identifier>,
+}
+
+/// Reads the package metadata of an EPUB file.
+///
+/// The declared scheme of an identifier is its EPUB 2 `opf:scheme`
+/// attribute or its EPUB 3 `identifier-type` refinement; ONIX code list 5
+/// values for ISBN (`02`, `15`) and DOI (`06`) are translated.
+///
+/// # Errors
+///
+/// Returns a boxed error when the archive, its container or the OPF cannot
+/// be read or parsed.
+pub(crate) fn read_package_metadata(path: &str) -> Result<PackageMetadata, BoxError> {
+    let mut archive = ZipArchive::new(BufReader::new(File::open(path)?))?;
+    let (_, opf) = read_opf(&mut archive)?;
+    let package = scan_package(&opf)?;
+    let elements = &package.metadata.children;
+
+    let local = |el: &Element| el.name.rsplit(':').next().unwrap_or(&el.name).to_string();
+    let texts = |name: &str| -> Vec<String> {
+        elements
+            .iter()
+            .filter(|el| local(el) == name && !el.text.is_empty())
+            .map(|el| el.text.clone())
+            .collect()
+    };
+
+    let modified = elements
+        .iter()
+        .find(|el| el.attr("property") == Some("dcterms:modified") && el.refines().is_none())
+        .or_else(|| {
+            elements
+                .iter()
+                .find(|el| local(el) == "date" && el.attr("event") == Some("modification"))
+        })
+        .map(|el| el.text.clone())
+        .filter(|t| !t.is_empty());
+
+    let identifiers = elements
+        .iter()
+        .filter(|el| local(el) == "identifier" && !el.text.is_empty())
+        .map(|el| {
+            let refined_type = el.attr("id").and_then(|id| {
+                elements.iter().find(|m| {
+                    m.refines() == Some(id) && m.attr("property") == Some("identifier-type")
+                })
+            });
+            let declared = match refined_type {
+                Some(meta) if meta.attr("scheme") == Some("onix:codelist5") => {
+                    match meta.text.as_str() {
+                        "02" | "15" => Some("isbn".to_string()),
+                        "06" => Some("doi".to_string()),
+                        _ => None,
+                    }
+                }
+                Some(meta) => Some(meta.text.clone()),
+                None => el.attr("scheme").map(str::to_string),
+            };
+            Identifier::classify(&el.text, declared.as_deref())
+        })
+        .collect();
+
+    Ok(PackageMetadata {
+        version: Some(package.version.clone()).filter(|v| !v.is_empty()),
+        description: texts("description").into_iter().next(),
+        language: texts("language").into_iter().next(),
+        subjects: texts("subject"),
+        rights: texts("rights").into_iter().next(),
+        modified,
+        identifiers,
+    })
+}
@@ -1,13 +1,26 @@
 use base64::{Engine as _, engine::general_purpose};
+use image::codecs::jpeg::JpegEncoder;
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageFormat};
 use pdf_oxide::PdfDocument;
 use pdf_oxide::extractors::xmp::XmpExtractor;
+use pdf_oxide::object::Object;
 use pdf_oxide::rendering::{RenderOptions, render_page};
-use serde::Serialize;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::io::Cursor;
+use std::ops::Range;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock, Mutex, PoisonError};
+use std::time::SystemTime;
 use tokio::task::JoinError;
 use walkdir::WalkDir;
 
-use crate::infrastructure::file_handlers::BookMetadata;
+use crate::domain::models::contributor::Contributor;
+use crate::domain::models::identifier::{Identifier, SCHEME_DOI, SCHEME_ISBN};
+use crate::domain::models::isbn::Isbn;
+use crate::domain::models::partial_date::PartialDate;
+use crate::infrastructure::file_handlers::{BookMetadata, ContentProfile, PROFILE_TEXT_CHARS};
 use crate::utils::file::compute_checksum;
 
 /// A rendered PDF page returned to the frontend.
@@ -15,24 +28,133 @@ use crate::utils::file::compute_checksum;
 pub struct PdfPage {
     /// 0-based page number.
     pub page_number: u32,
-    /// Base64-encoded PNG image data of the rendered page.
+    /// Base64-encoded image data of the rendered page, or of its clip.
     pub image_data: String,
+    /// Format of `image_data`.
+    pub format: PdfImageFormat,
     /// Width of the rendered image in pixels.
     pub width: u32,
     /// Height of the rendered image in pixels.
     pub height: u32,
-    /// Text spans extracted from the page with bounding box coordinates.
+    /// Width of the whole rendered page in pixels; the image's when it is
+    /// not clipped.
+    pub page_width: u32,
+    /// Height of the whole rendered page in pixels.
+    pub page_height: u32,
+    /// The part of the page the image shows, when it was clipped.
+    pub clip: Option<PixelRect>,
+    /// Text spans extracted from the page with bounding box coordinates, in
+    /// PDF user space.
     pub text_spans: Vec<PdfTextSpan>,
+    /// Pixels of the rendered image per PDF point.
+    pub scale: f32,
+    /// Clockwise rotation the page is displayed with (its `/Rotate`): 0, 90,
+    /// 180 or 270.
+    pub rotation: u32,
+    /// The page's characters in content order, in image pixels.
+    pub chars: Vec<PdfChar>,
+    /// The page's words in content order, in image pixels.
+    pub words: Vec<PdfWord>,
+}
+
+/// A character on a rendered PDF page.
+///
+/// The box is in pixels of the rendered image, with the origin at its
+/// top-left corner and `y` growing downwards.
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct PdfChar {
+    /// The character.
+    pub text: String,
+    /// X coordinate of the box's left edge.
+    pub x: f32,
+    /// Y coordinate of the box's top edge.
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+/// A word on a rendered PDF page: a run of characters on one line without
+/// whitespace or a wide gap between them.
+///
+/// The box is in pixels of the rendered image, like [`PdfChar`]'s.
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct PdfWord {
+    /// The word's characters.
+    pub text: String,
+    /// X coordinate of the box's left edge.
+    pub x: f32,
+    /// Y coordinate of the box's top edge.
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    /// Index of the word's first character in [`PdfPage::chars`].
+    pub start: usize,
+    /// Index just past the word's last character.
+    pub end: usize,
+}
+
+/// How to render a PDF page.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PdfRenderOptions {
+    /// Resolution in dots per inch; [`DEFAULT_RENDER_DPI`] when neither
+    /// this nor `target_width` is given.
+    pub dpi: Option<u32>,
+    /// Width of the whole rendered page in pixels; takes precedence over
+    /// `dpi`.
+    pub target_width: Option<u32>,
+    /// Part of the page to return, in pixels of the whole rendered page, for
+    /// tiled rendering at high zoom.
+    pub clip: Option<PixelRect>,
+    /// Output image format.
+    #[serde(default)]
+    pub format: PdfImageFormat,
+    /// JPEG quality from 1 to 100; [`DEFAULT_JPEG_QUALITY`] when not given.
+    /// WebP images are lossless and ignore it.
+    pub quality: Option<u8>,
+}
+
+/// An axis-aligned rectangle in pixels, `(x, y)` its top-left corner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PixelRect {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Image format of a rendered page.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PdfImageFormat {
+    #[default]
+    Png,
+    Jpeg,
+    Webp,
+}
+
+/// Maps PDF user space onto the pixels of a rendered page.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PageGeometry {
+    /// The displayed area of the page (its `CropBox`, else its `MediaBox`)
+    /// in user space: `[left, bottom, right, top]`.
+    pub page_box: [f32; 4],
+    /// Clockwise display rotation: 0, 90, 180 or 270.
+    pub rotation: u32,
+    /// Pixels per point.
+    pub scale: f32,
 }
 
 /// A text span extracted from a PDF page with positional information.
+///
+/// The bounding box is in PDF user space: points, with the origin at the
+/// bottom-left of the page.
 #[derive(Serialize, Clone)]
 pub struct PdfTextSpan {
     /// The text content of this span.
     pub text: String,
-    /// X coordinate of the bounding box origin.
+    /// X coordinate of the bounding box's left edge.
     pub x: f32,
-    /// Y coordinate of the bounding box origin.
+    /// Y coordinate of the bounding box's bottom edge.
     pub y: f32,
     /// Width of the bounding box.
     pub width: f32,
@@ -72,10 +194,38 @@ pub async fn scan_pdfs<P: AsRef<Path> + Send + 'static>(
     .await
 }
 
-/// Parses metadata from a PDF file using XMP metadata extraction.
+/// Resolution pages are rendered at unless a request asks otherwise.
+pub const DEFAULT_RENDER_DPI: u32 = 150;
+
+/// JPEG quality used when a render request names none.
+pub const DEFAULT_JPEG_QUALITY: u8 = 85;
+
+/// Highest resolution a page can be rendered at.
+const MAX_RENDER_DPI: u32 = 2400;
+
+/// Most pixels a whole-page render may have. Tiles are cut from a
+/// whole-page render, so this also bounds how far a page can be zoomed.
+const MAX_RENDER_PIXELS: u64 = 80_000_000;
+
+/// Most pixels of whole-page renders kept in [`RENDER_CACHE`]: enough for
+/// one page at the highest zoom, or several at lower ones.
+const RENDER_CACHE_PIXELS: u64 = MAX_RENDER_PIXELS;
+
+/// Whole-page renders that tiles were cut from, least recently used first,
+/// so the other tiles of the page do not render it again.
+static RENDER_CACHE: LazyLock<Mutex<Vec<CachedRender>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+
+/// Number of leading pages scanned for an ISBN or DOI.
+const IDENTIFIER_SCAN_PAGES: usize = 5;
+
+/// Parses metadata from a PDF file.
 ///
-/// Falls back to defaults ("Unknown Author", "Unknown Title", etc.) when XMP
-/// data is missing. ISBN is not extracted from PDFs.
+/// Each field is taken from the first source that provides it: XMP metadata,
+/// then the document Info dictionary (`Title`, `Author`, `Subject`,
+/// `Keywords`, `CreationDate`), then the filename for the title. Generic
+/// titles such as "Untitled" or "Microsoft Word - draft.docx" are ignored.
+/// The text of the first few pages is scanned for an ISBN and a DOI. The
+/// producing application (`CreatorTool`) is never used as the publisher.
 ///
 /// # Arguments
 ///
@@ -83,8 +233,10 @@ pub async fn scan_pdfs<P: AsRef<Path> + Send + 'static>(
 ///
 /// # Returns
 ///
-/// A populated [`BookMetadata`] struct with extracted title, authors,
-/// publisher, creation date, and the file's SHA-256 checksum.
+/// A populated [`BookMetadata`] struct with extracted title, authors (role `aut`),
+/// creation date, description, subjects, ISBN, DOI, and the file's SHA-256
+/// checksum. Authors and publisher default to "Unknown Author" / "Unknown
+/// Publisher".
 ///
 /// # Errors
 ///
@@ -98,39 +250,79 @@ pub async fn parse_pdf_meta(
     tokio::task::spawn_blocking(move || {
         let doc = PdfDocument::open(&path)?;
 
-        let mut title = "Unknown Title".to_string();
-        let mut authors = vec!["Unknown Author".to_string()];
-        let mut publishers = vec!["Unknown Publisher".to_string()];
+        let mut title: Option<String> = None;
+        let mut contributors: Vec<Contributor> = Vec::new();
         let mut published_date: Option<String> = None;
 
         if let Ok(Some(xmp)) = XmpExtractor::extract(&doc) {
-            if let Some(t) = &xmp.dc_title
-                && !t.is_empty()
-            {
-                title = t.clone();
-            }
+            title = xmp.dc_title.clone().filter(|t| !is_generic_title(t));
+            contributors = xmp
+                .dc_creator
+                .iter()
+                .filter(|c| !c.trim().is_empty())
+                .map(Contributor::author)
+                .collect();
+            published_date = xmp.xmp_create_date.clone().filter(|d| !d.is_empty());
+        }
 
-            if !xmp.dc_creator.is_empty() {
-                authors = xmp.dc_creator.clone();
-            }
+        let info = read_info_dictionary(&doc).unwrap_or_default();
+        if title.is_none() {
+            title = info.title.filter(|t| !is_generic_title(t));
+        }
+        if contributors.is_empty()
+            && let Some(author) = &info.author
+        {
+            contributors = split_authors(author)
+                .into_iter()
+                .map(Contributor::author)
+                .collect();
+        }
+        if published_date.is_none() {
+            published_date = info.creation_date.as_deref().and_then(parse_pdf_date);
+        }
+        let subjects = info
+            .keywords
+            .as_deref()
+            .map(split_keywords)
+            .unwrap_or_default();
 
-            if let Some(tool) = &xmp.xmp_creator_tool
-                && !tool.is_empty()
-            {
-                publishers = vec![tool.clone()];
-            }
+        let title = title.unwrap_or_else(|| title_from_filename(&path));
+        if contributors.is_empty() {
+            contributors.push(Contributor::author("Unknown Author"));
+        }
 
-            if let Some(date) = &xmp.xmp_create_date {
-                published_date = Some(date.clone());
+        let mut isbn = None;
+        let mut doi = None;
+        let page_count = doc.page_count().unwrap_or(0);
+        for idx in 0..page_count.min(IDENTIFIER_SCAN_PAGES) {
+            let Ok(text) = doc.extract_text(idx) else {
+                continue;
+            };
+            isbn = isbn.or_else(|| find_isbn(&text));
+            doi = doi.or_else(|| find_doi(&text));
+            if isbn.is_some() && doi.is_some() {
+                break;
             }
         }
 
         Ok(BookMetadata {
             title,
-            authors,
+            contributors,
             published_date,
-            publishers,
-            isbn: None,
+            publishers: vec!["Unknown Publisher".to_string()],
+            identifiers: isbn
+                .iter()
+                .map(|v| Identifier::new(SCHEME_ISBN, v.clone()))
+                .chain(doi.iter().map(|v| Identifier::new(SCHEME_DOI, v.clone())))
+                .collect(),
+            isbn,
+            doi,
+            description: info.subject,
+            subjects,
+            language: None,
+            rights: None,
+            modified_date: info.mod_date.as_deref().and_then(parse_pdf_date),
+            epub_version: None,
             file_path: path,
             cover_data: None,
             checksum,
@@ -139,31 +331,170 @@ pub async fn parse_pdf_meta(
     .await?
 }
 
-/// Renders the first page of a PDF as a PNG image (150 DPI).
+/// Text entries of a PDF's document Info dictionary.
+#[derive(Default)]
+struct PdfInfo {
+    title: Option<String>,
+    author: Option<String>,
+    subject: Option<String>,
+    keywords: Option<String>,
+    creation_date: Option<String>,
+    mod_date: Option<String>,
+}
+
+/// Reads the trailer's `/Info` dictionary, resolving an indirect reference.
+fn read_info_dictionary(doc: &PdfDocument) -> Option<PdfInfo> {
+    let info = doc.trailer().as_dict()?.get("Info")?.clone();
+    let info = match info.as_reference() {
+        Some(obj_ref) => doc.load_object(obj_ref).ok()?,
+        None => info,
+    };
+    let dict = info.as_dict()?;
+
+    let text = |key: &str| {
+        let value = dict.get(key)?;
+        let value = match value.as_reference() {
+            Some(obj_ref) => doc.load_object(obj_ref).ok()?,
+            None => value.clone(),
+        };
+        let decoded = decode_pdf_text(value.as_string()?);
+        let trimmed = decoded.trim();
+        (!trimmed.is_empty()).then(|| trimmed.to_string())
+    };
+
+    Some(PdfInfo {
+        title: text("Title"),
+        author: text("Author"),
+        subject: text("Subject"),
+        keywords: text("Keywords"),
+        creation_date: text("CreationDate"),
+        mod_date: text("ModDate"),
+    })
+}
+
+/// Decodes a PDF text string: UTF-16BE with a byte order mark, UTF-8 with a
+/// BOM, or PDFDocEncoding (treated as Latin-1).
+fn decode_pdf_text(bytes: &[u8]) -> String {
+    if let Some(utf16) = bytes.strip_prefix(&[0xFE, 0xFF]) {
+        let units: Vec<u16> = utf16
+            .chunks_exact(2)
+            .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+            .collect();
+        return String::from_utf16_lossy(&units);
+    }
+    if let Some(utf8) = bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]) {
+        return String::from_utf8_lossy(utf8).into_owned();
+    }
+    bytes.iter().map(|&b| b as char).collect()
+}
+
+/// Converts a PDF date (`D:YYYYMMDDHHmmSS...`) to `YYYY`, `YYYY-MM` or
+/// `YYYY-MM-DD`, depending on how much of the date is present.
+fn parse_pdf_date(raw: &str) -> Option<String> {
+    PartialDate::parse(raw).map(|d| d.to_string())
+}
+
+/// Splits an Info `Author` entry into names on `;`, `&` and " and ".
+///
+/// Commas are kept since they usually separate family and given names.
+fn split_authors(raw: &str) -> Vec<String> {
+    raw.split([';', '&'])
+        .flat_map(|part| part.split(" and "))
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Splits an Info `Keywords` entry on `,` or `;`.
+fn split_keywords(raw: &str) -> Vec<String> {
+    raw.split([',', ';'])
+        .map(str::trim)
+        .filter(|k| !k.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Whether a title is a placeholder written by the producing application.
+fn is_generic_title(title: &str) -> bool {
+    let lower = title.trim().to_lowercase();
+    lower.is_empty()
+        || matches!(lower.as_str(), "untitled" | "untitled document" | "title")
+        || lower.starts_with("microsoft word - ")
+        || lower.starts_with("microsoft powerpoint - ")
+        || [".doc", ".docx", ".odt", ".rtf", ".tex", ".indd", ".pdf"]
+            .iter()
+            .any(|ext| lower.ends_with(ext))
+}
+
+/// Derives a title from the file stem, turning `_` separators into spaces.
+fn title_from_filename(path: &str) -> String {
+    let stem = Path::new(path)
+        .file_stem()
+        .map(|s| s.to_string_lossy().replace('_', " "))
+        .unwrap_or_default();
+    let stem = stem.split_whitespace().collect::<Vec<_>>().join(" ");
+    if stem.is_empty() {
+        "Unknown Title".to_string()
+    } else {
+        stem
+    }
+}
+
+/// An `ISBN`-labelled 10- or 13-digit number, hyphens and spaces allowed.
+static ISBN_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\bISBN(?:-1[03])?[:\s]*((?:[0-9][\s-]?){12}[0-9]|(?:[0-9][\s-]?){9}[0-9X])\b")
+        .expect("valid regex")
+});
+
+/// A DOI: `10.`, a registrant code and a suffix.
+static DOI_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b10\.\d{4,9}/[-._;()/:A-Za-z0-9]+").expect("valid regex"));
+
+/// Finds the first `ISBN`-labelled number in page text whose check digit is
+/// valid, returned in its ISBN-13 form.
+fn find_isbn(text: &str) -> Option<String> {
+    ISBN_RE
+        .captures_iter(text)
+        .find_map(|caps| Isbn::parse(&caps[1]).ok())
+        .map(|isbn| isbn.to_isbn13())
+}
+
+/// Finds the first DOI (`10.NNNN/suffix`) in page text.
+fn find_doi(text: &str) -> Option<String> {
+    DOI_RE.find(text).map(|m| {
+        m.as_str()
+            .trim_end_matches(['.', ',', ';', ')'])
+            .to_string()
+    })
+}
+
+/// Renders the first page of a PDF as an image.
 ///
 /// Used for generating cover thumbnails.
 ///
 /// # Arguments
 ///
 /// * `path` - Absolute path to the PDF file.
+/// * `options` - Resolution, clip and output format.
 ///
 /// # Returns
 ///
-/// Raw PNG image bytes of the first page.
+/// Raw image bytes of the first page, in `options.format`.
 ///
 /// # Errors
 ///
-/// Returns a boxed error when the file cannot be opened or the page cannot
-/// be rendered.
+/// Returns a boxed error when the file cannot be opened, the options are out
+/// of range, or the page cannot be rendered.
 pub async fn get_pdf_cover(
     path: &str,
+    options: &PdfRenderOptions,
 ) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>> {
     let path_str = path.to_string();
+    let options = options.clone();
     tokio::task::spawn_blocking(move || {
         let doc = PdfDocument::open(&path_str)?;
-        let opts = RenderOptions::with_dpi(150);
-        let image = render_page(&doc, 0, &opts)?;
-        Ok(image.data)
+        Ok(render(&path_str, &doc, 0, &options)?.data)
     })
     .await?
 }
@@ -193,15 +524,62 @@ pub async fn get_pdf_page_count(
     .await?
 }
 
+/// Reads the page count and opening text of a PDF.
+///
+/// Text is extracted page by page until [`PROFILE_TEXT_CHARS`] characters
+/// are collected; pages whose text cannot be extracted are skipped.
+///
+/// # Arguments
+///
+/// * `path` - Absolute path to the PDF file.
+///
+/// # Returns
+///
+/// A [`ContentProfile`] whose `units` is the number of pages.
+///
+/// # Errors
+///
+/// Returns a boxed error when the file cannot be opened or is not a valid PDF.
+pub async fn read_content_profile(
+    path: &str,
+) -> Result<ContentProfile, Box<dyn std::error::Error + Send + Sync>> {
+    let path_str = path.to_string();
+    tokio::task::spawn_blocking(move || {
+        let doc = PdfDocument::open(&path_str)?;
+        let page_count = doc.page_count().unwrap_or(0);
+
+        let mut text = String::new();
+        for idx in 0..page_count {
+            if text.len() >= PROFILE_TEXT_CHARS {
+                break;
+            }
+            if let Ok(page_text) = doc.extract_text(idx) {
+                text.push_str(&page_text);
+                text.push(' ');
+            }
+        }
+
+        Ok(ContentProfile {
+            units: page_count as u32,
+            text: text.chars().take(PROFILE_TEXT_CHARS).collect(),
+        })
+    })
+    .await?
+}
+
 /// Renders a specific page of a PDF and extracts its text spans.
 ///
-/// The page is rendered at 150 DPI. Returns the page as a base64-encoded
-/// image along with positional text data for search and selection.
+/// The page is rendered as `options` ask (150 DPI PNG by default). Returns
+/// the page as a base64-encoded image along with positional text data for
+/// search and selection. With a clip only that part of the page is
+/// returned, but character and word boxes stay in pixels of the whole
+/// rendered page.
 ///
 /// # Arguments
 ///
 /// * `path` - Absolute path to the PDF file.
 /// * `page_number` - 0-based page index to render.
+/// * `options` - Resolution, clip and output format.
 ///
 /// # Returns
 ///
@@ -210,49 +588,503 @@ pub async fn get_pdf_page_count(
 ///
 /// # Errors
 ///
-/// Returns a boxed error when the file cannot be opened, the page number is
-/// out of range, or the page cannot be rendered.
+/// Returns a boxed error when the file cannot be opened, the page number or
+/// options are out of range, or the page cannot be rendered.
 pub async fn read_pdf_page(
     path: &str,
     page_number: u32,
+    options: &PdfRenderOptions,
 ) -> Result<PdfPage, Box<dyn std::error::Error + Send + Sync>> {
     let path_str = path.to_string();
+    let options = options.clone();
     tokio::task::spawn_blocking(move || {
         let doc = PdfDocument::open(&path_str)?;
-        let page_count = doc.page_count().unwrap_or(0);
+        let idx = page_index(&doc, page_number)?;
 
-        if (page_number as usize) >= page_count {
-            return Err(format!(
-                "Page {} out of range (PDF has {} pages)",
-                page_number, page_count
-            )
-            .into());
-        }
-
-        let idx = page_number as usize;
-        let render_opts = RenderOptions::with_dpi(150);
-        let image = render_page(&doc, idx, &render_opts)?;
+        let image = render(&path_str, &doc, idx, &options)?;
         let image_data = general_purpose::STANDARD.encode(&image.data);
 
-        let spans = doc.extract_spans(idx).unwrap_or_default();
-        let text_spans: Vec<PdfTextSpan> = spans
+        let geometry = PageGeometry::of_page(&doc, idx).with_width(image.page_width);
+        let user_chars = text_chars(&doc, idx);
+        let chars = user_chars
+            .iter()
+            .map(|c| {
+                let [x, y, width, height] = geometry.to_image([c.x, c.y, c.width, c.height]);
+                PdfChar {
+                    text: c.text.clone(),
+                    x,
+                    y,
+                    width,
+                    height,
+                }
+            })
+            .collect();
+        let words = word_ranges(&user_chars)
             .into_iter()
-            .map(|s| PdfTextSpan {
-                text: s.text,
-                x: s.bbox.x,
-                y: s.bbox.y,
-                width: s.bbox.width,
-                height: s.bbox.height,
+            .map(|range| {
+                let [x, y, width, height] = geometry.to_image(union(&user_chars[range.clone()]));
+                PdfWord {
+                    text: user_chars[range.clone()]
+                        .iter()
+                        .map(|c| c.text.as_str())
+                        .collect(),
+                    x,
+                    y,
+                    width,
+                    height,
+                    start: range.start,
+                    end: range.end,
+                }
             })
             .collect();
 
         Ok(PdfPage {
             page_number,
             image_data,
+            format: options.format,
             width: image.width,
             height: image.height,
-            text_spans,
+            page_width: image.page_width,
+            page_height: image.page_height,
+            clip: options.clip,
+            text_spans: text_spans(&doc, idx),
+            scale: geometry.scale,
+            rotation: geometry.rotation,
+            chars,
+            words,
         })
     })
     .await?
 }
+
+/// Extracts the characters of a PDF page in user space, without rendering
+/// it.
+///
+/// Returns one [`PdfTextSpan`] per character, in the order of
+/// [`PdfPage::chars`], so a selection the frontend makes by character index
+/// can be mapped back to user space.
+///
+/// # Arguments
+///
+/// * `path` - Absolute path to the PDF file.
+/// * `page_number` - 0-based page index.
+///
+/// # Errors
+///
+/// Returns a boxed error when the file cannot be opened or the page number is
+/// out of range.
+pub async fn read_pdf_chars(
+    path: &str,
+    page_number: u32,
+) -> Result<Vec<PdfTextSpan>, Box<dyn std::error::Error + Send + Sync>> {
+    let path_str = path.to_string();
+    tokio::task::spawn_blocking(move || {
+        let doc = PdfDocument::open(&path_str)?;
+        let idx = page_index(&doc, page_number)?;
+        Ok(text_chars(&doc, idx))
+    })
+    .await?
+}
+
+/// Groups characters into words, as [`PdfPage::words`] does.
+///
+/// Whitespace separates words and belongs to none. So does a move to
+/// another line (boxes whose vertical centres are more than half a line
+/// apart), a move back to the left, or a gap wider than a third of the
+/// line height.
+///
+/// # Arguments
+///
+/// * `chars` - Characters in user space, as [`read_pdf_chars`] returns them.
+///
+/// # Returns
+///
+/// The range of `chars` each word covers, in order.
+pub fn word_ranges(chars: &[PdfTextSpan]) -> Vec<Range<usize>> {
+    let mut words: Vec<Range<usize>> = Vec::new();
+    let mut previous: Option<&PdfTextSpan> = None;
+    for (index, c) in chars.iter().enumerate() {
+        if c.text.trim().is_empty() {
+            previous = None;
+            continue;
+        }
+        let continues = previous.is_some_and(|p| {
+            let line = p.height.max(c.height);
+            let centres = (p.y + p.height / 2.0 - (c.y + c.height / 2.0)).abs();
+            let gap = c.x - (p.x + p.width);
+            centres <= line / 2.0 && c.x >= p.x && gap <= line / 3.0
+        });
+        match words.last_mut() {
+            Some(word) if continues => word.end = index + 1,
+            _ => words.push(index..index + 1),
+        }
+        previous = Some(c);
+    }
+    words
+}
+
+impl PageGeometry {
+    /// Reads a page's displayed area and rotation, at one pixel per point. A
+    /// page whose boxes cannot be read is taken to be US Letter.
+    pub fn of_page(doc: &PdfDocument, idx: usize) -> Self {
+        let (page_box, rotation) =
+            page_attributes(doc, idx).unwrap_or(([0.0, 0.0, 612.0, 792.0], 0));
+        PageGeometry {
+            page_box,
+            rotation,
+            scale: 1.0,
+        }
+    }
+
+    /// Width and height of the displayed page in pixels, rotation applied.
+    pub fn size(&self) -> (f32, f32) {
+        let [left, bottom, right, top] = self.page_box;
+        let (width, height) = ((right - left) * self.scale, (top - bottom) * self.scale);
+        match self.rotation {
+            90 | 270 => (height, width),
+            _ => (width, height),
+        }
+    }
+
+    /// The same page at the scale that makes it `width` pixels wide.
+    pub fn with_width(self, width: u32) -> Self {
+        let (current, _) = self.size();
+        if current <= 0.0 {
+            return self;
+        }
+        PageGeometry {
+            scale: self.scale * width as f32 / current,
+            ..self
+        }
+    }
+
+    /// Maps a point in user space to image pixels (origin at the top-left of
+    /// the image, `y` down).
+    pub fn point_to_image(&self, x: f32, y: f32) -> (f32, f32) {
+        let [left, bottom, right, top] = self.page_box;
+        let (px, py) = ((x - left) * self.scale, (top - y) * self.scale);
+        let (width, height) = ((right - left) * self.scale, (top - bottom) * self.scale);
+        match self.rotation {
+            90 => (height - py, px),
+            180 => (width - px, height - py),
+            270 => (py, width - px),
+            _ => (px, py),
+        }
+    }
+
+    /// Maps a box in user space, `[x, y, width, height]` with `(x, y)` its
+    /// bottom-left corner, to image pixels, `(x, y)` then being its top-left
+    /// corner.
+    pub fn to_image(&self, [x, y, width, height]: [f32; 4]) -> [f32; 4] {
+        let (x1, y1) = self.point_to_image(x, y);
+        let (x2, y2) = self.point_to_image(x + width, y + height);
+        [x1.min(x2), y1.min(y2), (x2 - x1).abs(), (y2 - y1).abs()]
+    }
+}
+
+/// A whole-page render in [`RENDER_CACHE`], with what it was rendered from.
+struct CachedRender {
+    path: String,
+    modified: Option<SystemTime>,
+    idx: usize,
+    dpi: u32,
+    target_width: Option<u32>,
+    image: Arc<DynamicImage>,
+}
+
+/// An encoded page image and the size of the whole rendered page.
+struct RenderedImage {
+    data: Vec<u8>,
+    width: u32,
+    height: u32,
+    page_width: u32,
+    page_height: u32,
+}
+
+/// Renders a page as `options` ask.
+///
+/// pdf_oxide renders whole pages at whole DPIs, so the page is rendered at
+/// the smallest DPI reaching the target width and scaled down to it, and a
+/// clip is cut out of the whole render, which is kept in [`RENDER_CACHE`]
+/// for the page's other tiles. A plain PNG render is returned as pdf_oxide
+/// encodes it.
+fn render(
+    path: &str,
+    doc: &PdfDocument,
+    idx: usize,
+    options: &PdfRenderOptions,
+) -> Result<RenderedImage, Box<dyn std::error::Error + Send + Sync>> {
+    let (width_pt, height_pt) = PageGeometry::of_page(doc, idx).size();
+    let dpi = match (options.target_width, options.dpi) {
+        (Some(0), _) => return Err("Target width must be at least one pixel".into()),
+        (Some(target), _) => ((target as f32 * 72.0 / width_pt.max(1.0)).ceil() as u32).max(1),
+        (None, Some(dpi)) => dpi,
+        (None, None) => DEFAULT_RENDER_DPI,
+    };
+    if !(1..=MAX_RENDER_DPI).contains(&dpi) {
+        return Err(format!("Resolution must be between 1 and {} DPI", MAX_RENDER_DPI).into());
+    }
+    let pixels = (width_pt * dpi as f32 / 72.0) as u64 * (height_pt * dpi as f32 / 72.0) as u64;
+    if pixels > MAX_RENDER_PIXELS {
+        return Err(format!(
+            "Rendering the page at {} DPI would take {} pixels (at most {})",
+            dpi, pixels, MAX_RENDER_PIXELS
+        )
+        .into());
+    }
+
+    let page = match options.clip {
+        Some(_) => cached_page(path, doc, idx, dpi, options.target_width)?,
+        None => {
+            let rendered = render_page(doc, idx, &RenderOptions::with_dpi(dpi))?;
+            let resized = options
+                .target_width
+                .is_some_and(|target| target != rendered.width);
+            if !resized && options.format == PdfImageFormat::Png {
+                return Ok(RenderedImage {
+                    width: rendered.width,
+                    height: rendered.height,
+                    page_width: rendered.width,
+                    page_height: rendered.height,
+                    data: rendered.data,
+                });
+            }
+            Arc::new(decode_page(&rendered.data, options.target_width)?)
+        }
+    };
+
+    let (page_width, page_height) = (page.width(), page.height());
+    let image = match options.clip {
+        Some(clip) => {
+            let inside = clip.width > 0
+                && clip.height > 0
+                && clip.x as u64 + clip.width as u64 <= page_width as u64
+                && clip.y as u64 + clip.height as u64 <= page_height as u64;
+            if !inside {
+                return Err(format!(
+                    "Clip {:?} is not inside the {}x{} page",
+                    clip, page_width, page_height
+                )
+                .into());
+            }
+            page.crop_imm(clip.x, clip.y, clip.width, clip.height)
+        }
+        None => Arc::unwrap_or_clone(page),
+    };
+
+    let mut data = Vec::new();
+    match options.format {
+        PdfImageFormat::Png => image.write_to(&mut Cursor::new(&mut data), ImageFormat::Png)?,
+        PdfImageFormat::Jpeg => {
+            let quality = options
+                .quality
+                .unwrap_or(DEFAULT_JPEG_QUALITY)
+                .clamp(1, 100);
+            DynamicImage::ImageRgb8(image.to_rgb8())
+                .write_with_encoder(JpegEncoder::new_with_quality(&mut data, quality))?
+        }
+        PdfImageFormat::Webp => image.write_to(&mut Cursor::new(&mut data), ImageFormat::WebP)?,
+    }
+    Ok(RenderedImage {
+        data,
+        width: image.width(),
+        height: image.height(),
+        page_width,
+        page_height,
+    })
+}
+
+/// Decodes a page rendered by pdf_oxide, scaled to `target_width` if given.
+fn decode_page(
+    png: &[u8],
+    target_width: Option<u32>,
+) -> Result<DynamicImage, Box<dyn std::error::Error + Send + Sync>> {
+    let image = image::load_from_memory_with_format(png, ImageFormat::Png)?;
+    let resized = match target_width.filter(|&target| target != image.width()) {
+        Some(target) => {
+            let height = (image.height() as u64 * target as u64 / image.width() as u64).max(1);
+            image.resize_exact(target, height as u32, FilterType::Lanczos3)
+        }
+        None => image,
+    };
+    Ok(resized)
+}
+
+/// Returns the whole-page render to cut a tile from, from [`RENDER_CACHE`]
+/// when the page was rendered the same way since the file last changed.
+fn cached_page(
+    path: &str,
+    doc: &PdfDocument,
+    idx: usize,
+    dpi: u32,
+    target_width: Option<u32>,
+) -> Result<Arc<DynamicImage>, Box<dyn std::error::Error + Send + Sync>> {
+    let modified = std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok();
+    let same = |entry: &CachedRender| {
+        entry.path == path
+            && entry.modified == modified
+            && entry.idx == idx
+            && entry.dpi == dpi
+            && entry.target_width == target_width
+    };
+    let pixels = |entry: &CachedRender| entry.image.width() as u64 * entry.image.height() as u64;
+
+    {
+        let mut cache = RENDER_CACHE.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(position) = cache.iter().position(same) {
+            let entry = cache.remove(position);
+            let image = Arc::clone(&entry.image);
+            cache.push(entry);
+            return Ok(image);
+        }
+    }
+
+    // Rendered without holding the lock, so other pages are not held up.
+    let rendered = render_page(doc, idx, &RenderOptions::with_dpi(dpi))?;
+    let image = Arc::new(decode_page(&rendered.data, target_width)?);
+
+    let mut cache = RENDER_CACHE.lock().unwrap_or_else(PoisonError::into_inner);
+    cache.retain(|entry| !same(entry));
+    cache.push(CachedRender {
+        path: path.to_string(),
+        modified,
+        idx,
+        dpi,
+        target_width,
+        image: Arc::clone(&image),
+    });
+    let mut total: u64 = cache.iter().map(pixels).sum();
+    while total > RENDER_CACHE_PIXELS && cache.len() > 1 {
+        total -= pixels(&cache.remove(0));
+    }
+    Ok(image)
+}
+
+/// Extracts a page's characters; a page whose text cannot be extracted has
+/// none.
+fn text_chars(doc: &PdfDocument, idx: usize) -> Vec<PdfTextSpan> {
+    doc.extract_chars(idx)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|c| PdfTextSpan {
+            text: c.char.to_string(),
+            x: c.bbox.x,
+            y: c.bbox.y,
+            width: c.bbox.width,
+            height: c.bbox.height,
+        })
+        .collect()
+}
+
+/// The box, `[x, y, width, height]`, around a run of characters.
+fn union(chars: &[PdfTextSpan]) -> [f32; 4] {
+    let left = chars.iter().map(|c| c.x).fold(f32::INFINITY, f32::min);
+    let bottom = chars.iter().map(|c| c.y).fold(f32::INFINITY, f32::min);
+    let right = chars
+        .iter()
+        .map(|c| c.x + c.width)
+        .fold(f32::NEG_INFINITY, f32::max);
+    let top = chars
+        .iter()
+        .map(|c| c.y + c.height)
+        .fold(f32::NEG_INFINITY, f32::max);
+    [left, bottom, right - left, top - bottom]
+}
+
+/// Reads the displayed box (`CropBox`, else `MediaBox`) and rotation of a
+/// page, following the page tree from its root so inherited values apply.
+fn page_attributes(doc: &PdfDocument, idx: usize) -> Option<([f32; 4], u32)> {
+    let resolve = |object: &Object| match object.as_reference() {
+        Some(obj_ref) => doc.load_object(obj_ref).ok(),
+        None => Some(object.clone()),
+    };
+    let rect = |object: &Object| -> Option<[f32; 4]> {
+        let values = resolve(object)?
+            .as_array()?
+            .iter()
+            .map(|v| {
+                v.as_real()
+                    .map(|r| r as f32)
+                    .or_else(|| v.as_integer().map(|i| i as f32))
+            })
+            .collect::<Option<Vec<f32>>>()?;
+        let [x1, y1, x2, y2] = <[f32; 4]>::try_from(values).ok()?;
+        Some([x1.min(x2), y1.min(y2), x1.max(x2), y1.max(y2)])
+    };
+
+    let root = resolve(doc.trailer().as_dict()?.get("Root")?)?;
+    let mut node = resolve(root.as_dict()?.get("Pages")?)?;
+    let (mut media_box, mut crop_box, mut rotate) = (None, None, 0);
+    let mut remaining = idx;
+    // Bounded so a malformed, cyclic page tree cannot loop forever.
+    for _ in 0..64 {
+        let dict = node.as_dict()?;
+        if let Some(value) = dict.get("MediaBox").and_then(rect) {
+            media_box = Some(value);
+        }
+        if let Some(value) = dict.get("CropBox").and_then(rect) {
+            crop_box = Some(value);
+        }
+        if let Some(value) = dict
+            .get("Rotate")
+            .and_then(resolve)
+            .and_then(|r| r.as_integer())
+        {
+            rotate = value;
+        }
+
+        let Some(kids) = dict.get("Kids").and_then(resolve) else {
+            let rotation = rotate.rem_euclid(360) as u32 / 90 * 90;
+            return Some((crop_box.or(media_box)?, rotation));
+        };
+        let mut next = None;
+        for kid in kids.as_array()? {
+            let kid = resolve(kid)?;
+            let count = match kid.as_dict()?.get("Kids") {
+                Some(_) => kid.as_dict()?.get("Count")?.as_integer()? as usize,
+                None => 1,
+            };
+            if remaining < count {
+                next = Some(kid);
+                break;
+            }
+            remaining -= count;
+        }
+        node = next?;
+    }
+    None
+}
+
+/// Checks that a page exists and returns its index.
+fn page_index(
+    doc: &PdfDocument,
+    page_number: u32,
+) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+    let page_count = doc.page_count().unwrap_or(0);
+    if (page_number as usize) >= page_count {
+        return Err(format!(
+            "Page {} out of range (PDF has {} pages)",
+            page_number, page_count
+        )
+        .into());
+    }
+    Ok(page_number as usize)
+}
+
+/// Extracts a page's text spans; a page whose text cannot be extracted has
+/// none.
+fn text_spans(doc: &PdfDocument, idx: usize) -> Vec<PdfTextSpan> {
+    doc.extract_spans(idx)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|s| PdfTextSpan {
+            text: s.text,
+            x: s.bbox.x,
+            y: s.bbox.y,
+            width: s.bbox.width,
+            height: s.bbox.height,
+        })
+        .collect()
+}
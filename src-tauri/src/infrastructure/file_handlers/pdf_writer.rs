@@ -0,0 +1,242 @@
+use lopdf::{Dictionary, Document, Object, ObjectId, Stream, StringFormat, dictionary};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::path::PathBuf;
+
+use crate::domain::models::locator::Quad;
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Side of the square a note icon is drawn in, in points.
+const NOTE_ICON_SIZE: f32 = 20.0;
+
+/// Page used to place notes when a page has no readable `MediaBox`: US
+/// Letter, the PDF default.
+const DEFAULT_MEDIA_BOX: [f32; 4] = [0.0, 0.0, 612.0, 792.0];
+
+/// An annotation to write into a PDF.
+#[derive(Debug, Clone)]
+pub struct PdfAnnotation {
+    /// 0-based page index.
+    pub page_index: u32,
+    /// Highlighted areas in PDF user space. With quads the annotation is
+    /// written as a `Highlight`; without, as a `Text` note in the page's
+    /// top-left corner.
+    pub quads: Vec<Quad>,
+    /// Text shown in the annotation's popup.
+    pub contents: Option<String>,
+    /// RGB colour, each component from 0.0 to 1.0.
+    pub color: [f32; 3],
+    /// Unique name (`NM`) of the annotation within its page.
+    pub name: String,
+}
+
+/// Writes annotations into a copy of a PDF, so they show up in other PDF
+/// viewers.
+///
+/// Highlights get an appearance stream (the quads filled in multiply blend
+/// mode), so viewers that do not generate appearances still draw them.
+/// The annotations are added to the pages' `Annots`; everything else in the
+/// document is kept. The copy is written next to `destination` and then
+/// renamed over it.
+///
+/// # Arguments
+///
+/// * `source` - Absolute path to the PDF file; it is not modified.
+/// * `destination` - Path of the annotated copy.
+/// * `annotations` - The annotations to add.
+///
+/// # Errors
+///
+/// Returns a boxed error when the source cannot be read or is encrypted, an
+/// annotation is on a page the document does not have, or the copy cannot be
+/// written.
+pub async fn write_pdf_annotations(
+    source: String,
+    destination: String,
+    annotations: Vec<PdfAnnotation>,
+) -> Result<(), BoxError> {
+    tokio::task::spawn_blocking(move || {
+        write_pdf_annotations_blocking(&source, &destination, &annotations)
+    })
+    .await?
+}
+
+fn write_pdf_annotations_blocking(
+    source: &str,
+    destination: &str,
+    annotations: &[PdfAnnotation],
+) -> Result<(), BoxError> {
+    let mut doc = Document::load(source)?;
+    if doc.is_encrypted() {
+        return Err("Encrypted PDFs cannot be annotated".into());
+    }
+
+    let pages = doc.get_pages();
+    let modified = chrono::Utc::now().format("D:%Y%m%d%H%M%SZ").to_string();
+    let mut added: BTreeMap<ObjectId, Vec<Object>> = BTreeMap::new();
+    for annotation in annotations {
+        let page_id = *pages.get(&(annotation.page_index + 1)).ok_or_else(|| {
+            format!(
+                "Page {} out of range (PDF has {} pages)",
+                annotation.page_index,
+                pages.len()
+            )
+        })?;
+
+        let mut dict = dictionary! {
+            "Type" => "Annot",
+            "P" => page_id,
+            "NM" => text_string(&annotation.name),
+            "M" => text_string(&modified),
+            "F" => 4, // Print
+            "C" => annotation.color.iter().map(|&c| Object::Real(c)).collect::<Vec<_>>(),
+        };
+        if let Some(contents) = &annotation.contents {
+            dict.set("Contents", text_string(contents));
+        }
+
+        if annotation.quads.is_empty() {
+            let [left, _, _, top] = media_box(&doc, page_id).unwrap_or(DEFAULT_MEDIA_BOX);
+            dict.set("Subtype", "Text");
+            dict.set("Name", "Comment");
+            dict.set("Open", false);
+            dict.set(
+                "Rect",
+                rect([left, top - NOTE_ICON_SIZE, left + NOTE_ICON_SIZE, top]),
+            );
+        } else {
+            let bounds = annotation
+                .quads
+                .iter()
+                .map(Quad::bounds)
+                .reduce(|a, b| {
+                    [
+                        a[0].min(b[0]),
+                        a[1].min(b[1]),
+                        a[2].max(b[2]),
+                        a[3].max(b[3]),
+                    ]
+                })
+                .unwrap_or_default();
+            let quad_points: Vec<Object> = annotation
+                .quads
+                .iter()
+                .flat_map(|quad| quad.points)
+                .flat_map(|p| [Object::Real(p.x), Object::Real(p.y)])
+                .collect();
+            let appearance = doc.add_object(highlight_appearance(annotation, bounds));
+            dict.set("Subtype", "Highlight");
+            dict.set("Rect", rect(bounds));
+            dict.set("QuadPoints", quad_points);
+            dict.set("AP", dictionary! { "N" => appearance });
+        }
+
+        let id = doc.add_object(dict);
+        added
+            .entry(page_id)
+            .or_default()
+            .push(Object::Reference(id));
+    }
+
+    for (page_id, references) in added {
+        append_annots(&mut doc, page_id, references)?;
+    }
+
+    let tmp_path = PathBuf::from(format!("{destination}.tmp"));
+    if let Err(e) = doc.save(&tmp_path) {
+        let _ = std::fs::remove_file(&tmp_path);
+        return Err(e.into());
+    }
+    std::fs::rename(&tmp_path, destination)?;
+    Ok(())
+}
+
+/// Adds annotation references to a page's `Annots` array, which may be
+/// stored inline or as an indirect object.
+fn append_annots(
+    doc: &mut Document,
+    page_id: ObjectId,
+    references: Vec<Object>,
+) -> Result<(), BoxError> {
+    let existing = doc.get_dictionary(page_id)?.get(b"Annots").ok().cloned();
+    match existing {
+        Some(Object::Reference(id)) => doc.get_object_mut(id)?.as_array_mut()?.extend(references),
+        Some(Object::Array(_)) => doc
+            .get_dictionary_mut(page_id)?
+            .get_mut(b"Annots")?
+            .as_array_mut()?
+            .extend(references),
+        _ => doc.get_dictionary_mut(page_id)?.set("Annots", references),
+    }
+    Ok(())
+}
+
+/// The normal appearance of a highlight: its quads filled with its colour,
+/// multiplied with the page so the text stays readable.
+fn highlight_appearance(annotation: &PdfAnnotation, bounds: [f32; 4]) -> Stream {
+    let [r, g, b] = annotation.color;
+    let mut content = format!("/GS0 gs {r:.3} {g:.3} {b:.3} rg\n");
+    for quad in &annotation.quads {
+        let [ul, ur, ll, lr] = quad.points;
+        let _ = writeln!(
+            content,
+            "{:.2} {:.2} m {:.2} {:.2} l {:.2} {:.2} l {:.2} {:.2} l h f",
+            ul.x, ul.y, ur.x, ur.y, lr.x, lr.y, ll.x, ll.y
+        );
+    }
+
+    let resources = dictionary! {
+        "ExtGState" => dictionary! {
+            "GS0" => dictionary! {
+                "Type" => "ExtGState",
+                "BM" => "Multiply",
+            },
+        },
+    };
+    let dict = dictionary! {
+        "Type" => "XObject",
+        "Subtype" => "Form",
+        "BBox" => rect(bounds),
+        "Resources" => resources,
+    };
+    Stream::new(dict, content.into_bytes())
+}
+
+/// The page's `MediaBox`, inherited from the page tree when the page has
+/// none of its own.
+fn media_box(doc: &Document, page_id: ObjectId) -> Option<[f32; 4]> {
+    let mut node: &Dictionary = doc.get_dictionary(page_id).ok()?;
+    // The page tree is shallow; the bound guards against cycles.
+    for _ in 0..32 {
+        if let Ok(value) = node.get(b"MediaBox") {
+            let (_, value) = doc.dereference(value).ok()?;
+            let values = value
+                .as_array()
+                .ok()?
+                .iter()
+                .map(|v| v.as_float().ok())
+                .collect::<Option<Vec<f32>>>()?;
+            let [x1, y1, x2, y2] = <[f32; 4]>::try_from(values).ok()?;
+            return Some([x1.min(x2), y1.min(y2), x1.max(x2), y1.max(y2)]);
+        }
+        let parent = node.get(b"Parent").ok()?.as_reference().ok()?;
+        node = doc.get_dictionary(parent).ok()?;
+    }
+    None
+}
+
+fn rect(bounds: [f32; 4]) -> Vec<Object> {
+    bounds.into_iter().map(Object::Real).collect()
+}
+
+/// Encodes a PDF text string: literal for ASCII, UTF-16BE with a byte order
+/// mark otherwise.
+fn text_string(text: &str) -> Object {
+    if text.is_ascii() {
+        return Object::String(text.as_bytes().to_vec(), StringFormat::Literal);
+    }
+    let mut bytes = vec![0xFE, 0xFF];
+    bytes.extend(text.encode_utf16().flat_map(u16::to_be_bytes));
+    Object::String(bytes, StringFormat::Hexadecimal)
+}
@@ -0,0 +1,43 @@
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+/// Moves a file to the operating system's trash (the freedesktop.org trash
+/// under `$XDG_DATA_HOME/Trash` on Linux, the Recycle Bin on Windows, the
+/// Finder trash on macOS), from where the user can still restore it.
+///
+/// A file that no longer exists is left alone.
+///
+/// # Arguments
+///
+/// * `path` - Path of the file to move.
+///
+/// # Errors
+///
+/// Returns a boxed error when the trash cannot be reached or the file cannot
+/// be moved into it.
+pub async fn move_to_trash(path: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let path = PathBuf::from(path);
+    if !tokio::fs::try_exists(&path).await? {
+        return Ok(());
+    }
+    tokio::task::spawn_blocking(move || trash::delete(&path)).await??;
+    Ok(())
+}
+
+/// Deletes a file permanently.
+///
+/// A file that no longer exists is left alone.
+///
+/// # Arguments
+///
+/// * `path` - Path of the file to delete.
+///
+/// # Errors
+///
+/// Returns [`std::io::Error`] when the file exists but cannot be deleted.
+pub async fn delete_file(path: &str) -> Result<(), std::io::Error> {
+    match tokio::fs::remove_file(path).await {
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+        result => result,
+    }
+}
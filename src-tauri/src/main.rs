@@ -16,6 +16,7 @@ pub const MIGRATIONS: EmbeddedMigrations =
     embed_migrations!("./src/infrastructure/database/migrations");
 
 use stellaron_lib::application::state::AppState;
+use stellaron_lib::infrastructure::database::backfill;
 use stellaron_lib::infrastructure::database::database::create_pool;
 
 #[tokio::main]
@@ -31,6 +32,7 @@ async fn main() {
     connection
         .run_pending_migrations(MIGRATIONS)
         .expect("Error running database migrations");
+    backfill::run_backfills(&mut connection).expect("Error backfilling migrated columns");
 
     let _pool = create_pool(&database_url);
 
@@ -42,8 +44,16 @@ async fn main() {
         bookmark_repo: Arc::new(stellaron_lib::application::repository::bookmark_repo::BookmarkRepoImpl::new()),
         annotation_repo: Arc::new(stellaron_lib::application::repository::annotation_repo::AnnotationRepoImpl::new()),
         reading_progress_repo: Arc::new(stellaron_lib::application::repository::reading_progress_repo::ReadingProgressRepoImpl::new()),
+        work_repo: Arc::new(stellaron_lib::application::repository::work_repo::WorkRepoImpl::new()),
+        ignored_file_repo: Arc::new(stellaron_lib::application::repository::ignored_file_repo::IgnoredFileRepoImpl::new()),
+        metadata_history_repo: Arc::new(stellaron_lib::application::repository::metadata_history_repo::MetadataHistoryRepoImpl::new()),
+        metadata_provider: Arc::new(stellaron_lib::application::provider::open_library::OpenLibraryProvider::from_env()),
     };
 
+    stellaron_lib::application::book::purge_expired_trash(&app_state.book_repo)
+        .await
+        .expect("Error purging expired books from the trash");
+
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
@@ -57,21 +67,54 @@ async fn main() {
             stellaron_lib::api::commands::book_commands::get_pdf_page_count,
             stellaron_lib::api::commands::book_commands::read_pdf_page,
             stellaron_lib::api::commands::book_commands::list_books,
+            stellaron_lib::api::commands::book_commands::query_books,
             stellaron_lib::api::commands::book_commands::get_book_details,
             stellaron_lib::api::commands::book_commands::get_cover_img,
             stellaron_lib::api::commands::book_commands::remove_book,
+            stellaron_lib::api::commands::book_commands::list_trash,
+            stellaron_lib::api::commands::book_commands::restore_book,
+            stellaron_lib::api::commands::book_commands::empty_trash,
             stellaron_lib::api::commands::bookmark_commands::add_bookmark,
             stellaron_lib::api::commands::bookmark_commands::get_bookmarks,
             stellaron_lib::api::commands::bookmark_commands::delete_bookmark,
             stellaron_lib::api::commands::annotation_commands::add_annotation,
             stellaron_lib::api::commands::annotation_commands::get_annotations,
             stellaron_lib::api::commands::annotation_commands::delete_annotation,
+            stellaron_lib::api::commands::annotation_commands::reanchor_annotations,
+            stellaron_lib::api::commands::annotation_commands::export_pdf_annotations,
             stellaron_lib::api::commands::library_commands::scan_books_directory,
+            stellaron_lib::api::commands::library_commands::preview_filename_patterns,
+            stellaron_lib::api::commands::library_commands::find_duplicate_books,
+            stellaron_lib::api::commands::library_commands::merge_duplicate_books,
+            stellaron_lib::api::commands::library_commands::list_ignored_files,
+            stellaron_lib::api::commands::library_commands::unignore_file,
             stellaron_lib::api::commands::reading_progress_commands::update_reading_progress,
             stellaron_lib::api::commands::reading_progress_commands::get_reading_progress,
+            stellaron_lib::api::commands::locator_commands::resolve_epub_cfi,
+            stellaron_lib::api::commands::locator_commands::generate_epub_cfi,
+            stellaron_lib::api::commands::locator_commands::generate_pdf_locator,
             stellaron_lib::api::commands::metadata_commands::fetch_metadata,
             stellaron_lib::api::commands::metadata_commands::list_metadata,
-            stellaron_lib::api::commands::metadata_commands::update_metadata,
+            stellaron_lib::api::commands::metadata_commands::update_book_metadata,
+            stellaron_lib::api::commands::metadata_commands::write_metadata_to_file,
+            stellaron_lib::api::commands::metadata_commands::find_metadata_candidates,
+            stellaron_lib::api::commands::metadata_commands::apply_metadata_candidate,
+            stellaron_lib::api::commands::metadata_commands::get_metadata_history,
+            stellaron_lib::api::commands::metadata_commands::revert_metadata_change,
+            stellaron_lib::api::commands::metadata_commands::revert_metadata_change_set,
+            stellaron_lib::api::commands::author_commands::list_authors,
+            stellaron_lib::api::commands::author_commands::rename_author,
+            stellaron_lib::api::commands::author_commands::merge_authors,
+            stellaron_lib::api::commands::author_commands::delete_orphan_authors,
+            stellaron_lib::api::commands::publisher_commands::list_publishers,
+            stellaron_lib::api::commands::publisher_commands::rename_publisher,
+            stellaron_lib::api::commands::publisher_commands::merge_publishers,
+            stellaron_lib::api::commands::publisher_commands::delete_orphan_publishers,
+            stellaron_lib::api::commands::work_commands::suggest_work_links,
+            stellaron_lib::api::commands::work_commands::link_books,
+            stellaron_lib::api::commands::work_commands::unlink_book,
+            stellaron_lib::api::commands::work_commands::set_preferred_format,
+            stellaron_lib::api::commands::work_commands::get_preferred_book,
             exit_app,
         ])
         .run(tauri::generate_context!())
@@ -0,0 +1,118 @@
+use scraper::{ElementRef, Html, Node};
+
+/// Elements kept by [`sanitize_html`]. Anything else is unwrapped to its content.
+const ALLOWED_TAGS: &[&str] = &[
+    "p",
+    "br",
+    "b",
+    "strong",
+    "i",
+    "em",
+    "u",
+    "s",
+    "sub",
+    "sup",
+    "small",
+    "ul",
+    "ol",
+    "li",
+    "blockquote",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "a",
+    "span",
+    "div",
+];
+
+/// Elements dropped by [`sanitize_html`] together with their content.
+const DROPPED_TAGS: &[&str] = &[
+    "script", "style", "iframe", "object", "embed", "noscript", "template", "head", "title",
+];
+
+/// Reduces an HTML fragment to a small set of formatting elements.
+///
+/// Used for book descriptions, which often carry publisher markup. Only the
+/// tags in `ALLOWED_TAGS` survive, with no attributes except an `http`,
+/// `https` or `mailto` `href` on links. Scripts, styles and embedded content
+/// are removed entirely; other elements are replaced by their content. Plain
+/// text input is returned HTML-escaped.
+///
+/// # Arguments
+///
+/// * `input` - The HTML fragment to clean.
+///
+/// # Returns
+///
+/// The sanitized HTML, trimmed of surrounding whitespace.
+pub fn sanitize_html(input: &str) -> String {
+    let fragment = Html::parse_fragment(input);
+    let mut out = String::new();
+    write_children(fragment.root_element(), &mut out);
+    out.trim().to_string()
+}
+
+fn write_children(element: ElementRef, out: &mut String) {
+    for child in element.children() {
+        match child.value() {
+            Node::Text(text) => escape_into(text, out),
+            Node::Element(_) => {
+                if let Some(child) = ElementRef::wrap(child) {
+                    write_element(child, out);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn write_element(element: ElementRef, out: &mut String) {
+    let name = element.value().name();
+    if DROPPED_TAGS.contains(&name) {
+        return;
+    }
+    if !ALLOWED_TAGS.contains(&name) {
+        write_children(element, out);
+        return;
+    }
+    if name == "br" {
+        out.push_str("<br>");
+        return;
+    }
+
+    out.push('<');
+    out.push_str(name);
+    if name == "a"
+        && let Some(href) = element.value().attr("href")
+    {
+        let lower = href.trim().to_ascii_lowercase();
+        if ["http://", "https://", "mailto:"]
+            .iter()
+            .any(|scheme| lower.starts_with(scheme))
+        {
+            out.push_str(" href=\"");
+            escape_into(href.trim(), out);
+            out.push('"');
+        }
+    }
+    out.push('>');
+    write_children(element, out);
+    out.push_str("</");
+    out.push_str(name);
+    out.push('>');
+}
+
+fn escape_into(text: &str, out: &mut String) {
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            _ => out.push(c),
+        }
+    }
+}
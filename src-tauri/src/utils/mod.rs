@@ -1,5 +1,7 @@
-//! Shared utilities for checksums and custom serde serialization.
+//! Shared utilities for checksums, HTML sanitizing and custom serde
+//! serialization.
 
 pub mod deserializers;
 pub mod file;
+pub mod html;
 pub mod serializers;
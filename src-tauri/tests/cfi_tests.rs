@@ -0,0 +1,124 @@
+use stellaron_lib::domain::models::cfi::{Cfi, CfiSide, DomPosition};
+use stellaron_lib::infrastructure::file_handlers::epub_cfi::{
+    SpineDocument, read_spine, read_spine_document,
+};
+
+const CHAPTER: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<html xmlns="http://www.w3.org/1999/xhtml">
+<head><title>Chapter</title></head>
+<body id="body01">
+<p>First paragraph.</p>
+<p id="para02">Some <em>emphasised</em> text &amp; more.</p>
+<!-- end -->
+</body>
+</html>"#;
+
+fn position(element_path: &[usize], offset: Option<usize>) -> DomPosition {
+    DomPosition {
+        element_path: element_path.to_vec(),
+        offset,
+    }
+}
+
+#[test]
+fn test_cfi_parse_and_format() {
+    let cfi = Cfi::parse("epubcfi(/6/4[chap01ref]!/4[body01]/10[para05]/3:10)").unwrap();
+    assert_eq!(cfi.spine.index, 6);
+    assert_eq!(cfi.itemref.id.as_deref(), Some("chap01ref"));
+    assert_eq!(cfi.spine_index(), Some(1));
+    assert_eq!(cfi.start.steps.len(), 3);
+    assert_eq!(cfi.start.offset.as_ref().unwrap().chars, 10);
+    assert_eq!(
+        cfi.to_string(),
+        "epubcfi(/6/4[chap01ref]!/4[body01]/10[para05]/3:10)"
+    );
+
+    let cfi = Cfi::parse("epubcfi(/6/2!/4/2/1:3[xx,y^,y;s=a])").unwrap();
+    let offset = cfi.start.offset.as_ref().unwrap();
+    assert_eq!(offset.before.as_deref(), Some("xx"));
+    assert_eq!(offset.after.as_deref(), Some("y,y"));
+    assert_eq!(offset.side, Some(CfiSide::After));
+    assert_eq!(cfi.to_string(), "epubcfi(/6/2!/4/2/1:3[xx,y^,y;s=a])");
+}
+
+#[test]
+fn test_cfi_range_shares_its_common_path() {
+    let raw = "epubcfi(/6/4[chap01]!/4[body01]/4[para02],/2/1:0,/3:5)";
+    let cfi = Cfi::parse(raw).unwrap();
+    assert_eq!(cfi.start.steps.len(), 4);
+    assert_eq!(cfi.end.as_ref().unwrap().steps.len(), 3);
+    assert_eq!(cfi.to_string(), raw);
+}
+
+#[test]
+fn test_cfi_rejects_invalid_input() {
+    assert!(Cfi::parse("/6/4!/4/2/1:0").is_err(), "Missing epubcfi(...)");
+    assert!(
+        Cfi::parse("epubcfi(/6/4/4/2/1:0)").is_err(),
+        "No indirection into the content document"
+    );
+    assert!(
+        Cfi::parse("epubcfi(/6/4!/4/2@10:20)").is_err(),
+        "Spatial offsets are not supported"
+    );
+    assert!(Cfi::parse("epubcfi(/6/4!/4/2[unterminated)").is_err());
+}
+
+#[test]
+fn test_spine_document_resolves_cfi_paths() {
+    let document = SpineDocument::parse(CHAPTER).unwrap();
+
+    let cfi = Cfi::parse("epubcfi(/6/2!/4[body01]/4[para02]/2/1:3)").unwrap();
+    assert_eq!(
+        document.resolve(&cfi.start).unwrap(),
+        position(&[1, 1, 0], Some(3))
+    );
+
+    // The ID assertion wins over a step index that no longer matches.
+    let cfi = Cfi::parse("epubcfi(/6/2!/4[body01]/2[para02]/1:3)").unwrap();
+    assert_eq!(
+        document.resolve(&cfi.start).unwrap(),
+        position(&[1, 1], Some(3))
+    );
+
+    let cfi = Cfi::parse("epubcfi(/6/2!/4[body01]/8)").unwrap();
+    assert!(document.resolve(&cfi.start).is_err(), "Past the last child");
+}
+
+#[test]
+fn test_spine_document_generates_cfi_paths() {
+    let document = SpineDocument::parse(CHAPTER).unwrap();
+    let cfi = |point| {
+        let mut cfi = Cfi::parse("epubcfi(/6/2!/4)").unwrap();
+        cfi.start = point;
+        cfi.to_string()
+    };
+
+    let point = document.locate(&position(&[1, 1], Some(7))).unwrap();
+    assert_eq!(cfi(point), "epubcfi(/6/2!/4[body01]/4[para02]/2/1:2)");
+
+    // `&amp;` counts as one character.
+    let point = document.locate(&position(&[1, 1], Some(23))).unwrap();
+    assert_eq!(cfi(point), "epubcfi(/6/2!/4[body01]/4[para02]/3:8)");
+
+    let point = document.locate(&position(&[1, 0], None)).unwrap();
+    assert_eq!(cfi(point.clone()), "epubcfi(/6/2!/4[body01]/2)");
+    assert_eq!(document.resolve(&point).unwrap(), position(&[1, 0], None));
+
+    assert!(document.locate(&position(&[1, 1], Some(100))).is_err());
+    assert!(document.locate(&position(&[1, 5], None)).is_err());
+}
+
+#[tokio::test]
+async fn test_read_spine_document() {
+    let path = "pg1513-images-3.epub";
+    let spine = read_spine(path).await.unwrap();
+    assert_eq!(spine.spine_step, 6);
+    assert_eq!(spine.items[1].idref, "pg-header");
+
+    let document = read_spine_document(path, &spine.items[1].href)
+        .await
+        .unwrap();
+    let point = document.locate(&position(&[1], None)).unwrap();
+    assert_eq!(document.resolve(&point).unwrap(), position(&[1], None));
+}
@@ -0,0 +1,96 @@
+use stellaron_lib::infrastructure::file_handlers::cover_handler::*;
+
+#[tokio::test]
+async fn test_generate_placeholder_cover() {
+    let result = generate_placeholder_cover(
+        "The Left Hand of Darkness",
+        Some("Ursula K. Le Guin"),
+        "3f9a1c0e5b7d",
+    )
+    .await;
+    assert!(
+        result.is_ok(),
+        "Failed to generate placeholder cover: {:?}",
+        result.err()
+    );
+    let bytes = result.unwrap();
+    assert!(
+        bytes.starts_with(b"\x89PNG\r\n\x1a\n"),
+        "Placeholder cover should be a PNG image"
+    );
+}
+
+#[tokio::test]
+async fn test_placeholder_cover_is_deterministic() {
+    let first = generate_placeholder_cover("Dune", Some("Frank Herbert"), "b4c2d9")
+        .await
+        .expect("Failed to generate cover");
+    let second = generate_placeholder_cover("Dune", Some("Frank Herbert"), "b4c2d9")
+        .await
+        .expect("Failed to generate cover");
+    let other = generate_placeholder_cover("Dune", Some("Frank Herbert"), "07e1aa")
+        .await
+        .expect("Failed to generate cover");
+    assert_eq!(first, second, "Same input should produce identical covers");
+    assert_ne!(first, other, "Different checksums should change the color");
+}
+
+#[tokio::test]
+async fn test_placeholder_cover_fits_long_title() {
+    let title = "A Very Long Title That Keeps Going ".repeat(20);
+    let result = generate_placeholder_cover(&title, None, "ffffff").await;
+    assert!(
+        result.is_ok(),
+        "Long titles should still render: {:?}",
+        result.err()
+    );
+}
+
+#[tokio::test]
+async fn test_placeholder_cover_transliterates_or_drops_unsupported_scripts() {
+    let cyrillic = generate_placeholder_cover("Война и мир", None, "b4c2d9")
+        .await
+        .expect("Failed to generate cover");
+    let latin = generate_placeholder_cover("Voyna i mir", None, "b4c2d9")
+        .await
+        .expect("Failed to generate cover");
+    assert_eq!(cyrillic, latin, "Cyrillic titles should be transliterated");
+
+    let cjk = generate_placeholder_cover("三体", None, "b4c2d9")
+        .await
+        .expect("Failed to generate cover");
+    let untitled = generate_placeholder_cover("", None, "b4c2d9")
+        .await
+        .expect("Failed to generate cover");
+    assert_eq!(cjk, untitled, "Undrawable titles should draw no text");
+}
+
+#[tokio::test]
+async fn test_extract_cover_palette() {
+    let cover = generate_placeholder_cover("Dune", Some("Frank Herbert"), "b4c2d9")
+        .await
+        .expect("Failed to generate cover");
+    let result = extract_cover_palette(cover).await;
+    assert!(
+        result.is_ok(),
+        "Failed to extract cover palette: {:?}",
+        result.err()
+    );
+    let palette = result.unwrap();
+    assert!(
+        !palette.dominant_colors.is_empty(),
+        "Palette should contain at least one color"
+    );
+    assert!(
+        palette
+            .dominant_colors
+            .iter()
+            .all(|c| c.len() == 7 && c.starts_with('#')),
+        "Colors should be #rrggbb strings"
+    );
+    assert_eq!(
+        palette.blurhash.len(),
+        28,
+        "A 4x3 BlurHash should be 28 characters"
+    );
+}
@@ -0,0 +1,74 @@
+use stellaron_lib::domain::models::duplicate::{
+    DuplicateSignals, fingerprint_similarity, length_similarity, normalize_title, similarity,
+    text_fingerprint,
+};
+
+const OPENING: &str = "Many years later, as he faced the firing squad, Colonel Aureliano \
+    Buendia was to remember that distant afternoon when his father took him to discover ice. \
+    At that time Macondo was a village of twenty adobe houses, built on the bank of a river \
+    of clear water that ran along a bed of polished stones, which were white and enormous, \
+    like prehistoric eggs.";
+
+#[test]
+fn test_similarity_of_normalized_titles() {
+    let a = normalize_title("The Left Hand of Darkness", Some("en"));
+    let b = normalize_title("Left Hand of Darkness, The", None);
+    assert!(similarity(&a, &b) > 0.9);
+
+    assert!(
+        similarity(
+            &normalize_title("Dune", None),
+            &normalize_title("Emma", None)
+        ) < 0.2
+    );
+    assert_eq!(similarity("", ""), 1.0);
+    assert_eq!(similarity("dune", ""), 0.0);
+}
+
+#[test]
+fn test_length_similarity() {
+    assert_eq!(length_similarity(40, 40), Some(1.0));
+    assert_eq!(length_similarity(30, 60), Some(0.5));
+    assert_eq!(length_similarity(0, 60), None);
+}
+
+#[test]
+fn test_text_fingerprint_tolerates_small_edits() {
+    let original = text_fingerprint(OPENING).unwrap();
+    let reformatted = text_fingerprint(&OPENING.replace(", ", " ").to_uppercase()).unwrap();
+    assert_eq!(original, reformatted, "Case and punctuation are ignored");
+
+    let edited = text_fingerprint(&OPENING.replace("twenty", "thirty")).unwrap();
+    assert!(fingerprint_similarity(original, edited) > 0.85);
+
+    let other = text_fingerprint(
+        "It was the best of times, it was the worst of times, it was the age of wisdom, \
+         it was the age of foolishness, it was the epoch of belief, it was the epoch of \
+         incredulity, it was the season of Light, it was the season of Darkness.",
+    )
+    .unwrap();
+    assert!(fingerprint_similarity(original, other) < 0.8);
+
+    assert_eq!(text_fingerprint("Two words"), None);
+}
+
+#[test]
+fn test_score_ignores_missing_signals() {
+    let identical = DuplicateSignals {
+        title: 1.0,
+        author: Some(1.0),
+        isbn: None,
+        length: None,
+        text: None,
+    };
+    assert_eq!(identical.score(), 1.0);
+
+    let other_edition = DuplicateSignals {
+        title: 1.0,
+        author: Some(1.0),
+        isbn: Some(0.0),
+        length: Some(0.6),
+        text: Some(0.5),
+    };
+    assert!(other_edition.score() < 0.8);
+}
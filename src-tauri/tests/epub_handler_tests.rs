@@ -29,6 +29,33 @@ async fn test_parse_epub_meta() {
         !metadata.checksum.is_empty(),
         "Checksum should not be empty"
     );
+    assert!(
+        metadata.contributors.iter().any(|c| c.role == "aut"),
+        "At least one contributor should be credited as author"
+    );
+}
+
+#[tokio::test]
+async fn test_parse_epub_meta_extended_fields() {
+    let path = "Fundamental-Accessibility-Tests-Basic-Functionality-v2.0.0.epub".to_string();
+    let metadata = parse_epub_meta(path).await.unwrap();
+    assert_eq!(metadata.language.as_deref(), Some("en"));
+    assert_eq!(metadata.epub_version.as_deref(), Some("3.0"));
+    assert!(
+        metadata
+            .subjects
+            .contains(&"basic-functionality".to_string()),
+        "Subjects should include dc:subject entries"
+    );
+    assert!(
+        metadata
+            .identifiers
+            .iter()
+            .any(|i| i.scheme == "isbn" && i.value == "9781003410126"),
+        "ISBN identifier should be typed and compacted"
+    );
+    assert_eq!(metadata.isbn.as_deref(), Some("9781003410126"));
+    assert!(metadata.description.is_some(), "Description should be set");
 }
 
 #[tokio::test]
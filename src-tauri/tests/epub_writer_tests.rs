@@ -0,0 +1,62 @@
+use std::io::Read;
+
+use stellaron_lib::domain::models::contributor::Contributor;
+use stellaron_lib::infrastructure::file_handlers::epub_handler::parse_epub_meta;
+use stellaron_lib::infrastructure::file_handlers::epub_writer::*;
+
+const FIXTURE: &str = "Fundamental-Accessibility-Tests-Basic-Functionality-v2.0.0.epub";
+
+fn entries(path: &str) -> Vec<(String, Vec<u8>)> {
+    let mut archive = zip::ZipArchive::new(std::fs::File::open(path).unwrap()).unwrap();
+    (0..archive.len())
+        .map(|i| {
+            let mut entry = archive.by_index(i).unwrap();
+            let mut data = Vec::new();
+            entry.read_to_end(&mut data).unwrap();
+            (entry.name().to_string(), data)
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_write_epub_metadata() {
+    let path = std::env::temp_dir().join("stellaron-epub-writer-test.epub");
+    let path = path.to_string_lossy().to_string();
+    std::fs::copy(FIXTURE, &path).unwrap();
+
+    let update = EpubMetadataUpdate {
+        title: "Rewritten Title".to_string(),
+        contributors: vec![Contributor {
+            name: "Jane Doe".to_string(),
+            role: "aut".to_string(),
+            sort_name: Some("Doe, Jane".to_string()),
+        }],
+        publisher: Some("Example Press".to_string()),
+        published_date: Some("2001-02".to_string()),
+        isbn: Some("9780306406157".to_string()),
+        series: Some("Tests".to_string()),
+        series_index: Some(2.0),
+        cover: None,
+    };
+    let result = write_epub_metadata(path.clone(), update).await;
+    assert!(result.is_ok(), "Failed to write metadata: {:?}", result.err());
+
+    let metadata = parse_epub_meta(path.clone()).await.unwrap();
+    assert_eq!(metadata.title, "Rewritten Title");
+    assert_eq!(metadata.publishers, vec!["Example Press".to_string()]);
+    let author = metadata.contributors.iter().find(|c| c.role == "aut").unwrap();
+    assert_eq!(author.name, "Jane Doe");
+    assert_eq!(author.sort_name.as_deref(), Some("Doe, Jane"));
+
+    let before = entries(FIXTURE);
+    let after = entries(&path);
+    assert_eq!(before.len(), after.len(), "Entry count should not change");
+    for ((name_a, data_a), (name_b, data_b)) in before.iter().zip(after.iter()) {
+        assert_eq!(name_a, name_b, "Entry order should be preserved");
+        if !name_a.ends_with(".opf") {
+            assert_eq!(data_a, data_b, "{} should be unchanged", name_a);
+        }
+    }
+
+    std::fs::remove_file(&path).ok();
+}
@@ -0,0 +1,62 @@
+use std::path::Path;
+
+use stellaron_lib::domain::models::filename_pattern::FilenamePattern;
+
+#[test]
+fn test_filename_pattern_extracts_author_title_year() {
+    let pattern = FilenamePattern::parse("{author} - {title} ({year})").unwrap();
+    let meta = pattern
+        .extract(Path::new(
+            "/books/Ursula K. Le Guin - The Dispossessed (1974).pdf",
+        ))
+        .expect("pattern should match");
+    assert_eq!(meta.authors, vec!["Ursula K. Le Guin".to_string()]);
+    assert_eq!(meta.title.as_deref(), Some("The Dispossessed"));
+    assert_eq!(meta.year, Some(1974));
+
+    let meta = pattern
+        .extract(Path::new(
+            "Terry_Pratchett_&_Neil_Gaiman_-_Good_Omens_(1990).epub",
+        ))
+        .expect("underscores should read as spaces");
+    assert_eq!(
+        meta.authors,
+        vec!["Terry Pratchett".to_string(), "Neil Gaiman".to_string()]
+    );
+    assert_eq!(meta.title.as_deref(), Some("Good Omens"));
+
+    assert!(
+        pattern.extract(Path::new("The Dispossessed.pdf")).is_none(),
+        "No separator, no match"
+    );
+}
+
+#[test]
+fn test_filename_pattern_reads_folders() {
+    let pattern = FilenamePattern::parse("{author}/{series}/{index} - {title}").unwrap();
+    let meta = pattern
+        .extract(Path::new(
+            "/library/Frank Herbert/Dune Chronicles/2 - Dune Messiah.epub",
+        ))
+        .expect("pattern should match");
+    assert_eq!(meta.authors, vec!["Frank Herbert".to_string()]);
+    assert_eq!(meta.series.as_deref(), Some("Dune Chronicles"));
+    assert_eq!(meta.series_index, Some(2.0));
+    assert_eq!(meta.title.as_deref(), Some("Dune Messiah"));
+
+    assert!(
+        pattern
+            .extract(Path::new("2 - Dune Messiah.epub"))
+            .is_none(),
+        "Too few folders"
+    );
+}
+
+#[test]
+fn test_filename_pattern_rejects_invalid_patterns() {
+    assert!(FilenamePattern::parse("{author} - {name}").is_err());
+    assert!(FilenamePattern::parse("{title} - {title}").is_err());
+    assert!(FilenamePattern::parse("{title").is_err());
+    assert!(FilenamePattern::parse("plain text").is_err());
+    assert!(FilenamePattern::parse("{author}//{title}").is_err());
+}
@@ -0,0 +1,38 @@
+use stellaron_lib::domain::models::isbn::Isbn;
+
+#[test]
+fn test_isbn_parse_strips_prefixes_and_hyphens() {
+    let isbn = Isbn::parse("urn:isbn:978-0-306-40615-7").unwrap();
+    assert_eq!(isbn.as_str(), "9780306406157");
+
+    let isbn = Isbn::parse("ISBN-13: 978 0 306 40615 7").unwrap();
+    assert_eq!(isbn.as_str(), "9780306406157");
+}
+
+#[test]
+fn test_isbn_converts_between_10_and_13() {
+    let isbn = Isbn::parse("0-8044-2957-x").unwrap();
+    assert_eq!(isbn.to_isbn13(), "9780804429573");
+    assert_eq!(isbn.to_isbn10().as_deref(), Some("080442957X"));
+    assert_eq!(isbn, Isbn::parse("9780804429573").unwrap());
+
+    let isbn = Isbn::parse("979-10-90636-07-1").unwrap();
+    assert_eq!(isbn.to_isbn10(), None, "979 ISBNs have no ISBN-10 form");
+}
+
+#[test]
+fn test_isbn_rejects_invalid_input() {
+    assert!(
+        Isbn::parse("9780306406158").is_err(),
+        "Bad ISBN-13 check digit"
+    );
+    assert!(
+        Isbn::parse("0306406153").is_err(),
+        "Bad ISBN-10 check digit"
+    );
+    assert!(Isbn::parse("12345").is_err(), "Wrong length");
+    assert!(
+        Isbn::parse("123456789012X").is_err(),
+        "X only allowed in ISBN-10"
+    );
+}
@@ -0,0 +1,308 @@
+//! Use cases run against a real SQLite database.
+//!
+//! The repositories share a global connection pool, so every test in this
+//! file uses the same database, created in a temporary directory by
+//! [`setup`]. Tests run serially and create their own books.
+
+use std::path::PathBuf;
+use std::sync::{Arc, Once};
+
+use diesel::{Connection, SqliteConnection};
+use diesel_migrations::{EmbeddedMigrations, MigrationHarness, embed_migrations};
+
+use stellaron_lib::application::author::rename_author;
+use stellaron_lib::application::book::{
+    RemoveMode, get_cover, import_book, list_trash, remove_book,
+};
+use stellaron_lib::application::repository::author_repo::AuthorRepoImpl;
+use stellaron_lib::application::repository::book_author_repo::BookAuthorRepoImpl;
+use stellaron_lib::application::repository::book_repo::BookRepoImpl;
+use stellaron_lib::application::repository::ignored_file_repo::IgnoredFileRepoImpl;
+use stellaron_lib::application::repository::metadata_history_repo::MetadataHistoryRepoImpl;
+use stellaron_lib::application::repository::publisher_repo::PublisherRepoImpl;
+use stellaron_lib::application::service::book_service::update_book_metadata;
+use stellaron_lib::domain::models::book::Book;
+use stellaron_lib::domain::models::contributor::ROLE_AUTHOR;
+use stellaron_lib::domain::models::metadata_change::ChangeSource;
+use stellaron_lib::domain::repository::*;
+use stellaron_lib::infrastructure::database::backfill;
+
+const MIGRATIONS: EmbeddedMigrations =
+    embed_migrations!("./src/infrastructure/database/migrations");
+
+static SETUP: Once = Once::new();
+
+/// Repositories used by the tests.
+struct Repos {
+    book: Arc<dyn BookRepository>,
+    author: Arc<dyn AuthorRepository>,
+    book_author: Arc<dyn BookAuthorRepository>,
+    publisher: Arc<dyn PublisherRepository>,
+    ignored_file: Arc<dyn IgnoredFileRepository>,
+    history: Arc<dyn MetadataHistoryRepository>,
+}
+
+/// Points the connection pool and cover cache at a temporary directory
+/// holding a freshly migrated database, once per test run.
+///
+/// # Returns
+///
+/// The temporary directory and the repositories.
+fn setup() -> (PathBuf, Repos) {
+    let dir = std::env::temp_dir().join(format!("stellaron-library-tests-{}", std::process::id()));
+    SETUP.call_once(|| {
+        std::fs::create_dir_all(&dir).expect("Failed to create the test directory");
+        let database = dir.join("library.db");
+        // SAFETY: tests in this file are serial, and the environment is set
+        // before the connection pool first reads it.
+        unsafe {
+            std::env::set_var("DATABASE_URL", &database);
+            std::env::set_var("COVER_CACHE_DIR", dir.join("covers"));
+        }
+        let mut connection = SqliteConnection::establish(&database.to_string_lossy())
+            .expect("Failed to open the test database");
+        connection
+            .run_pending_migrations(MIGRATIONS)
+            .expect("Failed to migrate the test database");
+        backfill::run_backfills(&mut connection).expect("Failed to run backfills");
+    });
+    let repos = Repos {
+        book: Arc::new(BookRepoImpl::new()),
+        author: Arc::new(AuthorRepoImpl::new()),
+        book_author: Arc::new(BookAuthorRepoImpl::new()),
+        publisher: Arc::new(PublisherRepoImpl::new()),
+        ignored_file: Arc::new(IgnoredFileRepoImpl::new()),
+        history: Arc::new(MetadataHistoryRepoImpl::new()),
+    };
+    (dir, repos)
+}
+
+/// Inserts a book credited to `author`, with a file path that does not
+/// exist.
+async fn insert_book(repos: &Repos, title: &str, author: &str, checksum: &str) -> Book {
+    let author = repos
+        .author
+        .find_or_create(author)
+        .await
+        .expect("Failed to create author");
+    repos
+        .book
+        .import_with_links(
+            NewBook {
+                title: title.to_string(),
+                published_date: None,
+                publisher_id: None,
+                isbn: None,
+                file_type: "epub".to_string(),
+                file_path: format!("/nonexistent/{}.epub", checksum),
+                cover_image_path: None,
+                checksum: Some(checksum.to_string()),
+                dominant_colors: Vec::new(),
+                blurhash: None,
+                language: None,
+                description: None,
+                rights: None,
+                modified_date: None,
+                epub_version: None,
+                series: None,
+                series_index: None,
+                title_sort: None,
+                author_sort: None,
+                subjects: Vec::new(),
+                identifiers: Vec::new(),
+            },
+            &[NewBookAuthor {
+                author_id: author.id,
+                role: ROLE_AUTHOR.to_string(),
+                sort_name: None,
+            }],
+            None,
+        )
+        .await
+        .expect("Failed to insert book")
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn test_added_before_date_includes_that_day() {
+    let (_, repos) = setup();
+    let book = insert_book(&repos, "Added Today", "Jane Doe", "added-before-boundary").await;
+    let added_on = book
+        .added_at
+        .as_deref()
+        .and_then(|added| added.get(..10))
+        .expect("The book should have an added_at timestamp")
+        .to_string();
+
+    let listed = |added_before: String| {
+        let query = BookQuery {
+            filter: BookFilter {
+                added_before: Some(added_before),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let book_repo = Arc::clone(&repos.book);
+        async move {
+            book_repo
+                .query(&query)
+                .await
+                .expect("Failed to query books")
+                .books
+                .iter()
+                .any(|listed| listed.id == book.id)
+        }
+    };
+    assert!(listed(added_on).await, "The bound day should be included");
+    assert!(!listed("2000-01-01".to_string()).await);
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn test_placeholder_cover_is_redrawn_after_title_edit() {
+    let (_, repos) = setup();
+    let book = insert_book(&repos, "Old Title", "Jane Doe", "placeholder-title").await;
+
+    let first = get_cover(book.id, &repos.book, &repos.author)
+        .await
+        .expect("Failed to get cover")
+        .expect("A placeholder cover should be generated");
+    let cached = repos.book.find_by_id(book.id).await.unwrap().unwrap();
+    assert!(cached.cover_is_placeholder);
+    assert!(cached.cover_image_path.is_some());
+
+    let patch = BookMetadataPatch {
+        title: Some("New Title".to_string()),
+        ..Default::default()
+    };
+    update_book_metadata(
+        book.id,
+        patch,
+        ChangeSource::User,
+        &repos.book,
+        &repos.author,
+        &repos.publisher,
+        &repos.history,
+    )
+    .await
+    .expect("Failed to update metadata");
+
+    let edited = repos.book.find_by_id(book.id).await.unwrap().unwrap();
+    assert_eq!(edited.cover_image_path, None);
+    assert_eq!(edited.blurhash, None);
+
+    let second = get_cover(book.id, &repos.book, &repos.author)
+        .await
+        .expect("Failed to get cover")
+        .expect("A placeholder cover should be generated");
+    assert_ne!(first, second, "The cover should show the new title");
+    let redrawn = repos.book.find_by_id(book.id).await.unwrap().unwrap();
+    assert!(redrawn.cover_is_placeholder);
+    assert!(redrawn.blurhash.is_some());
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn test_rename_author_updates_author_sort() {
+    let (_, repos) = setup();
+    let book = insert_book(&repos, "Renamed Author", "Jon Smyth", "rename-author-sort").await;
+    let author = repos
+        .author
+        .find_or_create("Jon Smyth")
+        .await
+        .expect("Failed to find author");
+
+    rename_author(
+        author.id,
+        "John Smith",
+        &repos.book,
+        &repos.author,
+        &repos.publisher,
+        &repos.history,
+    )
+    .await
+    .expect("Failed to rename author");
+
+    let renamed = repos.book.find_by_id(book.id).await.unwrap().unwrap();
+    assert_eq!(renamed.author_sort.as_deref(), Some("Smith, John"));
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn test_reimport_restores_book_whose_file_was_deleted() {
+    let (dir, repos) = setup();
+    let original = dir.join("deleted.pdf");
+    let copy = dir.join("copy of deleted.pdf");
+    std::fs::copy("tests/fixtures/test.pdf", &original).expect("Failed to copy the fixture");
+    std::fs::copy("tests/fixtures/test.pdf", &copy).expect("Failed to copy the fixture");
+    let import = |path: PathBuf| {
+        let repos = &repos;
+        async move {
+            import_book(
+                &path,
+                &repos.book,
+                &repos.author,
+                &repos.book_author,
+                &repos.publisher,
+                &repos.ignored_file,
+                &repos.history,
+            )
+            .await
+            .expect("Failed to import book")
+        }
+    };
+
+    let imported = import(original.clone()).await;
+    remove_book(
+        imported.id,
+        RemoveMode::DeleteFile,
+        &repos.book,
+        &repos.ignored_file,
+    )
+    .await
+    .expect("Failed to remove book");
+    assert!(!original.exists());
+
+    let restored = import(copy.clone()).await;
+    assert_eq!(restored.id, imported.id);
+    let book = repos.book.find_by_id(imported.id).await.unwrap().unwrap();
+    assert_eq!(book.deleted_at, None);
+    assert_eq!(
+        book.file_path.as_deref(),
+        Some(copy.to_string_lossy().as_ref())
+    );
+    let ignored = repos.ignored_file.find_all().await.unwrap();
+    assert!(
+        ignored
+            .iter()
+            .all(|file| file.file_path != original.to_string_lossy()
+                && file.file_path != copy.to_string_lossy()),
+        "No path of the restored book should be ignored: {:?}",
+        ignored
+    );
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn test_list_trash_resolves_trashed_books() {
+    let (_, repos) = setup();
+    let book = insert_book(&repos, "Trashed Book", "Jane Doe", "list-trash").await;
+    remove_book(
+        book.id,
+        RemoveMode::KeepFile,
+        &repos.book,
+        &repos.ignored_file,
+    )
+    .await
+    .expect("Failed to remove book");
+
+    let trash = list_trash(&repos.book).await.expect("Failed to list trash");
+    let listed = trash
+        .iter()
+        .find(|dto| dto.id == book.id)
+        .expect("The trashed book should be listed");
+    assert!(listed.deleted_at.is_some());
+    assert_eq!(listed.title, "Trashed Book");
+    assert_eq!(listed.formats.len(), 1);
+    assert!(listed.authors.iter().any(|a| a == "Jane Doe"));
+}
@@ -0,0 +1,100 @@
+use stellaron_lib::domain::models::locator::{
+    EpubLocation, Locator, LocatorTarget, LocatorText, PdfLocation, PdfPoint, Quad,
+};
+
+fn epub_locator(cfi: &str, progression: f64) -> Locator {
+    Locator {
+        target: LocatorTarget::Epub(EpubLocation {
+            href: "text/ch03.xhtml".into(),
+            cfi: Some(cfi.into()),
+            progression: Some(progression),
+            total_progression: None,
+        }),
+        text: None,
+    }
+}
+
+#[test]
+fn test_locator_round_trips_through_stored_form() {
+    let epub = Locator {
+        text: Some(LocatorText {
+            before: Some("It was ".into()),
+            highlight: Some("the best of times".into()),
+            after: Some(", it was".into()),
+        }),
+        ..epub_locator("epubcfi(/6/8!/4/2/1:7)", 0.25)
+    };
+    let stored = epub.to_stored();
+    assert!(stored.contains(r#""type":"epub""#));
+    assert_eq!(Locator::from_stored(&stored), epub);
+
+    let point = |x, y| PdfPoint { x, y };
+    let pdf = Locator {
+        target: LocatorTarget::Pdf(PdfLocation {
+            page_index: 41,
+            quads: vec![Quad {
+                points: [
+                    point(72.0, 700.0),
+                    point(300.0, 700.0),
+                    point(72.0, 688.0),
+                    point(300.0, 688.0),
+                ],
+            }],
+        }),
+        text: None,
+    };
+    assert_eq!(Locator::from_stored(&pdf.to_stored()), pdf);
+}
+
+#[test]
+fn test_locator_keeps_unparseable_values_as_legacy() {
+    let locator = Locator::from_stored("1534");
+    assert_eq!(
+        locator.target,
+        LocatorTarget::Legacy {
+            value: "1534".into()
+        }
+    );
+    assert_eq!(locator.to_stored(), r#"{"type":"legacy","value":"1534"}"#);
+}
+
+#[test]
+fn test_locator_validate() {
+    assert!(
+        epub_locator("epubcfi(/6/4!/4/2/1:0)", 0.5)
+            .validate()
+            .is_ok()
+    );
+    assert!(
+        epub_locator("/6/4!/4/2/1:0", 0.5).validate().is_err(),
+        "CFI without epubcfi(...)"
+    );
+    assert!(
+        epub_locator("epubcfi(/6/4!/4/2/1:0)", 1.5)
+            .validate()
+            .is_err(),
+        "Progression above 1"
+    );
+}
+
+#[test]
+fn test_pdf_location_merges_text_boxes_into_lines() {
+    let location = PdfLocation::from_text_boxes(
+        3,
+        [
+            [72.0, 700.0, 100.0, 12.0],
+            [172.0, 701.0, 80.0, 10.0],
+            [72.0, 686.0, 60.0, 12.0],
+        ],
+    );
+    assert_eq!(location.page_index, 3);
+    assert_eq!(location.quads.len(), 2, "One quad per line");
+    assert_eq!(location.quads[0].bounds(), [72.0, 700.0, 252.0, 712.0]);
+    assert_eq!(location.quads[1], Quad::from_rect(72.0, 686.0, 60.0, 12.0));
+
+    // QuadPoints order: upper-left, upper-right, lower-left, lower-right.
+    let [upper_left, _, lower_left, lower_right] = location.quads[1].points;
+    assert_eq!(upper_left, PdfPoint { x: 72.0, y: 698.0 });
+    assert_eq!(lower_left, PdfPoint { x: 72.0, y: 686.0 });
+    assert_eq!(lower_right, PdfPoint { x: 132.0, y: 686.0 });
+}
@@ -0,0 +1,60 @@
+use stellaron_lib::domain::models::metadata_change::{
+    ChangeSource, FIELD_AUTHORS, FIELD_SERIES_INDEX, FIELD_TITLE, MetadataSnapshot, split_list,
+};
+
+fn snapshot() -> MetadataSnapshot {
+    MetadataSnapshot {
+        title: Some("The Dispossessed".into()),
+        authors: vec!["Ursula K. Le Guin".into()],
+        series: Some("Hainish Cycle".into()),
+        series_index: Some(5.0),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_diff_lists_changed_fields_only() {
+    let before = snapshot();
+    let mut after = snapshot();
+    after.title = Some("The Dispossessed: An Ambiguous Utopia".into());
+    after.authors.push("Second Author".into());
+
+    let changes = before.diff(&after);
+    assert_eq!(changes.len(), 2);
+    assert_eq!(changes[0].field, FIELD_TITLE);
+    assert_eq!(changes[0].old_value.as_deref(), Some("The Dispossessed"));
+    assert_eq!(changes[1].field, FIELD_AUTHORS);
+    assert_eq!(
+        split_list(changes[1].new_value.as_deref()),
+        vec!["Ursula K. Le Guin", "Second Author"]
+    );
+
+    assert!(before.diff(&snapshot()).is_empty());
+}
+
+#[test]
+fn test_diff_from_empty_snapshot() {
+    let changes = MetadataSnapshot::default().diff(&snapshot());
+    assert_eq!(changes.len(), 4);
+    assert!(changes.iter().all(|c| c.old_value.is_none()));
+    let index = changes
+        .iter()
+        .find(|c| c.field == FIELD_SERIES_INDEX)
+        .unwrap();
+    assert_eq!(index.new_value.as_deref(), Some("5"));
+
+    assert!(split_list(None).is_empty());
+}
+
+#[test]
+fn test_change_source_round_trip() {
+    for source in [
+        ChangeSource::User,
+        ChangeSource::Provider,
+        ChangeSource::Import,
+        ChangeSource::FileWriteback,
+    ] {
+        assert_eq!(ChangeSource::parse(source.as_str()), Some(source));
+    }
+    assert_eq!(ChangeSource::parse("robot"), None);
+}
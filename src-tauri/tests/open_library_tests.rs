@@ -0,0 +1,136 @@
+use stellaron_lib::application::provider::MetadataProvider;
+use stellaron_lib::application::provider::open_library::OpenLibraryProvider;
+use stellaron_lib::domain::models::isbn::Isbn;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+const COVER_BYTES: &[u8] = b"\xff\xd8\xff\xe0fake-jpeg";
+
+/// Starts a minimal HTTP server answering like Open Library and returns its
+/// base URL. Every request path (with query) is served from `route`.
+async fn mock_server(route: fn(&str) -> (&'static str, &'static str, Vec<u8>)) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        loop {
+            let Ok((mut socket, _)) = listener.accept().await else {
+                return;
+            };
+            tokio::spawn(async move {
+                let mut buf = vec![0u8; 8192];
+                let n = socket.read(&mut buf).await.unwrap_or(0);
+                let request = String::from_utf8_lossy(&buf[..n]);
+                let target = request.split_whitespace().nth(1).unwrap_or("/");
+                let (status, content_type, body) = route(target);
+                let head = format!(
+                    "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    status,
+                    content_type,
+                    body.len()
+                );
+                let _ = socket.write_all(head.as_bytes()).await;
+                let _ = socket.write_all(&body).await;
+            });
+        }
+    });
+    format!("http://{}", addr)
+}
+
+fn open_library(target: &str) -> (&'static str, &'static str, Vec<u8>) {
+    if target.starts_with("/api/books") && target.contains("ISBN%3A9780306406157") {
+        let body = r#"{"ISBN:9780306406157": {
+            "key": "/books/OL1M",
+            "title": "The Dispossessed",
+            "authors": [{"name": "Ursula K. Le Guin"}],
+            "publishers": [{"name": "Harper & Row"}],
+            "publish_date": "May 1974",
+            "subjects": [{"name": "Science fiction"}],
+            "cover": {"medium": "COVER_URL/b/id/42-M.jpg"}
+        }}"#;
+        ("200 OK", "application/json", body.as_bytes().to_vec())
+    } else if target.starts_with("/api/books") {
+        ("200 OK", "application/json", b"{}".to_vec())
+    } else if target.starts_with("/search.json") {
+        let body = r#"{"numFound": 1, "docs": [{
+            "key": "/works/OL2W",
+            "title": "The Lathe of Heaven",
+            "author_name": ["Ursula K. Le Guin"],
+            "publisher": ["Scribner", "Avon"],
+            "first_publish_year": 1971,
+            "isbn": ["0-306-40615-2", "9780804429573"],
+            "language": ["eng"],
+            "cover_i": 42
+        }]}"#;
+        ("200 OK", "application/json", body.as_bytes().to_vec())
+    } else if target.starts_with("/b/id/42-M.jpg") {
+        ("200 OK", "image/jpeg", COVER_BYTES.to_vec())
+    } else {
+        ("404 Not Found", "text/plain", b"not found".to_vec())
+    }
+}
+
+#[tokio::test]
+async fn test_open_library_search_by_isbn() {
+    let base = mock_server(open_library).await;
+    let provider = OpenLibraryProvider::new(&base, &base);
+
+    let isbn = Isbn::parse("0-306-40615-2").unwrap();
+    let candidates = provider.search_by_isbn(&isbn).await.unwrap();
+    assert_eq!(candidates.len(), 1);
+
+    let book = &candidates[0];
+    assert_eq!(book.title, "The Dispossessed");
+    assert_eq!(book.contributors[0].name, "Ursula K. Le Guin");
+    assert_eq!(book.publishers, vec!["Harper & Row".to_string()]);
+    assert_eq!(book.published_date.as_deref(), Some("1974"));
+    assert_eq!(book.isbn.as_deref(), Some("9780306406157"));
+    assert_eq!(book.subjects, vec!["Science fiction".to_string()]);
+    assert!(
+        book.identifiers
+            .iter()
+            .any(|i| i.scheme == "openlibrary" && i.value == "OL1M")
+    );
+    // The cover URL in the mock response does not resolve, so no cover.
+    assert!(book.cover_data.is_none());
+
+    let unknown = Isbn::parse("9780804429573").unwrap();
+    assert!(provider.search_by_isbn(&unknown).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_open_library_search_by_title_fetches_covers() {
+    let base = mock_server(open_library).await;
+    let provider = OpenLibraryProvider::new(&format!("{}/", base), &base);
+
+    let candidates = provider
+        .search_by_title("The Lathe of Heaven", Some("Le Guin"))
+        .await
+        .unwrap();
+    assert_eq!(candidates.len(), 1);
+
+    let book = &candidates[0];
+    assert_eq!(book.title, "The Lathe of Heaven");
+    assert_eq!(book.publishers, vec!["Scribner".to_string()]);
+    assert_eq!(book.published_date.as_deref(), Some("1971"));
+    assert_eq!(
+        book.isbn.as_deref(),
+        Some("9780804429573"),
+        "ISBN-13 preferred"
+    );
+    assert_eq!(book.language.as_deref(), Some("en"));
+    let (bytes, mime) = book.cover_data.as_ref().expect("cover should be fetched");
+    assert_eq!(bytes.as_slice(), COVER_BYTES);
+    assert_eq!(mime, "image/jpeg");
+}
+
+#[tokio::test]
+async fn test_open_library_server_error() {
+    let base = mock_server(|_| ("500 Internal Server Error", "text/plain", Vec::new())).await;
+    let provider = OpenLibraryProvider::new(&base, &base);
+
+    let result = provider.search_by_title("Anything", None).await;
+    assert!(
+        result.is_err(),
+        "HTTP errors should surface as provider errors"
+    );
+}
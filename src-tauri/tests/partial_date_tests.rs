@@ -0,0 +1,42 @@
+use stellaron_lib::domain::models::partial_date::PartialDate;
+
+#[test]
+fn test_partial_date_parse_keeps_precision() {
+    let parse = |raw: &str| PartialDate::parse(raw).map(|d| d.to_string());
+    assert_eq!(parse("2019").as_deref(), Some("2019"));
+    assert_eq!(parse("2019-05").as_deref(), Some("2019-05"));
+    assert_eq!(
+        parse("2019-05-04T10:22:11+02:00").as_deref(),
+        Some("2019-05-04")
+    );
+    assert_eq!(
+        parse("D:20190504102211+02'00'").as_deref(),
+        Some("2019-05-04")
+    );
+    assert_eq!(
+        parse("2019-02-29").as_deref(),
+        Some("2019-02"),
+        "Not a leap year"
+    );
+    assert_eq!(parse("0101-01-01T00:00:00+00:00"), None, "Placeholder year");
+    assert_eq!(parse("unknown"), None);
+}
+
+#[test]
+fn test_partial_date_parse_strict() {
+    assert!(PartialDate::parse_strict("2020-02-29").is_ok());
+    assert!(PartialDate::parse_strict("2019-02-29").is_err());
+    assert!(PartialDate::parse_strict("2019-5").is_err());
+    assert!(PartialDate::parse_strict("2019-05-04T10:00").is_err());
+}
+
+#[test]
+fn test_partial_date_ordering() {
+    let mut dates: Vec<PartialDate> = ["2019-05", "2019", "2018-12-31"]
+        .iter()
+        .filter_map(|d| PartialDate::parse(d))
+        .collect();
+    dates.sort();
+    let sorted: Vec<String> = dates.iter().map(|d| d.to_string()).collect();
+    assert_eq!(sorted, vec!["2018-12-31", "2019", "2019-05"]);
+}
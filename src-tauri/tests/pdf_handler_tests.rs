@@ -1,3 +1,4 @@
+use base64::{Engine as _, engine::general_purpose};
 use stellaron_lib::infrastructure::file_handlers::pdf_handler::*;
 
 fn fixture_path(name: &str) -> String {
@@ -30,10 +31,21 @@ async fn test_parse_pdf_meta() {
     );
 }
 
+#[tokio::test]
+async fn test_parse_pdf_meta_falls_back_to_filename() {
+    let path = fixture_path("test.pdf");
+    let metadata = parse_pdf_meta(path).await.unwrap();
+    assert_eq!(
+        metadata.title, "test",
+        "Title should come from the filename when XMP and Info are empty"
+    );
+    assert_eq!(metadata.publishers, vec!["Unknown Publisher".to_string()]);
+}
+
 #[tokio::test]
 async fn test_get_pdf_cover() {
     let path = fixture_path("test.pdf");
-    let result = get_pdf_cover(&path).await;
+    let result = get_pdf_cover(&path, &PdfRenderOptions::default()).await;
     assert!(
         result.is_ok(),
         "Failed to get PDF cover: {:?}",
@@ -58,7 +70,7 @@ async fn test_get_pdf_page_count() {
 #[tokio::test]
 async fn test_read_pdf_page() {
     let path = fixture_path("test.pdf");
-    let result = read_pdf_page(&path, 0).await;
+    let result = read_pdf_page(&path, 0, &PdfRenderOptions::default()).await;
     assert!(
         result.is_ok(),
         "Failed to read PDF page: {:?}",
@@ -92,27 +104,94 @@ async fn test_parse_pdf_meta_nonexistent() {
 
 #[tokio::test]
 async fn test_get_pdf_cover_nonexistent() {
-    let result = get_pdf_cover("nonexistent.pdf").await;
+    let result = get_pdf_cover("nonexistent.pdf", &PdfRenderOptions::default()).await;
     assert!(result.is_err(), "Should fail for nonexistent file");
 }
 
 #[tokio::test]
 async fn test_read_pdf_page_nonexistent() {
-    let result = read_pdf_page("nonexistent.pdf", 0).await;
+    let result = read_pdf_page("nonexistent.pdf", 0, &PdfRenderOptions::default()).await;
     assert!(result.is_err(), "Should fail for nonexistent file");
 }
 
 #[tokio::test]
 async fn test_read_pdf_page_out_of_range() {
     let path = fixture_path("test.pdf");
-    let result = read_pdf_page(&path, 999).await;
+    let result = read_pdf_page(&path, 999, &PdfRenderOptions::default()).await;
     assert!(result.is_err(), "Should fail for out-of-range page");
 }
 
+#[tokio::test]
+async fn test_read_pdf_page_at_target_width_clipped_to_jpeg() {
+    let path = fixture_path("test.pdf");
+    let options = PdfRenderOptions {
+        target_width: Some(400),
+        clip: Some(PixelRect {
+            x: 100,
+            y: 50,
+            width: 200,
+            height: 120,
+        }),
+        format: PdfImageFormat::Jpeg,
+        quality: Some(70),
+        ..Default::default()
+    };
+    let page = read_pdf_page(&path, 0, &options).await.unwrap();
+    assert_eq!(
+        page.page_width, 400,
+        "Whole page should be the target width"
+    );
+    assert_eq!(
+        (page.width, page.height),
+        (200, 120),
+        "Image should be the clip"
+    );
+    assert_eq!(page.format, PdfImageFormat::Jpeg);
+
+    let bytes = general_purpose::STANDARD.decode(&page.image_data).unwrap();
+    assert_eq!(&bytes[..2], &[0xFF, 0xD8], "Image should be a JPEG");
+    let image = image::load_from_memory(&bytes).unwrap();
+    assert_eq!((image.width(), image.height()), (200, 120));
+
+    let whole = read_pdf_page(&path, 0, &PdfRenderOptions::default())
+        .await
+        .unwrap();
+    let ratio = 400.0 / whole.width as f32;
+    assert!(
+        (page.scale - whole.scale * ratio).abs() < 0.01,
+        "Scale should follow the target width"
+    );
+}
+
+#[tokio::test]
+async fn test_read_pdf_page_rejects_bad_render_options() {
+    let path = fixture_path("test.pdf");
+    let too_fine = PdfRenderOptions {
+        dpi: Some(100_000),
+        ..Default::default()
+    };
+    assert!(read_pdf_page(&path, 0, &too_fine).await.is_err());
+
+    let outside = PdfRenderOptions {
+        target_width: Some(100),
+        clip: Some(PixelRect {
+            x: 50,
+            y: 0,
+            width: 100,
+            height: 10,
+        }),
+        ..Default::default()
+    };
+    assert!(
+        read_pdf_page(&path, 0, &outside).await.is_err(),
+        "Should fail for a clip outside the page"
+    );
+}
+
 #[tokio::test]
 async fn test_pdf_text_spans() {
     let path = fixture_path("test.pdf");
-    let result = read_pdf_page(&path, 0).await;
+    let result = read_pdf_page(&path, 0, &PdfRenderOptions::default()).await;
     assert!(
         result.is_ok(),
         "Failed to read PDF page: {:?}",
@@ -131,3 +210,89 @@ async fn test_pdf_text_spans() {
         combined_text
     );
 }
+
+#[tokio::test]
+async fn test_pdf_chars_and_words_in_image_pixels() {
+    let path = fixture_path("test.pdf");
+    let page = read_pdf_page(&path, 0, &PdfRenderOptions::default())
+        .await
+        .unwrap();
+    assert!(page.scale > 0.0, "Scale should be positive");
+    assert_eq!(page.rotation % 90, 0, "Rotation should be a right angle");
+    assert!(!page.chars.is_empty(), "Should have characters");
+    assert!(!page.words.is_empty(), "Should have words");
+
+    for c in &page.chars {
+        assert!(
+            c.x >= -1.0 && c.x + c.width <= page.width as f32 + 1.0,
+            "Character {:?} should be inside the image horizontally",
+            c
+        );
+        assert!(
+            c.y >= -1.0 && c.y + c.height <= page.height as f32 + 1.0,
+            "Character {:?} should be inside the image vertically",
+            c
+        );
+    }
+    for word in &page.words {
+        let text: String = page.chars[word.start..word.end]
+            .iter()
+            .map(|c| c.text.as_str())
+            .collect();
+        assert_eq!(word.text, text, "A word is the text of its characters");
+        assert!(!word.text.contains(char::is_whitespace));
+    }
+    assert!(page.words.iter().any(|w| w.text.contains("Test")));
+
+    let chars = read_pdf_chars(&path, 0).await.unwrap();
+    assert_eq!(chars.len(), page.chars.len(), "Same characters, same order");
+}
+
+#[test]
+fn test_word_ranges() {
+    let char_at = |text: &str, x: f32, y: f32| PdfTextSpan {
+        text: text.to_string(),
+        x,
+        y,
+        width: 5.0,
+        height: 10.0,
+    };
+    let chars = [
+        char_at("a", 0.0, 700.0),
+        char_at("b", 5.0, 700.0),
+        char_at(" ", 10.0, 700.0),
+        char_at("c", 15.0, 700.0),
+        char_at("d", 20.0, 700.0),
+        // A gap wider than a third of the line height.
+        char_at("e", 40.0, 700.0),
+        // The next line.
+        char_at("f", 0.0, 686.0),
+    ];
+    assert_eq!(word_ranges(&chars), vec![0..2, 3..5, 5..6, 6..7]);
+}
+
+#[test]
+fn test_page_geometry_maps_user_space_to_image_pixels() {
+    // A 100 x 200 pt page rendered at 2 pixels per point.
+    let geometry = PageGeometry {
+        page_box: [0.0, 0.0, 100.0, 200.0],
+        rotation: 0,
+        scale: 2.0,
+    };
+    let user_box = [10.0, 150.0, 20.0, 10.0];
+    assert_eq!(geometry.to_image(user_box), [20.0, 80.0, 40.0, 20.0]);
+
+    // Rotated a quarter turn clockwise, the top of the page is on the right.
+    let rotated = PageGeometry {
+        rotation: 90,
+        ..geometry
+    };
+    assert_eq!(rotated.point_to_image(0.0, 200.0), (400.0, 0.0));
+    assert_eq!(rotated.to_image(user_box), [300.0, 20.0, 20.0, 40.0]);
+
+    let upside_down = PageGeometry {
+        rotation: 180,
+        ..geometry
+    };
+    assert_eq!(upside_down.to_image(user_box), [140.0, 300.0, 40.0, 20.0]);
+}
@@ -0,0 +1,81 @@
+use lopdf::{Document, Object};
+use stellaron_lib::domain::models::locator::Quad;
+use stellaron_lib::infrastructure::file_handlers::pdf_writer::*;
+
+fn fixture_path(name: &str) -> String {
+    format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+#[tokio::test]
+async fn test_write_pdf_annotations() {
+    let source = fixture_path("test.pdf");
+    let destination = std::env::temp_dir().join("stellaron-pdf-writer-test.pdf");
+    let destination = destination.to_string_lossy().to_string();
+    let before = std::fs::read(&source).unwrap();
+
+    let annotations = vec![
+        PdfAnnotation {
+            page_index: 0,
+            quads: vec![
+                Quad::from_rect(72.0, 700.0, 180.0, 12.0),
+                Quad::from_rect(72.0, 686.0, 60.0, 12.0),
+            ],
+            contents: Some("Noted \u{2014} twice".to_string()),
+            color: [1.0, 0.92, 0.23],
+            name: "stellaron-annotation-1".to_string(),
+        },
+        PdfAnnotation {
+            page_index: 0,
+            quads: Vec::new(),
+            contents: Some("A page note".to_string()),
+            color: [0.45, 0.75, 1.0],
+            name: "stellaron-annotation-2".to_string(),
+        },
+    ];
+    let result = write_pdf_annotations(source.clone(), destination.clone(), annotations).await;
+    assert!(
+        result.is_ok(),
+        "Failed to write annotations: {:?}",
+        result.err()
+    );
+    assert_eq!(
+        std::fs::read(&source).unwrap(),
+        before,
+        "Source should be unchanged"
+    );
+
+    let doc = Document::load(&destination).unwrap();
+    let page_id = doc.get_pages()[&1];
+    let annots = doc.get_dictionary(page_id).unwrap().get(b"Annots").unwrap();
+    let (_, annots) = doc.dereference(annots).unwrap();
+    let subtypes: Vec<(Vec<u8>, usize)> = annots
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|annot| {
+            let dict = doc.get_dictionary(annot.as_reference().ok()?).ok()?;
+            let subtype = dict.get(b"Subtype").ok()?.as_name().ok()?.to_vec();
+            let quad_points = dict
+                .get(b"QuadPoints")
+                .and_then(Object::as_array)
+                .map_or(0, Vec::len);
+            Some((subtype, quad_points))
+        })
+        .collect();
+    assert!(subtypes.contains(&(b"Highlight".to_vec(), 16)));
+    assert!(subtypes.contains(&(b"Text".to_vec(), 0)));
+
+    let result = write_pdf_annotations(
+        source,
+        destination,
+        vec![PdfAnnotation {
+            page_index: 999,
+            quads: Vec::new(),
+            contents: None,
+            color: [1.0, 1.0, 0.0],
+            name: "out-of-range".to_string(),
+        }],
+    )
+    .await;
+    assert!(result.is_err(), "Page past the end should be rejected");
+}
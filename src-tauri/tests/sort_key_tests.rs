@@ -0,0 +1,46 @@
+use stellaron_lib::domain::models::contributor::Contributor;
+use stellaron_lib::domain::models::sort_key::{author_sort, authors_sort, title_sort};
+
+#[test]
+fn test_author_sort_moves_surname_first() {
+    assert_eq!(author_sort("Ursula K. Le Guin"), "Le Guin, Ursula K.");
+    assert_eq!(author_sort("Vincent van Gogh"), "van Gogh, Vincent");
+    assert_eq!(author_sort("Frank  Herbert"), "Herbert, Frank");
+    assert_eq!(
+        author_sort("Martin Luther King Jr."),
+        "King, Martin Luther, Jr."
+    );
+    assert_eq!(author_sort("Le Guin, Ursula K."), "Le Guin, Ursula K.");
+    assert_eq!(author_sort("Homer"), "Homer");
+}
+
+#[test]
+fn test_authors_sort_prefers_file_as() {
+    let mut pratchett = Contributor::author("Terry Pratchett");
+    pratchett.sort_name = Some("Pratchett, Terence".to_string());
+    let mut editor = Contributor::author("Some Editor");
+    editor.role = "edt".to_string();
+    let contributors = vec![pratchett, Contributor::author("Neil Gaiman"), editor];
+    assert_eq!(
+        authors_sort(&contributors).as_deref(),
+        Some("Pratchett, Terence & Gaiman, Neil")
+    );
+    assert_eq!(authors_sort(&[Contributor::author("Unknown Author")]), None);
+}
+
+#[test]
+fn test_title_sort_drops_leading_articles() {
+    assert_eq!(title_sort("The Hobbit", None), "Hobbit");
+    assert_eq!(
+        title_sort("An Instance of the Fingerpost", Some("en-GB")),
+        "Instance of the Fingerpost"
+    );
+    assert_eq!(title_sort("L'Étranger", Some("fr")), "Étranger");
+    assert_eq!(title_sort("Der Zauberberg", Some("de")), "Zauberberg");
+    assert_eq!(
+        title_sort("Die Hard", Some("en")),
+        "Die Hard",
+        "Articles depend on language"
+    );
+    assert_eq!(title_sort("The", None), "The", "A title is never emptied");
+}
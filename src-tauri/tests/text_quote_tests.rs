@@ -0,0 +1,89 @@
+use stellaron_lib::domain::models::cfi::DomPosition;
+use stellaron_lib::domain::models::text_quote::{REANCHOR_CONFIDENCE, TextQuote};
+use stellaron_lib::infrastructure::file_handlers::epub_cfi::SpineDocument;
+
+const TEXT: &str = "It was the best of times, it was the worst of times, \
+                    it was the age of wisdom, it was the age of foolishness.";
+
+fn quote(exact: &str, prefix: Option<&str>, suffix: Option<&str>) -> TextQuote {
+    TextQuote {
+        exact: exact.into(),
+        prefix: prefix.map(Into::into),
+        suffix: suffix.map(Into::into),
+    }
+}
+
+#[test]
+fn test_text_quote_finds_exact_matches() {
+    let found = quote("the worst of times", None, None).find_in(TEXT);
+    assert_eq!(found.len(), 1);
+    assert_eq!(&TEXT[found[0].start..found[0].end], "the worst of times");
+    assert_eq!(found[0].score, 1.0);
+
+    // Case, whitespace and typographic quotes do not matter.
+    let text = "He said \u{201c}Hello,\n   World\u{201d} twice.";
+    let found = quote("\"hello, world\"", None, None).find_in(text);
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].score, 1.0);
+}
+
+#[test]
+fn test_text_quote_context_ranks_repeated_passages() {
+    let found = quote(
+        "it was the age of",
+        Some("best of times, "),
+        Some(" wisdom"),
+    )
+    .find_in(TEXT);
+    assert_eq!(found.len(), 2);
+    assert!(TEXT[found[0].end..].starts_with(" wisdom"));
+    assert!(found[0].score > found[1].score);
+}
+
+#[test]
+fn test_text_quote_finds_edited_passages() {
+    // A corrected edition fixed the typo the highlight was made on.
+    let found = quote("the age of wisdomm, it was teh age", None, None).find_in(TEXT);
+    assert_eq!(
+        &TEXT[found[0].start..found[0].end],
+        "the age of wisdom, it was the age"
+    );
+    assert!(found[0].score >= REANCHOR_CONFIDENCE);
+
+    let found = quote("a passage that is not in the book", None, None).find_in(TEXT);
+    assert!(found.iter().all(|m| m.score < REANCHOR_CONFIDENCE));
+
+    // Long highlights are matched by their head and tail.
+    let long: String = (0..40)
+        .map(|i| format!("Sentence number {} of the chapter. ", i))
+        .collect();
+    let exact = long[10..600].replace("chapter", "chaptre");
+    let found = quote(&exact, None, None).find_in(&long);
+    assert_eq!((found[0].start, found[0].end), (10, 600));
+}
+
+#[test]
+fn test_text_quote_at_captures_context() {
+    let start = TEXT.find("worst").unwrap();
+    let quote = TextQuote::at(TEXT, start, start + "worst of times".len());
+    assert_eq!(quote.exact, "worst of times");
+    let prefix = quote.prefix.unwrap();
+    assert_eq!(prefix.len(), 32);
+    assert!(prefix.ends_with("best of times, it was the"));
+    assert!(quote.suffix.unwrap().starts_with(", it was the age"));
+}
+
+#[test]
+fn test_spine_document_text_offsets() {
+    let document = SpineDocument::parse(
+        r#"<html xmlns="http://www.w3.org/1999/xhtml"><body><p>One</p><p>Two <em>three</em></p></body></html>"#,
+    )
+    .unwrap();
+    assert_eq!(document.text(), "OneTwo three");
+
+    let position = DomPosition {
+        element_path: vec![0, 1, 0],
+        offset: Some(2),
+    };
+    assert_eq!(document.text_offset(&position).unwrap(), 9);
+}
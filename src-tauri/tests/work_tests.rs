@@ -0,0 +1,29 @@
+use stellaron_lib::domain::models::work::work_key;
+
+#[test]
+fn test_work_key_matches_formats_of_the_same_book() {
+    let epub = work_key(
+        "The Dispossessed: An Ambiguous Utopia",
+        Some("en"),
+        Some("Le Guin, Ursula K."),
+    );
+    let pdf = work_key("Dispossessed", None, Some("Le Guin, Ursula K"));
+    assert!(epub.is_some());
+    assert_eq!(epub, pdf);
+
+    assert_ne!(
+        work_key("The Dispossessed", None, Some("Le Guin, Ursula K.")),
+        work_key("The Dispossessed", None, Some("Smith, Jane")),
+        "Different authors are different works"
+    );
+    assert_ne!(
+        work_key("The Dispossessed", None, None),
+        work_key("The Lathe of Heaven", None, None)
+    );
+}
+
+#[test]
+fn test_work_key_requires_a_title() {
+    assert_eq!(work_key("   ", None, Some("Herbert, Frank")), None);
+    assert_eq!(work_key("!?", None, None), None);
+}
@@ -16,7 +16,17 @@ import {
   FiHeart
 } from "react-icons/fi";
 import { tauriService } from "../services/tauriService";
-import { BookDetails, Chapter, Bookmark, ProgressInfo } from "../types";
+import {
+  BookDetails,
+  Chapter,
+  Bookmark,
+  Locator,
+  ProgressInfo,
+  locatorPageNumber,
+  locatorScrollOffset,
+  pdfPageLocator,
+  scrollLocator,
+} from "../types";
 import SettingsModal from "../components/SettingsModal";
 
 interface BookOutletContext {
@@ -369,11 +379,7 @@ const BookPage: React.FC<BookPageProps> = ({ userId: propUserId }) => {
       const bmarks = await tauriService.getBookmarks({ userId, bookId });
       setBookmarks(bmarks || []);
 
-      if (prog && prog.current_position) {
-        setTargetScroll(parseFloat(prog.current_position));
-      } else {
-        setTargetScroll(0);
-      }
+      setTargetScroll(locatorScrollOffset(prog?.current_position) ?? 0);
     } catch (e) {
       console.error("Failed to fetch progress/bookmarks:", e);
     }
@@ -462,7 +468,7 @@ const BookPage: React.FC<BookPageProps> = ({ userId: propUserId }) => {
         await tauriService.updateReadingProgress({
           userId,
           bookId: bookDetails.id,
-          currentPosition: String(currentPage),
+          currentPosition: pdfPageLocator(currentPage),
           chapterTitle: `Page ${currentPage}`,
           pageNumber: currentPage,
           progressPercentage: progressPercent
@@ -492,20 +498,20 @@ const BookPage: React.FC<BookPageProps> = ({ userId: propUserId }) => {
       if (!container || !bookDetails || !userId) return;
 
       let progressPercent = 0;
-      let positionVal = "0";
+      let scrollOffset = 0;
 
       if (readerLayoutMode === "redesign") {
         const { scrollLeft, scrollWidth, clientWidth } = container;
         progressPercent = scrollWidth > clientWidth 
           ? (scrollLeft / (scrollWidth - clientWidth)) * 100 
           : 0;
-        positionVal = String(scrollLeft);
+        scrollOffset = scrollLeft;
       } else {
         const { scrollTop, scrollHeight, clientHeight } = container;
         progressPercent = scrollHeight > clientHeight 
           ? (scrollTop / (scrollHeight - clientHeight)) * 100 
           : 0;
-        positionVal = String(scrollTop);
+        scrollOffset = scrollTop;
       }
 
       const { activeChapter: currentChap, currentPage: currentPg } = stateRef.current;
@@ -514,7 +520,7 @@ const BookPage: React.FC<BookPageProps> = ({ userId: propUserId }) => {
         await tauriService.updateReadingProgress({
           userId,
           bookId: bookDetails.id,
-          currentPosition: positionVal,
+          currentPosition: scrollLocator(scrollOffset),
           chapterTitle: currentChap,
           pageNumber: currentPg,
           progressPercentage: progressPercent
@@ -618,7 +624,7 @@ const BookPage: React.FC<BookPageProps> = ({ userId: propUserId }) => {
     if (!bookDetails) return;
 
     if (bookDetails.file_type === "pdf") {
-      const existing = bookmarks.find(b => parseInt(b.position, 10) === currentPage);
+      const existing = bookmarks.find(b => locatorPageNumber(b.position) === currentPage);
       try {
         if (existing) {
           await tauriService.deleteBookmark(existing.bookmark_id);
@@ -626,7 +632,7 @@ const BookPage: React.FC<BookPageProps> = ({ userId: propUserId }) => {
           await tauriService.addBookmark({
             userId,
             bookId: bookDetails.id,
-            position: String(currentPage),
+            position: pdfPageLocator(currentPage),
             chapterTitle: `Page ${currentPage}`,
             pageNumber: currentPage
           });
@@ -644,7 +650,10 @@ const BookPage: React.FC<BookPageProps> = ({ userId: propUserId }) => {
     const scrollPos = readerLayoutMode === "redesign" ? container.scrollLeft : container.scrollTop;
 
     const threshold = 150;
-    const existing = bookmarks.find(b => Math.abs(parseFloat(b.position) - scrollPos) < threshold);
+    const existing = bookmarks.find(b => {
+      const offset = locatorScrollOffset(b.position);
+      return offset !== null && Math.abs(offset - scrollPos) < threshold;
+    });
 
     try {
       if (existing) {
@@ -653,7 +662,7 @@ const BookPage: React.FC<BookPageProps> = ({ userId: propUserId }) => {
         await tauriService.addBookmark({
           userId,
           bookId: bookDetails.id,
-          position: String(scrollPos),
+          position: scrollLocator(scrollPos),
           chapterTitle: activeChapter,
           pageNumber: currentPage
         });
@@ -665,10 +674,10 @@ const BookPage: React.FC<BookPageProps> = ({ userId: propUserId }) => {
     }
   };
 
-  const handleBookmarkClick = (position: string) => {
+  const handleBookmarkClick = (position: Locator) => {
     if (bookDetails?.file_type === "pdf") {
-      const pgNum = parseInt(position, 10);
-      if (!isNaN(pgNum)) {
+      const pgNum = locatorPageNumber(position);
+      if (pgNum !== null) {
         setCurrentPage(pgNum);
         setActiveChapter(`Page ${pgNum}`);
       }
@@ -676,11 +685,12 @@ const BookPage: React.FC<BookPageProps> = ({ userId: propUserId }) => {
       return;
     }
 
-    if (readerRef.current) {
+    const offset = locatorScrollOffset(position);
+    if (readerRef.current && offset !== null) {
       if (readerLayoutMode === "redesign") {
-        readerRef.current.scrollTo({ left: parseFloat(position), behavior: "smooth" });
+        readerRef.current.scrollTo({ left: offset, behavior: "smooth" });
       } else {
-        readerRef.current.scrollTo({ top: parseFloat(position), behavior: "smooth" });
+        readerRef.current.scrollTo({ top: offset, behavior: "smooth" });
       }
       updatePaginationInfo();
     }
@@ -689,11 +699,14 @@ const BookPage: React.FC<BookPageProps> = ({ userId: propUserId }) => {
 
   const isBookmarked = () => {
     if (bookDetails?.file_type === "pdf") {
-      return bookmarks.some(b => parseInt(b.position, 10) === currentPage);
+      return bookmarks.some(b => locatorPageNumber(b.position) === currentPage);
     }
     if (!readerRef.current) return false;
     const scrollPos = readerLayoutMode === "redesign" ? readerRef.current.scrollLeft : readerRef.current.scrollTop;
-    return bookmarks.some(b => Math.abs(parseFloat(b.position) - scrollPos) < 150);
+    return bookmarks.some(b => {
+      const offset = locatorScrollOffset(b.position);
+      return offset !== null && Math.abs(offset - scrollPos) < 150;
+    });
   };
 
   const getThemeStyles = () => {
@@ -175,14 +175,23 @@ const HomePage: React.FC = () => {
         directory: true
       });
       if (selected && typeof selected === "string") {
-        const errors = await tauriService.scanBooksDirectory(selected);
+        const { errors, reanchored } = await tauriService.scanBooksDirectory(selected);
         await loadData();
-        if (errors && errors.length > 0) {
+        if (errors.length > 0) {
           await message(
             `Imported books, but some files failed to import:\n\n${errors.join("\n")}`,
             { title: "Folder Import Warning", kind: "warning" }
           );
         }
+        const needsReview = reanchored
+          .flatMap((report) => report.annotations)
+          .filter((annotation) => annotation.outcome === "needs_review").length;
+        if (needsReview > 0) {
+          await message(
+            `Some books changed on disk, and ${needsReview} annotation(s) could not be found again. They are flagged for review.`,
+            { title: "Annotations Need Review", kind: "warning" }
+          );
+        }
       }
     } catch (err) {
       console.error("Failed to import folder:", err);
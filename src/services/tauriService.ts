@@ -4,6 +4,8 @@ import {
   BookDetails, 
   Bookmark, 
   Annotation, 
+  Locator, 
+  ScanReport, 
   UserInfo 
 } from "../types";
 
@@ -34,8 +36,8 @@ export const tauriService = {
     await invoke("remove_book", { bookId });
   },
 
-  async scanBooksDirectory(directoryPath: string): Promise<string[]> {
-    return await invoke<string[]>("scan_books_directory", { directoryPath });
+  async scanBooksDirectory(directoryPath: string): Promise<ScanReport> {
+    return await invoke<ScanReport>("scan_books_directory", { directoryPath });
   },
 
   async getCoverImg(bookId: number): Promise<number[]> {
@@ -50,7 +52,7 @@ export const tauriService = {
   async updateReadingProgress(params: {
     userId: number;
     bookId: number;
-    currentPosition: string;
+    currentPosition: Locator;
     chapterTitle: string;
     pageNumber: number;
     progressPercentage: number;
@@ -66,7 +68,7 @@ export const tauriService = {
   async addBookmark(params: {
     userId: number;
     bookId: number;
-    position: string;
+    position: Locator;
     chapterTitle: string;
     pageNumber: number;
   }): Promise<void> {
@@ -1,8 +1,10 @@
+import { Locator } from "./locator";
+
 export interface Bookmark {
   bookmark_id: number;
   book_id: number;
   user_id: number;
-  position: string;
+  position: Locator;
   chapter_title?: string;
   page_number?: number;
 }
@@ -21,3 +23,21 @@ export interface ExtendedAnnotation extends Annotation {
   bookTitle: string;
   bookAuthor: string;
 }
+
+export type ReanchorOutcome = "unchanged" | "moved" | "needs_review";
+
+export interface ReanchoredAnnotation {
+  annotation_id: number;
+  outcome: ReanchorOutcome;
+  confidence?: number | null;
+}
+
+export interface ReanchorReport {
+  book_id: number;
+  annotations: ReanchoredAnnotation[];
+}
+
+export interface ScanReport {
+  errors: string[];
+  reanchored: ReanchorReport[];
+}
@@ -0,0 +1,59 @@
+export interface LocatorText {
+  before?: string;
+  highlight?: string;
+  after?: string;
+}
+
+export interface EpubLocator {
+  type: "epub";
+  href: string;
+  cfi?: string;
+  progression?: number;
+  total_progression?: number;
+}
+
+export interface PdfPoint {
+  x: number;
+  y: number;
+}
+
+export interface PdfLocator {
+  type: "pdf";
+  page_index: number;
+  quads?: { points: [PdfPoint, PdfPoint, PdfPoint, PdfPoint] }[];
+}
+
+export interface LegacyLocator {
+  type: "legacy";
+  value: string;
+}
+
+export type Locator = (EpubLocator | PdfLocator | LegacyLocator) & { text?: LocatorText };
+
+// PDF positions are 1-based page numbers in the reader, 0-based page indexes in locators.
+export const pdfPageLocator = (pageNumber: number): Locator => ({
+  type: "pdf",
+  page_index: pageNumber - 1,
+});
+
+// The HTML reader stores its scroll offset, which is not tied to the book's structure.
+export const scrollLocator = (offset: number): Locator => ({
+  type: "legacy",
+  value: String(offset),
+});
+
+export const locatorPageNumber = (locator?: Locator | null): number | null => {
+  if (!locator) return null;
+  if (locator.type === "pdf") return locator.page_index + 1;
+  if (locator.type === "legacy") {
+    const page = parseInt(locator.value, 10);
+    return isNaN(page) ? null : page;
+  }
+  return null;
+};
+
+export const locatorScrollOffset = (locator?: Locator | null): number | null => {
+  if (!locator || locator.type !== "legacy") return null;
+  const offset = parseFloat(locator.value);
+  return isNaN(offset) ? null : offset;
+};
@@ -1,5 +1,7 @@
+import { Locator } from "./locator";
+
 export interface ProgressInfo {
-  current_position?: string | null;
+  current_position?: Locator | null;
   progress_percentage?: number;
 }
 